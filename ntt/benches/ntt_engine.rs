@@ -1,6 +1,8 @@
-use divan;
 /// Benchmarks for NTTEngine with divan
-use ntt::{NTTEngine, Pow2};
+use {
+    ark_bn254::Fr,
+    ntt::{ntt_nr, ntt_nr_with_options, NTTEngine, NttOptions, Pow2, TwiddleCache, NTT},
+};
 
 fn main() {
     // Initialize Rayon thread pool
@@ -14,5 +16,70 @@ const ORDER: usize = 1 << 24;
 /// Benchmark NTTEngine creation with order 1<<24
 #[divan::bench]
 fn create_engine() {
-    let _engine = divan::black_box(NTTEngine::with_order(Pow2::new(ORDER).unwrap()));
+    let _engine = divan::black_box(NTTEngine::<Fr>::with_order(Pow2::new(ORDER).unwrap()));
+}
+
+/// Order and count of the repeated transforms, similar to the Reed-Solomon
+/// encoding done by the prover.
+const REPEATED_ORDER: usize = 1 << 16;
+const REPEATS: usize = 16;
+
+/// Repeated NTTs through the global engine
+#[divan::bench]
+fn repeated_ntt_global(bencher: divan::Bencher) {
+    bencher
+        .with_inputs(|| NTT::new(vec![Fr::from(1); REPEATED_ORDER], 1).unwrap())
+        .bench_local_values(|mut values| {
+            for _ in 0..REPEATS {
+                ntt_nr(&mut values);
+            }
+            values
+        });
+}
+
+/// Repeated NTTs using a shared twiddle cache
+#[divan::bench]
+fn repeated_ntt_cached(bencher: divan::Bencher) {
+    let cache = TwiddleCache::new(Pow2::new(REPEATED_ORDER).unwrap());
+    bencher
+        .with_inputs(|| NTT::new(vec![Fr::from(1); REPEATED_ORDER], 1).unwrap())
+        .bench_local_values(|mut values| {
+            for _ in 0..REPEATS {
+                cache.ntt_nr_cached(&mut values);
+            }
+            values
+        });
+}
+
+/// Full size NTT on a single thread
+#[divan::bench]
+fn ntt_sequential(bencher: divan::Bencher) {
+    bencher
+        .with_inputs(|| NTT::new(vec![Fr::from(1); ORDER], 1).unwrap())
+        .bench_local_values(|mut values| {
+            ntt_nr_with_options(&mut values, NttOptions::sequential());
+            values
+        });
+}
+
+/// Full size NTT on a single thread with only radix-2 butterflies
+#[divan::bench]
+fn ntt_sequential_radix2(bencher: divan::Bencher) {
+    bencher
+        .with_inputs(|| NTT::new(vec![Fr::from(1); ORDER], 1).unwrap())
+        .bench_local_values(|mut values| {
+            ntt_nr_with_options(&mut values, NttOptions::sequential().radix2());
+            values
+        });
+}
+
+/// Full size NTT split across the rayon pool
+#[divan::bench]
+fn ntt_parallel(bencher: divan::Bencher) {
+    bencher
+        .with_inputs(|| NTT::new(vec![Fr::from(1); ORDER], 1).unwrap())
+        .bench_local_values(|mut values| {
+            ntt_nr_with_options(&mut values, NttOptions::default());
+            values
+        });
 }
@@ -0,0 +1,240 @@
+//! Arbitrary length NTTs using Bluestein's algorithm.
+//!
+//! Bluestein's algorithm rewrites a DFT of length `n` as a convolution with a
+//! chirp sequence. The convolution is then evaluated using a power-of-two NTT
+//! of length at least `2n - 1`.
+//!
+//! Using `jk = (j² + k² - (k - j)²) / 2` and a primitive `2n`-th root of unity
+//! `ψ` with `ψ² = ω`:
+//!
+//! ```text
+//! X_k = Σ_j x_j ω^{jk} = ψ^{k²} Σ_j (x_j ψ^{j²}) ψ^{-(k-j)²}
+//! ```
+use {
+    crate::{intt_rn, ntt_nr, NTT},
+    ark_bn254::Fr,
+    ark_ff::{AdditiveGroup, BigInt, FftField, Field, PrimeField},
+};
+
+/// Returns true if [`ntt_arbitrary`] supports transforms of length `n`.
+///
+/// Bluestein's algorithm requires a primitive `2n`-th root of unity, so `2n`
+/// has to divide the order of the multiplicative group of the field. The
+/// length must not be zero.
+pub fn is_supported_length(n: usize) -> bool {
+    n == 1 || (n > 1 && primitive_root_of_unity(2 * n).is_some())
+}
+
+/// Performs an in-place DFT of arbitrary length `n` in natural order.
+///
+/// `values` may hold multiple interleaved polynomials of length `n`, see
+/// [`NTT`]. For power-of-two lengths the result is the same as [`ntt_nr`]
+/// followed by a bit-reversal permutation.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, if `values.len()` is not a multiple of `n` or if
+/// the length is not supported by the field, see [`is_supported_length`].
+pub fn ntt_arbitrary(values: &mut [Fr], n: usize) {
+    assert!(n > 0, "transform length must not be zero");
+    if values.is_empty() || n == 1 {
+        return;
+    }
+    assert_eq!(
+        values.len() % n,
+        0,
+        "values must contain a whole number of polynomials"
+    );
+    let psi = primitive_root_of_unity(2 * n)
+        .unwrap_or_else(|| panic!("no primitive root of unity of order 2·{n}"));
+    let width = values.len() / n;
+
+    // Chirp sequence ψ^{k²} for k ∈ [0, n). Exponents are reduced mod 2n.
+    let chirp = (0..n)
+        .map(|k| psi.pow([((k * k) % (2 * n)) as u64]))
+        .collect::<Vec<_>>();
+    let inverse_chirp = chirp
+        .iter()
+        .map(|c| c.inverse().unwrap())
+        .collect::<Vec<_>>();
+
+    // The linear convolution of two sequences of length n has length 2n - 1.
+    let m = (2 * n - 1).next_power_of_two();
+    // The interleaved NTT requires the total length to be a power of two, so
+    // the scratch rows are padded.
+    let stride = width.next_power_of_two();
+
+    let mut a = vec![Fr::ZERO; m * stride];
+    for ((row, dst), c) in values
+        .chunks_exact(width)
+        .zip(a.chunks_exact_mut(stride))
+        .zip(&chirp)
+    {
+        dst.iter_mut().zip(row).for_each(|(dst, x)| *dst = *x * c);
+    }
+
+    // ψ^{-m²} for m ∈ (-n, n) stored cyclically.
+    let mut b = vec![Fr::ZERO; m * stride];
+    for (k, c) in inverse_chirp.iter().enumerate() {
+        b[k * stride..(k + 1) * stride].fill(*c);
+        if k != 0 {
+            b[(m - k) * stride..(m - k + 1) * stride].fill(*c);
+        }
+    }
+
+    let mut a = NTT::new(a, stride).unwrap();
+    let mut b = NTT::new(b, stride).unwrap();
+    ntt_nr(&mut a);
+    ntt_nr(&mut b);
+    a.iter_mut().zip(b.iter()).for_each(|(a, b)| *a *= b);
+    intt_rn(&mut a);
+
+    for ((row, src), c) in values
+        .chunks_exact_mut(width)
+        .zip(a.chunks_exact(stride))
+        .zip(&chirp)
+    {
+        row.iter_mut().zip(src).for_each(|(dst, x)| *dst = *x * c);
+    }
+}
+
+/// Returns a primitive `n`-th root of unity if `n` divides `r - 1`.
+///
+/// For powers of two this matches [`FftField::get_root_of_unity`].
+pub(crate) fn primitive_root_of_unity(n: usize) -> Option<Fr> {
+    // (r - 1) / n = ((r - 1) / 2) / (n / 2) for even n and
+    // 2 · ((r - 1) / 2) / n for odd n.
+    let half = Fr::MODULUS_MINUS_ONE_DIV_TWO;
+    if n % 2 == 0 {
+        div_exact(half, (n / 2) as u64).map(|exponent| Fr::GENERATOR.pow(exponent))
+    } else {
+        div_exact(half, n as u64).map(|exponent| Fr::GENERATOR.pow(exponent).square())
+    }
+}
+
+/// Divide a big integer by a small divisor, returning `None` if there is a
+/// remainder.
+fn div_exact<const N: usize>(value: BigInt<N>, divisor: u64) -> Option<BigInt<N>> {
+    if divisor == 0 {
+        return None;
+    }
+    let mut result = BigInt::<N>::zero();
+    let mut remainder = 0_u128;
+    for (i, limb) in value.0.iter().enumerate().rev() {
+        let current = (remainder << 64) | u128::from(*limb);
+        result.0[i] = (current / u128::from(divisor)) as u64;
+        remainder = current % u128::from(divisor);
+    }
+    (remainder == 0).then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{ntt_nr, NTT},
+        ark_ff::Zero,
+        proptest::{collection, prelude::*},
+    };
+
+    fn naive_dft(values: &[Fr], n: usize) -> Vec<Fr> {
+        let omega = primitive_root_of_unity(n).unwrap();
+        (0..n)
+            .map(|k| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(j, x)| *x * omega.pow([(j * k) as u64]))
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roots_are_primitive() {
+        for n in [1, 2, 3, 6, 9, 13, 26, 29, 58, 1 << 10, 983] {
+            let root = primitive_root_of_unity(n).unwrap();
+            assert_eq!(root.pow([n as u64]), Fr::ONE);
+            for d in (1..n).filter(|d| n % d == 0) {
+                assert_ne!(root.pow([d as u64]), Fr::ONE);
+            }
+        }
+        assert_eq!(
+            primitive_root_of_unity(1 << 20),
+            Fr::get_root_of_unity(1 << 20)
+        );
+        assert!(primitive_root_of_unity(5).is_none());
+        assert!(!is_supported_length(7));
+        assert!(!is_supported_length(0));
+    }
+
+    #[test]
+    fn matches_naive_dft() {
+        // Primes and composites dividing r - 1
+        for n in [2, 3, 6, 9, 12, 13, 26, 29, 39, 58, 117] {
+            let values = (0..n).map(|i| Fr::from(i as u64 + 7)).collect::<Vec<_>>();
+            let mut transformed = values.clone();
+            ntt_arbitrary(&mut transformed, n);
+            assert_eq!(transformed, naive_dft(&values, n), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn interleaved() {
+        let (n, width) = (13, 3);
+        let values = (0..n * width)
+            .map(|i| Fr::from(i as u64 * i as u64))
+            .collect::<Vec<_>>();
+        let mut transformed = values.clone();
+        ntt_arbitrary(&mut transformed, n);
+        for column in 0..width {
+            let poly = values.iter().skip(column).step_by(width).copied();
+            let expected = naive_dft(&poly.collect::<Vec<_>>(), n);
+            let actual = transformed.iter().skip(column).step_by(width).copied();
+            assert_eq!(actual.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn trivial_lengths() {
+        let mut empty: Vec<Fr> = vec![];
+        ntt_arbitrary(&mut empty, 3);
+        let mut single = vec![Fr::from(5)];
+        ntt_arbitrary(&mut single, 1);
+        assert_eq!(single, vec![Fr::from(5)]);
+        let mut zeros = vec![Fr::zero(); 9];
+        ntt_arbitrary(&mut zeros, 9);
+        assert!(zeros.iter().all(Zero::is_zero));
+    }
+
+    #[test]
+    #[should_panic(expected = "transform length must not be zero")]
+    fn zero_length() {
+        ntt_arbitrary(&mut [], 0);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_power_of_two_ntt(
+            values in (0_usize..8).prop_flat_map(|k| collection::vec(any::<u64>(), 1 << k))
+        ) {
+            let values = values.into_iter().map(Fr::from).collect::<Vec<_>>();
+            let n = values.len();
+
+            let mut expected = NTT::new(values.clone(), 1).unwrap();
+            ntt_nr(&mut expected);
+            let mut expected = expected.into_inner();
+            if n > 1 {
+                let bits = n.trailing_zeros();
+                let reversed = (0..n)
+                    .map(|i| expected[i.reverse_bits() >> (usize::BITS - bits)])
+                    .collect::<Vec<_>>();
+                expected = reversed;
+            }
+
+            let mut actual = values;
+            ntt_arbitrary(&mut actual, n);
+            prop_assert_eq!(actual, expected);
+        }
+    }
+}
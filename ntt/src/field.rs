@@ -0,0 +1,101 @@
+//! Fields over which the NTT can be computed.
+use {
+    crate::Pow2,
+    ark_ff::{
+        fields::{Fp64, MontBackend, MontConfig},
+        FftField, Field,
+    },
+};
+
+/// A field that supports power-of-two NTTs.
+pub trait NttField: Field {
+    /// Base two logarithm of the largest supported NTT order.
+    const LOG_MAX_ORDER: u32;
+
+    /// Returns a primitive root of unity of the given order, if it exists.
+    fn root_of_unity(order: Pow2<usize>) -> Option<Self>;
+}
+
+impl<F: FftField> NttField for F {
+    const LOG_MAX_ORDER: u32 = F::TWO_ADICITY;
+
+    fn root_of_unity(order: Pow2<usize>) -> Option<Self> {
+        F::get_root_of_unity(*order as u64)
+    }
+}
+
+/// Configuration of the Goldilocks field with modulus `2⁶⁴ - 2³² + 1`.
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct GoldilocksConfig;
+
+/// The Goldilocks field. Supports NTTs up to order `2³²`.
+pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+/// Configuration of the Mersenne-31 field with modulus `2³¹ - 1`.
+#[derive(MontConfig)]
+#[modulus = "2147483647"]
+#[generator = "7"]
+pub struct M31Config;
+
+/// The Mersenne-31 field.
+///
+/// Note: `p - 1 = 2 · (2³⁰ - 1)` so only NTTs up to order 2 are supported.
+/// Larger transforms need an extension field.
+pub type M31 = Fp64<MontBackend<M31Config, 1>>;
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use {
+        super::*,
+        crate::{intt_rn, ntt_nr, NTT},
+        proptest::{collection, prelude::*},
+    };
+
+    #[test]
+    fn goldilocks_known_transform() {
+        let omega = Goldilocks::root_of_unity(Pow2::new(4).unwrap()).unwrap();
+        assert_eq!(omega.square(), -Goldilocks::ONE);
+
+        let input = [1, 2, 3, 4].map(Goldilocks::from).to_vec();
+        let mut values = NTT::new(input.clone(), 1).unwrap();
+        ntt_nr(&mut values);
+
+        // Output is in bit reversed order: [X0, X2, X1, X3]
+        let two = Goldilocks::from(2);
+        let expected = vec![
+            Goldilocks::from(10),
+            -two,
+            -two - two * omega,
+            -two + two * omega,
+        ];
+        assert_eq!(*values, expected);
+
+        intt_rn(&mut values);
+        assert_eq!(values.into_inner(), input);
+    }
+
+    #[test]
+    fn m31_order_two() {
+        assert_eq!(M31::LOG_MAX_ORDER, 1);
+        assert!(M31::root_of_unity(Pow2::new(4).unwrap()).is_none());
+
+        let mut values = NTT::new(vec![M31::from(5), M31::from(3)], 1).unwrap();
+        ntt_nr(&mut values);
+        assert_eq!(*values, [M31::from(8), M31::from(2)]);
+    }
+
+    proptest! {
+        #[test]
+        fn goldilocks_round_trip(
+            input in (0_usize..12).prop_flat_map(|k| collection::vec(any::<u64>(), 1 << k))
+        ) {
+            let input = input.into_iter().map(Goldilocks::from).collect::<Vec<_>>();
+            let mut values = NTT::new(input.clone(), 1).unwrap();
+            ntt_nr(&mut values);
+            intt_rn(&mut values);
+            prop_assert_eq!(values.into_inner(), input);
+        }
+    }
+}
@@ -1,18 +1,35 @@
+//! Without the default `std` feature only [`NTT`], the fields and the
+//! transforms through a [`TwiddleCache`] are available, using `core` and
+//! `alloc`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![feature(vec_split_at_spare)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod bluestein;
+pub mod field;
 pub mod ntt;
-pub use ntt::*;
-use std::{
-    marker::PhantomData,
-    num::NonZero,
-    ops::{Deref, DerefMut},
+#[cfg(feature = "std")]
+pub use bluestein::ntt_arbitrary;
+use {
+    alloc::{vec, vec::Vec},
+    core::{
+        marker::PhantomData,
+        num::NonZero,
+        ops::{Deref, DerefMut},
+    },
+};
+pub use {
+    field::{Goldilocks, NttField, M31},
+    ntt::*,
 };
 
 pub trait NTTContainer<T>: AsRef<[T]> + AsMut<[T]> {}
 impl<T, C: AsRef<[T]> + AsMut<[T]>> NTTContainer<T> for C {}
 
 /// The NTT is optimized for NTTs of a power of two. Arbitrary sized NTTs are
-/// not supported. Note: empty vectors (size 0) are also supported as a special
-/// case.
+/// not supported, use [`ntt_arbitrary`] instead. Note: empty vectors (size 0)
+/// are also supported as a special case.
 ///
 /// NTTContainer can be a single polynomial or multiple polynomials that are
 /// interleaved. interleaved polynomials; `[a0, b0, c0, d0, a1, b1, c1, d1,
@@ -49,6 +66,43 @@ impl<T, C: NTTContainer<T>> NTT<T, C> {
     pub fn into_inner(self) -> C {
         self.container
     }
+
+    /// Returns each interleaved polynomial as a contiguous vector.
+    ///
+    /// An NTT of order 0 holds no coefficients, so no polynomials are
+    /// returned.
+    pub fn transpose_out(&self) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        let order = *self.order;
+        if order == 0 {
+            return vec![];
+        }
+        let values = self.container.as_ref();
+        let width = values.len() / order;
+        (0..width)
+            .map(|column| values[column..].iter().step_by(width).cloned().collect())
+            .collect()
+    }
+}
+
+impl<T: Clone> NTT<T, Vec<T>> {
+    /// Interleaves separate polynomials into a single NTT, the inverse of
+    /// [`NTT::transpose_out`].
+    ///
+    /// Returns `None` if there are no polynomials, or if they do not all have
+    /// the same power of two length.
+    pub fn from_separate(polys: &[Vec<T>]) -> Option<Self> {
+        let order = polys.first()?.len();
+        if polys.iter().any(|poly| poly.len() != order) {
+            return None;
+        }
+        let values = (0..order)
+            .flat_map(|row| polys.iter().map(move |poly| poly[row].clone()))
+            .collect();
+        Self::new(values, polys.len())
+    }
 }
 
 impl<T, C: NTTContainer<T>> Deref for NTT<T, C> {
@@ -106,3 +160,39 @@ impl InPowerOfTwoSet for NonZero<usize> {
         self.get().is_power_of_two()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{Goldilocks, NttField, Pow2, TwiddleCache, NTT},
+        alloc::vec::Vec,
+        ark_ff::{AdditiveGroup, Field},
+    };
+
+    /// Only uses `core` and `alloc`, so this also runs without `std`.
+    #[test]
+    fn cached_transform_with_alloc_vec() {
+        let order = Pow2::new(8).unwrap();
+        let cache = TwiddleCache::<Goldilocks>::new(order);
+        let input = (1..=8_u64).map(Goldilocks::from).collect::<Vec<_>>();
+        let mut values = NTT::new(input.clone(), 1).unwrap();
+        cache.ntt_nr_cached(&mut values);
+
+        // Output `i` is the evaluation at ω^k, with `k` the bit reversal of `i`
+        let omega = Goldilocks::root_of_unity(order).unwrap();
+        for (i, value) in values.iter().enumerate() {
+            let k = i.reverse_bits() >> (usize::BITS - 3);
+            let point = omega.pow([k as u64]);
+            let expected = input
+                .iter()
+                .rev()
+                .fold(Goldilocks::ZERO, |acc, coefficient| {
+                    acc * point + coefficient
+                });
+            assert_eq!(*value, expected);
+        }
+
+        cache.intt_rn_cached(&mut values);
+        assert_eq!(values.into_inner(), input);
+    }
+}
@@ -1,14 +1,111 @@
 /// Executable for profiling NTT
 use {
+    argh::FromArgs,
     ark_bn254::Fr,
-    ntt::{ntt_nr, NTT},
-    std::hint::black_box,
+    ntt::{intt_rn, ntt_nr, Goldilocks, NttField, NTT},
+    std::{fmt, hint::black_box, process::exit, str::FromStr, time::Instant},
 };
 
+/// Time power-of-two NTTs.
+#[derive(FromArgs)]
+struct Args {
+    /// base two logarithm of the transform size
+    #[argh(option, default = "24")]
+    log_size: u32,
+
+    /// field of the transform, bn254 or goldilocks
+    #[argh(option, default = "Field::Bn254")]
+    field: Field,
+
+    /// number of transforms to time
+    #[argh(option, default = "1")]
+    iterations: usize,
+
+    /// time the inverse transform
+    #[argh(switch)]
+    inverse: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Bn254,
+    Goldilocks,
+}
+
+impl FromStr for Field {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bn254" => Ok(Self::Bn254),
+            "goldilocks" => Ok(Self::Goldilocks),
+            _ => Err(format!("unknown field {s}, expected bn254 or goldilocks")),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bn254 => "bn254",
+            Self::Goldilocks => "goldilocks",
+        })
+    }
+}
+
 fn main() {
+    let args: Args = argh::from_env();
     rayon::ThreadPoolBuilder::new().build_global().unwrap();
 
-    let mut input = NTT::new(vec![Fr::from(1); 2_usize.pow(24)], 1).unwrap();
-    ntt_nr(&mut input);
+    let seconds = match args.field {
+        Field::Bn254 => profile::<Fr>(&args),
+        Field::Goldilocks => profile::<Goldilocks>(&args),
+    };
+    println!(
+        "{} {} of size 2^{}: {}s per transform",
+        args.field,
+        if args.inverse { "inverse NTT" } else { "NTT" },
+        args.log_size,
+        human(seconds)
+    );
+}
+
+/// Runs the transforms and returns the mean time of one in seconds
+fn profile<F: NttField>(args: &Args) -> f64 {
+    if args.log_size > F::LOG_MAX_ORDER {
+        eprintln!(
+            "{} supports NTTs up to size 2^{}",
+            args.field,
+            F::LOG_MAX_ORDER
+        );
+        exit(1);
+    }
+    if args.iterations == 0 {
+        eprintln!("iterations must be positive");
+        exit(1);
+    }
+
+    let mut input = NTT::new(vec![F::ONE; 1 << args.log_size], 1).unwrap();
+    let start = Instant::now();
+    for _ in 0..args.iterations {
+        if args.inverse {
+            intt_rn(&mut input);
+        } else {
+            ntt_nr(&mut input);
+        }
+    }
+    let elapsed = start.elapsed();
     black_box(input);
+    elapsed.as_secs_f64() / args.iterations as f64
+}
+
+/// Formats `value` with an SI prefix and three significant digits
+fn human(value: f64) -> String {
+    let (scaled, prefix) = [(1e0, ""), (1e-3, "m"), (1e-6, "μ"), (1e-9, "n")]
+        .into_iter()
+        .find(|(scale, _)| value >= *scale)
+        .map_or((value * 1e12, "p"), |(scale, prefix)| {
+            (value / scale, prefix)
+        });
+    format!("{scaled:.3} {prefix}")
 }
@@ -1,15 +1,22 @@
+#[cfg(feature = "parallel")]
+use rayon::{
+    iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+#[cfg(feature = "std")]
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+};
 use {
-    crate::{NTTContainer, Pow2, NTT},
-    ark_bn254::Fr,
-    ark_ff::{FftField, Field},
-    rayon::{
-        iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator},
-        slice::ParallelSliceMut,
-    },
-    std::{
-        mem::size_of,
-        sync::{LazyLock, RwLock},
+    crate::{NTTContainer, NttField, Pow2, NTT},
+    alloc::{
+        sync::Arc,
+        vec::{self, Vec},
     },
+    ark_bn254::Fr,
+    core::mem::size_of,
 };
 
 // Taken from utils in noir-r1cs crate
@@ -22,17 +29,14 @@ pub const fn workload_size<T: Sized>() -> usize {
 }
 
 /// NTTEngine allows for reusing twiddle factors between computations
-pub struct NTTEngine(Vec<Fr>);
+pub struct NTTEngine<F: NttField = Fr>(Vec<F>);
 
-impl NTTEngine {
+impl<F: NttField> NTTEngine<F> {
     /// Initialize an NTT Engine
     ///
     /// Note: new will initialize half a L1 cache size worth of twiddle factors
     pub fn new() -> Self {
-        let init = init_roots_reverse_ordered(
-            Pow2::new(workload_size::<Fr>().next_power_of_two()).unwrap(),
-            None,
-        );
+        let init = init_roots_reverse_ordered(initial_order::<F>(), None);
         NTTEngine(init)
     }
 
@@ -41,10 +45,7 @@ impl NTTEngine {
     /// Note: with_order will initialise at least half a L1 cache size worth of
     /// twiddle factors.
     pub fn with_order(order: Pow2<usize>) -> Self {
-        let init = init_roots_reverse_ordered(
-            Pow2::new(workload_size::<Fr>().next_power_of_two()).unwrap(),
-            Some(*order / 2),
-        );
+        let init = init_roots_reverse_ordered(initial_order::<F>(), Some(*order / 2));
         let mut engine = NTTEngine(init);
         engine.extend_roots_table(order);
         engine
@@ -56,8 +57,9 @@ impl NTTEngine {
     /// When there is not enough space available in the underlying vector the
     /// old roots will be copied over and they do not have to be recomputed.
     ///
-    /// The new roots are computed in parallel based on the old roots thus
-    /// ensure a large enough initial root table for proper parallelization.
+    /// With the `parallel` feature, the new roots are computed in parallel
+    /// based on the old roots thus ensure a large enough initial root table for
+    /// proper parallelization.
     fn extend_roots_table(&mut self, order: Pow2<usize>) {
         let table = &mut self.0;
 
@@ -68,24 +70,29 @@ impl NTTEngine {
 
         if new_half_order > old_half_order {
             let col_len = new_half_order / old_half_order;
-            let unity = Fr::get_root_of_unity(*order as u64).unwrap();
+            let unity = F::root_of_unity(order).unwrap();
             table.reserve_exact(new_half_order - old_half_order);
             let (init, uninit) = table.split_at_spare_mut();
 
             // When viewing the roots as a matrix every row is a multiple of the first row
             // row[j] = row[0] * unity^(reverse order j)
 
-            uninit
-                .par_chunks_mut(old_half_order)
-                .enumerate()
-                .for_each(|(i, row)| {
-                    // start counting from one as 0 is init above
-                    let pow = reverse_bits(1 + i, col_len.trailing_zeros());
-                    let root = unity.pow([pow as u64]);
-                    row.par_iter_mut().enumerate().for_each(|(j, elem)| {
-                        elem.write(init[j] * root);
-                    })
-                });
+            #[cfg(feature = "parallel")]
+            let rows = uninit.par_chunks_mut(old_half_order);
+            #[cfg(not(feature = "parallel"))]
+            let rows = uninit.chunks_mut(old_half_order);
+            rows.enumerate().for_each(|(i, row)| {
+                // start counting from one as 0 is init above
+                let pow = reverse_bits(1 + i, col_len.trailing_zeros());
+                let root = unity.pow([pow as u64]);
+                #[cfg(feature = "parallel")]
+                let elems = row.par_iter_mut();
+                #[cfg(not(feature = "parallel"))]
+                let elems = row.iter_mut();
+                elems.enumerate().for_each(|(j, elem)| {
+                    elem.write(init[j] * root);
+                })
+            });
 
             unsafe {
                 table.set_len(new_half_order);
@@ -99,7 +106,132 @@ impl NTTEngine {
     }
 }
 
-static ENGINE: LazyLock<RwLock<NTTEngine>> = LazyLock::new(|| RwLock::new(NTTEngine::new()));
+/// Immutable table of twiddle factors for NTTs up to a fixed order.
+///
+/// Unlike [`ntt_nr`], which goes through a global lock on every call, the cache
+/// is computed once and can be cheaply cloned and shared between threads, e.g.
+/// across a rayon pool, for repeated transforms of the same size.
+#[derive(Clone, Debug)]
+pub struct TwiddleCache<F: NttField = Fr> {
+    order: Pow2<usize>,
+    roots: Arc<[F]>,
+}
+
+impl<F: NttField> TwiddleCache<F> {
+    /// Precompute the twiddle factors for NTTs up to `order`.
+    pub fn new(order: Pow2<usize>) -> Self {
+        let engine = NTTEngine::with_order(order);
+        Self {
+            order: engine.order(),
+            roots: engine.0.into(),
+        }
+    }
+
+    /// The maximum order of NTTs supported by this cache.
+    pub fn order(&self) -> Pow2<usize> {
+        self.order
+    }
+
+    /// Same as [`ntt_nr`] but uses the cached twiddle factors.
+    ///
+    /// # Panics
+    /// Panics if the order of `values` exceeds [`TwiddleCache::order`].
+    pub fn ntt_nr_cached<C: NTTContainer<F>>(&self, values: &mut NTT<F, C>) {
+        assert!(
+            values.order() <= self.order,
+            "NTT of order {} exceeds twiddle cache of order {}",
+            *values.order(),
+            *self.order
+        );
+        interleaved_ntt_nr(&self.roots, values, NttOptions::for_field::<F>())
+    }
+
+    /// Same as [`intt_rn`] but uses the cached twiddle factors. This is the
+    /// inverse of [`TwiddleCache::ntt_nr_cached`].
+    ///
+    /// # Panics
+    /// Panics if the order of `values` exceeds [`TwiddleCache::order`].
+    pub fn intt_rn_cached<C: NTTContainer<F>>(&self, values: &mut NTT<F, C>) {
+        reverse_order(values);
+        intt_nr(values, |values| self.ntt_nr_cached(values));
+        reverse_order(values);
+    }
+}
+
+/// Order of the initial twiddle table: half a L1 cache size worth of twiddle
+/// factors, limited by the roots of unity available in the field.
+fn initial_order<F: NttField>() -> Pow2<usize> {
+    let order = workload_size::<F>()
+        .next_power_of_two()
+        .min(1 << F::LOG_MAX_ORDER.min(usize::BITS - 1));
+    Pow2::new(order).unwrap()
+}
+
+/// Returns the global engine for the field `F`.
+///
+/// The engines are created on first use and live for the rest of the program.
+#[cfg(feature = "std")]
+fn engine<F: NttField>() -> &'static RwLock<NTTEngine<F>> {
+    type Engines = HashMap<TypeId, &'static (dyn Any + Send + Sync)>;
+    static ENGINES: LazyLock<RwLock<Engines>> = LazyLock::new(Default::default);
+
+    let id = TypeId::of::<F>();
+    let engine = ENGINES.read().unwrap().get(&id).copied();
+    let engine = engine.unwrap_or_else(|| {
+        *ENGINES
+            .write()
+            .unwrap()
+            .entry(id)
+            .or_insert_with(|| Box::leak(Box::new(RwLock::new(NTTEngine::<F>::new()))))
+    });
+    engine.downcast_ref().unwrap()
+}
+
+/// Tuning options for the NTT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NttOptions {
+    /// Sub-transforms with more elements than this are split across the rayon
+    /// pool. Smaller sub-transforms run sequentially on a single thread.
+    /// Without the `parallel` feature, all transforms run sequentially.
+    pub parallel_threshold: usize,
+    /// Fuse pairs of stages of the sequential sub-transforms into radix-4
+    /// butterflies, halving the passes over the data. With an odd number of
+    /// stages one radix-2 stage remains.
+    pub radix4:             bool,
+}
+
+impl NttOptions {
+    /// Default options for the field `F`: sub-transforms that fit in the L1
+    /// cache run sequentially.
+    pub const fn for_field<F>() -> Self {
+        Self {
+            parallel_threshold: workload_size::<F>(),
+            radix4:             true,
+        }
+    }
+
+    /// Run the entire transform on the current thread.
+    pub const fn sequential() -> Self {
+        Self {
+            parallel_threshold: usize::MAX,
+            radix4:             true,
+        }
+    }
+
+    /// Use only radix-2 butterflies.
+    pub const fn radix2(self) -> Self {
+        Self {
+            radix4: false,
+            ..self
+        }
+    }
+}
+
+impl Default for NttOptions {
+    fn default() -> Self {
+        Self::for_field::<Fr>()
+    }
+}
 
 /// Performs an in-place, interleaved Number Theoretic Transform (NTT) in
 /// normal-to-reverse bit order.
@@ -107,29 +239,98 @@ static ENGINE: LazyLock<RwLock<NTTEngine>> = LazyLock::new(|| RwLock::new(NTTEng
 /// # Arguments
 /// * `values` - A mutable reference to an NTT container holding the
 ///   coefficients to be transformed.
-pub fn ntt_nr<C: NTTContainer<Fr>>(values: &mut NTT<Fr, C>) {
-    let roots = ENGINE.read().unwrap();
+#[cfg(feature = "std")]
+pub fn ntt_nr<F: NttField, C: NTTContainer<F>>(values: &mut NTT<F, C>) {
+    ntt_nr_with_options(values, NttOptions::for_field::<F>());
+}
+
+/// Same as [`ntt_nr`] with explicit [`NttOptions`].
+///
+/// The result is identical for all options, only the scheduling differs.
+#[cfg(feature = "std")]
+pub fn ntt_nr_with_options<F: NttField, C: NTTContainer<F>>(
+    values: &mut NTT<F, C>,
+    options: NttOptions,
+) {
+    let engine = engine::<F>();
+    let roots = engine.read().unwrap();
     let new_root = if roots.order() >= values.order() {
         roots
     } else {
         // Drop read lock
         drop(roots);
-        let mut roots = ENGINE.write().unwrap();
+        let mut roots = engine.write().unwrap();
         roots.extend_roots_table(values.order());
         // Drop write lock
         drop(roots);
-        ENGINE.read().unwrap()
+        engine.read().unwrap()
     };
 
-    interleaved_ntt_nr(&new_root.0, values)
+    interleaved_ntt_nr(&new_root.0, values, options)
 }
 
-impl Default for NTTEngine {
+impl<F: NttField> Default for NTTEngine<F> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "std")]
+impl<F: NttField, C: NTTContainer<F>> NTT<F, C> {
+    /// Multiplies the polynomials `a` and `b` modulo `xⁿ + 1`.
+    ///
+    /// Interleaved polynomials are multiplied pairwise, i.e. the `i`-th
+    /// polynomial of `a` with the `i`-th polynomial of `b`. The result uses
+    /// the same interleaved layout.
+    ///
+    /// The inputs are twisted by powers of a primitive `2n`-th root of unity
+    /// `ψ`, which turns the negacyclic convolution into a cyclic one.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` have different lengths or orders, or if the field
+    /// has no primitive `2n`-th root of unity.
+    pub fn negacyclic_mul(a: &Self, b: &Self) -> Vec<F> {
+        assert_eq!(a.len(), b.len(), "inputs must have the same length");
+        assert_eq!(a.order(), b.order(), "inputs must have the same order");
+        let n = *a.order();
+        if n == 0 {
+            return vec![];
+        }
+        let width = a.len() / n;
+        let psi = F::root_of_unity(Pow2::new(2 * n).unwrap())
+            .expect("field has no primitive 2n-th root of unity");
+
+        let twist = |values: &Self| {
+            let mut twisted = values.to_vec();
+            let mut psi_i = F::ONE;
+            for row in twisted.chunks_exact_mut(width) {
+                row.iter_mut().for_each(|x| *x *= psi_i);
+                psi_i *= psi;
+            }
+            let mut twisted = NTT::new(twisted, width).unwrap();
+            ntt_nr(&mut twisted);
+            twisted
+        };
+
+        let mut product = twist(a);
+        let twisted_b = twist(b);
+        #[cfg(feature = "parallel")]
+        let products = product.par_iter_mut();
+        #[cfg(not(feature = "parallel"))]
+        let products = product.iter_mut();
+        products.zip(&twisted_b[..]).for_each(|(a, b)| *a *= b);
+        intt_rn(&mut product);
+
+        let psi_inv = psi.inverse().unwrap();
+        let mut psi_inv_i = F::ONE;
+        for row in product.chunks_exact_mut(width) {
+            row.iter_mut().for_each(|x| *x *= psi_inv_i);
+            psi_inv_i *= psi_inv;
+        }
+        product.into_inner()
+    }
+}
+
 /// In-place Number Theoretic Transform (NTT) from normal order to reverse bit
 /// order.
 ///
@@ -147,7 +348,11 @@ impl Default for NTTEngine {
 ///   order.
 /// * `values` - coefficients to be transformed in place with evaluation or vice
 ///   versa.
-fn interleaved_ntt_nr<C: NTTContainer<Fr>>(reversed_ordered_roots: &[Fr], values: &mut NTT<Fr, C>) {
+fn interleaved_ntt_nr<F: NttField, C: NTTContainer<F>>(
+    reversed_ordered_roots: &[F],
+    values: &mut NTT<F, C>,
+    options: NttOptions,
+) {
     // Reversed ordered roots idea from "Inside the FFT blackbox"
     // Implementation is a DIT NR algorithm
 
@@ -163,12 +368,47 @@ fn interleaved_ntt_nr<C: NTTContainer<Fr>>(reversed_ordered_roots: &[Fr], values
 
     let number_of_polynomials = n / order;
 
+    // Small transforms are done sequentially to avoid the rayon overhead.
+    #[cfg(feature = "parallel")]
+    if n > options.parallel_threshold {
+        parallel_ntt_nr(
+            reversed_ordered_roots,
+            values,
+            order,
+            number_of_polynomials,
+            options,
+        );
+        return;
+    }
+
+    dit_nr_cache(
+        reversed_ordered_roots,
+        0,
+        values,
+        number_of_polynomials,
+        options.radix4,
+    );
+}
+
+/// Splits the stages of a transform larger than the parallel threshold across
+/// the rayon pool, finishing the sub-transforms that fit with [`dit_nr_cache`].
+#[cfg(feature = "parallel")]
+fn parallel_ntt_nr<F: NttField>(
+    reversed_ordered_roots: &[F],
+    values: &mut [F],
+    order: usize,
+    number_of_polynomials: usize,
+    options: NttOptions,
+) {
+    let n = values.len();
+    let threshold = options.parallel_threshold;
+
     // Each unique twiddle factor within a stage is a group.
     let mut pairs_in_group = n / 2;
     let mut num_of_groups = 1;
 
     // For large NTTs we start with linear scans through memory and once all the
-    // elements of the sub NTTs reach the size of workload_size we know that they
+    // elements of the sub NTTs reach the size of the threshold we know that they
     // are contiguous in cache memory and we switch over to a different strategy.
     // If at the start the NTT already fits in cache memory we go directly to the
     // cache strategy strategy.
@@ -179,7 +419,7 @@ fn interleaved_ntt_nr<C: NTTContainer<Fr>>(reversed_ordered_roots: &[Fr], values
 
     // Parallelizing over the groups is most effective but in the beginning there
     // aren't enough groups to occupy all threads.
-    while num_of_groups < 32.min(order) && 2 * pairs_in_group > workload_size::<Fr>() {
+    while num_of_groups < 32.min(order) && 2 * pairs_in_group > threshold {
         values
             .chunks_exact_mut(2 * pairs_in_group)
             .enumerate()
@@ -195,7 +435,7 @@ fn interleaved_ntt_nr<C: NTTContainer<Fr>>(reversed_ordered_roots: &[Fr], values
         num_of_groups *= 2;
     }
 
-    while num_of_groups < order && 2 * pairs_in_group > workload_size::<Fr>() {
+    while num_of_groups < order && 2 * pairs_in_group > threshold {
         values
             .par_chunks_exact_mut(2 * pairs_in_group)
             .enumerate()
@@ -211,19 +451,31 @@ fn interleaved_ntt_nr<C: NTTContainer<Fr>>(reversed_ordered_roots: &[Fr], values
         num_of_groups *= 2;
     }
 
+    // A tiny threshold can complete all stages in the loops above.
+    if num_of_groups == order {
+        return;
+    }
+
     values
         .par_chunks_exact_mut(2 * pairs_in_group)
         .enumerate()
         .for_each(|(k, group)| {
-            dit_nr_cache(reversed_ordered_roots, k, group, number_of_polynomials);
+            dit_nr_cache(
+                reversed_ordered_roots,
+                k,
+                group,
+                number_of_polynomials,
+                options.radix4,
+            );
         });
 }
 
-fn dit_nr_cache(
-    reverse_ordered_roots: &[Fr],
+fn dit_nr_cache<F: NttField>(
+    reverse_ordered_roots: &[F],
     segment: usize,
-    input: &mut [Fr],
+    input: &mut [F],
     num_of_polys: usize,
+    radix4: bool,
 ) {
     let n = input.len();
     debug_assert!(n.is_power_of_two());
@@ -233,7 +485,12 @@ fn dit_nr_cache(
 
     let single_n = n / num_of_polys;
 
-    while num_of_groups < single_n {
+    // An odd stage left over by the radix-4 butterflies is done first.
+    let radix2_stages = match radix4 {
+        true => single_n.trailing_zeros() % 2,
+        false => single_n.trailing_zeros(),
+    };
+    for _ in 0..radix2_stages {
         let twiddle_base = segment * num_of_groups;
         for (k, group) in input.chunks_exact_mut(2 * pairs_in_group).enumerate() {
             let twiddle = twiddle_base + k;
@@ -246,6 +503,38 @@ fn dit_nr_cache(
         pairs_in_group /= 2;
         num_of_groups *= 2;
     }
+
+    // Each radix-4 butterfly does the radix-2 butterflies of two consecutive
+    // stages on four elements: group `k` of the first stage and groups `2k`
+    // and `2k + 1` of the second.
+    while num_of_groups < single_n {
+        let quarter = pairs_in_group / 2;
+        let twiddle_base = segment * num_of_groups;
+        for (k, group) in input.chunks_exact_mut(4 * quarter).enumerate() {
+            let twiddle = twiddle_base + k;
+            let omega = reverse_ordered_roots[twiddle];
+            let omega_even = reverse_ordered_roots[2 * twiddle];
+            let omega_odd = reverse_ordered_roots[2 * twiddle + 1];
+            let (low, high) = group.split_at_mut(2 * quarter);
+            let (a, b) = low.split_at_mut(quarter);
+            let (c, d) = high.split_at_mut(quarter);
+            a.iter_mut()
+                .zip(b)
+                .zip(c.iter_mut().zip(d))
+                .for_each(|((a, b), (c, d))| {
+                    let omega_c = omega * *c;
+                    let omega_d = omega * *d;
+                    let (a1, c1) = (*a + omega_c, *a - omega_c);
+                    let (b1, d1) = (*b + omega_d, *b - omega_d);
+                    let omega_b1 = omega_even * b1;
+                    let omega_d1 = omega_odd * d1;
+                    (*a, *b) = (a1 + omega_b1, a1 - omega_b1);
+                    (*c, *d) = (c1 + omega_d1, c1 - omega_d1);
+                });
+        }
+        pairs_in_group /= 4;
+        num_of_groups *= 4;
+    }
 }
 
 /// Bit reverses val for a given bit size
@@ -273,21 +562,21 @@ fn reverse_bits(val: usize, bits: u32) -> usize {
 /// * `order` - The order of the NTT (must be a power of 2 or zero)
 /// * `capacity` - Optional capacity hint for the vector. If `None`, defaults to
 ///   `n`. If provided, will use `max(capacity, n)` to ensure sufficient space.
-fn init_roots_reverse_ordered(order: Pow2<usize>, capacity: Option<usize>) -> Vec<Fr> {
+fn init_roots_reverse_ordered<F: NttField>(order: Pow2<usize>, capacity: Option<usize>) -> Vec<F> {
     match *order / 2 {
         0 => vec![],
         // 1 is a separate case due to `1.trailing_zeros = 0` which reverse_bit requires >0
-        1 => vec![Fr::ONE],
+        1 => vec![F::ONE],
         n => {
             // Use provided capacity or default to n, ensuring it's at least n
             let actual_capacity = capacity.map_or(n, |cap| cap.max(n));
 
-            let root = Fr::get_root_of_unity(*order as u64).unwrap();
+            let root = F::root_of_unity(order).unwrap();
 
             let mut roots = Vec::with_capacity(actual_capacity);
             let uninit = roots.spare_capacity_mut();
 
-            let mut omega_k = Fr::ONE;
+            let mut omega_k = F::ONE;
 
             for index in 0..n {
                 let rev = reverse_bits(index, n.trailing_zeros());
@@ -305,38 +594,67 @@ fn init_roots_reverse_ordered(order: Pow2<usize>, capacity: Option<usize>) -> Ve
 }
 
 // Reorder the input in reverse bit order, allows to convert from normal order
-// to reverse order or vice versa
+// to reverse order or vice versa. Interleaved polynomials are reordered
+// together.
 fn reverse_order<T, C: NTTContainer<T>>(values: &mut NTT<T, C>) {
     match *values.order() {
         0 | 1 => (),
         n => {
+            let width = values.len() / n;
             for index in 0..n {
                 let rev = reverse_bits(index, n.trailing_zeros());
                 if index < rev {
-                    values.swap(index, rev);
+                    let (low, high) = values.split_at_mut(rev * width);
+                    low[index * width..(index + 1) * width].swap_with_slice(&mut high[..width]);
                 }
             }
         }
     }
 }
 
+/// Performs an in-place, interleaved inverse NTT in reverse-to-normal bit
+/// order.
+///
+/// This is the inverse of [`ntt_nr`].
+///
 /// Note: not specifically optimized
-pub fn intt_rn<C: NTTContainer<Fr>>(input: &mut NTT<Fr, C>) {
+#[cfg(feature = "std")]
+pub fn intt_rn<F: NttField, C: NTTContainer<F>>(input: &mut NTT<F, C>) {
     reverse_order(input);
-    intt_nr(input);
+    intt_nr(input, ntt_nr);
     reverse_order(input);
 }
 
-// Inverse NTT
-fn intt_nr<C: NTTContainer<Fr>>(values: &mut NTT<Fr, C>) {
+/// Performs an in-place, interleaved inverse NTT with input and output in
+/// normal order.
+///
+/// The input holds the evaluations `[p(ω⁰), p(ω¹), ...]` of each polynomial
+/// and the output its coefficients.
+#[cfg(feature = "std")]
+pub fn intt<F: NttField, C: NTTContainer<F>>(values: &mut NTT<F, C>) {
+    intt_nr(values, ntt_nr);
+    reverse_order(values);
+}
+
+// Inverse NTT, using `ntt_nr` as the forward transform
+fn intt_nr<F: NttField, C: NTTContainer<F>>(
+    values: &mut NTT<F, C>,
+    ntt_nr: impl FnOnce(&mut NTT<F, C>),
+) {
     match *values.order() {
         0 => (),
         n => {
-            // Reverse the input such that the roots act as inverse roots
-            values[1..].reverse();
+            // Reverse the input such that the roots act as inverse roots. Only
+            // the order of the rows is reversed, interleaved polynomials stay in
+            // place within a row.
+            let width = values.len() / n;
+            values[width..].reverse();
+            values[width..]
+                .chunks_exact_mut(width)
+                .for_each(<[F]>::reverse);
             ntt_nr(values);
 
-            let factor = Fr::ONE / Fr::from(n as u64);
+            let factor = F::ONE / F::from(n as u64);
 
             for i in values.iter_mut() {
                 *i *= factor;
@@ -345,14 +663,14 @@ fn intt_nr<C: NTTContainer<Fr>>(values: &mut NTT<Fr, C>) {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     #[cfg(test)]
     use proptest::prelude::*;
     use {
         super::{init_roots_reverse_ordered, reverse_order},
         crate::{
-            ntt::{intt_rn, NTTEngine},
+            ntt::{intt, intt_rn, ntt_nr_with_options, NTTEngine, NttOptions, TwiddleCache},
             ntt_nr, Pow2, NTT,
         },
         ark_bn254::Fr,
@@ -429,6 +747,124 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn round_trip_ntt_interleaved((_, _, original) in interleaving_strategy(0_usize..12))
+        {
+            let mut s = original.clone();
+            ntt_nr(&mut s.0);
+            intt_rn(&mut s.0);
+            prop_assert_eq!(original, s);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_intt_normal_order((_, _, original) in interleaving_strategy(0_usize..12))
+        {
+            let mut s = original.clone();
+            ntt_nr(&mut s.0);
+            reverse_order(&mut s.0);
+            intt(&mut s.0);
+            prop_assert_eq!(original, s);
+        }
+    }
+
+    #[test]
+    fn intt_small() {
+        let mut v = NTT::new(Vec::<Fr>::new(), 1).unwrap();
+        intt_rn(&mut v);
+        intt(&mut v);
+
+        let mut v = NTT::new(vec![Fr::from(3), Fr::from(5)], 2).unwrap();
+        intt_rn(&mut v);
+        assert_eq!(v.into_inner(), vec![Fr::from(3), Fr::from(5)]);
+    }
+
+    fn schoolbook_negacyclic(a: &[Fr], b: &[Fr]) -> Vec<Fr> {
+        let n = a.len();
+        let mut c = vec![Fr::from(0); n];
+        for i in 0..n {
+            for j in 0..n {
+                if i + j < n {
+                    c[i + j] += a[i] * b[j];
+                } else {
+                    c[i + j - n] -= a[i] * b[j];
+                }
+            }
+        }
+        c
+    }
+
+    proptest! {
+        #[test]
+        fn negacyclic_mul(
+            (width, a, b) in (0_usize..=8, 0_usize..=2).prop_flat_map(|(k, w)| {
+                let len = (1 << k) << w;
+                (
+                    Just(1 << w),
+                    collection::vec(fr(), len..=len),
+                    collection::vec(fr(), len..=len),
+                )
+            })
+        ) {
+            let product = NTT::negacyclic_mul(
+                &NTT::new(a.clone(), width).unwrap(),
+                &NTT::new(b.clone(), width).unwrap(),
+            );
+            for column in 0..width {
+                let a = a.iter().skip(column).step_by(width).copied().collect::<Vec<_>>();
+                let b = b.iter().skip(column).step_by(width).copied().collect::<Vec<_>>();
+                let c = product.iter().skip(column).step_by(width).copied().collect::<Vec<_>>();
+                prop_assert_eq!(c, schoolbook_negacyclic(&a, &b));
+            }
+        }
+    }
+
+    #[test]
+    fn negacyclic_mul_wraps() {
+        // x · x^{n-1} = x^n = -1 mod x^n + 1
+        let n = 256;
+        let mut a = vec![Fr::from(0); n];
+        let mut b = vec![Fr::from(0); n];
+        a[1] = Fr::from(1);
+        b[n - 1] = Fr::from(1);
+        let c = NTT::negacyclic_mul(&NTT::new(a, 1).unwrap(), &NTT::new(b, 1).unwrap());
+        assert_eq!(c[0], -Fr::from(1));
+        assert!(c[1..].iter().all(|x| *x == Fr::from(0)));
+    }
+
+    proptest! {
+        #[test]
+        fn ntt_options_identical(
+            (_, _, original) in interleaving_strategy(0_usize..14),
+            threshold in 0_usize..1 << 15,
+        ) {
+            let mut expected = original.clone();
+            ntt_nr_with_options(&mut expected.0, NttOptions::sequential());
+            let mut actual = original;
+            ntt_nr_with_options(&mut actual.0, NttOptions {
+                parallel_threshold: threshold,
+                ..NttOptions::default()
+            });
+            prop_assert_eq!(actual, expected);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn radix4_matches_radix2((_, _, original) in interleaving_strategy(0_usize..14)) {
+            let mut radix2 = original.clone();
+            ntt_nr_with_options(&mut radix2.0, NttOptions::sequential().radix2());
+            let mut radix4 = original.clone();
+            ntt_nr_with_options(&mut radix4.0, NttOptions::sequential());
+            prop_assert_eq!(&radix4, &radix2);
+
+            intt_rn(&mut radix4.0);
+            prop_assert_eq!(radix4, original);
+        }
+    }
+
     // TODO Replace by parallel alternative to speed up tests
     fn transpose<T: Copy>(matrix: &[T], rows: usize, columns: usize) -> Vec<T> {
         assert_eq!(matrix.len(), rows * columns);
@@ -506,19 +942,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transpose_out_round_trip() {
+        let polys = (0..4_u64)
+            .map(|poly| (0..8).map(|i| Fr::from(10 * poly + i)).collect())
+            .collect::<Vec<Vec<Fr>>>();
+        let interleaved = NTT::from_separate(&polys).unwrap();
+        assert_eq!(*interleaved.order(), 8);
+        assert_eq!(&interleaved[..4], &[
+            Fr::from(0),
+            Fr::from(10),
+            Fr::from(20),
+            Fr::from(30)
+        ]);
+        assert_eq!(interleaved.transpose_out(), polys);
+        assert_eq!(interleaved.into_inner(), transpose(&polys.concat(), 4, 8));
+    }
+
+    #[test]
+    fn from_separate_rejects_invalid_lengths() {
+        assert!(NTT::<Fr, _>::from_separate(&[]).is_none());
+        assert!(NTT::from_separate(&[vec![Fr::from(1); 4], vec![Fr::from(1); 2]]).is_none());
+        assert!(NTT::from_separate(&[vec![Fr::from(1); 3], vec![Fr::from(1); 3]]).is_none());
+    }
+
     #[test]
     // The roundtrip test doesn't test size 0.
     fn ntt_empty() {
-        let mut v = NTT::new(vec![], 1).unwrap();
+        let mut v = NTT::new(Vec::<Fr>::new(), 1).unwrap();
         ntt_nr(&mut v);
     }
 
+    proptest! {
+        #[test]
+        fn twiddle_cache_matches_global((_, _, original) in interleaving_strategy(0_usize..14)) {
+            let cache = TwiddleCache::new(Pow2::new(1 << 14).unwrap());
+            let mut cached = original.clone();
+            cache.clone().ntt_nr_cached(&mut cached.0);
+            let mut global = original;
+            ntt_nr(&mut global.0);
+            prop_assert_eq!(cached, global);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds twiddle cache")]
+    fn twiddle_cache_too_small() {
+        let cache = TwiddleCache::new(Pow2::new(2).unwrap());
+        let order = 2 * *cache.order();
+        let mut values = NTT::new(vec![Fr::from(1); order], 1).unwrap();
+        cache.ntt_nr_cached(&mut values);
+    }
+
     // Compare direct generation of the roots vs. extending from a base set of roots
     #[test]
     fn roots_direct_vs_extended() {
         let order = Pow2::new(2_usize.pow(20)).unwrap();
         let roots = init_roots_reverse_ordered(order, None);
-        let engine = NTTEngine::with_order(order);
+        let engine = NTTEngine::<Fr>::with_order(order);
         assert_eq!(engine.0.len(), roots.len());
         assert_eq!(engine.0, roots)
     }
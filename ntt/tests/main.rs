@@ -0,0 +1,34 @@
+use std::process::Command;
+
+#[test]
+fn profiles_small_transforms() {
+    for args in [
+        &["--log-size", "4"][..],
+        &[
+            "--log-size",
+            "4",
+            "--field",
+            "goldilocks",
+            "--iterations",
+            "3",
+        ],
+        &["--log-size", "4", "--inverse"],
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_ntt"))
+            .args(args)
+            .output()
+            .expect("Running ntt");
+        assert!(output.status.success(), "{args:?}: {output:?}");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("per transform"), "{stdout}");
+    }
+}
+
+#[test]
+fn rejects_unsupported_size() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ntt"))
+        .args(["--log-size", "40", "--field", "goldilocks"])
+        .output()
+        .expect("Running ntt");
+    assert!(!output.status.success());
+}
@@ -2,40 +2,56 @@ pub mod mock_generator;
 pub mod mock_keys;
 mod parser;
 
-pub use crate::parser::{binary::Binary, sod::SOD};
+pub use crate::parser::{
+    binary::Binary,
+    ecdsa::EC_COORDINATE_BYTES,
+    masterlist::load_csca_from_masterlist,
+    sod::SOD,
+    types::{DigestAlgorithm, EcCurve, PassportError},
+    utils::{CscaKey, CscaMasterlist},
+};
 use {
     crate::parser::{
-        types::{
-            PassportError, SignatureAlgorithmName, MAX_DG1_SIZE, MAX_ECONTENT_SIZE,
-            MAX_SIGNED_ATTRIBUTES_SIZE, MAX_TBS_SIZE, SIG_BYTES,
-        },
+        ecdsa::EcdsaPublicKey,
+        mrz,
+        signature::verify_signer_info,
+        types::{SignatureAlgorithmName, MAX_DG1_SIZE, MAX_TBS_SIZE},
         utils::{
-            find_offset, fit, load_csca_public_keys, to_fixed_array, to_u32, ASN1_HEADER_LEN,
-            ASN1_OCTET_STRING_TAG,
+            byte_array, csca_keys_for, find_offset, find_offset_after, fit, fit_to,
+            load_csca_public_keys, pkcs1v15_scheme, read_tlv, to_sized_vec, to_u32,
+            ASN1_HEADER_LEN, ASN1_INTEGER_TAG, ASN1_OCTET_STRING_TAG, ASN1_SET_TAG, EF_DG1_TAG,
+            EF_SOD_TAG, MRZ_TAG,
         },
     },
     base64::{engine::general_purpose::STANDARD, Engine as _},
+    chrono::DateTime,
     noir_bignum_paramgen::compute_barrett_reduction_parameter,
     rsa::{
         pkcs1::DecodeRsaPublicKey, pkcs8::DecodePublicKey, traits::PublicKeyParts, BigUint,
-        Pkcs1v15Sign, Pss, RsaPublicKey,
+        RsaPublicKey,
     },
-    sha2::{Digest, Sha256},
-    std::{fmt::Write as _, path::Path},
+    serde::{Deserialize, Serialize},
+    std::{borrow::Cow, fmt::Write as _, path::Path},
 };
 
 /// Parsed passport data
 pub struct PassportReader {
     dg1:         Binary,
+    /// Optional DG2 (face image), checked against the SOD when present
+    dg2:         Option<Binary>,
     sod:         SOD,
     /// Indicates whether this reader contains mock data or real passport data
     mockdata:    bool,
     /// Optional CSCA public key when using mock data
     csca_pubkey: Option<RsaPublicKey>,
+    /// CSCA keys to use instead of `csca_registry/csca_public_key.json`
+    masterlist:  Option<CscaMasterlist>,
 }
 
 /// Circuit inputs for Noir
+#[derive(Serialize, Deserialize)]
 pub struct CircuitInputs {
+    #[serde(with = "byte_array")]
     pub dg1: [u8; MAX_DG1_SIZE],
     pub dg1_padded_length: usize,
     pub current_date: u64,
@@ -45,58 +61,213 @@ pub struct CircuitInputs {
 }
 
 /// Extracted validity contents from SOD
+#[derive(Serialize, Deserialize)]
 pub struct PassportValidityContent {
-    pub signed_attributes: [u8; MAX_SIGNED_ATTRIBUTES_SIZE],
+    /// Padded to [`DigestAlgorithm::max_signed_attributes_size`]
+    pub signed_attributes: Vec<u8>,
     pub signed_attributes_size: usize,
-    pub econtent: [u8; MAX_ECONTENT_SIZE],
+    /// Padded to [`DigestAlgorithm::max_econtent_size`]
+    pub econtent: Vec<u8>,
     pub econtent_len: usize,
-    pub dsc_pubkey: [u8; SIG_BYTES],
-    pub dsc_barrett_mu: [u8; SIG_BYTES + 1],
-    pub dsc_signature: [u8; SIG_BYTES],
-    pub dsc_rsa_exponent: u32,
-    pub csc_pubkey: [u8; SIG_BYTES * 2],
-    pub csc_barrett_mu: [u8; (SIG_BYTES * 2) + 1],
-    pub dsc_cert_signature: [u8; SIG_BYTES * 2],
+    #[serde(flatten)]
+    pub dsc: DscInputs,
+    /// CSCA modulus, as long as the CSCA key
+    pub csc_pubkey: Vec<u8>,
+    pub csc_barrett_mu: Vec<u8>,
+    pub dsc_cert_signature: Vec<u8>,
     pub csc_rsa_exponent: u32,
     pub dg1_hash_offset: usize,
     pub econtent_hash_offset: usize,
     pub dsc_pubkey_offset_in_dsc_cert: usize,
+    #[serde(with = "byte_array")]
     pub dsc_cert: [u8; MAX_TBS_SIZE],
     pub dsc_cert_len: usize,
 }
 
+/// DSC public key and SignedAttributes signature, by DSC key type. Serialized
+/// under the same `dsc_*` names as in `Prover.toml`.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DscInputs {
+    /// RSA key of any size: `pubkey` and `signature` are as long as the
+    /// modulus and `barrett_mu` one byte longer
+    Rsa {
+        #[serde(rename = "dsc_pubkey")]
+        pubkey:     Vec<u8>,
+        #[serde(rename = "dsc_barrett_mu")]
+        barrett_mu: Vec<u8>,
+        #[serde(rename = "dsc_signature")]
+        signature:  Vec<u8>,
+        #[serde(rename = "dsc_rsa_exponent")]
+        exponent:   u32,
+    },
+    Ecdsa {
+        /// Only in JSON, the circuit is specific to the curve
+        #[serde(rename = "dsc_curve")]
+        curve:       EcCurve,
+        #[serde(rename = "dsc_pubkey_x")]
+        pubkey_x:    [u8; EC_COORDINATE_BYTES],
+        #[serde(rename = "dsc_pubkey_y")]
+        pubkey_y:    [u8; EC_COORDINATE_BYTES],
+        #[serde(rename = "dsc_signature_r")]
+        signature_r: [u8; EC_COORDINATE_BYTES],
+        #[serde(rename = "dsc_signature_s")]
+        signature_s: [u8; EC_COORDINATE_BYTES],
+    },
+}
+
+impl DscInputs {
+    /// Public key bytes as they appear in the DSC certificate: the RSA
+    /// modulus, or the EC point coordinates `X || Y`
+    fn pubkey_bytes(&self) -> Vec<u8> {
+        match self {
+            DscInputs::Rsa { pubkey, .. } => pubkey.clone(),
+            DscInputs::Ecdsa {
+                pubkey_x, pubkey_y, ..
+            } => [&pubkey_x[..], &pubkey_y[..]].concat(),
+        }
+    }
+}
+
+/// CSCA modulus, exponent, Barrett mu and DSC certificate signature
+type CscaInputs = (Vec<u8>, u32, Vec<u8>, Vec<u8>);
+
+/// RSA modulus, exponent and Barrett mu. The modulus is as long as the key
+/// and the Barrett mu one byte longer.
+fn rsa_key_inputs(
+    pubkey: &RsaPublicKey,
+    label: &str,
+) -> Result<(Vec<u8>, u32, Vec<u8>), PassportError> {
+    let modulus = pubkey.n().to_bytes_be();
+    let exponent = to_u32(pubkey.e().to_bytes_be())?;
+    let barrett = to_sized_vec(
+        &compute_barrett_reduction_parameter(&BigUint::from_bytes_be(&modulus)).to_bytes_be(),
+        modulus.len() + 1,
+        &format!("{label} Barrett"),
+    )?;
+    Ok((modulus, exponent, barrett))
+}
+
 impl PassportReader {
     pub fn new(dg1: Binary, sod: SOD, mockdata: bool, csca_pubkey: Option<RsaPublicKey>) -> Self {
         Self {
             dg1,
+            dg2: None,
             sod,
             mockdata,
             csca_pubkey,
+            masterlist: None,
+        }
+    }
+
+    /// Reads a real passport from the EF.SOD and EF.DG1 files as read from the
+    /// chip. Bytes after the outer TLV of each file, such as the status word
+    /// of the last read, are ignored.
+    pub fn from_nfc_dump(ef_sod: &[u8], ef_dg1: &[u8]) -> Result<Self, PassportError> {
+        let (tag, sod, _) = read_tlv(ef_sod, "EF.SOD")?;
+        if tag != EF_SOD_TAG {
+            return Err(PassportError::MalformedTlv(format!(
+                "EF.SOD, unexpected tag {}",
+                hex::encode(tag)
+            )));
+        }
+        let sod = SOD::from_der(&mut Binary::from_slice(sod))?;
+
+        let (tag, contents, rest) = read_tlv(ef_dg1, "EF.DG1")?;
+        let (mrz_tag, ..) = read_tlv(contents, "EF.DG1 MRZ")?;
+        if tag != EF_DG1_TAG || mrz_tag != MRZ_TAG {
+            return Err(PassportError::MalformedTlv(format!(
+                "EF.DG1, unexpected tags {} and {}",
+                hex::encode(tag),
+                hex::encode(mrz_tag)
+            )));
+        }
+        // The SOD holds the hash of the whole file, tag and length included
+        let dg1 = Binary::from_slice(&ef_dg1[..ef_dg1.len() - rest.len()]);
+
+        Ok(Self::new(dg1, sod, false, None))
+    }
+
+    /// Also verify the hash of DG2 (face image) in [`Self::validate`]
+    pub fn with_dg2(mut self, dg2: Binary) -> Self {
+        self.dg2 = Some(dg2);
+        self
+    }
+
+    /// Look up the CSCA keys of real passports in `masterlist`, e.g. loaded
+    /// with [`load_csca_from_masterlist`], instead of the bundled JSON
+    pub fn with_masterlist(mut self, masterlist: CscaMasterlist) -> Self {
+        self.masterlist = Some(masterlist);
+        self
+    }
+
+    /// CSCA keys set with [`Self::with_masterlist`], or else the bundled ones
+    fn csca_masterlist(&self) -> Result<Cow<'_, CscaMasterlist>, Box<dyn std::error::Error>> {
+        match &self.masterlist {
+            Some(masterlist) => Ok(Cow::Borrowed(masterlist)),
+            None => load_csca_public_keys().map(Cow::Owned),
         }
     }
 
+    /// Digest of the data group hashes stored in eContent
+    fn lds_digest(&self) -> DigestAlgorithm {
+        self.sod.encap_content_info.e_content.hash_algorithm
+    }
+
+    /// Digest of eContent stored in SignedAttributes
+    fn signer_digest(&self) -> DigestAlgorithm {
+        self.sod.signer_info.digest_algorithm
+    }
+
     /// Extract SignedAttributes (padded + size)
-    fn extract_signed_attrs(
-        &self,
-    ) -> Result<([u8; MAX_SIGNED_ATTRIBUTES_SIZE], usize), PassportError> {
+    fn extract_signed_attrs(&self) -> Result<(Vec<u8>, usize), PassportError> {
         let signed_attrs = self.sod.signer_info.signed_attrs.bytes.as_bytes();
         let size = signed_attrs.len();
-        let padded = fit::<MAX_SIGNED_ATTRIBUTES_SIZE>(signed_attrs)?;
+        let padded = fit_to(
+            signed_attrs,
+            self.signer_digest().max_signed_attributes_size(),
+        )?;
         Ok((padded, size))
     }
 
     /// Extract eContent (padded + size + raw bytes)
-    fn extract_econtent(&self) -> Result<([u8; MAX_ECONTENT_SIZE], usize, &[u8]), PassportError> {
+    fn extract_econtent(&self) -> Result<(Vec<u8>, usize, &[u8]), PassportError> {
         let econtent_bytes = self.sod.encap_content_info.e_content.bytes.as_bytes();
         let len = econtent_bytes.len();
-        let padded = fit::<MAX_ECONTENT_SIZE>(econtent_bytes)?;
+        let padded = fit_to(econtent_bytes, self.lds_digest().max_econtent_size())?;
         Ok((padded, len, econtent_bytes))
     }
 
-    /// Extract DSC public key, exponent, Barrett mu, and signature
-    fn extract_dsc(
-        &self,
-    ) -> Result<([u8; SIG_BYTES], u32, [u8; SIG_BYTES + 1], [u8; SIG_BYTES]), PassportError> {
+    /// Whether the DSC holds an EC public key rather than an RSA one
+    fn has_ecdsa_dsc(&self) -> bool {
+        matches!(
+            self.sod
+                .certificate
+                .tbs
+                .subject_public_key_info
+                .signature_algorithm
+                .name,
+            SignatureAlgorithmName::EcPublicKey
+        )
+    }
+
+    /// Extract DSC public key and signature: RSA modulus, exponent and
+    /// Barrett mu, or EC point coordinates and signature r/s
+    fn extract_dsc(&self) -> Result<DscInputs, PassportError> {
+        if self.has_ecdsa_dsc() {
+            let pubkey =
+                EcdsaPublicKey::from_spki(&self.sod.certificate.tbs.subject_public_key_info)?;
+            let (signature_r, signature_s) =
+                pubkey.signature_components(self.sod.signer_info.signature.as_bytes())?;
+            return Ok(DscInputs::Ecdsa {
+                curve: pubkey.curve,
+                pubkey_x: pubkey.x,
+                pubkey_y: pubkey.y,
+                signature_r,
+                signature_s,
+            });
+        }
+
         let der = self
             .sod
             .certificate
@@ -107,100 +278,69 @@ impl PassportReader {
         let pubkey =
             RsaPublicKey::from_pkcs1_der(der).map_err(|_| PassportError::DscPublicKeyInvalid)?;
 
-        let modulus = to_fixed_array::<SIG_BYTES>(&pubkey.n().to_bytes_be(), "DSC modulus")?;
-        let exponent = to_u32(pubkey.e().to_bytes_be())?;
-        let barrett = to_fixed_array::<{ SIG_BYTES + 1 }>(
-            &compute_barrett_reduction_parameter(&BigUint::from_bytes_be(&modulus)).to_bytes_be(),
-            "DSC Barrett",
-        )?;
-        let signature = to_fixed_array::<SIG_BYTES>(
+        let (modulus, exponent, barrett) = rsa_key_inputs(&pubkey, "DSC")?;
+        let signature = to_sized_vec(
             self.sod.signer_info.signature.as_bytes(),
+            modulus.len(),
             "DSC signature",
         )?;
 
-        Ok((modulus, exponent, barrett, signature))
+        Ok(DscInputs::Rsa {
+            pubkey: modulus,
+            barrett_mu: barrett,
+            signature,
+            exponent,
+        })
     }
 
-    /// Extract CSCA public key, exponent, Barrett mu, and signature
+    /// Extract CSCA public key, exponent, Barrett mu, and signature for the
+    /// `idx`-th CSCA key of `country`
     fn extract_csca(
         &self,
+        masterlist: &CscaMasterlist,
+        country: &str,
         idx: usize,
-    ) -> Result<
-        (
-            [u8; SIG_BYTES * 2],
-            u32,
-            [u8; SIG_BYTES * 2 + 1],
-            [u8; SIG_BYTES * 2],
-        ),
-        PassportError,
-    > {
-        let csca_keys = load_csca_public_keys().map_err(|_| PassportError::FailedToLoadCscaKeys)?;
-        let usa_csca = csca_keys.get("USA").ok_or(PassportError::NoUsaCsca)?;
+    ) -> Result<CscaInputs, PassportError> {
+        let country_csca = csca_keys_for(masterlist, country)?;
         let der = STANDARD
-            .decode(usa_csca[idx].public_key.as_bytes())
+            .decode(country_csca[idx].public_key.as_bytes())
             .map_err(|e| PassportError::Base64DecodingFailed(e.to_string()))?;
         let pubkey = RsaPublicKey::from_public_key_der(&der)
             .map_err(|_| PassportError::CscaPublicKeyInvalid)?;
 
-        let modulus =
-            to_fixed_array::<{ SIG_BYTES * 2 }>(&pubkey.n().to_bytes_be(), "CSCA modulus")?;
-        let exponent = to_u32(pubkey.e().to_bytes_be())?;
-        let barrett = to_fixed_array::<{ SIG_BYTES * 2 + 1 }>(
-            &compute_barrett_reduction_parameter(&BigUint::from_bytes_be(&modulus)).to_bytes_be(),
-            "CSCA Barrett",
-        )?;
-        let signature = to_fixed_array::<{ SIG_BYTES * 2 }>(
-            self.sod.certificate.signature.as_bytes(),
-            "CSCA signature",
-        )?;
-
-        Ok((modulus, exponent, barrett, signature))
+        self.extract_csca_from_pubkey(&pubkey)
     }
 
     /// Extract CSCA data from an in-memory public key (used for mock data)
-    fn extract_csca_from_pubkey(
-        &self,
-        pubkey: &RsaPublicKey,
-    ) -> Result<
-        (
-            [u8; SIG_BYTES * 2],
-            u32,
-            [u8; SIG_BYTES * 2 + 1],
-            [u8; SIG_BYTES * 2],
-        ),
-        PassportError,
-    > {
-        let modulus =
-            to_fixed_array::<{ SIG_BYTES * 2 }>(&pubkey.n().to_bytes_be(), "CSCA modulus")?;
-        let exponent = to_u32(pubkey.e().to_bytes_be())?;
-        let barrett = to_fixed_array::<{ SIG_BYTES * 2 + 1 }>(
-            &compute_barrett_reduction_parameter(&BigUint::from_bytes_be(&modulus)).to_bytes_be(),
-            "CSCA Barrett",
-        )?;
-        let signature = to_fixed_array::<{ SIG_BYTES * 2 }>(
+    fn extract_csca_from_pubkey(&self, pubkey: &RsaPublicKey) -> Result<CscaInputs, PassportError> {
+        let (modulus, exponent, barrett) = rsa_key_inputs(pubkey, "CSCA")?;
+        let signature = to_sized_vec(
             self.sod.certificate.signature.as_bytes(),
+            modulus.len(),
             "CSCA signature",
         )?;
 
         Ok((modulus, exponent, barrett, signature))
     }
 
-    /// Extract DSC certificate (padded + len + offset of modulus inside cert)
+    /// Extract DSC certificate (padded + len + offset of public key inside
+    /// cert)
     fn extract_dsc_cert(
         &self,
-        dsc_modulus: &[u8; SIG_BYTES],
+        dsc_pubkey: &[u8],
     ) -> Result<([u8; MAX_TBS_SIZE], usize, usize), PassportError> {
         let tbs_bytes = self.sod.certificate.tbs.bytes.as_bytes();
         let cert_len = tbs_bytes.len();
         let padded = fit::<MAX_TBS_SIZE>(tbs_bytes)?;
-        let pubkey_offset = find_offset(tbs_bytes, dsc_modulus, "DSC modulus in cert")?;
+        let pubkey_offset = find_offset(tbs_bytes, dsc_pubkey, "DSC public key in cert")?;
         Ok((padded, cert_len, pubkey_offset))
     }
 
-    /// Validate DG1, eContent, and signatures against DSC + CSCA
-    pub fn validate(&self) -> Result<usize, PassportError> {
+    /// Validate DG1, eContent, and signatures against DSC + CSCA, looking up
+    /// the CSCA keys of `country` (ISO 3166-1 alpha-3) in the masterlist
+    pub fn validate(&self, country: &str) -> Result<usize, PassportError> {
         // 1. Check DG1 hash inside eContent
-        let dg1_hash = Sha256::digest(self.dg1.as_bytes());
+        let dg1_hash = self.lds_digest().digest(self.dg1.as_bytes())?;
         let dg1_from_econtent = self
             .sod
             .encap_content_info
@@ -215,8 +355,27 @@ impl PassportReader {
             return Err(PassportError::Dg1HashMismatch);
         }
 
+        if let Some(dg2) = &self.dg2 {
+            let dg2_hash = self.lds_digest().digest(dg2.as_bytes())?;
+            let dg2_from_econtent = self
+                .sod
+                .encap_content_info
+                .e_content
+                .data_group_hash_values
+                .values
+                .get(&2)
+                .ok_or(PassportError::MissingDg2Hash)?
+                .as_bytes();
+
+            if dg2_from_econtent != dg2_hash.as_slice() {
+                return Err(PassportError::Dg2HashMismatch);
+            }
+        }
+
         // 2. Check hash(eContent) inside SignedAttributes
-        let econtent_hash = Sha256::digest(self.sod.encap_content_info.e_content.bytes.as_bytes());
+        let econtent_hash = self
+            .signer_digest()
+            .digest(self.sod.encap_content_info.e_content.bytes.as_bytes())?;
         let mut msg_digest = self.sod.signer_info.signed_attrs.message_digest.as_bytes();
 
         if msg_digest.len() > ASN1_HEADER_LEN && msg_digest[0] == ASN1_OCTET_STRING_TAG {
@@ -228,89 +387,131 @@ impl PassportReader {
         }
 
         // 3. Verify SignedAttributes signature with DSC
-        let signed_attr_hash = Sha256::digest(self.sod.signer_info.signed_attrs.bytes.as_bytes());
-        let dsc_pubkey_bytes = self
-            .sod
-            .certificate
-            .tbs
-            .subject_public_key_info
-            .subject_public_key
-            .as_bytes();
-        let dsc_pubkey = RsaPublicKey::from_pkcs1_der(dsc_pubkey_bytes)
-            .map_err(|_| PassportError::DscPublicKeyInvalid)?;
-
-        let dsc_signature = self.sod.signer_info.signature.as_bytes();
-
-        let verify_result = match &self.sod.signer_info.signature_algorithm.name {
-            SignatureAlgorithmName::Sha256WithRsaEncryption
-            | SignatureAlgorithmName::RsaEncryption => dsc_pubkey.verify(
-                Pkcs1v15Sign::new::<Sha256>(),
-                signed_attr_hash.as_slice(),
-                dsc_signature,
-            ),
-            SignatureAlgorithmName::RsassaPss => dsc_pubkey.verify(
-                Pss::new::<Sha256>(),
-                signed_attr_hash.as_slice(),
-                dsc_signature,
-            ),
-            unsupported => {
-                return Err(PassportError::UnsupportedSignatureAlgorithm(format!(
-                    "{:?}",
-                    unsupported
-                )))
-            }
-        };
-        verify_result.map_err(|_| PassportError::DscSignatureInvalid)?;
+        verify_signer_info(
+            &self.sod.signer_info,
+            &self.sod.certificate.tbs.subject_public_key_info,
+        )?;
 
         // 4. Verify DSC certificate signature with CSCA
-        let tbs_bytes = self.sod.certificate.tbs.bytes.as_bytes();
-        let tbs_digest = Sha256::digest(tbs_bytes);
-        let csca_signature = self.sod.certificate.signature.as_bytes();
-
         if let Some(key) = &self.csca_pubkey {
+            let (tbs_digest_algorithm, tbs_digest) = self.tbs_digest()?;
             key.verify(
-                Pkcs1v15Sign::new::<Sha256>(),
-                tbs_digest.as_slice(),
-                csca_signature,
+                pkcs1v15_scheme(tbs_digest_algorithm)?,
+                &tbs_digest,
+                self.sod.certificate.signature.as_bytes(),
             )
             .map_err(|_| PassportError::CscaSignatureInvalid)?;
             return Ok(0);
         }
 
-        let all_csca = load_csca_public_keys().map_err(|_| PassportError::CscaKeysMissing)?;
-        let usa_csca = all_csca.get("USA").ok_or(PassportError::NoUsaCsca)?;
+        let masterlist = self
+            .csca_masterlist()
+            .map_err(|_| PassportError::CscaKeysMissing)?;
+        self.find_csca_index(&masterlist, country)
+    }
 
-        for (i, csca) in usa_csca.iter().enumerate() {
+    /// Digest algorithm and digest of the DSC certificate TBS bytes
+    fn tbs_digest(&self) -> Result<(DigestAlgorithm, Vec<u8>), PassportError> {
+        let digest_algorithm = self
+            .sod
+            .certificate
+            .signature_algorithm
+            .name
+            .digest_algorithm()
+            .unwrap_or(DigestAlgorithm::SHA256);
+        let digest = digest_algorithm.digest(self.sod.certificate.tbs.bytes.as_bytes())?;
+        Ok((digest_algorithm, digest))
+    }
+
+    /// Index of the CSCA key of `country` that signed the DSC certificate.
+    /// CSCA keys may be RSA or EC, independently of the DSC key.
+    fn find_csca_index(
+        &self,
+        masterlist: &CscaMasterlist,
+        country: &str,
+    ) -> Result<usize, PassportError> {
+        let (tbs_digest_algorithm, tbs_digest) = self.tbs_digest()?;
+        let tbs_bytes = self.sod.certificate.tbs.bytes.as_bytes();
+        let csca_algorithm = &self.sod.certificate.signature_algorithm.name;
+        let csca_signature = self.sod.certificate.signature.as_bytes();
+
+        for (i, csca) in csca_keys_for(masterlist, country)?.iter().enumerate() {
             let der = STANDARD
                 .decode(csca.public_key.as_bytes())
                 .map_err(|e| PassportError::Base64DecodingFailed(e.to_string()))?;
-            let csca_pubkey = RsaPublicKey::from_public_key_der(&der)
-                .map_err(|_| PassportError::CscaPublicKeyInvalid)?;
-            if csca_pubkey
-                .verify(
-                    Pkcs1v15Sign::new::<Sha256>(),
-                    tbs_digest.as_slice(),
-                    csca_signature,
-                )
-                .is_ok()
-            {
+            let verified = match RsaPublicKey::from_public_key_der(&der) {
+                Ok(csca_pubkey) => csca_pubkey
+                    .verify(
+                        pkcs1v15_scheme(tbs_digest_algorithm)?,
+                        &tbs_digest,
+                        csca_signature,
+                    )
+                    .is_ok(),
+                Err(_) => EcdsaPublicKey::from_public_key_der(&der)
+                    .ok_or(PassportError::CscaPublicKeyInvalid)?
+                    .verify(csca_algorithm, tbs_bytes, csca_signature)
+                    .is_ok(),
+            };
+            if verified {
                 return Ok(i);
             }
         }
         Err(PassportError::CscaSignatureInvalid)
     }
 
+    /// Checks the age range and the DG1 dates the circuit reads, with the
+    /// same century pivots as the circuit
+    fn check_dates(
+        dg1: &[u8; MAX_DG1_SIZE],
+        current_date: u64,
+        min_age_required: u8,
+        max_age_required: u8,
+    ) -> Result<(), PassportError> {
+        if max_age_required != 0 && min_age_required > max_age_required {
+            return Err(PassportError::InvalidAgeRange(
+                min_age_required,
+                max_age_required,
+            ));
+        }
+
+        let today = i64::try_from(current_date)
+            .ok()
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .ok_or_else(|| PassportError::InvalidDate(format!("timestamp {current_date}")))?
+            .date_naive();
+        let birthdate = mrz::birthdate(dg1, today)?;
+        mrz::expiry_date(dg1, today)?;
+        if today < birthdate {
+            return Err(PassportError::CurrentDateBeforeBirthdate(
+                today.to_string(),
+                birthdate.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Convert to circuit inputs for Noir Circuits
+    ///
+    /// `current_date` is a unix timestamp in seconds. A `max_age_required` of
+    /// 0 means no maximum age, otherwise it must not be below
+    /// `min_age_required`.
     pub fn to_circuit_inputs(
         &self,
         current_date: u64,
         min_age_required: u8,
         max_age_required: u8,
+        country: &str,
         csca_key_index: usize,
     ) -> Result<CircuitInputs, PassportError> {
         // === Step 1. DG1 ===
         let dg1_padded = fit::<MAX_DG1_SIZE>(self.dg1.as_bytes())?;
         let dg1_len = self.dg1.len();
+        Self::check_dates(
+            &dg1_padded,
+            current_date,
+            min_age_required,
+            max_age_required,
+        )?;
 
         // === Step 2. SignedAttributes ===
         let (signed_attrs, signed_attributes_size) = self.extract_signed_attrs()?;
@@ -319,7 +520,7 @@ impl PassportReader {
         let (econtent, econtent_len, econtent_bytes) = self.extract_econtent()?;
 
         // === Step 4. DSC ===
-        let (dsc_modulus, dsc_exponent, dsc_barrett, dsc_signature) = self.extract_dsc()?;
+        let dsc = self.extract_dsc()?;
 
         // === Step 5. CSCA ===
         let (csca_modulus, csca_exponent, csca_barrett, csca_signature) = if self.mockdata {
@@ -329,19 +530,45 @@ impl PassportReader {
                 .ok_or(PassportError::MissingCscaMockKey)?;
             self.extract_csca_from_pubkey(key)?
         } else {
-            self.extract_csca(csca_key_index)?
+            let masterlist = self
+                .csca_masterlist()
+                .map_err(|_| PassportError::FailedToLoadCscaKeys)?;
+            self.extract_csca(&masterlist, country, csca_key_index)?
         };
 
         // === Step 6. Offsets ===
-        let dg1_hash = Sha256::digest(self.dg1.as_bytes());
-        let dg1_hash_offset = find_offset(econtent_bytes, dg1_hash.as_slice(), "DG1 hash")?;
+        // The hashes may also appear elsewhere, so prefer the occurrence inside
+        // DataGroupHash { 1, OCTET STRING } and the messageDigest attribute
+        let dg1_hash = self.lds_digest().digest(self.dg1.as_bytes())?;
+        let dg1_hash_offset = find_offset_after(
+            econtent_bytes,
+            &dg1_hash,
+            &[
+                ASN1_INTEGER_TAG,
+                1,
+                1,
+                ASN1_OCTET_STRING_TAG,
+                dg1_hash.len() as u8,
+            ],
+            "DG1 hash",
+        )?;
 
-        let econtent_hash = Sha256::digest(econtent_bytes);
-        let econtent_hash_offset =
-            find_offset(&signed_attrs, econtent_hash.as_slice(), "eContent hash")?;
+        let econtent_hash = self.signer_digest().digest(econtent_bytes)?;
+        let econtent_hash_offset = find_offset_after(
+            &signed_attrs,
+            &econtent_hash,
+            &[
+                ASN1_SET_TAG,
+                econtent_hash.len() as u8 + 2,
+                ASN1_OCTET_STRING_TAG,
+                econtent_hash.len() as u8,
+            ],
+            "eContent hash",
+        )?;
 
         // === Step 7. DSC Certificate ===
-        let (dsc_cert, dsc_cert_len, dsc_pubkey_offset) = self.extract_dsc_cert(&dsc_modulus)?;
+        let (dsc_cert, dsc_cert_len, dsc_pubkey_offset) =
+            self.extract_dsc_cert(&dsc.pubkey_bytes())?;
 
         // === Step 8. Build CircuitInputs ===
         Ok(CircuitInputs {
@@ -355,10 +582,7 @@ impl PassportReader {
                 signed_attributes_size,
                 econtent,
                 econtent_len,
-                dsc_pubkey: dsc_modulus,
-                dsc_barrett_mu: dsc_barrett,
-                dsc_signature,
-                dsc_rsa_exponent: dsc_exponent,
+                dsc,
                 csc_pubkey: csca_modulus,
                 csc_barrett_mu: csca_barrett,
                 dsc_cert_signature: csca_signature,
@@ -392,10 +616,31 @@ impl CircuitInputs {
         );
         let _ = writeln!(out, "econtent = {:?}", pvc.econtent);
         let _ = writeln!(out, "econtent_len = {}", pvc.econtent_len);
-        let _ = writeln!(out, "dsc_signature = {:?}", pvc.dsc_signature);
-        let _ = writeln!(out, "dsc_rsa_exponent = {}", pvc.dsc_rsa_exponent);
-        let _ = writeln!(out, "dsc_pubkey = {:?}", pvc.dsc_pubkey);
-        let _ = writeln!(out, "dsc_barrett_mu = {:?}", pvc.dsc_barrett_mu);
+        match &pvc.dsc {
+            DscInputs::Rsa {
+                pubkey,
+                barrett_mu,
+                signature,
+                exponent,
+            } => {
+                let _ = writeln!(out, "dsc_signature = {:?}", signature);
+                let _ = writeln!(out, "dsc_rsa_exponent = {}", exponent);
+                let _ = writeln!(out, "dsc_pubkey = {:?}", pubkey);
+                let _ = writeln!(out, "dsc_barrett_mu = {:?}", barrett_mu);
+            }
+            DscInputs::Ecdsa {
+                curve: _,
+                pubkey_x,
+                pubkey_y,
+                signature_r,
+                signature_s,
+            } => {
+                let _ = writeln!(out, "dsc_signature_r = {:?}", signature_r);
+                let _ = writeln!(out, "dsc_signature_s = {:?}", signature_s);
+                let _ = writeln!(out, "dsc_pubkey_x = {:?}", pubkey_x);
+                let _ = writeln!(out, "dsc_pubkey_y = {:?}", pubkey_y);
+            }
+        }
         let _ = writeln!(out, "csc_pubkey = {:?}", pvc.csc_pubkey);
         let _ = writeln!(out, "csc_barrett_mu = {:?}", pvc.csc_barrett_mu);
         let _ = writeln!(out, "dsc_cert_signature = {:?}", pvc.dsc_cert_signature);
@@ -415,4 +660,15 @@ impl CircuitInputs {
     pub fn save_to_toml_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
         std::fs::write(path, self.to_toml_string())
     }
+
+    /// Same fields as [`Self::to_toml_string`], with byte arrays as arrays of
+    /// numbers
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("circuit inputs serialize to JSON")
+    }
+
+    pub fn save_to_json_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.to_json_value())?;
+        std::fs::write(path, json)
+    }
 }
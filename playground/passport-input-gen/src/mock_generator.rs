@@ -1,20 +1,31 @@
 use {
-    crate::parser::{
-        binary::Binary,
-        dsc::{SubjectPublicKeyInfo, TbsCertificate, DSC},
-        sod::SOD,
-        types::{
-            DataGroupHashValues, DigestAlgorithm, EContent, EncapContentInfo, SignatureAlgorithm,
-            SignatureAlgorithmName, SignedAttrs, SignerIdentifier, SignerInfo, MAX_DG1_SIZE,
+    crate::{
+        mock_keys::{MOCK_CSCA_PRIV_KEY_B64, MOCK_DSC_PRIV_KEY_B64},
+        parser::{
+            binary::Binary,
+            dsc::{SubjectPublicKeyInfo, TbsCertificate, DSC},
+            pss::PssParameters,
+            sod::SOD,
+            types::{
+                DataGroupHashValues, DigestAlgorithm, EContent, EcCurve, EncapContentInfo,
+                SignatureAlgorithm, SignatureAlgorithmName, SignedAttrs, SignerIdentifier,
+                SignerInfo, MAX_DG1_SIZE,
+            },
         },
+        PassportError, PassportReader,
     },
+    base64::{engine::general_purpose::STANDARD, Engine as _},
+    p256::elliptic_curve::sec1::ToEncodedPoint,
     rsa::{
         pkcs1::EncodeRsaPublicKey,
         pkcs1v15::SigningKey,
-        signature::{SignatureEncoding, Signer},
+        pkcs8::DecodePrivateKey,
+        pss,
+        rand_core::OsRng,
+        signature::{RandomizedSigner, SignatureEncoding, Signer},
         RsaPrivateKey, RsaPublicKey,
     },
-    sha2::{Digest, Sha256},
+    sha2::{Sha256, Sha384, Sha512},
     std::collections::HashMap,
 };
 
@@ -32,6 +43,54 @@ pub fn dg1_bytes_with_birthdate_expiry_date(birthdate: &[u8; 6], expiry: &[u8; 6
     dg1
 }
 
+/// Build a mock USA passport whose holder was born on `birthdate` and whose
+/// passport expires on `expiry`, both as YYMMDD. The SOD is signed with the
+/// mock DSC key, whose certificate is signed with the mock CSCA key. The
+/// reader checks against that CSCA key, so it passes validation.
+pub fn generate_usa_passport_sample(birthdate: &[u8; 6], expiry: &[u8; 6]) -> PassportReader {
+    let csca_priv = load_csca_mock_private_key();
+    let csca_pub = csca_priv.to_public_key();
+    let dsc_priv = load_dsc_mock_private_key();
+    let dsc_pub = dsc_priv.to_public_key();
+
+    let dg1 = dg1_bytes_with_birthdate_expiry_date(birthdate, expiry);
+    let sod = generate_fake_sod(&dg1, &dsc_priv, &dsc_pub, &csca_priv, &csca_pub);
+    PassportReader::new(Binary::from_slice(&dg1), sod, true, Some(csca_pub))
+}
+
+/// Circuit inputs of [`generate_usa_passport_sample`] for an age check at
+/// `current_date`, formatted as the TOML of a `Prover.toml` file.
+pub fn generate_usa_passport_sample_toml(
+    birthdate: &[u8; 6],
+    expiry: &[u8; 6],
+    current_date: u64,
+    min_age_required: u8,
+    max_age_required: u8,
+) -> Result<String, PassportError> {
+    let inputs = generate_usa_passport_sample(birthdate, expiry).to_circuit_inputs(
+        current_date,
+        min_age_required,
+        max_age_required,
+        "USA",
+        0,
+    )?;
+    Ok(inputs.to_toml_string())
+}
+
+fn load_csca_mock_private_key() -> RsaPrivateKey {
+    let der = STANDARD
+        .decode(MOCK_CSCA_PRIV_KEY_B64)
+        .expect("decode CSCA private key");
+    RsaPrivateKey::from_pkcs8_der(&der).expect("CSCA key")
+}
+
+fn load_dsc_mock_private_key() -> RsaPrivateKey {
+    let der = STANDARD
+        .decode(MOCK_DSC_PRIV_KEY_B64)
+        .expect("decode DSC private key");
+    RsaPrivateKey::from_pkcs8_der(&der).expect("DSC key")
+}
+
 /// Generate a synthetic SOD structure for the given DG1 and key pairs.
 pub fn generate_fake_sod(
     dg1: &[u8],
@@ -40,15 +99,247 @@ pub fn generate_fake_sod(
     csca_priv: &RsaPrivateKey,
     _csca_pub: &RsaPublicKey,
 ) -> SOD {
-    // Hash DG1 and build eContent
-    let dg1_hash = Sha256::digest(dg1);
-    let econtent_bytes = dg1_hash.to_vec();
+    generate_fake_sod_with_digest(dg1, DigestAlgorithm::SHA256, dsc_priv, dsc_pub, csca_priv)
+}
+
+/// Generate a synthetic SOD structure using `digest` for the data group
+/// hashes, eContent hash and both RSA signatures. Only SHA-256, SHA-384 and
+/// SHA-512 are supported.
+pub fn generate_fake_sod_with_digest(
+    dg1: &[u8],
+    digest: DigestAlgorithm,
+    dsc_priv: &RsaPrivateKey,
+    dsc_pub: &RsaPublicKey,
+    csca_priv: &RsaPrivateKey,
+) -> SOD {
+    fake_rsa_sod(&[(1, dg1)], digest, dsc_priv, dsc_pub, csca_priv)
+}
+
+/// Generate a synthetic SOD structure holding the hashes of both DG1 and DG2
+/// (face image).
+pub fn generate_fake_sod_with_dg2(
+    dg1: &[u8],
+    dg2: &[u8],
+    dsc_priv: &RsaPrivateKey,
+    dsc_pub: &RsaPublicKey,
+    csca_priv: &RsaPrivateKey,
+) -> SOD {
+    fake_rsa_sod(
+        &[(1, dg1), (2, dg2)],
+        DigestAlgorithm::SHA256,
+        dsc_priv,
+        dsc_pub,
+        csca_priv,
+    )
+}
+
+fn fake_rsa_sod(
+    data_groups: &[(u32, &[u8])],
+    digest: DigestAlgorithm,
+    dsc_priv: &RsaPrivateKey,
+    dsc_pub: &RsaPublicKey,
+    csca_priv: &RsaPrivateKey,
+) -> SOD {
+    let (encap_content_info, signed_attrs) = fake_signed_content(data_groups, digest);
+
+    // Sign SignedAttributes with DSC private key
+    let (algorithm, dsc_signature) = rsa_sign(dsc_priv, digest, signed_attrs.bytes.as_bytes());
+    let signer_info = fake_signer_info(signed_attrs, digest, algorithm, &dsc_signature);
+
+    // Build fake DSC certificate (TBS = DER of DSC public key)
+    let dsc_pub_der = dsc_pub.to_pkcs1_der().expect("pkcs1 der").to_vec();
+    let spki = SubjectPublicKeyInfo {
+        signature_algorithm: SignatureAlgorithm {
+            name:       SignatureAlgorithmName::RsaEncryption,
+            parameters: None,
+        },
+        subject_public_key:  Binary::from_slice(&dsc_pub_der),
+    };
+    let dsc_cert = fake_dsc_cert(spki, &dsc_pub_der, csca_priv, digest);
+
+    fake_sod(encap_content_info, signer_info, dsc_cert, digest)
+}
+
+/// Generate a synthetic SOD structure whose SignedAttributes are signed with
+/// RSASSA-PSS using SHA-256 and a salt of `salt_length` bytes.
+pub fn generate_fake_sod_pss(
+    dg1: &[u8],
+    salt_length: usize,
+    dsc_priv: &RsaPrivateKey,
+    dsc_pub: &RsaPublicKey,
+    csca_priv: &RsaPrivateKey,
+) -> SOD {
+    let mut sod = fake_rsa_sod(
+        &[(1, dg1)],
+        DigestAlgorithm::SHA256,
+        dsc_priv,
+        dsc_pub,
+        csca_priv,
+    );
+
+    // Replace the PKCS#1 v1.5 signature of SignedAttributes
+    let parameters = PssParameters {
+        salt_length,
+        ..PssParameters::with_digest(DigestAlgorithm::SHA256)
+    };
+    let signature = pss::SigningKey::<Sha256>::new_with_salt_len(dsc_priv.clone(), salt_length)
+        .sign_with_rng(&mut OsRng, sod.signer_info.signed_attrs.bytes.as_bytes())
+        .to_vec();
+    sod.signer_info.signature_algorithm = SignatureAlgorithm {
+        name:       SignatureAlgorithmName::RsassaPss,
+        parameters: Some(Binary::from_slice(&parameters.to_der())),
+    };
+    sod.signer_info.signature = Binary::from_slice(&signature);
+    sod
+}
+
+/// Generate a synthetic SOD structure signed by an ECDSA DSC on `curve`,
+/// whose private key is the big-endian scalar `dsc_secret`.
+pub fn generate_fake_sod_ecdsa(
+    dg1: &[u8],
+    curve: EcCurve,
+    dsc_secret: &[u8; 32],
+    csca_priv: &RsaPrivateKey,
+) -> SOD {
+    let (encap_content_info, signed_attrs) =
+        fake_signed_content(&[(1, dg1)], DigestAlgorithm::SHA256);
+
+    // Sign SignedAttributes with DSC private key
+    let (dsc_signature, dsc_point) = ecdsa_sign(curve, dsc_secret, signed_attrs.bytes.as_bytes());
+    let signer_info = fake_signer_info(
+        signed_attrs,
+        DigestAlgorithm::SHA256,
+        SignatureAlgorithmName::EcdsaWithSha256,
+        &dsc_signature,
+    );
+
+    // Build fake DSC certificate (TBS = SEC1 encoding of DSC public key)
+    let spki = SubjectPublicKeyInfo {
+        signature_algorithm: SignatureAlgorithm {
+            name:       SignatureAlgorithmName::EcPublicKey,
+            parameters: Some(Binary::from_slice(curve.oid())),
+        },
+        subject_public_key:  Binary::from_slice(&dsc_point),
+    };
+    let dsc_cert = fake_dsc_cert(spki, &dsc_point, csca_priv, DigestAlgorithm::SHA256);
+
+    fake_sod(
+        encap_content_info,
+        signer_info,
+        dsc_cert,
+        DigestAlgorithm::SHA256,
+    )
+}
+
+/// Generate a synthetic SOD structure with an RSA DSC whose certificate is
+/// signed by an ECDSA CSCA on `csca_curve`, whose private key is the
+/// big-endian scalar `csca_secret`.
+pub fn generate_fake_sod_with_ecdsa_csca(
+    dg1: &[u8],
+    dsc_priv: &RsaPrivateKey,
+    dsc_pub: &RsaPublicKey,
+    csca_curve: EcCurve,
+    csca_secret: &[u8; 32],
+) -> SOD {
+    let mut sod = fake_rsa_sod(
+        &[(1, dg1)],
+        DigestAlgorithm::SHA256,
+        dsc_priv,
+        dsc_pub,
+        dsc_priv,
+    );
+
+    // Replace the RSA signature of the DSC certificate
+    let (signature, _) = ecdsa_sign(
+        csca_curve,
+        csca_secret,
+        sod.certificate.tbs.bytes.as_bytes(),
+    );
+    let algorithm = SignatureAlgorithm {
+        name:       SignatureAlgorithmName::EcdsaWithSha256,
+        parameters: None,
+    };
+    sod.certificate.tbs.signature_algorithm = algorithm.clone();
+    sod.certificate.signature_algorithm = algorithm;
+    sod.certificate.signature = Binary::from_slice(&signature);
+    sod
+}
+
+/// ECDSA with SHA-256 signature over `message`, DER encoded, and the
+/// uncompressed SEC1 public key of the big-endian scalar `secret`.
+fn ecdsa_sign(curve: EcCurve, secret: &[u8; 32], message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    match curve {
+        EcCurve::Secp256r1 => {
+            let key = p256::ecdsa::SigningKey::from_slice(secret).expect("P-256 key");
+            let signature: p256::ecdsa::Signature = key.sign(message);
+            (
+                signature.to_der().as_bytes().to_vec(),
+                key.verifying_key()
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .to_vec(),
+            )
+        }
+        EcCurve::Secp256k1 => {
+            let key = k256::ecdsa::SigningKey::from_slice(secret).expect("secp256k1 key");
+            let signature: k256::ecdsa::Signature = key.sign(message);
+            (
+                signature.to_der().as_bytes().to_vec(),
+                key.verifying_key()
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .to_vec(),
+            )
+        }
+    }
+}
+
+/// RSA PKCS#1 v1.5 signature over `message` and its algorithm name.
+fn rsa_sign(
+    key: &RsaPrivateKey,
+    digest: DigestAlgorithm,
+    message: &[u8],
+) -> (SignatureAlgorithmName, Vec<u8>) {
+    match digest {
+        DigestAlgorithm::SHA256 => (
+            SignatureAlgorithmName::Sha256WithRsaEncryption,
+            SigningKey::<Sha256>::new(key.clone())
+                .sign(message)
+                .to_vec(),
+        ),
+        DigestAlgorithm::SHA384 => (
+            SignatureAlgorithmName::Sha384WithRsaEncryption,
+            SigningKey::<Sha384>::new(key.clone())
+                .sign(message)
+                .to_vec(),
+        ),
+        DigestAlgorithm::SHA512 => (
+            SignatureAlgorithmName::Sha512WithRsaEncryption,
+            SigningKey::<Sha512>::new(key.clone())
+                .sign(message)
+                .to_vec(),
+        ),
+        unsupported => panic!("mock passports do not support {unsupported:?}"),
+    }
+}
+
+/// Hash the data groups into eContent and eContent into SignedAttributes.
+fn fake_signed_content(
+    data_groups: &[(u32, &[u8])],
+    digest: DigestAlgorithm,
+) -> (EncapContentInfo, SignedAttrs) {
+    // Hash data groups and build eContent
+    let mut econtent_bytes = Vec::new();
     let mut dg_map = HashMap::new();
-    dg_map.insert(1u32, Binary::from_slice(&dg1_hash));
+    for &(number, data) in data_groups {
+        let hash = digest.digest(data).expect("supported digest");
+        econtent_bytes.extend_from_slice(&hash);
+        dg_map.insert(number, Binary::from_slice(&hash));
+    }
     let data_group_hashes = DataGroupHashValues { values: dg_map };
     let econtent = EContent {
         version:                0,
-        hash_algorithm:         DigestAlgorithm::SHA256,
+        hash_algorithm:         digest,
         data_group_hash_values: data_group_hashes,
         bytes:                  Binary::from_slice(&econtent_bytes),
     };
@@ -58,8 +349,8 @@ pub fn generate_fake_sod(
     };
 
     // Hash eContent and build SignedAttributes
-    let econtent_hash = Sha256::digest(&econtent_bytes);
-    let signed_attr_bytes = econtent_hash.to_vec();
+    let econtent_hash = digest.digest(&econtent_bytes).expect("supported digest");
+    let signed_attr_bytes = econtent_hash.clone();
     let signed_attrs = SignedAttrs {
         content_type:   "data".to_string(),
         message_digest: Binary::from_slice(&econtent_hash),
@@ -67,37 +358,46 @@ pub fn generate_fake_sod(
         bytes:          Binary::from_slice(&signed_attr_bytes),
     };
 
-    // Sign SignedAttributes with DSC private key
-    let dsc_signer = SigningKey::<Sha256>::new(dsc_priv.clone());
-    let dsc_signature = dsc_signer.sign(&signed_attr_bytes).to_bytes();
-    let signer_info = SignerInfo {
+    (encap_content_info, signed_attrs)
+}
+
+fn fake_signer_info(
+    signed_attrs: SignedAttrs,
+    digest: DigestAlgorithm,
+    algorithm: SignatureAlgorithmName,
+    signature: &[u8],
+) -> SignerInfo {
+    SignerInfo {
         version: 1,
         signed_attrs,
-        digest_algorithm: DigestAlgorithm::SHA256,
+        digest_algorithm: digest,
         signature_algorithm: SignatureAlgorithm {
-            name:       SignatureAlgorithmName::Sha256WithRsaEncryption,
+            name:       algorithm,
             parameters: None,
         },
-        signature: Binary::from_slice(&dsc_signature),
+        signature: Binary::from_slice(signature),
         sid: SignerIdentifier {
             issuer_and_serial_number: None,
             subject_key_identifier:   None,
         },
-    };
-
-    // Build fake DSC certificate (TBS = DER of DSC public key)
-    let dsc_pub_der = dsc_pub.to_pkcs1_der().expect("pkcs1 der").to_vec();
-    let tbs_bytes = dsc_pub_der.clone();
+    }
+}
 
-    let csca_signer = SigningKey::<Sha256>::new(csca_priv.clone());
-    let csca_signature = csca_signer.sign(&tbs_bytes).to_bytes();
+/// Build a fake DSC certificate whose TBS is `tbs_bytes`, signed by the CSCA.
+fn fake_dsc_cert(
+    spki: SubjectPublicKeyInfo,
+    tbs_bytes: &[u8],
+    csca_priv: &RsaPrivateKey,
+    digest: DigestAlgorithm,
+) -> DSC {
+    let (algorithm, csca_signature) = rsa_sign(csca_priv, digest, tbs_bytes);
 
-    let dsc_cert = DSC {
+    DSC {
         tbs:                 TbsCertificate {
             version:                 1,
             serial_number:           Binary::from_slice(&[1]),
             signature_algorithm:     SignatureAlgorithm {
-                name:       SignatureAlgorithmName::Sha256WithRsaEncryption,
+                name:       algorithm.clone(),
                 parameters: None,
             },
             issuer:                  "CSCA".to_string(),
@@ -112,28 +412,29 @@ pub fn generate_fake_sod(
                 ))
                 .expect("valid duration after 5 years"), // after 5 years
             subject:                 "DSC".to_string(),
-            subject_public_key_info: SubjectPublicKeyInfo {
-                signature_algorithm: SignatureAlgorithm {
-                    name:       SignatureAlgorithmName::RsaEncryption,
-                    parameters: None,
-                },
-                subject_public_key:  Binary::from_slice(&dsc_pub_der),
-            },
+            subject_public_key_info: spki,
             issuer_unique_id:        None,
             subject_unique_id:       None,
             extensions:              HashMap::new(),
-            bytes:                   Binary::from_slice(&tbs_bytes),
+            bytes:                   Binary::from_slice(tbs_bytes),
         },
         signature_algorithm: SignatureAlgorithm {
-            name:       SignatureAlgorithmName::Sha256WithRsaEncryption,
+            name:       algorithm,
             parameters: None,
         },
         signature:           Binary::from_slice(&csca_signature),
-    };
+    }
+}
 
+fn fake_sod(
+    encap_content_info: EncapContentInfo,
+    signer_info: SignerInfo,
+    dsc_cert: DSC,
+    digest: DigestAlgorithm,
+) -> SOD {
     SOD {
         version: 1,
-        digest_algorithms: vec![DigestAlgorithm::SHA256],
+        digest_algorithms: vec![digest],
         encap_content_info,
         signer_info,
         certificate: dsc_cert,
@@ -146,26 +447,27 @@ mod tests {
     use {
         super::*,
         crate::{
-            mock_keys::{MOCK_CSCA_PRIV_KEY_B64, MOCK_DSC_PRIV_KEY_B64},
-            PassportReader,
+            load_csca_from_masterlist,
+            mock_keys::MOCK_ECDSA_DSC_SECRET,
+            parser::utils::{CscaKey, CscaMasterlist},
+            CircuitInputs, DscInputs,
         },
-        base64::{engine::general_purpose::STANDARD, Engine as _},
-        chrono::Utc,
-        rsa::pkcs8::DecodePrivateKey,
+        chrono::{NaiveDate, Utc},
+        rsa::{pkcs8::EncodePublicKey, traits::PublicKeyParts},
     };
 
-    fn load_csca_mock_private_key() -> RsaPrivateKey {
-        let der = STANDARD
-            .decode(MOCK_CSCA_PRIV_KEY_B64)
-            .expect("decode CSCA private key");
-        RsaPrivateKey::from_pkcs8_der(&der).expect("CSCA key")
+    fn mock_csca_entry(key: &RsaPublicKey) -> CscaKey {
+        mock_csca_entry_der(key.to_public_key_der().expect("CSCA SPKI").as_bytes())
     }
 
-    fn load_dsc_mock_private_key() -> RsaPrivateKey {
-        let der = STANDARD
-            .decode(MOCK_DSC_PRIV_KEY_B64)
-            .expect("decode DSC private key");
-        RsaPrivateKey::from_pkcs8_der(&der).expect("DSC key")
+    fn mock_csca_entry_der(der: &[u8]) -> CscaKey {
+        CscaKey {
+            _filename:   "mock.pem".to_string(),
+            public_key:  STANDARD.encode(der),
+            _not_before: String::new(),
+            _not_after:  String::new(),
+            _serial:     String::new(),
+        }
     }
 
     #[test]
@@ -179,20 +481,659 @@ mod tests {
         let sod = generate_fake_sod(&dg1, &dsc_priv, &dsc_pub, &csca_priv, &csca_pub);
         let reader = PassportReader {
             dg1: Binary::from_slice(&dg1),
+            dg2: None,
             sod,
             mockdata: true,
             csca_pubkey: Some(csca_pub),
+            masterlist: None,
         };
-        assert!(reader.validate().is_ok());
+        assert!(reader.validate("USA").is_ok());
 
         let current_date = Utc::now();
         let current_timestamp = current_date.timestamp() as u64;
 
         let inputs = reader
-            .to_circuit_inputs(current_timestamp, 18, 70, 0)
+            .to_circuit_inputs(current_timestamp, 18, 70, "USA", 0)
             .expect("to circuit inputs");
         let _toml_output = inputs.to_toml_string();
 
         println!("{}", _toml_output);
     }
+
+    #[test]
+    fn test_usa_passport_sample() {
+        let reader = generate_usa_passport_sample(b"070101", b"320101");
+        assert_eq!(reader.validate("USA").expect("valid sample"), 0);
+
+        let current_timestamp = Utc::now().timestamp() as u64;
+        let inputs = reader
+            .to_circuit_inputs(current_timestamp, 18, 70, "USA", 0)
+            .expect("to circuit inputs");
+        let toml =
+            generate_usa_passport_sample_toml(b"070101", b"320101", current_timestamp, 18, 70)
+                .expect("sample TOML");
+        assert_eq!(toml, inputs.to_toml_string());
+    }
+
+    #[test]
+    fn test_generate_and_validate_sod_per_digest() {
+        let csca_priv = load_csca_mock_private_key();
+        let csca_pub = csca_priv.to_public_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+
+        for digest in [
+            DigestAlgorithm::SHA256,
+            DigestAlgorithm::SHA384,
+            DigestAlgorithm::SHA512,
+        ] {
+            let sod = generate_fake_sod_with_digest(&dg1, digest, &dsc_priv, &dsc_pub, &csca_priv);
+            let reader = PassportReader {
+                dg1: Binary::from_slice(&dg1),
+                dg2: None,
+                sod,
+                mockdata: true,
+                csca_pubkey: Some(csca_pub.clone()),
+                masterlist: None,
+            };
+            assert_eq!(
+                reader.validate("USA").expect("valid passport"),
+                0,
+                "{digest:?}"
+            );
+
+            let inputs = reader
+                .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "USA", 0)
+                .expect("to circuit inputs");
+            let pvc = &inputs.passport_validity_contents;
+            assert_eq!(pvc.econtent.len(), digest.max_econtent_size());
+            assert_eq!(
+                pvc.signed_attributes.len(),
+                digest.max_signed_attributes_size()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rsa_key_size_combinations() {
+        // The mock DSC key is 2048-bit and the mock CSCA key 4096-bit
+        let key_2048 = load_dsc_mock_private_key();
+        let key_4096 = load_csca_mock_private_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+
+        for (dsc_priv, csca_priv) in [
+            (&key_2048, &key_4096),
+            (&key_4096, &key_4096),
+            (&key_2048, &key_2048),
+            (&key_4096, &key_2048),
+        ] {
+            let dsc_pub = dsc_priv.to_public_key();
+            let csca_pub = csca_priv.to_public_key();
+            let sod = generate_fake_sod(&dg1, dsc_priv, &dsc_pub, csca_priv, &csca_pub);
+            let reader = PassportReader {
+                dg1: Binary::from_slice(&dg1),
+                dg2: None,
+                sod,
+                mockdata: true,
+                csca_pubkey: Some(csca_pub.clone()),
+                masterlist: None,
+            };
+            assert_eq!(reader.validate("USA").expect("valid passport"), 0);
+
+            let inputs = reader
+                .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "USA", 0)
+                .expect("to circuit inputs");
+            let pvc = &inputs.passport_validity_contents;
+            let DscInputs::Rsa {
+                pubkey,
+                barrett_mu,
+                signature,
+                ..
+            } = &pvc.dsc
+            else {
+                panic!("expected an RSA DSC");
+            };
+            assert_eq!(pubkey.len(), dsc_pub.size());
+            assert_eq!(barrett_mu.len(), dsc_pub.size() + 1);
+            assert_eq!(signature.len(), dsc_pub.size());
+            assert_eq!(pvc.csc_pubkey.len(), csca_pub.size());
+            assert_eq!(pvc.csc_barrett_mu.len(), csca_pub.size() + 1);
+            assert_eq!(pvc.dsc_cert_signature.len(), csca_pub.size());
+        }
+    }
+
+    #[test]
+    fn test_csca_lookup_by_country() {
+        let csca_priv = load_csca_mock_private_key();
+        let csca_pub = csca_priv.to_public_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let sod = generate_fake_sod(&dg1, &dsc_priv, &dsc_pub, &csca_priv, &csca_pub);
+        let reader = PassportReader {
+            dg1: Binary::from_slice(&dg1),
+            dg2: None,
+            sod,
+            mockdata: false,
+            csca_pubkey: None,
+            masterlist: None,
+        };
+
+        // The DSC key stands in for an unrelated CSCA
+        let masterlist = CscaMasterlist::from([
+            ("USA".to_string(), vec![
+                mock_csca_entry(&dsc_pub),
+                mock_csca_entry(&csca_pub),
+            ]),
+            ("DEU".to_string(), vec![mock_csca_entry(&csca_pub)]),
+            ("GBR".to_string(), vec![mock_csca_entry(&dsc_pub)]),
+        ]);
+
+        for (country, index) in [("USA", 1), ("DEU", 0)] {
+            assert_eq!(
+                reader
+                    .find_csca_index(&masterlist, country)
+                    .expect("CSCA found"),
+                index
+            );
+            let (modulus, ..) = reader
+                .extract_csca(&masterlist, country, index)
+                .expect("extract CSCA");
+            assert_eq!(modulus, csca_pub.n().to_bytes_be());
+        }
+        assert!(matches!(
+            reader.find_csca_index(&masterlist, "GBR"),
+            Err(PassportError::CscaSignatureInvalid)
+        ));
+        assert!(matches!(
+            reader.find_csca_index(&masterlist, "FRA"),
+            Err(PassportError::NoCscaForCountry(country)) if country == "FRA"
+        ));
+    }
+
+    #[test]
+    fn test_csca_lookup_with_ecdsa_csca() {
+        let csca_pub = load_csca_mock_private_key().to_public_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let sod = generate_fake_sod_with_ecdsa_csca(
+            &dg1,
+            &dsc_priv,
+            &dsc_pub,
+            EcCurve::Secp256r1,
+            &MOCK_ECDSA_DSC_SECRET,
+        );
+        let reader = PassportReader {
+            dg1: Binary::from_slice(&dg1),
+            dg2: None,
+            sod,
+            mockdata: false,
+            csca_pubkey: None,
+            masterlist: None,
+        };
+
+        // Same secret on the wrong curve, then the signing key
+        let k256_der = k256::ecdsa::SigningKey::from_slice(&MOCK_ECDSA_DSC_SECRET)
+            .unwrap()
+            .verifying_key()
+            .to_public_key_der()
+            .unwrap();
+        let p256_der = p256::ecdsa::SigningKey::from_slice(&MOCK_ECDSA_DSC_SECRET)
+            .unwrap()
+            .verifying_key()
+            .to_public_key_der()
+            .unwrap();
+        let masterlist = CscaMasterlist::from([
+            ("USA".to_string(), vec![
+                mock_csca_entry(&csca_pub),
+                mock_csca_entry_der(k256_der.as_bytes()),
+                mock_csca_entry_der(p256_der.as_bytes()),
+            ]),
+            ("DEU".to_string(), vec![mock_csca_entry(&csca_pub)]),
+        ]);
+
+        assert!(matches!(
+            reader.find_csca_index(&masterlist, "DEU"),
+            Err(PassportError::CscaSignatureInvalid)
+        ));
+        assert_eq!(
+            reader
+                .with_masterlist(masterlist)
+                .validate("USA")
+                .expect("valid passport"),
+            2
+        );
+    }
+
+    #[test]
+    fn test_csca_lookup_in_icao_masterlist() {
+        let csca_priv = load_csca_mock_private_key();
+        let csca_pub = csca_priv.to_public_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let sod = generate_fake_sod(&dg1, &dsc_priv, &dsc_pub, &csca_priv, &csca_pub);
+
+        // CSCAs for Germany (mock CSCA key) and France (mock DSC key)
+        let masterlist =
+            load_csca_from_masterlist("fixtures/mock_masterlist.ml").expect("load masterlist");
+        let reader = PassportReader::new(Binary::from_slice(&dg1), sod, false, None)
+            .with_masterlist(masterlist);
+
+        assert_eq!(reader.validate("DEU").expect("valid passport"), 0);
+        let inputs = reader
+            .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "DEU", 0)
+            .expect("to circuit inputs");
+        assert_eq!(
+            inputs.passport_validity_contents.csc_pubkey,
+            csca_pub.n().to_bytes_be()
+        );
+        assert!(matches!(
+            reader.validate("FRA"),
+            Err(PassportError::CscaSignatureInvalid)
+        ));
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::NoCscaForCountry(_))
+        ));
+    }
+
+    /// EF.SOD and EF.DG1 of a mock German passport, whose Document Signer is
+    /// issued by the mock masterlist's DE CSCA
+    fn mock_nfc_dump() -> (Vec<u8>, Vec<u8>) {
+        (
+            std::fs::read("fixtures/mock_ef_sod.bin").expect("read EF.SOD"),
+            std::fs::read("fixtures/mock_ef_dg1.bin").expect("read EF.DG1"),
+        )
+    }
+
+    #[test]
+    fn test_reader_from_nfc_dump() {
+        let masterlist =
+            load_csca_from_masterlist("fixtures/mock_masterlist.ml").expect("load masterlist");
+        let (ef_sod, ef_dg1) = mock_nfc_dump();
+
+        // With and without the status word of the last read
+        for status_word in [&[][..], &[0x90, 0x00][..]] {
+            let reader = PassportReader::from_nfc_dump(
+                &[&ef_sod[..], status_word].concat(),
+                &[&ef_dg1[..], status_word].concat(),
+            )
+            .expect("parse NFC dump")
+            .with_masterlist(masterlist.clone());
+            assert_eq!(reader.validate("DEU").expect("valid passport"), 0);
+
+            let inputs = reader
+                .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "DEU", 0)
+                .expect("to circuit inputs");
+            assert_eq!(inputs.dg1_padded_length, ef_dg1.len());
+            assert_eq!(&inputs.dg1[..ef_dg1.len()], &ef_dg1[..]);
+        }
+    }
+
+    #[test]
+    fn test_malformed_nfc_dump() {
+        let (ef_sod, ef_dg1) = mock_nfc_dump();
+
+        let truncated = &ef_sod[..ef_sod.len() - 10];
+        assert!(matches!(
+            PassportReader::from_nfc_dump(truncated, &ef_dg1),
+            Err(PassportError::MalformedTlv(_))
+        ));
+
+        // DG2 instead of DG1
+        let mut ef_dg2 = ef_dg1.clone();
+        ef_dg2[0] = 0x75;
+        assert!(matches!(
+            PassportReader::from_nfc_dump(&ef_sod, &ef_dg2),
+            Err(PassportError::MalformedTlv(_))
+        ));
+        assert!(matches!(
+            PassportReader::from_nfc_dump(&ef_dg1, &ef_dg1),
+            Err(PassportError::MalformedTlv(_))
+        ));
+        assert!(matches!(
+            PassportReader::from_nfc_dump(&[], &ef_dg1),
+            Err(PassportError::MalformedTlv(_))
+        ));
+    }
+
+    #[test]
+    fn test_circuit_inputs_json_round_trip() {
+        let csca_priv = load_csca_mock_private_key();
+        let csca_pub = csca_priv.to_public_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+
+        for sod in [
+            generate_fake_sod(&dg1, &dsc_priv, &dsc_pub, &csca_priv, &csca_pub),
+            generate_fake_sod_ecdsa(&dg1, EcCurve::Secp256r1, &MOCK_ECDSA_DSC_SECRET, &csca_priv),
+        ] {
+            let reader = PassportReader {
+                dg1: Binary::from_slice(&dg1),
+                dg2: None,
+                sod,
+                mockdata: true,
+                csca_pubkey: Some(csca_pub.clone()),
+                masterlist: None,
+            };
+            let inputs = reader
+                .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "USA", 0)
+                .expect("to circuit inputs");
+            let toml = inputs.to_toml_string();
+
+            let json = inputs.to_json_value();
+            let decoded: CircuitInputs =
+                serde_json::from_value(json.clone()).expect("decode JSON circuit inputs");
+            assert_eq!(decoded.to_toml_string(), toml);
+
+            // Apart from the curve, the JSON holds exactly the TOML values
+            let mut json = json;
+            json["passport_validity_contents"]
+                .as_object_mut()
+                .expect("validity contents object")
+                .remove("dsc_curve");
+            let from_toml = serde_json::to_value(
+                toml::from_str::<toml::Value>(&toml).expect("parse Prover.toml"),
+            )
+            .expect("TOML to JSON");
+            assert_eq!(json, from_toml);
+        }
+    }
+
+    #[test]
+    fn test_validate_dg2_hash() {
+        let csca_priv = load_csca_mock_private_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let mut dg2 = vec![0x5a; 512];
+        let sod = generate_fake_sod_with_dg2(&dg1, &dg2, &dsc_priv, &dsc_pub, &csca_priv);
+
+        let reader = PassportReader::new(
+            Binary::from_slice(&dg1),
+            sod.clone(),
+            true,
+            Some(csca_priv.to_public_key()),
+        );
+        // DG2 is only checked when provided
+        assert_eq!(reader.validate("USA").expect("valid without DG2"), 0);
+        let reader = reader.with_dg2(Binary::from_slice(&dg2));
+        assert_eq!(reader.validate("USA").expect("valid with DG2"), 0);
+        reader
+            .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "USA", 0)
+            .expect("to circuit inputs");
+
+        dg2[100] ^= 0xff;
+        let reader = PassportReader::new(
+            Binary::from_slice(&dg1),
+            sod,
+            true,
+            Some(csca_priv.to_public_key()),
+        )
+        .with_dg2(Binary::from_slice(&dg2));
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::Dg2HashMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_validate_with_wrong_digest_fails() {
+        let csca_priv = load_csca_mock_private_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+
+        let mut sod = generate_fake_sod_with_digest(
+            &dg1,
+            DigestAlgorithm::SHA384,
+            &dsc_priv,
+            &dsc_pub,
+            &csca_priv,
+        );
+        sod.encap_content_info.e_content.hash_algorithm = DigestAlgorithm::SHA256;
+
+        let reader = PassportReader {
+            dg1: Binary::from_slice(&dg1),
+            dg2: None,
+            sod,
+            mockdata: true,
+            csca_pubkey: Some(csca_priv.to_public_key()),
+            masterlist: None,
+        };
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::Dg1HashMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_generate_and_validate_ecdsa_sod() {
+        let csca_priv = load_csca_mock_private_key();
+        let csca_pub = csca_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+
+        for curve in [EcCurve::Secp256r1, EcCurve::Secp256k1] {
+            let sod = generate_fake_sod_ecdsa(&dg1, curve, &MOCK_ECDSA_DSC_SECRET, &csca_priv);
+            let reader = PassportReader {
+                dg1: Binary::from_slice(&dg1),
+                dg2: None,
+                sod,
+                mockdata: true,
+                csca_pubkey: Some(csca_pub.clone()),
+                masterlist: None,
+            };
+            assert_eq!(reader.validate("USA").expect("valid ECDSA passport"), 0);
+
+            let inputs = reader
+                .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "USA", 0)
+                .expect("to circuit inputs");
+            assert!(matches!(
+                inputs.passport_validity_contents.dsc,
+                DscInputs::Ecdsa { curve: c, .. } if c == curve
+            ));
+            // The point X || Y follows the SEC1 0x04 prefix in the mock TBS
+            assert_eq!(
+                inputs
+                    .passport_validity_contents
+                    .dsc_pubkey_offset_in_dsc_cert,
+                1
+            );
+            let toml = inputs.to_toml_string();
+            assert!(toml.contains("dsc_pubkey_x = "));
+            assert!(toml.contains("dsc_signature_s = "));
+            assert!(!toml.contains("dsc_rsa_exponent"));
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_sod_with_tampered_signed_attributes_fails() {
+        let csca_priv = load_csca_mock_private_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let mut sod =
+            generate_fake_sod_ecdsa(&dg1, EcCurve::Secp256r1, &MOCK_ECDSA_DSC_SECRET, &csca_priv);
+        let mut signature = sod.signer_info.signature.as_bytes().to_vec();
+        let last = signature.len() - 1;
+        signature[last] ^= 1;
+        sod.signer_info.signature = Binary::from_slice(&signature);
+
+        let reader = PassportReader {
+            dg1: Binary::from_slice(&dg1),
+            dg2: None,
+            sod,
+            mockdata: true,
+            csca_pubkey: Some(csca_priv.to_public_key()),
+            masterlist: None,
+        };
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::DscSignatureInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_ecdsa_sod_with_unsupported_curve() {
+        let csca_priv = load_csca_mock_private_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let mut sod =
+            generate_fake_sod_ecdsa(&dg1, EcCurve::Secp256r1, &MOCK_ECDSA_DSC_SECRET, &csca_priv);
+        // secp384r1 (1.3.132.0.34)
+        sod.certificate
+            .tbs
+            .subject_public_key_info
+            .signature_algorithm
+            .parameters = Some(Binary::from_slice(&[0x2b, 0x81, 0x04, 0x00, 0x22]));
+
+        let reader = PassportReader {
+            dg1: Binary::from_slice(&dg1),
+            dg2: None,
+            sod,
+            mockdata: true,
+            csca_pubkey: Some(csca_priv.to_public_key()),
+            masterlist: None,
+        };
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::UnsupportedSignatureAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_and_validate_pss_sod() {
+        let csca_priv = load_csca_mock_private_key();
+        let csca_pub = csca_priv.to_public_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+
+        // The SHA-256 default and a salt longer than the digest
+        for salt_length in [32, 50] {
+            let sod = generate_fake_sod_pss(&dg1, salt_length, &dsc_priv, &dsc_pub, &csca_priv);
+            let reader =
+                PassportReader::new(Binary::from_slice(&dg1), sod, true, Some(csca_pub.clone()));
+            assert_eq!(
+                reader.validate("USA").expect("valid PSS passport"),
+                0,
+                "salt length {salt_length}"
+            );
+            reader
+                .to_circuit_inputs(Utc::now().timestamp() as u64, 18, 70, "USA", 0)
+                .expect("to circuit inputs");
+        }
+    }
+
+    #[test]
+    fn test_pss_sod_with_wrong_salt_length_fails() {
+        let csca_priv = load_csca_mock_private_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let dg1 = dg1_bytes_with_birthdate_expiry_date(b"070101", b"320101");
+        let mut sod = generate_fake_sod_pss(&dg1, 50, &dsc_priv, &dsc_pub, &csca_priv);
+
+        // Signed with a 50-byte salt but declaring the default for SHA-256
+        let parameters = PssParameters::with_digest(DigestAlgorithm::SHA256);
+        sod.signer_info.signature_algorithm.parameters =
+            Some(Binary::from_slice(&parameters.to_der()));
+        let reader = PassportReader::new(
+            Binary::from_slice(&dg1),
+            sod.clone(),
+            true,
+            Some(csca_priv.to_public_key()),
+        );
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::DscSignatureInvalid)
+        ));
+
+        // MGF1 with another digest is not supported by the verifier
+        let parameters = PssParameters {
+            mgf_digest: DigestAlgorithm::SHA512,
+            ..parameters
+        };
+        sod.signer_info.signature_algorithm.parameters =
+            Some(Binary::from_slice(&parameters.to_der()));
+        let reader = PassportReader::new(
+            Binary::from_slice(&dg1),
+            sod,
+            true,
+            Some(csca_priv.to_public_key()),
+        );
+        assert!(matches!(
+            reader.validate("USA"),
+            Err(PassportError::UnsupportedSignatureAlgorithm(_))
+        ));
+    }
+
+    fn mock_reader(dg1: &[u8]) -> PassportReader {
+        let csca_priv = load_csca_mock_private_key();
+        let dsc_priv = load_dsc_mock_private_key();
+        let dsc_pub = dsc_priv.to_public_key();
+        let sod = generate_fake_sod(
+            dg1,
+            &dsc_priv,
+            &dsc_pub,
+            &csca_priv,
+            &csca_priv.to_public_key(),
+        );
+        PassportReader::new(
+            Binary::from_slice(dg1),
+            sod,
+            true,
+            Some(csca_priv.to_public_key()),
+        )
+    }
+
+    /// Unix timestamp of noon UTC on the given day
+    fn timestamp(year: i32, month: u32, day: u32) -> u64 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .expect("valid date")
+            .and_utc()
+            .timestamp() as u64
+    }
+
+    #[test]
+    fn test_age_boundaries() {
+        // Turns 18 on the current date
+        let reader = mock_reader(&dg1_bytes_with_birthdate_expiry_date(b"070615", b"320101"));
+        let today = timestamp(2025, 6, 15);
+        let inputs = reader
+            .to_circuit_inputs(today, 18, 18, "USA", 0)
+            .expect("exactly min age");
+        assert_eq!(inputs.min_age_required, 18);
+        assert!(reader.to_circuit_inputs(today, 18, 0, "USA", 0).is_ok());
+        assert!(reader.to_circuit_inputs(today, 0, 18, "USA", 0).is_ok());
+        assert!(matches!(
+            reader.to_circuit_inputs(today, 19, 18, "USA", 0),
+            Err(PassportError::InvalidAgeRange(19, 18))
+        ));
+
+        // Born on the current date, or one day after it
+        let reader = mock_reader(&dg1_bytes_with_birthdate_expiry_date(b"250615", b"320101"));
+        assert!(reader.to_circuit_inputs(today, 0, 0, "USA", 0).is_ok());
+        assert!(matches!(
+            reader.to_circuit_inputs(timestamp(2025, 6, 14), 0, 0, "USA", 0),
+            Err(PassportError::CurrentDateBeforeBirthdate(..))
+        ));
+    }
+
+    #[test]
+    fn test_malformed_dg1_dates() {
+        let today = timestamp(2025, 6, 15);
+        for (birthdate, expiry) in [
+            (b"070230", b"320101"),
+            (b"07O615", b"320101"),
+            (b"070615", b"321301"),
+        ] {
+            let reader = mock_reader(&dg1_bytes_with_birthdate_expiry_date(birthdate, expiry));
+            assert!(matches!(
+                reader.to_circuit_inputs(today, 18, 70, "USA", 0),
+                Err(PassportError::InvalidMrzDate(_))
+            ));
+        }
+    }
 }
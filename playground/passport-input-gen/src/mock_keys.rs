@@ -79,3 +79,10 @@ pub const MOCK_DSC_PRIV_KEY_B64: &str = concat!(
     "8nFvIYWjqcNAaxypyNladBlnsNpF0gby2BNAV4+HlprX5xFVzsgflHgB1SIHP4/Z",
     "lxVcRfuIVt0q/b4RcHCPZZo="
 );
+
+/// Private scalar of the mock ECDSA DSC, valid on both secp256r1 and
+/// secp256k1.
+pub const MOCK_ECDSA_DSC_SECRET: [u8; 32] = [
+    0x1f, 0x4e, 0x5a, 0x3c, 0x82, 0x91, 0x0d, 0x6b, 0x27, 0xc4, 0x5e, 0x13, 0x9a, 0x70, 0xb8, 0x46,
+    0xe2, 0x0f, 0x63, 0xd5, 0x38, 0xa1, 0x7c, 0x94, 0x5b, 0x2e, 0xf0, 0x81, 0x4d, 0xc6, 0x19, 0x07,
+];
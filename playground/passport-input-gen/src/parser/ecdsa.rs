@@ -0,0 +1,152 @@
+use {
+    crate::parser::{
+        dsc::SubjectPublicKeyInfo,
+        types::{EcCurve, PassportError, SignatureAlgorithmName},
+    },
+    p256::{elliptic_curve::sec1::ToEncodedPoint, pkcs8::DecodePublicKey},
+    sha2::{Digest, Sha256, Sha384, Sha512},
+    signature::hazmat::PrehashVerifier,
+};
+
+/// Size in bytes of a coordinate or scalar on the supported 256-bit curves
+pub const EC_COORDINATE_BYTES: usize = 32;
+
+/// ECDSA public key of a DSC, as affine coordinates
+#[derive(Debug, Clone)]
+pub struct EcdsaPublicKey {
+    pub curve: EcCurve,
+    pub x:     [u8; EC_COORDINATE_BYTES],
+    pub y:     [u8; EC_COORDINATE_BYTES],
+}
+
+impl EcdsaPublicKey {
+    /// Parses an EC `SubjectPublicKeyInfo`. The named curve comes from the
+    /// algorithm parameters and the key may be a compressed or uncompressed
+    /// SEC1 point.
+    pub fn from_spki(spki: &SubjectPublicKeyInfo) -> Result<Self, PassportError> {
+        let parameters = spki
+            .signature_algorithm
+            .parameters
+            .as_ref()
+            .ok_or(PassportError::DscPublicKeyInvalid)?;
+        let curve = EcCurve::from_parameters(parameters.as_bytes()).ok_or_else(|| {
+            PassportError::UnsupportedSignatureAlgorithm(format!(
+                "ECDSA curve {}",
+                parameters.to_hex()
+            ))
+        })?;
+
+        let point = spki.subject_public_key.as_bytes();
+        let uncompressed = match curve {
+            EcCurve::Secp256r1 => p256::PublicKey::from_sec1_bytes(point)
+                .map(|key| key.to_encoded_point(false).as_bytes().to_vec()),
+            EcCurve::Secp256k1 => k256::PublicKey::from_sec1_bytes(point)
+                .map(|key| key.to_encoded_point(false).as_bytes().to_vec()),
+        }
+        .map_err(|_| PassportError::DscPublicKeyInvalid)?;
+
+        Self::from_uncompressed(curve, &uncompressed)
+    }
+
+    /// Parses a DER encoded `SubjectPublicKeyInfo`, as stored for CSCA keys.
+    /// Returns `None` if it is not a key on a supported curve.
+    pub fn from_public_key_der(der: &[u8]) -> Option<Self> {
+        let (curve, uncompressed) = match p256::PublicKey::from_public_key_der(der) {
+            Ok(key) => (
+                EcCurve::Secp256r1,
+                key.to_encoded_point(false).as_bytes().to_vec(),
+            ),
+            Err(_) => (
+                EcCurve::Secp256k1,
+                k256::PublicKey::from_public_key_der(der)
+                    .ok()?
+                    .to_encoded_point(false)
+                    .as_bytes()
+                    .to_vec(),
+            ),
+        };
+        Self::from_uncompressed(curve, &uncompressed).ok()
+    }
+
+    /// Splits an uncompressed SEC1 point, 0x04 || X || Y
+    fn from_uncompressed(curve: EcCurve, uncompressed: &[u8]) -> Result<Self, PassportError> {
+        let (x, y) = uncompressed[1..].split_at(EC_COORDINATE_BYTES);
+        Ok(Self {
+            curve,
+            x: x.try_into()
+                .map_err(|_| PassportError::DscPublicKeyInvalid)?,
+            y: y.try_into()
+                .map_err(|_| PassportError::DscPublicKeyInvalid)?,
+        })
+    }
+
+    /// Uncompressed SEC1 encoding of the key
+    pub fn to_sec1_bytes(&self) -> Vec<u8> {
+        [&[0x04][..], &self.x, &self.y].concat()
+    }
+
+    /// Verifies a DER encoded signature over `message`
+    pub fn verify(
+        &self,
+        algorithm: &SignatureAlgorithmName,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), PassportError> {
+        let prehash = match algorithm {
+            SignatureAlgorithmName::EcdsaWithSha256 => Sha256::digest(message).to_vec(),
+            SignatureAlgorithmName::EcdsaWithSha384 => Sha384::digest(message).to_vec(),
+            SignatureAlgorithmName::EcdsaWithSha512 => Sha512::digest(message).to_vec(),
+            unsupported => {
+                return Err(PassportError::UnsupportedSignatureAlgorithm(format!(
+                    "{:?}",
+                    unsupported
+                )))
+            }
+        };
+
+        let sec1 = self.to_sec1_bytes();
+        let verified = match self.curve {
+            EcCurve::Secp256r1 => {
+                let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&sec1)
+                    .map_err(|_| PassportError::DscPublicKeyInvalid)?;
+                let signature = p256::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| PassportError::DscSignatureInvalid)?;
+                key.verify_prehash(&prehash, &signature)
+            }
+            EcCurve::Secp256k1 => {
+                let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&sec1)
+                    .map_err(|_| PassportError::DscPublicKeyInvalid)?;
+                let signature = k256::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| PassportError::DscSignatureInvalid)?;
+                // k256 only accepts low-S signatures, which passports need not use
+                let signature = signature.normalize_s().unwrap_or(signature);
+                key.verify_prehash(&prehash, &signature)
+            }
+        };
+        verified.map_err(|_| PassportError::DscSignatureInvalid)
+    }
+
+    /// Splits a DER encoded signature into its big-endian `r` and `s`
+    pub fn signature_components(
+        &self,
+        signature: &[u8],
+    ) -> Result<([u8; EC_COORDINATE_BYTES], [u8; EC_COORDINATE_BYTES]), PassportError> {
+        let (r, s) = match self.curve {
+            EcCurve::Secp256r1 => p256::ecdsa::Signature::from_der(signature)
+                .map(|signature| signature.split_bytes())
+                .map(|(r, s)| (r.to_vec(), s.to_vec())),
+            EcCurve::Secp256k1 => k256::ecdsa::Signature::from_der(signature)
+                .map(|signature| signature.split_bytes())
+                .map(|(r, s)| (r.to_vec(), s.to_vec())),
+        }
+        .map_err(|_| PassportError::DscSignatureInvalid)?;
+
+        let r = r
+            .try_into()
+            .map_err(|_| PassportError::DscSignatureInvalid)?;
+        let s = s
+            .try_into()
+            .map_err(|_| PassportError::DscSignatureInvalid)?;
+        Ok((r, s))
+    }
+}
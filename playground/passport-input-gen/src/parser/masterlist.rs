@@ -0,0 +1,265 @@
+use {
+    crate::parser::{
+        binary::Binary,
+        country::alpha3_from_alpha2,
+        dsc::DSC,
+        oid_registry::REGISTRY,
+        signature::verify_signer_info,
+        sod::SOD,
+        types::{CscaMasterList, PassportError},
+        utils::{oid_to_string, CscaKey, CscaMasterlist, ASN1_HEADER_LEN, ASN1_OCTET_STRING_TAG},
+    },
+    base64::{engine::general_purpose::STANDARD, Engine as _},
+    chrono::{DateTime, SecondsFormat, Utc},
+    rasn::der,
+    rasn_cms::{CertificateChoices, ContentInfo, SignedData},
+    std::{fs, path::Path},
+    x509_parser::{parse_x509_certificate, time::ASN1Time},
+};
+
+/// id-icao-cscaMasterList, the eContent type of masterlists
+const CSCA_MASTER_LIST_OID: &str = "2.23.136.1.1.2";
+/// LDIF attribute holding a base64 encoded masterlist
+const LDIF_MASTERLIST_ATTRIBUTE: &str = "pkdMasterListContent::";
+const ASN1_SEQUENCE_TAG: u8 = 0x30;
+
+/// Loads the CSCA keys of an ICAO masterlist, given either as a DER CMS
+/// `SignedData` or as an LDIF file whose `pkdMasterListContent` values hold
+/// such masterlists.
+///
+/// Keys are grouped by the ISO 3166-1 alpha-3 code of the country in the
+/// certificate subject, like in `csca_registry/csca_public_key.json`. The
+/// signature of each masterlist is verified when it is signed.
+pub fn load_csca_from_masterlist(path: impl AsRef<Path>) -> Result<CscaMasterlist, PassportError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)
+        .map_err(|e| PassportError::MasterlistReadFailed(format!("{}: {e}", path.display())))?;
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let masterlists = match bytes.first() {
+        Some(&ASN1_SEQUENCE_TAG) => vec![bytes],
+        _ => ldif_masterlists(&bytes)?,
+    };
+    let mut csca_keys = CscaMasterlist::new();
+    for masterlist in masterlists {
+        for (country, key) in parse_masterlist(&masterlist, &filename)? {
+            csca_keys.entry(country).or_default().push(key);
+        }
+    }
+    Ok(csca_keys)
+}
+
+/// Base64 decoded `pkdMasterListContent` values of an LDIF file
+fn ldif_masterlists(ldif: &[u8]) -> Result<Vec<Vec<u8>>, PassportError> {
+    let ldif = std::str::from_utf8(ldif).map_err(|_| {
+        PassportError::MasterlistReadFailed("masterlist is neither DER nor LDIF".to_string())
+    })?;
+    // Continuation lines start with a single space
+    let unfolded = ldif.replace("\r\n", "\n").replace("\n ", "");
+    let masterlists = unfolded
+        .lines()
+        .filter_map(|line| line.strip_prefix(LDIF_MASTERLIST_ATTRIBUTE))
+        .map(|value| {
+            STANDARD
+                .decode(value.trim())
+                .map_err(|e| PassportError::Base64DecodingFailed(e.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if masterlists.is_empty() {
+        return Err(PassportError::DataNotFound(
+            "pkdMasterListContent in LDIF".to_string(),
+        ));
+    }
+    Ok(masterlists)
+}
+
+/// Country and key of each CSCA certificate in a DER masterlist
+fn parse_masterlist(der: &[u8], filename: &str) -> Result<Vec<(String, CscaKey)>, PassportError> {
+    let content_info: ContentInfo =
+        der::decode(der).map_err(|e| PassportError::CmsParsingFailed(e.to_string()))?;
+    let signed_data: SignedData = der::decode(content_info.content.as_bytes())
+        .map_err(|e| PassportError::CmsParsingFailed(e.to_string()))?;
+
+    let content_type = oid_to_string(&signed_data.encap_content_info.content_type);
+    if content_type != CSCA_MASTER_LIST_OID {
+        return Err(PassportError::CmsParsingFailed(format!(
+            "content type {content_type} is not a CSCA masterlist"
+        )));
+    }
+    let econtent = signed_data
+        .encap_content_info
+        .content
+        .as_ref()
+        .ok_or(PassportError::MissingRequiredField("eContent".to_string()))?;
+    verify_masterlist_signature(&signed_data, econtent)?;
+
+    let masterlist: CscaMasterList =
+        der::decode(econtent).map_err(|e| PassportError::Asn1DecodingFailed(e.to_string()))?;
+    let mut keys = Vec::new();
+    for certificate in &masterlist.cert_list {
+        keys.push(csca_key(certificate.as_bytes(), filename)?);
+    }
+    Ok(keys)
+}
+
+/// Verifies each signer of a masterlist against the certificates it carries.
+/// Masterlists without signers are accepted as they are.
+fn verify_masterlist_signature(
+    signed_data: &SignedData,
+    econtent: &[u8],
+) -> Result<(), PassportError> {
+    let mut certificates = Vec::new();
+    for choice in signed_data.certificates.iter().flatten() {
+        if let CertificateChoices::Certificate(certificate) = choice {
+            let der = der::encode(&**certificate)
+                .map_err(|e| PassportError::X509ParsingFailed(e.to_string()))?;
+            certificates.push(DSC::from_der(&Binary::from_slice(&der))?);
+        }
+    }
+
+    for signer_info_raw in &signed_data.signer_infos {
+        let signer_info = SOD::parse_signer_info(signer_info_raw, &REGISTRY)?;
+
+        let econtent_hash = signer_info.digest_algorithm.digest(econtent)?;
+        let mut message_digest = signer_info.signed_attrs.message_digest.as_bytes();
+        if message_digest.len() > ASN1_HEADER_LEN && message_digest[0] == ASN1_OCTET_STRING_TAG {
+            message_digest = &message_digest[ASN1_HEADER_LEN..];
+        }
+        if econtent_hash.as_slice() != message_digest {
+            return Err(PassportError::MasterlistSignatureInvalid);
+        }
+
+        let verified = certificates.iter().any(|certificate| {
+            verify_signer_info(&signer_info, &certificate.tbs.subject_public_key_info).is_ok()
+        });
+        if !verified {
+            return Err(PassportError::MasterlistSignatureInvalid);
+        }
+    }
+    Ok(())
+}
+
+/// Issuing country (ISO 3166-1 alpha-3) and public key of a DER CSCA
+/// certificate
+fn csca_key(der: &[u8], filename: &str) -> Result<(String, CscaKey), PassportError> {
+    let (_, certificate) =
+        parse_x509_certificate(der).map_err(|e| PassportError::X509ParsingFailed(e.to_string()))?;
+    let alpha2 = certificate
+        .subject()
+        .iter_country()
+        .next()
+        .and_then(|country| country.as_str().ok())
+        .ok_or_else(|| PassportError::MissingRequiredField("CSCA country".to_string()))?
+        .to_uppercase();
+    // Codes outside ISO 3166-1 are kept as they are
+    let country = alpha3_from_alpha2(&alpha2).map_or(alpha2, str::to_string);
+
+    let validity = certificate.validity();
+    Ok((country, CscaKey {
+        _filename:   filename.to_string(),
+        public_key:  STANDARD.encode(certificate.public_key().raw),
+        _not_before: rfc3339(&validity.not_before),
+        _not_after:  rfc3339(&validity.not_after),
+        _serial:     hex::encode_upper(certificate.raw_serial()),
+    }))
+}
+
+fn rfc3339(time: &ASN1Time) -> String {
+    DateTime::<Utc>::from_timestamp(time.timestamp(), 0)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::mock_keys::{MOCK_CSCA_PRIV_KEY_B64, MOCK_DSC_PRIV_KEY_B64},
+        rsa::{
+            pkcs8::{DecodePrivateKey, EncodePublicKey},
+            RsaPrivateKey,
+        },
+    };
+
+    /// Masterlist holding self-signed CSCAs for DE (mock CSCA key) and FR
+    /// (mock DSC key), signed with the mock DSC key by a masterlist signer
+    /// issued by the DE CSCA. Made with OpenSSL `cms -sign`.
+    const FIXTURE_MASTERLIST: &str = "fixtures/mock_masterlist.ml";
+
+    /// Base64 SPKI of a mock private key
+    fn spki(private_key_b64: &str) -> String {
+        let der = STANDARD.decode(private_key_b64).unwrap();
+        let key = RsaPrivateKey::from_pkcs8_der(&der).unwrap();
+        STANDARD.encode(key.to_public_key().to_public_key_der().unwrap().as_bytes())
+    }
+
+    fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{name}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_der_masterlist() {
+        let masterlist = load_csca_from_masterlist(FIXTURE_MASTERLIST).unwrap();
+        assert_eq!(masterlist.len(), 2);
+
+        let germany = &masterlist["DEU"];
+        assert_eq!(germany.len(), 1);
+        assert_eq!(germany[0].public_key, spki(MOCK_CSCA_PRIV_KEY_B64));
+        assert_eq!(germany[0]._serial, "1001");
+        assert_eq!(germany[0]._not_before, "2020-01-01T00:00:00Z");
+        assert_eq!(masterlist["FRA"][0].public_key, spki(MOCK_DSC_PRIV_KEY_B64));
+    }
+
+    #[test]
+    fn loads_ldif_masterlist() {
+        let der = fs::read(FIXTURE_MASTERLIST).unwrap();
+        let encoded = STANDARD.encode(&der);
+        let mut ldif = String::from(
+            "dn: cn=mock,o=ml,c=DE,dc=data,dc=download,dc=pkd,dc=icao,dc=int\nobjectClass: \
+             pkdMasterList\n",
+        );
+        ldif.push_str(LDIF_MASTERLIST_ATTRIBUTE);
+        ldif.push(' ');
+        for (i, chunk) in encoded.as_bytes().chunks(76).enumerate() {
+            if i > 0 {
+                ldif.push_str("\n ");
+            }
+            ldif.push_str(std::str::from_utf8(chunk).unwrap());
+        }
+        ldif.push('\n');
+
+        let path = temp_file("masterlist.ldif", ldif.as_bytes());
+        let from_ldif = load_csca_from_masterlist(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let from_der = load_csca_from_masterlist(FIXTURE_MASTERLIST).unwrap();
+        assert_eq!(from_ldif.len(), from_der.len());
+        for (country, keys) in &from_der {
+            assert_eq!(from_ldif[country][0].public_key, keys[0].public_key);
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_masterlist() {
+        let mut der = fs::read(FIXTURE_MASTERLIST).unwrap();
+        let name = b"Mock CSCA FR";
+        let offset = der
+            .windows(name.len())
+            .position(|window| window == name)
+            .unwrap();
+        der[offset] = b'X';
+
+        let path = temp_file("tampered-masterlist.ml", &der);
+        let result = load_csca_from_masterlist(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(PassportError::MasterlistSignatureInvalid)
+        ));
+    }
+}
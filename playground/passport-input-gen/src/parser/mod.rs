@@ -1,6 +1,12 @@
 pub(crate) mod binary;
+mod country;
 pub(crate) mod dsc;
+pub(crate) mod ecdsa;
+pub(crate) mod masterlist;
+pub(crate) mod mrz;
 mod oid_registry;
+pub(crate) mod pss;
+pub(crate) mod signature;
 pub(crate) mod sod;
 pub(crate) mod types;
 pub(crate) mod utils;
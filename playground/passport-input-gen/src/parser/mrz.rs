@@ -0,0 +1,121 @@
+use {
+    crate::parser::types::{PassportError, MAX_DG1_SIZE},
+    chrono::{Datelike, NaiveDate},
+};
+
+/// Offset of the MRZ in DG1, after the DG1 and MRZ tags and lengths
+const DG1_TO_MRZ_OFFSET: usize = 5;
+/// Index of the birthdate (YYMMDD) in a TD3 (passport) MRZ
+const PASSPORT_BIRTHDATE_INDEX: usize = 57;
+/// Index of the expiry date (YYMMDD) in a TD3 (passport) MRZ
+const PASSPORT_EXPIRY_DATE_INDEX: usize = 65;
+/// Index of the birthdate (YYMMDD) in a TD1 (ID card) MRZ
+const ID_CARD_BIRTHDATE_INDEX: usize = 30;
+/// Index of the expiry date (YYMMDD) in a TD1 (ID card) MRZ
+const ID_CARD_EXPIRY_DATE_INDEX: usize = 38;
+const MRZ_DATE_LENGTH: usize = 6;
+/// Years after the current date up to which an expiry date may fall
+const EXPIRY_DATE_HORIZON_YEARS: i32 = 20;
+
+/// Whether the padded DG1 holds an ID card MRZ. Passport DG1s are 93 bytes
+/// long, so their last two padding bytes are zero.
+fn is_id_card(dg1: &[u8; MAX_DG1_SIZE]) -> bool {
+    dg1[93] != 0 && dg1[94] != 0
+}
+
+/// Birthdate of the MRZ in `dg1`, with its century chosen so that it is not
+/// after the year of `current_date`, like the age circuit does.
+pub fn birthdate(
+    dg1: &[u8; MAX_DG1_SIZE],
+    current_date: NaiveDate,
+) -> Result<NaiveDate, PassportError> {
+    let index = match is_id_card(dg1) {
+        true => ID_CARD_BIRTHDATE_INDEX,
+        false => PASSPORT_BIRTHDATE_INDEX,
+    };
+    mrz_date(dg1, index, current_date.year(), "birthdate")
+}
+
+/// Expiry date of the MRZ in `dg1`, with its century chosen so that it is at
+/// most 20 years after `current_date`, like the date circuit does.
+pub fn expiry_date(
+    dg1: &[u8; MAX_DG1_SIZE],
+    current_date: NaiveDate,
+) -> Result<NaiveDate, PassportError> {
+    let index = match is_id_card(dg1) {
+        true => ID_CARD_EXPIRY_DATE_INDEX,
+        false => PASSPORT_EXPIRY_DATE_INDEX,
+    };
+    let threshold_year = current_date.year() + EXPIRY_DATE_HORIZON_YEARS;
+    mrz_date(dg1, index, threshold_year, "expiry date")
+}
+
+/// Parses the YYMMDD date at `index` in the MRZ, placing its year in the
+/// century that keeps it at or before `threshold_year`
+fn mrz_date(
+    dg1: &[u8; MAX_DG1_SIZE],
+    index: usize,
+    threshold_year: i32,
+    field: &str,
+) -> Result<NaiveDate, PassportError> {
+    let start = DG1_TO_MRZ_OFFSET + index;
+    let bytes = &dg1[start..start + MRZ_DATE_LENGTH];
+    let invalid = || {
+        PassportError::InvalidMrzDate(format!(
+            "{field} {:?} is not a YYMMDD date",
+            String::from_utf8_lossy(bytes)
+        ))
+    };
+    if !bytes.iter().all(u8::is_ascii_digit) {
+        return Err(invalid());
+    }
+    let pair = |i: usize| ((bytes[i] - b'0') * 10 + bytes[i + 1] - b'0') as u32;
+
+    let mut year = threshold_year - threshold_year.rem_euclid(100) + pair(0) as i32;
+    if year > threshold_year {
+        year -= 100;
+    }
+    NaiveDate::from_ymd_opt(year, pair(2), pair(4)).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::mock_generator::dg1_bytes_with_birthdate_expiry_date};
+
+    fn dg1(birthdate: &[u8; 6], expiry: &[u8; 6]) -> [u8; MAX_DG1_SIZE] {
+        dg1_bytes_with_birthdate_expiry_date(birthdate, expiry)
+            .try_into()
+            .unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn pivots_century_on_current_date() {
+        let today = date(2025, 6, 15);
+        let dg1 = dg1(b"250615", b"450101");
+        assert_eq!(birthdate(&dg1, today).unwrap(), date(2025, 6, 15));
+        assert_eq!(expiry_date(&dg1, today).unwrap(), date(2045, 1, 1));
+
+        let dg1 = dg1(b"260101", b"460101");
+        assert_eq!(birthdate(&dg1, today).unwrap(), date(1926, 1, 1));
+        assert_eq!(expiry_date(&dg1, today).unwrap(), date(1946, 1, 1));
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        let today = date(2025, 6, 15);
+        for birthdate_bytes in [b"07A101", b"071301", b"070230", b"<<<<<<"] {
+            let dg1 = dg1(birthdate_bytes, b"320101");
+            assert!(matches!(
+                birthdate(&dg1, today),
+                Err(PassportError::InvalidMrzDate(_))
+            ));
+        }
+        // 2000 is a leap year, 1900 is not
+        assert!(birthdate(&dg1(b"000229", b"320101"), today).is_ok());
+        assert!(birthdate(&dg1(b"000229", b"320101"), date(1999, 1, 1)).is_err());
+    }
+}
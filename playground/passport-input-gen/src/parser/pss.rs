@@ -0,0 +1,298 @@
+use {
+    crate::parser::{
+        types::{DigestAlgorithm, PassportError},
+        utils::pss_scheme,
+    },
+    rsa::Pss,
+};
+
+const SEQUENCE_TAG: u8 = 0x30;
+const INTEGER_TAG: u8 = 0x02;
+const OID_TAG: u8 = 0x06;
+const HASH_ALGORITHM_TAG: u8 = 0xa0;
+const MASK_GEN_ALGORITHM_TAG: u8 = 0xa1;
+const SALT_LENGTH_TAG: u8 = 0xa2;
+const TRAILER_FIELD_TAG: u8 = 0xa3;
+/// DER encoding of an ASN.1 `NULL`, the parameters of the hash algorithms
+const NULL: &[u8] = &[0x05, 0x00];
+
+/// DER content bytes of id-mgf1 (1.2.840.113549.1.1.8)
+const MGF1_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x08];
+/// DER content bytes of the hash algorithm OIDs
+const DIGEST_OIDS: [(DigestAlgorithm, &[u8]); 5] = [
+    (DigestAlgorithm::SHA1, &[0x2b, 0x0e, 0x03, 0x02, 0x1a]),
+    (DigestAlgorithm::SHA224, &[
+        0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04,
+    ]),
+    (DigestAlgorithm::SHA256, &[
+        0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+    ]),
+    (DigestAlgorithm::SHA384, &[
+        0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02,
+    ]),
+    (DigestAlgorithm::SHA512, &[
+        0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
+    ]),
+];
+
+/// Salt length when `saltLength` is absent
+const DEFAULT_SALT_LENGTH: usize = 20;
+
+/// RSASSA-PSS signature parameters (RFC 4055):
+///
+/// ```text
+/// RSASSA-PSS-params ::= SEQUENCE {
+///   hashAlgorithm    [0] HashAlgorithm DEFAULT sha1,
+///   maskGenAlgorithm [1] MaskGenAlgorithm DEFAULT mgf1SHA1,
+///   saltLength       [2] INTEGER DEFAULT 20,
+///   trailerField     [3] TrailerField DEFAULT trailerFieldBC
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PssParameters {
+    pub digest:      DigestAlgorithm,
+    /// Digest used by the MGF1 mask generation function
+    pub mgf_digest:  DigestAlgorithm,
+    pub salt_length: usize,
+}
+
+impl PssParameters {
+    /// Parameters using `digest` for both the message and MGF1, with a salt as
+    /// long as the digest
+    pub fn with_digest(digest: DigestAlgorithm) -> Self {
+        Self {
+            digest,
+            mgf_digest: digest,
+            salt_length: digest.output_size(),
+        }
+    }
+
+    /// Parses the parameters of an RSASSA-PSS algorithm identifier, given
+    /// either as the full DER `SEQUENCE` or as its content bytes.
+    pub fn from_der(parameters: &[u8]) -> Result<Self, PassportError> {
+        let mut fields = match parameters.first() {
+            Some(&SEQUENCE_TAG) => expect_last(expect_element(parameters, SEQUENCE_TAG)?)?,
+            _ => parameters,
+        };
+
+        let mut result = Self {
+            digest:      DigestAlgorithm::SHA1,
+            mgf_digest:  DigestAlgorithm::SHA1,
+            salt_length: DEFAULT_SALT_LENGTH,
+        };
+        while !fields.is_empty() {
+            let (tag, contents, rest) = der_element(fields)?;
+            match tag {
+                HASH_ALGORITHM_TAG => result.digest = hash_algorithm(contents)?,
+                MASK_GEN_ALGORITHM_TAG => result.mgf_digest = mask_gen_algorithm(contents)?,
+                SALT_LENGTH_TAG => result.salt_length = integer(contents)?,
+                TRAILER_FIELD_TAG => {
+                    let trailer = integer(contents)?;
+                    if trailer != 1 {
+                        return Err(PassportError::UnsupportedSignatureAlgorithm(format!(
+                            "RSASSA-PSS trailer field {trailer}"
+                        )));
+                    }
+                }
+                _ => return Err(invalid()),
+            }
+            fields = rest;
+        }
+        Ok(result)
+    }
+
+    /// DER encoding of the parameters, omitting the fields equal to their
+    /// default
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut fields = Vec::new();
+        if self.digest != DigestAlgorithm::SHA1 {
+            fields.extend(der(HASH_ALGORITHM_TAG, &hash_identifier(self.digest)));
+        }
+        if self.mgf_digest != DigestAlgorithm::SHA1 {
+            let mgf = [der(OID_TAG, MGF1_OID), hash_identifier(self.mgf_digest)].concat();
+            fields.extend(der(MASK_GEN_ALGORITHM_TAG, &der(SEQUENCE_TAG, &mgf)));
+        }
+        if self.salt_length != DEFAULT_SALT_LENGTH {
+            let salt = self.salt_length.to_be_bytes();
+            let start = salt.iter().position(|&b| b != 0).unwrap_or(salt.len() - 1);
+            // Keep the integer positive
+            let salt = match salt[start] {
+                0x80.. => [&[0], &salt[start..]].concat(),
+                _ => salt[start..].to_vec(),
+            };
+            fields.extend(der(SALT_LENGTH_TAG, &der(INTEGER_TAG, &salt)));
+        }
+        der(SEQUENCE_TAG, &fields)
+    }
+
+    /// Verifier for these parameters. MGF1 must use the message digest.
+    pub fn scheme(&self) -> Result<Pss, PassportError> {
+        if self.mgf_digest != self.digest {
+            return Err(PassportError::UnsupportedSignatureAlgorithm(format!(
+                "RSASSA-PSS with {:?} and MGF1 with {:?}",
+                self.digest, self.mgf_digest
+            )));
+        }
+        pss_scheme(self.digest, self.salt_length)
+    }
+}
+
+fn invalid() -> PassportError {
+    PassportError::Asn1DecodingFailed("invalid RSASSA-PSS parameters".to_string())
+}
+
+/// Splits the DER element at the start of `der` into its tag, its contents
+/// and the bytes following it
+fn der_element(der: &[u8]) -> Result<(u8, &[u8], &[u8]), PassportError> {
+    let (&tag, rest) = der.split_first().ok_or_else(invalid)?;
+    let (&length, rest) = rest.split_first().ok_or_else(invalid)?;
+    let (length, rest) = match length {
+        0..=0x7f => (length as usize, rest),
+        0x81..=0x84 => {
+            let count = (length & 0x7f) as usize;
+            if rest.len() < count {
+                return Err(invalid());
+            }
+            let (length, rest) = rest.split_at(count);
+            let length = length
+                .iter()
+                .fold(0usize, |acc, &byte| acc << 8 | byte as usize);
+            (length, rest)
+        }
+        _ => return Err(invalid()),
+    };
+    if rest.len() < length {
+        return Err(invalid());
+    }
+    let (contents, rest) = rest.split_at(length);
+    Ok((tag, contents, rest))
+}
+
+/// Contents and trailing bytes of the element at the start of `der`, which
+/// must have tag `tag`
+fn expect_element(der: &[u8], tag: u8) -> Result<(&[u8], &[u8]), PassportError> {
+    match der_element(der)? {
+        (found, contents, rest) if found == tag => Ok((contents, rest)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Contents of an element that must not be followed by anything
+fn expect_last((contents, rest): (&[u8], &[u8])) -> Result<&[u8], PassportError> {
+    match rest {
+        [] => Ok(contents),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a hash `AlgorithmIdentifier`, whose parameters are ignored
+fn hash_algorithm(der: &[u8]) -> Result<DigestAlgorithm, PassportError> {
+    let identifier = expect_last(expect_element(der, SEQUENCE_TAG)?)?;
+    let (oid, _parameters) = expect_element(identifier, OID_TAG)?;
+    DIGEST_OIDS
+        .iter()
+        .find(|(_, known)| *known == oid)
+        .map(|&(digest, _)| digest)
+        .ok_or_else(|| {
+            PassportError::UnsupportedDigestAlgorithm(format!("OID {}", hex::encode(oid)))
+        })
+}
+
+/// Parses an MGF1 `AlgorithmIdentifier` and returns its hash algorithm
+fn mask_gen_algorithm(der: &[u8]) -> Result<DigestAlgorithm, PassportError> {
+    let identifier = expect_last(expect_element(der, SEQUENCE_TAG)?)?;
+    let (oid, parameters) = expect_element(identifier, OID_TAG)?;
+    if oid != MGF1_OID {
+        return Err(PassportError::UnsupportedSignatureAlgorithm(format!(
+            "RSASSA-PSS mask generation function {}",
+            hex::encode(oid)
+        )));
+    }
+    hash_algorithm(parameters)
+}
+
+/// Parses a non-negative `INTEGER` element
+fn integer(der: &[u8]) -> Result<usize, PassportError> {
+    let bytes = expect_last(expect_element(der, INTEGER_TAG)?)?;
+    match bytes {
+        [] => Err(invalid()),
+        [first, ..] if first & 0x80 != 0 => Err(invalid()),
+        _ if bytes.len() > size_of::<usize>() + 1 => Err(invalid()),
+        _ => Ok(bytes
+            .iter()
+            .fold(0usize, |acc, &byte| acc << 8 | byte as usize)),
+    }
+}
+
+/// DER encoding of a hash `AlgorithmIdentifier` with `NULL` parameters
+fn hash_identifier(digest: DigestAlgorithm) -> Vec<u8> {
+    let (_, oid) = DIGEST_OIDS
+        .iter()
+        .find(|(known, _)| *known == digest)
+        .expect("every digest has an OID");
+    der(SEQUENCE_TAG, &[&der(OID_TAG, oid)[..], NULL].concat())
+}
+
+/// DER element with tag `tag` and contents `contents`
+fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let length = contents.len();
+    let mut out = vec![tag];
+    match length {
+        0..=0x7f => out.push(length as u8),
+        0x80..=0xff => out.extend([0x81, length as u8]),
+        _ => out.extend([0x82, (length >> 8) as u8, length as u8]),
+    }
+    out.extend_from_slice(contents);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RSASSA-PSS parameters of the SOD fixture: SHA-256, MGF1 with SHA-256
+    /// and a 32-byte salt
+    const SHA256_PARAMETERS: &str = "3034a00f300d06096086480165030402010500a11c301a06092a864886f70d010108300d06096086480165030402010500a203020120";
+
+    #[test]
+    fn parses_full_and_content_encodings() {
+        let der = hex::decode(SHA256_PARAMETERS).unwrap();
+        let expected = PssParameters::with_digest(DigestAlgorithm::SHA256);
+        assert_eq!(PssParameters::from_der(&der).unwrap(), expected);
+        // x509-parser only keeps the content bytes
+        assert_eq!(PssParameters::from_der(&der[2..]).unwrap(), expected);
+        assert_eq!(expected.to_der(), der);
+    }
+
+    #[test]
+    fn defaults_and_round_trip() {
+        let defaults = PssParameters::from_der(&[0x30, 0x00]).unwrap();
+        assert_eq!(defaults.digest, DigestAlgorithm::SHA1);
+        assert_eq!(defaults.mgf_digest, DigestAlgorithm::SHA1);
+        assert_eq!(defaults.salt_length, 20);
+        assert_eq!(defaults.to_der(), [0x30, 0x00]);
+
+        for salt_length in [0, 20, 64, 200, 1000] {
+            let parameters = PssParameters {
+                salt_length,
+                ..PssParameters::with_digest(DigestAlgorithm::SHA384)
+            };
+            assert_eq!(
+                PssParameters::from_der(&parameters.to_der()).unwrap(),
+                parameters
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_mgf_digest() {
+        let parameters = PssParameters {
+            mgf_digest: DigestAlgorithm::SHA512,
+            ..PssParameters::with_digest(DigestAlgorithm::SHA256)
+        };
+        assert!(matches!(
+            parameters.scheme(),
+            Err(PassportError::UnsupportedSignatureAlgorithm(_))
+        ));
+    }
+}
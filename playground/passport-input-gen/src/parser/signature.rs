@@ -0,0 +1,69 @@
+use {
+    crate::parser::{
+        dsc::SubjectPublicKeyInfo,
+        ecdsa::EcdsaPublicKey,
+        pss::PssParameters,
+        types::{PassportError, SignatureAlgorithmName, SignerInfo},
+        utils::pkcs1v15_scheme,
+    },
+    rsa::{pkcs1::DecodeRsaPublicKey, RsaPublicKey},
+};
+
+/// Verifies the SignedAttributes signature of `signer_info` with the key in
+/// `spki`, which may be an RSA or EC key
+pub fn verify_signer_info(
+    signer_info: &SignerInfo,
+    spki: &SubjectPublicKeyInfo,
+) -> Result<(), PassportError> {
+    let signed_attrs = signer_info.signed_attrs.bytes.as_bytes();
+    let signature = signer_info.signature.as_bytes();
+    let signature_algorithm = &signer_info.signature_algorithm.name;
+
+    if matches!(
+        spki.signature_algorithm.name,
+        SignatureAlgorithmName::EcPublicKey
+    ) {
+        return EcdsaPublicKey::from_spki(spki)?.verify(
+            signature_algorithm,
+            signed_attrs,
+            signature,
+        );
+    }
+
+    let pubkey = RsaPublicKey::from_pkcs1_der(spki.subject_public_key.as_bytes())
+        .map_err(|_| PassportError::DscPublicKeyInvalid)?;
+
+    let verify_result = match signature_algorithm {
+        SignatureAlgorithmName::Sha256WithRsaEncryption
+        | SignatureAlgorithmName::Sha384WithRsaEncryption
+        | SignatureAlgorithmName::Sha512WithRsaEncryption
+        | SignatureAlgorithmName::RsaEncryption => {
+            let digest = signature_algorithm
+                .digest_algorithm()
+                .unwrap_or(signer_info.digest_algorithm);
+            let signed_attr_hash = digest.digest(signed_attrs)?;
+            pubkey.verify(pkcs1v15_scheme(digest)?, &signed_attr_hash, signature)
+        }
+        SignatureAlgorithmName::RsassaPss => {
+            let pss = pss_parameters(signer_info)?;
+            let signed_attr_hash = pss.digest.digest(signed_attrs)?;
+            pubkey.verify(pss.scheme()?, &signed_attr_hash, signature)
+        }
+        unsupported => {
+            return Err(PassportError::UnsupportedSignatureAlgorithm(format!(
+                "{:?}",
+                unsupported
+            )))
+        }
+    };
+    verify_result.map_err(|_| PassportError::DscSignatureInvalid)
+}
+
+/// RSASSA-PSS parameters of the SignedAttributes signature. Without
+/// parameters, the signer digest is used with a salt of the same length.
+fn pss_parameters(signer_info: &SignerInfo) -> Result<PssParameters, PassportError> {
+    match &signer_info.signature_algorithm.parameters {
+        Some(parameters) => PssParameters::from_der(parameters.as_bytes()),
+        None => Ok(PssParameters::with_digest(signer_info.digest_algorithm)),
+    }
+}
@@ -2,21 +2,26 @@ use {
     crate::parser::binary::Binary,
     chrono::{DateTime, Utc},
     rasn::{
-        types::{Integer, OctetString, PrintableString, SequenceOf},
+        types::{Any, Integer, OctetString, PrintableString, SequenceOf, SetOf},
         AsnType, Decode, Encode,
     },
     rasn_pkix::AlgorithmIdentifier,
+    serde::{Deserialize, Serialize},
+    sha2::{Digest, Sha224, Sha256, Sha384, Sha512},
     std::collections::HashMap,
     thiserror::Error,
 };
 
 pub const MAX_SIGNED_ATTRIBUTES_SIZE: usize = 200;
 pub const MAX_DG1_SIZE: usize = 95;
-pub const SIG_BYTES: usize = 256;
 pub const MAX_ECONTENT_SIZE: usize = 200;
 pub const MAX_TBS_SIZE: usize = 1300;
+pub const MAX_SIGNED_ATTRIBUTES_SIZE_SHA384: usize = 216;
+pub const MAX_SIGNED_ATTRIBUTES_SIZE_SHA512: usize = 232;
+pub const MAX_ECONTENT_SIZE_SHA384: usize = 300;
+pub const MAX_ECONTENT_SIZE_SHA512: usize = 380;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DigestAlgorithm {
     SHA1,
     SHA224,
@@ -36,6 +41,52 @@ impl DigestAlgorithm {
             _ => None,
         }
     }
+
+    /// Hashes `data`. SHA-1 is recognised when parsing but not accepted for
+    /// validation.
+    pub fn digest(&self, data: &[u8]) -> Result<Vec<u8>, PassportError> {
+        match self {
+            Self::SHA224 => Ok(Sha224::digest(data).to_vec()),
+            Self::SHA256 => Ok(Sha256::digest(data).to_vec()),
+            Self::SHA384 => Ok(Sha384::digest(data).to_vec()),
+            Self::SHA512 => Ok(Sha512::digest(data).to_vec()),
+            Self::SHA1 => Err(PassportError::UnsupportedDigestAlgorithm(format!(
+                "{:?}",
+                self
+            ))),
+        }
+    }
+
+    /// Digest size in bytes
+    pub fn output_size(&self) -> usize {
+        match self {
+            Self::SHA1 => 20,
+            Self::SHA224 => 28,
+            Self::SHA256 => 32,
+            Self::SHA384 => 48,
+            Self::SHA512 => 64,
+        }
+    }
+
+    /// Circuit buffer size for eContent, which grows with the size of the
+    /// data group hashes it holds
+    pub fn max_econtent_size(&self) -> usize {
+        match self {
+            Self::SHA1 | Self::SHA224 | Self::SHA256 => MAX_ECONTENT_SIZE,
+            Self::SHA384 => MAX_ECONTENT_SIZE_SHA384,
+            Self::SHA512 => MAX_ECONTENT_SIZE_SHA512,
+        }
+    }
+
+    /// Circuit buffer size for SignedAttributes, which hold the eContent
+    /// digest
+    pub fn max_signed_attributes_size(&self) -> usize {
+        match self {
+            Self::SHA1 | Self::SHA224 | Self::SHA256 => MAX_SIGNED_ATTRIBUTES_SIZE,
+            Self::SHA384 => MAX_SIGNED_ATTRIBUTES_SIZE_SHA384,
+            Self::SHA512 => MAX_SIGNED_ATTRIBUTES_SIZE_SHA512,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -125,6 +176,56 @@ impl SignatureAlgorithmName {
             _ => None,
         }
     }
+
+    /// Digest fixed by the algorithm, or `None` for bare key algorithms and
+    /// RSASSA-PSS, whose digest is given separately
+    pub fn digest_algorithm(&self) -> Option<DigestAlgorithm> {
+        match self {
+            Self::Sha1WithRsaSignature | Self::EcdsaWithSha1 => Some(DigestAlgorithm::SHA1),
+            Self::Sha256WithRsaEncryption | Self::EcdsaWithSha256 => Some(DigestAlgorithm::SHA256),
+            Self::Sha384WithRsaEncryption | Self::EcdsaWithSha384 => Some(DigestAlgorithm::SHA384),
+            Self::Sha512WithRsaEncryption | Self::EcdsaWithSha512 => Some(DigestAlgorithm::SHA512),
+            Self::RsassaPss | Self::RsaEncryption | Self::EcPublicKey => None,
+        }
+    }
+}
+
+/// Named curves supported for ECDSA DSC keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EcCurve {
+    /// NIST P-256 (prime256v1)
+    Secp256r1,
+    /// SEC 2 secp256k1
+    Secp256k1,
+}
+
+impl EcCurve {
+    /// DER content bytes of prime256v1 (1.2.840.10045.3.1.7)
+    pub const SECP256R1_OID: &'static [u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+    /// DER content bytes of secp256k1 (1.3.132.0.10)
+    pub const SECP256K1_OID: &'static [u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+    /// Parses the named curve from EC `SubjectPublicKeyInfo` parameters,
+    /// given either as the OID content bytes or as a full DER OID.
+    pub fn from_parameters(parameters: &[u8]) -> Option<Self> {
+        let oid = match parameters {
+            [0x06, len, rest @ ..] if *len as usize == rest.len() => rest,
+            _ => parameters,
+        };
+        match oid {
+            Self::SECP256R1_OID => Some(Self::Secp256r1),
+            Self::SECP256K1_OID => Some(Self::Secp256k1),
+            _ => None,
+        }
+    }
+
+    pub fn oid(&self) -> &'static [u8] {
+        match self {
+            Self::Secp256r1 => Self::SECP256R1_OID,
+            Self::Secp256k1 => Self::SECP256K1_OID,
+        }
+    }
 }
 
 /// DataGroupNumber ::= INTEGER (1..16)
@@ -164,10 +265,22 @@ pub struct LDSSecurityObject {
     pub lds_version_info:       Option<LDSVersionInfo>,
 }
 
+/// CscaMasterList ::= SEQUENCE {
+///   version CscaMasterListVersion,
+///   certList SET OF Certificate
+/// }
+#[derive(Debug, Clone, AsnType, Decode, Encode)]
+pub struct CscaMasterList {
+    pub version:   Integer,
+    pub cert_list: SetOf<Any>,
+}
+
 #[derive(Debug, Error)]
 pub enum PassportError {
     #[error("DG1 hash mismatch in eContent")]
     Dg1HashMismatch,
+    #[error("DG2 hash mismatch in eContent")]
+    Dg2HashMismatch,
     #[error("eContent hash mismatch in SignedAttributes")]
     EcontentHashMismatch,
     #[error("Invalid DSC public key")]
@@ -176,8 +289,8 @@ pub enum PassportError {
     DscSignatureInvalid,
     #[error("Failed to load CSCA keys")]
     CscaKeysMissing,
-    #[error("No USA CSCA keys found")]
-    NoUsaCsca,
+    #[error("No CSCA keys found for country {0}")]
+    NoCscaForCountry(String),
     #[error("CSCA signature verification failed")]
     CscaSignatureInvalid,
     #[error("DSC Public key invalid")]
@@ -206,12 +319,26 @@ pub enum PassportError {
     InvalidCertificateType,
     #[error("Missing DG1 hash in eContent")]
     MissingDg1Hash,
+    #[error("Missing DG2 hash in eContent")]
+    MissingDg2Hash,
     #[error("Missing CSCA public key for mock data")]
     MissingCscaMockKey,
     #[error("Failed to load CSCA public keys")]
     FailedToLoadCscaKeys,
+    #[error("Failed to read masterlist: {0}")]
+    MasterlistReadFailed(String),
+    #[error("Masterlist signature verification failed")]
+    MasterlistSignatureInvalid,
+    #[error("Malformed TLV in {0}")]
+    MalformedTlv(String),
     #[error("Invalid date: {0}")]
     InvalidDate(String),
+    #[error("Invalid MRZ date: {0}")]
+    InvalidMrzDate(String),
+    #[error("Minimum age {0} is above maximum age {1}")]
+    InvalidAgeRange(u8, u8),
+    #[error("Current date {0} is before the birthdate {1}")]
+    CurrentDateBeforeBirthdate(String, String),
     #[error("Unsupported digest algorithm: {0}")]
     UnsupportedDigestAlgorithm(String),
 }
@@ -1,6 +1,11 @@
 use {
-    crate::parser::{binary::Binary, types::PassportError},
+    crate::parser::{
+        binary::Binary,
+        types::{DigestAlgorithm, PassportError},
+    },
+    rsa::{Pkcs1v15Sign, Pss},
     serde::Deserialize,
+    sha2::{Sha224, Sha256, Sha384, Sha512},
     std::{collections::HashMap, fs},
 };
 
@@ -59,7 +64,49 @@ pub fn fit<const N: usize>(data: &[u8]) -> Result<[u8; N], PassportError> {
     Ok(buf)
 }
 
-#[derive(Deserialize)]
+/// Like [`fit`], for buffer sizes only known at runtime
+pub fn fit_to(data: &[u8], size: usize) -> Result<Vec<u8>, PassportError> {
+    if data.len() > size {
+        return Err(PassportError::BufferOverflow(format!(
+            "data size {} exceeds buffer size {}",
+            data.len(),
+            size
+        )));
+    }
+    let mut buf = vec![0u8; size];
+    buf[..data.len()].copy_from_slice(data);
+    Ok(buf)
+}
+
+pub fn pkcs1v15_scheme(digest: DigestAlgorithm) -> Result<Pkcs1v15Sign, PassportError> {
+    match digest {
+        DigestAlgorithm::SHA224 => Ok(Pkcs1v15Sign::new::<Sha224>()),
+        DigestAlgorithm::SHA256 => Ok(Pkcs1v15Sign::new::<Sha256>()),
+        DigestAlgorithm::SHA384 => Ok(Pkcs1v15Sign::new::<Sha384>()),
+        DigestAlgorithm::SHA512 => Ok(Pkcs1v15Sign::new::<Sha512>()),
+        DigestAlgorithm::SHA1 => Err(PassportError::UnsupportedDigestAlgorithm(format!(
+            "{:?}",
+            digest
+        ))),
+    }
+}
+
+/// RSASSA-PSS verifier using `digest` for the message and MGF1, with a salt
+/// of `salt_len` bytes
+pub fn pss_scheme(digest: DigestAlgorithm, salt_len: usize) -> Result<Pss, PassportError> {
+    match digest {
+        DigestAlgorithm::SHA224 => Ok(Pss::new_with_salt::<Sha224>(salt_len)),
+        DigestAlgorithm::SHA256 => Ok(Pss::new_with_salt::<Sha256>(salt_len)),
+        DigestAlgorithm::SHA384 => Ok(Pss::new_with_salt::<Sha384>(salt_len)),
+        DigestAlgorithm::SHA512 => Ok(Pss::new_with_salt::<Sha512>(salt_len)),
+        DigestAlgorithm::SHA1 => Err(PassportError::UnsupportedDigestAlgorithm(format!(
+            "{:?}",
+            digest
+        ))),
+    }
+}
+
+#[derive(Clone, Deserialize)]
 pub struct CscaKey {
     #[serde(rename = "filename")]
     pub _filename:   String,
@@ -73,32 +120,96 @@ pub struct CscaKey {
     pub _serial:     String,
 }
 
+pub const ASN1_INTEGER_TAG: u8 = 0x02;
 pub const ASN1_OCTET_STRING_TAG: u8 = 0x04;
+pub const ASN1_SET_TAG: u8 = 0x31;
 pub const ASN1_HEADER_LEN: usize = 2;
+/// Tag of the EF.SOD file
+pub const EF_SOD_TAG: &[u8] = &[0x77];
+/// Tag of the EF.DG1 file
+pub const EF_DG1_TAG: &[u8] = &[0x61];
+/// Tag of the MRZ inside EF.DG1
+pub const MRZ_TAG: &[u8] = &[0x5f, 0x1f];
+
+/// Splits the BER-TLV element at the start of `data` into its tag, its value
+/// and the bytes following it
+pub fn read_tlv<'a>(
+    data: &'a [u8],
+    label: &str,
+) -> Result<(&'a [u8], &'a [u8], &'a [u8]), PassportError> {
+    let malformed = || PassportError::MalformedTlv(label.to_string());
+    // Tags with the low five bits of the first byte set continue while the high
+    // bit is set
+    let tag_len = match data {
+        [] => return Err(malformed()),
+        [first, rest @ ..] if first & 0x1f == 0x1f => {
+            rest.iter()
+                .position(|byte| byte & 0x80 == 0)
+                .ok_or_else(malformed)?
+                + 2
+        }
+        _ => 1,
+    };
+    let (tag, rest) = data.split_at(tag_len);
+    let (&first, rest) = rest.split_first().ok_or_else(malformed)?;
+    let (len, rest) = match first {
+        0..=0x7f => (first as usize, rest),
+        0x81..=0x83 => {
+            let (len, rest) = rest
+                .split_at_checked((first & 0x7f) as usize)
+                .ok_or_else(malformed)?;
+            (
+                len.iter().fold(0, |acc, &byte| acc << 8 | byte as usize),
+                rest,
+            )
+        }
+        _ => return Err(malformed()),
+    };
+    let (value, rest) = rest.split_at_checked(len).ok_or_else(malformed)?;
+    Ok((tag, value, rest))
+}
+
+/// CSCA public keys by issuing country (ISO 3166-1 alpha-3)
+pub type CscaMasterlist = HashMap<String, Vec<CscaKey>>;
 
-pub fn load_csca_public_keys() -> Result<HashMap<String, Vec<CscaKey>>, Box<dyn std::error::Error>>
-{
+pub fn load_csca_public_keys() -> Result<CscaMasterlist, Box<dyn std::error::Error>> {
     let path = "csca_registry/csca_public_key.json";
     let file_content = fs::read_to_string(path)?;
-    let csca_keys: HashMap<String, Vec<CscaKey>> = serde_json::from_str(&file_content)?;
+    let csca_keys: CscaMasterlist = serde_json::from_str(&file_content)?;
     Ok(csca_keys)
 }
 
-pub fn to_fixed_array<const N: usize>(bytes: &[u8], label: &str) -> Result<[u8; N], PassportError> {
-    bytes.try_into().map_err(|_| {
-        PassportError::BufferOverflow(format!(
-            "{label} must be exactly {N} bytes, got {}",
+pub fn csca_keys_for<'a>(
+    masterlist: &'a CscaMasterlist,
+    country: &str,
+) -> Result<&'a [CscaKey], PassportError> {
+    masterlist
+        .get(country)
+        .map(Vec::as_slice)
+        .ok_or_else(|| PassportError::NoCscaForCountry(country.to_string()))
+}
+
+pub fn to_sized_vec(bytes: &[u8], len: usize, label: &str) -> Result<Vec<u8>, PassportError> {
+    if bytes.len() != len {
+        return Err(PassportError::BufferOverflow(format!(
+            "{label} must be exactly {len} bytes, got {}",
             bytes.len()
-        ))
-    })
+        )));
+    }
+    Ok(bytes.to_vec())
 }
 
+/// Parse a big-endian unsigned integer, e.g. an RSA exponent, into a `u32`.
+/// Leading zero bytes are ignored; values above `u32::MAX` are rejected rather
+/// than truncated.
 pub fn to_u32(bytes: Vec<u8>) -> Result<u32, PassportError> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
     if bytes.len() > 4 {
         return Err(PassportError::RsaExponentTooLarge);
     }
     let mut buf = [0u8; 4];
-    buf[4 - bytes.len()..].copy_from_slice(&bytes);
+    buf[4 - bytes.len()..].copy_from_slice(bytes);
     Ok(u32::from_be_bytes(buf))
 }
 
@@ -108,3 +219,102 @@ pub fn find_offset(haystack: &[u8], needle: &[u8], label: &str) -> Result<usize,
         .position(|w| w == needle)
         .ok_or_else(|| PassportError::DataNotFound(label.to_string()))
 }
+
+/// Offsets of all occurrences of `needle` in `haystack`
+pub fn find_offsets(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    haystack
+        .windows(needle.len())
+        .enumerate()
+        .filter(|(_, w)| *w == needle)
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+/// Offset of `needle` in `haystack` where it directly follows `prefix`, the
+/// DER header of the structure expected to hold it. A single occurrence is
+/// accepted without the prefix, for data that is not DER encoded.
+pub fn find_offset_after(
+    haystack: &[u8],
+    needle: &[u8],
+    prefix: &[u8],
+    label: &str,
+) -> Result<usize, PassportError> {
+    let offsets = find_offsets(haystack, needle);
+    if let [offset] = offsets[..] {
+        return Ok(offset);
+    }
+    offsets
+        .into_iter()
+        .find(|&offset| haystack[..offset].ends_with(prefix))
+        .ok_or_else(|| PassportError::DataNotFound(label.to_string()))
+}
+
+/// Serde helpers for byte arrays longer than serde's built-in 32, as arrays
+/// of numbers
+pub mod byte_array {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        bytes: &[u8; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[u8; N], D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(bytes.len(), &format!("{N} bytes").as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_u32_parses_rsa_exponents() {
+        assert_eq!(to_u32(vec![0x01, 0x00, 0x01]).unwrap(), 65537);
+        assert_eq!(to_u32(vec![0x03]).unwrap(), 3);
+        assert_eq!(
+            to_u32(vec![0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01]).unwrap(),
+            65537
+        );
+    }
+
+    #[test]
+    fn find_offset_after_skips_repeated_bytes() {
+        // A DG1 hash repeated before its DataGroupHash { 1, OCTET STRING }
+        let hash = [0xab; 32];
+        let prefix = [ASN1_INTEGER_TAG, 0x01, 0x01, ASN1_OCTET_STRING_TAG, 0x20];
+        let econtent = [&hash[..], &[0x30, 0x25], &prefix, &hash].concat();
+
+        assert_eq!(find_offsets(&econtent, &hash), vec![0, 39]);
+        assert_eq!(find_offset(&econtent, &hash, "DG1 hash").unwrap(), 0);
+        assert_eq!(
+            find_offset_after(&econtent, &hash, &prefix, "DG1 hash").unwrap(),
+            39
+        );
+        // Without the expected structure, a unique match is still used
+        assert_eq!(
+            find_offset_after(&hash, &hash, &prefix, "DG1 hash").unwrap(),
+            0
+        );
+        assert!(matches!(
+            find_offset_after(&[&hash[..], &hash].concat(), &hash, &prefix, "DG1 hash"),
+            Err(PassportError::DataNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn to_u32_rejects_large_exponents() {
+        assert!(matches!(
+            to_u32(vec![0x01, 0x00, 0x00, 0x00, 0x01]),
+            Err(PassportError::RsaExponentTooLarge)
+        ));
+    }
+}
@@ -6,7 +6,7 @@ use {
     serde::{Deserialize, Serialize},
     std::{
         fs::File,
-        io::{Read, Write},
+        io::{self, Read, Write},
         path::Path,
     },
     tracing::{info, instrument},
@@ -23,11 +23,46 @@ pub fn write_bin<T: Serialize>(
     value: &T,
     path: &Path,
     format: [u8; 8],
-    (major, minor): (u16, u16),
+    version: (u16, u16),
 ) -> Result<()> {
     // Open file
     let mut file = File::create(path).context("while creating output file")?;
-    let mut file_counter = CountingWriter::new(&mut file);
+    let (compressed, uncompressed) = encode_bin(value, &mut file, format, version)?;
+
+    // Close file
+    let size = file.metadata().map(|m| m.len()).ok();
+    file.sync_all().context("while syncing output file")?;
+    drop(file);
+
+    // Log
+    let ratio = compressed as f64 / uncompressed as f64;
+    info!(
+        ?path,
+        size,
+        compressed,
+        uncompressed,
+        "Wrote {}B bytes to {path:?} ({ratio:.2} compression ratio)",
+        human(compressed as f64)
+    );
+    Ok(())
+}
+
+/// Size in bytes of the binary file of `value`, without writing it.
+pub fn bin_size<T: Serialize>(value: &T, format: [u8; 8], version: (u16, u16)) -> Result<usize> {
+    let (compressed, _) = encode_bin(value, io::sink(), format, version)?;
+    Ok(compressed)
+}
+
+/// Write the header and compressed encoding of `value` to `writer`.
+///
+/// Returns the number of bytes written and the uncompressed size.
+fn encode_bin<T: Serialize>(
+    value: &T,
+    writer: impl Write,
+    format: [u8; 8],
+    (major, minor): (u16, u16),
+) -> Result<(usize, usize)> {
+    let mut file_counter = CountingWriter::new(writer);
 
     // Write header
     let mut header = BytesMut::with_capacity(HEADER_SIZE);
@@ -51,23 +86,7 @@ pub fn write_bin<T: Serialize>(
     let uncompressed = compressor_counter.count();
     compressor.finish().context("while closing compressor")?;
 
-    // Close file
-    let compressed = file_counter.count();
-    let size = file.metadata().map(|m| m.len()).ok();
-    file.sync_all().context("while syncing output file")?;
-    drop(file);
-
-    // Log
-    let ratio = compressed as f64 / uncompressed as f64;
-    info!(
-        ?path,
-        size,
-        compressed,
-        uncompressed,
-        "Wrote {}B bytes to {path:?} ({ratio:.2} compression ratio)",
-        human(compressed as f64)
-    );
-    Ok(())
+    Ok((file_counter.count(), uncompressed))
 }
 
 /// Read a compressed binary file.
@@ -75,37 +94,142 @@ pub fn write_bin<T: Serialize>(
 pub fn read_bin<T: for<'a> Deserialize<'a>>(
     path: &Path,
     format: [u8; 8],
-    (major, minor): (u16, u16),
+    version: (u16, u16),
 ) -> Result<T> {
-    let mut file = File::open(path).context("while opening input file")?;
+    let file = File::open(path).context("while opening input file")?;
+    decode_bin(file, format, version, u64::MAX)
+}
 
+/// Decode a compressed binary value from a reader, e.g. an in-memory buffer
+/// received over the network.
+///
+/// Fails once more than `max_uncompressed` bytes have been decompressed, so a
+/// small malicious input cannot expand into an unbounded allocation.
+pub fn decode_bin<T: for<'a> Deserialize<'a>>(
+    mut reader: impl Read,
+    format: [u8; 8],
+    (major, minor): (u16, u16),
+    max_uncompressed: u64,
+) -> Result<T> {
     // Read header
     let mut buffer = [0; HEADER_SIZE];
-    file.read_exact(&mut buffer)
+    reader
+        .read_exact(&mut buffer)
         .context("while reading header")?;
     let mut header = Bytes::from_owner(buffer);
     ensure!(
         header.get_bytes::<8>() == MAGIC_BYTES,
-        "Invalid magic bytes"
+        "Invalid magic bytes, not a ProveKit file"
     );
-    ensure!(header.get_bytes::<8>() == format, "Invalid format");
+    let found_format = header.get_bytes::<8>();
+    let name = String::from_utf8_lossy(&format);
     ensure!(
-        header.get_u16_le() == major,
-        "Incompatible format major version"
+        found_format == format,
+        "File is a {} file but {name} was expected",
+        String::from_utf8_lossy(&found_format)
     );
+    let (found_major, found_minor) = (header.get_u16_le(), header.get_u16_le());
     ensure!(
-        header.get_u16_le() >= minor,
-        "Incompatible format minor version"
+        found_major == major && found_minor >= minor,
+        "File is {name} v{found_major}.{found_minor} but this build expects v{major}.{minor}; \
+         regenerate it with this version of ProveKit"
     );
 
     // Decompressor
-    let mut decompressor = ZstdDecoder::new(&mut file).context("while creating decompressor")?;
+    let mut decompressor = ZstdDecoder::new(reader).context("while creating decompressor")?;
 
     // Postcard
     // See <https://github.com/jamesmunns/postcard/pull/212> for the reason for the full uncompressed buffer.
     let mut uncompressed = Vec::new();
     decompressor
+        .take(max_uncompressed.saturating_add(1))
         .read_to_end(&mut uncompressed)
         .context("while reading decompressed data")?;
+    ensure!(
+        uncompressed.len() as u64 <= max_uncompressed,
+        "Decompressed data exceeds the limit of {}B",
+        human(max_uncompressed as f64)
+    );
     postcard::from_bytes(&uncompressed).context("while decoding from postcard")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: [u8; 8] = *b"TestFile";
+
+    /// Writes `[1, 2, 3]` with the given version and returns the file bytes.
+    fn encode(version: (u16, u16)) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!(
+            "provekit-bin-{}-{}-{}",
+            std::process::id(),
+            version.0,
+            version.1
+        ));
+        write_bin(&vec![1u32, 2, 3], &path, FORMAT, version).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn size_matches_file() {
+        let bytes = encode((0, 0));
+        assert_eq!(
+            bin_size(&vec![1u32, 2, 3], FORMAT, (0, 0)).unwrap(),
+            bytes.len()
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let bytes = encode((0, 1));
+        let value: Vec<u32> = decode_bin(&bytes[..], FORMAT, (0, 1), u64::MAX).unwrap();
+        assert_eq!(value, [1, 2, 3]);
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let mut bytes = encode((0, 0));
+        // Tamper with the minor version in the header.
+        bytes[18..20].copy_from_slice(&1u16.to_le_bytes());
+        let error = decode_bin::<Vec<u32>>(&bytes[..], FORMAT, (1, 0), u64::MAX).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "File is TestFile v0.1 but this build expects v1.0; regenerate it with this version \
+             of ProveKit"
+        );
+    }
+
+    #[test]
+    fn format_mismatch() {
+        let bytes = encode((0, 0));
+        let error = decode_bin::<Vec<u32>>(&bytes[..], *b"NrProScm", (0, 0), u64::MAX).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "File is a TestFile file but NrProScm was expected"
+        );
+    }
+
+    #[test]
+    fn invalid_magic() {
+        let mut bytes = encode((0, 0));
+        bytes[0] ^= 0xff;
+        let error = decode_bin::<Vec<u32>>(&bytes[..], FORMAT, (0, 0), u64::MAX).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid magic bytes, not a ProveKit file"
+        );
+    }
+
+    #[test]
+    fn decompression_limit() {
+        let bytes = encode((0, 0));
+        // Postcard encodes `[1, 2, 3]` in four bytes.
+        let value: Vec<u32> = decode_bin(&bytes[..], FORMAT, (0, 0), 4).unwrap();
+        assert_eq!(value, [1, 2, 3]);
+        let error = decode_bin::<Vec<u32>>(&bytes[..], FORMAT, (0, 0), 3).unwrap_err();
+        assert!(error.to_string().starts_with("Decompressed data exceeds"));
+    }
+}
@@ -3,7 +3,7 @@ use {
     crate::utils::human,
     anyhow::{Context as _, Result},
     serde::{Deserialize, Serialize},
-    std::{fs::File, path::Path},
+    std::{fs::File, io, path::Path},
     tracing::{info, instrument},
 };
 
@@ -30,6 +30,13 @@ pub fn write_json<T: Serialize>(value: &T, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Size in bytes of the JSON file of `value`, without writing it.
+pub fn json_size<T: Serialize>(value: &T) -> Result<usize> {
+    let mut counter = CountingWriter::new(io::sink());
+    serde_json::to_writer_pretty(&mut counter, value).context("while encoding JSON")?;
+    Ok(counter.count())
+}
+
 /// Read a JSON file.
 #[instrument(fields(size = path.metadata().map(|m| m.len()).ok()))]
 pub fn read_json<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<T> {
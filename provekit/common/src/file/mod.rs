@@ -5,10 +5,10 @@ mod json;
 
 use {
     self::{
-        bin::{read_bin, write_bin},
+        bin::{bin_size, decode_bin, read_bin, write_bin},
         buf_ext::BufExt,
         counting_writer::CountingWriter,
-        json::{read_json, write_json},
+        json::{json_size, read_json, write_json},
     },
     crate::{NoirProof, NoirProofScheme, Prover, Verifier},
     anyhow::Result,
@@ -27,19 +27,19 @@ pub trait FileFormat: Serialize + for<'a> Deserialize<'a> {
 impl FileFormat for NoirProofScheme {
     const FORMAT: [u8; 8] = *b"NrProScm";
     const EXTENSION: &'static str = "nps";
-    const VERSION: (u16, u16) = (0, 0);
+    const VERSION: (u16, u16) = (1, 0);
 }
 
 impl FileFormat for Prover {
     const FORMAT: [u8; 8] = *b"PrvKitPr";
     const EXTENSION: &'static str = "pkp";
-    const VERSION: (u16, u16) = (0, 0);
+    const VERSION: (u16, u16) = (1, 0);
 }
 
 impl FileFormat for Verifier {
     const FORMAT: [u8; 8] = *b"PrvKitVr";
     const EXTENSION: &'static str = "pkv";
-    const VERSION: (u16, u16) = (0, 0);
+    const VERSION: (u16, u16) = (1, 0);
 }
 
 impl FileFormat for NoirProof {
@@ -61,6 +61,19 @@ pub fn write<T: FileFormat>(value: &T, path: &Path) -> Result<()> {
     }
 }
 
+/// Size in bytes of the file [`write`] would create at `path`, computed
+/// without writing anything.
+pub fn serialized_size<T: FileFormat>(value: &T, path: &Path) -> Result<usize> {
+    match path.extension().and_then(OsStr::to_str) {
+        Some("json") => json_size(value),
+        Some(ext) if ext == T::EXTENSION => bin_size(value, T::FORMAT, T::VERSION),
+        _ => Err(anyhow::anyhow!(
+            "Unsupported file extension, please specify .{} or .json",
+            T::EXTENSION
+        )),
+    }
+}
+
 /// Read a file with format determined from extension.
 #[instrument()]
 pub fn read<T: FileFormat>(path: &Path) -> Result<T> {
@@ -73,3 +86,73 @@ pub fn read<T: FileFormat>(path: &Path) -> Result<T> {
         )),
     }
 }
+
+/// Read a value from the contents of a binary file, e.g. a request body.
+#[instrument(skip(bytes), fields(size = bytes.len()))]
+pub fn read_bytes<T: FileFormat>(bytes: &[u8]) -> Result<T> {
+    decode_bin(bytes, T::FORMAT, T::VERSION, u64::MAX)
+}
+
+/// Like [`read_bytes`], but fails if the value decompresses to more than
+/// `max_uncompressed` bytes. Use this for untrusted input.
+#[instrument(skip(bytes), fields(size = bytes.len()))]
+pub fn read_bytes_limited<T: FileFormat>(bytes: &[u8], max_uncompressed: u64) -> Result<T> {
+    decode_bin(bytes, T::FORMAT, T::VERSION, max_uncompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{FieldElement, PublicInputs, WhirR1CSProof},
+    };
+
+    #[test]
+    fn serialized_size_matches_written_file() {
+        let proof = NoirProof {
+            public_inputs:   PublicInputs(vec![FieldElement::from(42u64); 3]),
+            whir_r1cs_proof: WhirR1CSProof {
+                transcript: (0..=255).cycle().take(10_000).collect(),
+            },
+        };
+        for extension in [NoirProof::EXTENSION, "json"] {
+            let path = std::env::temp_dir()
+                .join(format!("provekit-size-{}.{extension}", std::process::id()));
+            write(&proof, &path).unwrap();
+            let size = std::fs::metadata(&path).unwrap().len();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(serialized_size(&proof, &path).unwrap() as u64, size);
+        }
+        assert!(serialized_size(&proof, Path::new("proof.txt")).is_err());
+    }
+
+    /// Reads a `T` file written with header version v0.0, returning the
+    /// error.
+    fn read_v0_error<T: FileFormat>() -> Option<String> {
+        let path = std::env::temp_dir().join(format!(
+            "provekit-v0-{}.{}",
+            std::process::id(),
+            T::EXTENSION
+        ));
+        // The header is checked before the payload is decoded.
+        write_bin(&vec![1u32, 2, 3], &path, T::FORMAT, (0, 0)).unwrap();
+        let result = read::<T>(&path);
+        std::fs::remove_file(&path).unwrap();
+        result.err().map(|error| error.to_string())
+    }
+
+    #[test]
+    fn v0_files_are_rejected() {
+        // v0 stored whether there are public inputs as a bool, which postcard
+        // would silently decode as a count of one.
+        let expected = |name: &str| {
+            Some(format!(
+                "File is {name} v0.0 but this build expects v1.0; regenerate it with this version \
+                 of ProveKit"
+            ))
+        };
+        assert_eq!(read_v0_error::<NoirProofScheme>(), expected("NrProScm"));
+        assert_eq!(read_v0_error::<Prover>(), expected("PrvKitPr"));
+        assert_eq!(read_v0_error::<Verifier>(), expected("PrvKitVr"));
+    }
+}
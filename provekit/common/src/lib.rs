@@ -5,6 +5,7 @@ mod prover;
 mod r1cs;
 pub mod skyscraper;
 mod sparse_matrix;
+pub mod transcript;
 pub mod utils;
 mod verifier;
 mod whir_r1cs;
@@ -12,6 +12,9 @@ use {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirProofScheme {
     pub program:                Program<NoirElement>,
+    /// Index of the ACIR function in `program` the R1CS is compiled from.
+    /// Zero is the entry point.
+    pub function:               u32,
     pub r1cs:                   R1CS,
     pub split_witness_builders: SplitWitnessBuilders,
     pub witness_generator:      NoirWitnessGenerator,
@@ -24,9 +27,61 @@ pub struct NoirProof {
     pub whir_r1cs_proof: WhirR1CSProof,
 }
 
+impl NoirProof {
+    /// The public inputs the proof was made for.
+    #[must_use]
+    pub const fn public_inputs(&self) -> &PublicInputs {
+        &self.public_inputs
+    }
+
+    /// The WHIR transcript, as consumed by the verifier together with the
+    /// public inputs.
+    #[must_use]
+    pub fn transcript_bytes(&self) -> &[u8] {
+        &self.whir_r1cs_proof.transcript
+    }
+}
+
 impl NoirProofScheme {
     #[must_use]
     pub const fn size(&self) -> (usize, usize) {
         (self.r1cs.num_constraints(), self.r1cs.num_witnesses())
     }
+
+    /// The number of R1CS constraints.
+    #[must_use]
+    pub const fn num_constraints(&self) -> usize {
+        self.r1cs.num_constraints()
+    }
+
+    /// The number of R1CS witnesses, including the constant one witness.
+    #[must_use]
+    pub const fn num_witnesses(&self) -> usize {
+        self.r1cs.num_witnesses()
+    }
+
+    /// The number of public inputs a proof for this scheme carries.
+    #[must_use]
+    pub const fn public_input_count(&self) -> usize {
+        self.whir_for_witness.num_public_inputs
+    }
+
+    /// The number of variables of the committed witness polynomial.
+    #[must_use]
+    pub const fn whir_m(&self) -> usize {
+        self.whir_for_witness.m
+    }
+
+    /// The number of variables of the constraint sumcheck.
+    #[must_use]
+    pub const fn whir_m_0(&self) -> usize {
+        self.whir_for_witness.m_0
+    }
+
+    /// Whether this scheme was prepared from the ACIR `program`, e.g. the
+    /// `bytecode` of a compiled program artifact.
+    #[must_use]
+    pub fn is_compatible_with(&self, program: &Program<NoirElement>) -> bool {
+        self.program == *program
+    }
 }
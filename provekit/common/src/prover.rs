@@ -13,6 +13,8 @@ use {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prover {
     pub program:                Program<NoirElement>,
+    /// Index of the ACIR function in `program` whose witness is proven
+    pub function:               u32,
     pub r1cs:                   R1CS,
     pub split_witness_builders: SplitWitnessBuilders,
     pub witness_generator:      NoirWitnessGenerator,
@@ -23,6 +25,7 @@ impl Prover {
     pub fn from_noir_proof_scheme(noir_proof_scheme: NoirProofScheme) -> Self {
         Self {
             program:                noir_proof_scheme.program,
+            function:               noir_proof_scheme.function,
             r1cs:                   noir_proof_scheme.r1cs,
             split_witness_builders: noir_proof_scheme.split_witness_builders,
             witness_generator:      noir_proof_scheme.witness_generator,
@@ -33,4 +36,11 @@ impl Prover {
     pub const fn size(&self) -> (usize, usize) {
         (self.r1cs.num_constraints(), self.r1cs.num_witnesses())
     }
+
+    /// Whether this prover was prepared from the ACIR `program`, e.g. the
+    /// `bytecode` of a compiled program artifact.
+    #[must_use]
+    pub fn is_compatible_with(&self, program: &Program<NoirElement>) -> bool {
+        self.program == *program
+    }
 }
@@ -1,6 +1,15 @@
 use {
     crate::{FieldElement, HydratedSparseMatrix, Interner, SparseMatrix},
+    anyhow::{ensure, Context, Result},
+    ark_ff::PrimeField,
+    rayon::iter::{IntoParallelIterator, ParallelIterator},
     serde::{Deserialize, Serialize},
+    std::{
+        fmt::Display,
+        fs::{self, File},
+        io::{BufWriter, Write},
+        path::Path,
+    },
 };
 
 /// Represents a R1CS constraint system.
@@ -101,4 +110,194 @@ impl R1CS {
             );
         }
     }
+
+    /// Index of the first constraint that `witness` does not satisfy, or
+    /// `None` if it satisfies all of them.
+    pub fn first_unsatisfied_constraint(&self, witness: &[FieldElement]) -> Option<usize> {
+        self.check_witness_size(witness);
+        (0..self.num_constraints()).find(|&row| !self.is_satisfied(row, witness))
+    }
+
+    /// Like [`Self::first_unsatisfied_constraint`], but checks the
+    /// constraints in parallel. The failing indices are min-reduced, so the
+    /// result is the same.
+    pub fn par_first_unsatisfied_constraint(&self, witness: &[FieldElement]) -> Option<usize> {
+        self.check_witness_size(witness);
+        (0..self.num_constraints())
+            .into_par_iter()
+            .filter(|&row| !self.is_satisfied(row, witness))
+            .min()
+    }
+
+    fn check_witness_size(&self, witness: &[FieldElement]) {
+        assert_eq!(
+            witness.len(),
+            self.num_witnesses(),
+            "Witness size does not match"
+        );
+    }
+
+    /// Whether `witness` satisfies constraint `row`.
+    fn is_satisfied(&self, row: usize, witness: &[FieldElement]) -> bool {
+        let eval = |matrix: HydratedSparseMatrix<'_>| -> FieldElement {
+            matrix
+                .iter_row(row)
+                .map(|(col, value)| value * witness[col])
+                .sum()
+        };
+        eval(self.a()) * eval(self.b()) == eval(self.c())
+    }
+
+    /// Write the sparsity patterns of the A, B and C matrices to `a.mtx`,
+    /// `b.mtx` and `c.mtx` in `dir` using the Matrix Market coordinate format,
+    /// with one row per constraint and one column per witness.
+    ///
+    /// Field elements do not fit the `integer` or `real` Matrix Market types,
+    /// so the files use the `pattern` type, which SciPy and MATLAB read
+    /// directly. The entry values go to `a.values`, `b.values` and
+    /// `c.values`, one per line in the order of the `.mtx` entries, as signed
+    /// decimals using the representative in `(-p/2, p/2]` so that small
+    /// negative coefficients stay readable.
+    pub fn write_matrix_market(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir).with_context(|| format!("while creating {}", dir.display()))?;
+        for (name, matrix) in [("a", self.a()), ("b", self.b()), ("c", self.c())] {
+            let path = dir.join(format!("{name}.mtx"));
+            write_text(&path, |out| {
+                writeln!(out, "%%MatrixMarket matrix coordinate pattern general")?;
+                writeln!(out, "% values in {name}.values")?;
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    self.num_constraints(),
+                    self.num_witnesses(),
+                    matrix.matrix.num_entries()
+                )?;
+                for ((row, col), _) in matrix.iter() {
+                    writeln!(out, "{} {}", row + 1, col + 1)?;
+                }
+                Ok(())
+            })
+            .with_context(|| format!("while writing {}", path.display()))?;
+
+            let path = dir.join(format!("{name}.values"));
+            write_text(&path, |out| {
+                for (_, value) in matrix.iter() {
+                    writeln!(out, "{}", signed(value))?;
+                }
+                Ok(())
+            })
+            .with_context(|| format!("while writing {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Write a witness for this instance to `w.values` in `dir`, one value
+    /// per line in the format of [`Self::write_matrix_market`].
+    pub fn write_witness_values(&self, dir: &Path, witness: &[FieldElement]) -> Result<()> {
+        ensure!(
+            witness.len() == self.num_witnesses(),
+            "Witness size does not match"
+        );
+        fs::create_dir_all(dir).with_context(|| format!("while creating {}", dir.display()))?;
+        let path = dir.join("w.values");
+        write_text(&path, |out| {
+            for value in witness {
+                writeln!(out, "{}", signed(*value))?;
+            }
+            Ok(())
+        })
+        .with_context(|| format!("while writing {}", path.display()))
+    }
+}
+
+fn write_text(
+    path: &Path,
+    contents: impl FnOnce(&mut BufWriter<File>) -> std::io::Result<()>,
+) -> Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    contents(&mut out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// The representative of `value` in `(-p/2, p/2]`.
+fn signed(value: FieldElement) -> impl Display {
+    if value.into_bigint() > FieldElement::MODULUS_MINUS_ONE_DIV_TWO {
+        format!("-{}", -value)
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::io::BufRead};
+
+    /// Returns the header line following the Matrix Market banner.
+    fn read_header(path: &Path) -> Vec<usize> {
+        let file = File::open(path).expect("Opening Matrix Market file");
+        let line = std::io::BufReader::new(file)
+            .lines()
+            .map(|line| line.expect("Reading Matrix Market file"))
+            .find(|line| !line.starts_with('%'))
+            .expect("Missing header");
+        line.split_whitespace()
+            .map(|n| n.parse().expect("Parsing header"))
+            .collect()
+    }
+
+    #[test]
+    fn parallel_satisfaction_matches_sequential() {
+        let one = FieldElement::from(1u64);
+        let mut r1cs = R1CS::new();
+        r1cs.add_witnesses(2);
+        // w1 * w1 = w1
+        for _ in 0..100 {
+            r1cs.add_constraint(&[(one, 1)], &[(one, 1)], &[(one, 1)]);
+        }
+        let witness = [one, one];
+        assert_eq!(r1cs.first_unsatisfied_constraint(&witness), None);
+        assert_eq!(r1cs.par_first_unsatisfied_constraint(&witness), None);
+
+        // Break a few constraints into w1 * w1 = w1 + 1
+        for row in [99, 37, 60] {
+            r1cs.c.set(row, 0, r1cs.interner.intern(one));
+        }
+        assert_eq!(r1cs.first_unsatisfied_constraint(&witness), Some(37));
+        assert_eq!(r1cs.par_first_unsatisfied_constraint(&witness), Some(37));
+
+        // Every constraint fails
+        let witness = [one, FieldElement::from(2u64)];
+        assert_eq!(r1cs.first_unsatisfied_constraint(&witness), Some(0));
+        assert_eq!(r1cs.par_first_unsatisfied_constraint(&witness), Some(0));
+    }
+
+    #[test]
+    fn matrix_market_header() {
+        let mut r1cs = R1CS::new();
+        r1cs.add_witnesses(3);
+        let one = FieldElement::from(1u64);
+        // w1 * w1 = w2 and (w1 + w2) * 1 = w2 + 2
+        r1cs.add_constraint(&[(one, 1)], &[(one, 1)], &[(one, 2)]);
+        r1cs.add_constraint(&[(one, 1), (one, 2)], &[(one, 0)], &[
+            (one, 2),
+            (FieldElement::from(2u64), 0),
+        ]);
+
+        let dir = std::env::temp_dir().join(format!("provekit-mtx-{}", std::process::id()));
+        r1cs.write_matrix_market(&dir).unwrap();
+        r1cs.write_witness_values(&dir, &[one, -one, one]).unwrap();
+
+        assert_eq!(read_header(&dir.join("a.mtx")), [2, 3, 3]);
+        assert_eq!(read_header(&dir.join("b.mtx")), [2, 3, 2]);
+        assert_eq!(read_header(&dir.join("c.mtx")), [2, 3, 3]);
+        let c = fs::read_to_string(dir.join("c.mtx")).unwrap();
+        assert!(c.starts_with("%%MatrixMarket matrix coordinate pattern general\n"));
+        let values = fs::read_to_string(dir.join("c.values")).unwrap();
+        assert_eq!(values.lines().count(), 3);
+        let witness = fs::read_to_string(dir.join("w.values")).unwrap();
+        assert_eq!(witness, "1\n-1\n1\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
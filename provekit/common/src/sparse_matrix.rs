@@ -111,6 +111,45 @@ impl SparseMatrix {
         })
     }
 
+    /// Iterate over the rows of the matrix, with the non-default entries
+    /// `(col, value)` of each.
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            usize,
+            impl Iterator<Item = (usize, InternedFieldElement)> + use<'_>,
+        ),
+    > + use<'_> {
+        (0..self.num_rows).map(move |row| (row, self.iter_row(row)))
+    }
+
+    /// Iterate over the columns of the matrix, with the non-default entries
+    /// `(row, value)` of each.
+    pub fn cols(&self) -> impl Iterator<Item = (usize, Vec<(usize, InternedFieldElement)>)> {
+        let transposed = self.transpose();
+        (0..transposed.num_rows).map(move |col| (col, transposed.iter_row(col).collect()))
+    }
+
+    /// The transpose of the matrix, using the same interner.
+    pub fn transpose(&self) -> Self {
+        // Stable sort, so the rows of each column stay in order
+        let mut entries: Vec<_> = self
+            .iter()
+            .map(|((row, col), value)| (col, row, value))
+            .collect();
+        entries.sort_by_key(|&(col, ..)| col);
+        Self {
+            num_rows:        self.num_cols,
+            num_cols:        self.num_rows,
+            new_row_indices: (0..self.num_cols)
+                .map(|col| entries.partition_point(|&(c, ..)| c < col) as u32)
+                .collect(),
+            col_indices:     entries.iter().map(|&(_, row, _)| row as u32).collect(),
+            values:          entries.iter().map(|&(.., value)| value).collect(),
+        }
+    }
+
     fn row_range(&self, row: usize) -> Range<usize> {
         let start = *self
             .new_row_indices
@@ -171,6 +210,32 @@ impl HydratedSparseMatrix<'_> {
         })
     }
 
+    /// Iterate over the rows of the matrix, with the non-default entries
+    /// `(col, value)` of each.
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<Item = (usize, impl Iterator<Item = (usize, FieldElement)> + use<'_>)> + use<'_>
+    {
+        (0..self.matrix.num_rows).map(move |row| (row, self.iter_row(row)))
+    }
+
+    /// Iterate over the columns of the matrix, with the non-default entries
+    /// `(row, value)` of each.
+    pub fn cols(&self) -> impl Iterator<Item = (usize, Vec<(usize, FieldElement)>)> + use<'_> {
+        self.matrix.cols().map(move |(col, entries)| {
+            let entries = entries
+                .into_iter()
+                .map(|(row, value)| {
+                    (
+                        row,
+                        self.interner.get(value).expect("Value not in interner."),
+                    )
+                })
+                .collect();
+            (col, entries)
+        })
+    }
+
     /// Iterate over the non-default entries of the matrix.
     pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), FieldElement)> + use<'_> {
         self.matrix.iter().map(|((i, j), v)| {
@@ -219,3 +284,64 @@ impl Mul<HydratedSparseMatrix<'_>> for &[FieldElement] {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The 3x4 matrix
+    /// ```text
+    /// 1 0 0 2
+    /// 0 0 0 0
+    /// 0 3 0 4
+    /// ```
+    fn example(interner: &mut Interner) -> SparseMatrix {
+        let mut matrix = SparseMatrix::new(3, 4);
+        for ((row, col), value) in [((0, 0), 1u64), ((0, 3), 2), ((2, 1), 3), ((2, 3), 4)] {
+            matrix.set(row, col, interner.intern(FieldElement::from(value)));
+        }
+        matrix
+    }
+
+    #[test]
+    fn transpose_twice_is_identity() {
+        let mut interner = Interner::new();
+        let matrix = example(&mut interner);
+        let transposed = matrix.transpose();
+        assert_eq!((transposed.num_rows, transposed.num_cols), (4, 3));
+        let entry = |index, value: u64| (index, FieldElement::from(value));
+        assert_eq!(transposed.hydrate(&interner).iter().collect::<Vec<_>>(), [
+            entry((0, 0), 1),
+            entry((1, 2), 3),
+            entry((3, 0), 2),
+            entry((3, 2), 4),
+        ]);
+        assert_eq!(transposed.transpose(), matrix);
+    }
+
+    #[test]
+    fn rows_and_cols_visit_all_entries() {
+        let mut interner = Interner::new();
+        let matrix = example(&mut interner);
+        let hydrated = matrix.hydrate(&interner);
+        let mut entries: Vec<_> = hydrated.iter().collect();
+
+        let from_rows: Vec<_> = hydrated
+            .rows()
+            .flat_map(|(row, cols)| cols.map(move |(col, value)| ((row, col), value)))
+            .collect();
+        assert_eq!(from_rows, entries);
+
+        let mut from_cols: Vec<_> = hydrated
+            .cols()
+            .flat_map(|(col, rows)| {
+                rows.into_iter()
+                    .map(move |(row, value)| ((row, col), value))
+            })
+            .collect();
+        assert_eq!(hydrated.cols().count(), 4);
+        from_cols.sort_by_key(|&(index, _)| index);
+        entries.sort_by_key(|&(index, _)| index);
+        assert_eq!(from_cols, entries);
+    }
+}
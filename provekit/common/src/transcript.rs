@@ -0,0 +1,44 @@
+//! Sources of the verifier challenges drawn while solving witnesses.
+
+use {
+    crate::{skyscraper::SkyscraperSponge, FieldElement},
+    ark_ff::Zero,
+    spongefish::{codecs::arkworks_algebra::UnitToField, ProverState},
+};
+
+/// Transcript the witness solver draws challenges from.
+pub trait ChallengeTranscript {
+    /// Draws the next challenge.
+    fn challenge(&mut self) -> FieldElement;
+}
+
+impl ChallengeTranscript for ProverState<SkyscraperSponge, FieldElement> {
+    fn challenge(&mut self) -> FieldElement {
+        let mut challenge = [FieldElement::zero(); 1];
+        let _ = self.fill_challenge_scalars(&mut challenge);
+        challenge[0]
+    }
+}
+
+/// Transcript returning the challenges 1, 2, 3, ... in order.
+///
+/// Lets witnesses be solved without a Fiat-Shamir transcript, e.g. in tests.
+/// The challenges are known in advance, so proofs built on them are unsound.
+#[derive(Debug, Clone, Default)]
+pub struct MockTranscript {
+    /// Number of challenges drawn so far
+    drawn: u64,
+}
+
+impl MockTranscript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ChallengeTranscript for MockTranscript {
+    fn challenge(&mut self) -> FieldElement {
+        self.drawn += 1;
+        FieldElement::from(self.drawn)
+    }
+}
@@ -11,7 +11,8 @@ pub mod zk_utils;
 pub use self::print_abi::PrintAbi;
 use {
     crate::{FieldElement, NoirElement},
-    ark_ff::{BigInt, Field, PrimeField},
+    acir::AcirField,
+    ark_ff::{BigInt, BigInteger, Field, PrimeField},
     ruint::{aliases::U256, uint},
     std::{
         fmt::{Display, Formatter, Result as FmtResult},
@@ -69,6 +70,15 @@ pub fn noir_to_native(n: NoirElement) -> FieldElement {
     FieldElement::from(BigInt(limbs))
 }
 
+/// Convert a native `FieldElement` to a Noir field element
+///
+/// Both are BN254 scalars, but `From` impls between them would break the
+/// orphan rule, as neither type is defined here.
+#[inline(always)]
+pub fn native_to_noir(n: FieldElement) -> NoirElement {
+    NoirElement::from_le_bytes_reduce(&n.into_bigint().to_bytes_le())
+}
+
 /// Calculates the degree of the next smallest power of two
 pub const fn next_power_of_two(n: usize) -> usize {
     let mut power = 1;
@@ -162,3 +172,22 @@ pub fn batch_inverse_montgomery(values: &[FieldElement]) -> Vec<FieldElement> {
 
     inverses
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        proptest::{prelude::any, proptest},
+    };
+
+    #[test]
+    fn native_noir_round_trip() {
+        proptest!(|(bytes in any::<[u8; 32]>())| {
+            let native = FieldElement::from_le_bytes_mod_order(&bytes);
+            assert_eq!(noir_to_native(native_to_noir(native)), native);
+
+            let noir = NoirElement::from_le_bytes_reduce(&bytes);
+            assert_eq!(native_to_noir(noir_to_native(noir)), noir);
+        });
+    }
+}
@@ -216,7 +216,7 @@ pub fn calculate_eq(r: &[FieldElement], alpha: &[FieldElement]) -> FieldElement
 #[instrument(skip_all)]
 pub fn calculate_external_row_of_r1cs_matrices(
     alpha: Vec<FieldElement>,
-    r1cs: R1CS,
+    r1cs: &R1CS,
 ) -> [Vec<FieldElement>; 3] {
     let eq_alpha = calculate_evaluations_over_boolean_hypercube_for_eq(alpha);
     let eq_alpha = &eq_alpha[..r1cs.num_constraints()];
@@ -1,5 +1,8 @@
 use {
-    crate::FieldElement, ark_ff::UniformRand, rayon::prelude::*,
+    crate::FieldElement,
+    ark_ff::UniformRand,
+    ark_std::rand::{rngs::StdRng, RngCore, SeedableRng},
+    rayon::prelude::*,
     whir::poly_utils::evals::EvaluationsList,
 };
 
@@ -13,7 +16,13 @@ pub fn create_masked_polynomial(
     EvaluationsList::new(combined)
 }
 
-pub fn generate_random_multilinear_polynomial(num_vars: usize) -> Vec<FieldElement> {
+/// Random evaluations of a multilinear polynomial in `num_vars` variables.
+/// Each chunk filled in parallel gets its own seed drawn from `rng`, so the
+/// result only depends on the state of `rng`.
+pub fn generate_random_multilinear_polynomial(
+    num_vars: usize,
+    rng: &mut impl RngCore,
+) -> Vec<FieldElement> {
     let num_elements = 1 << num_vars;
     let mut elements = Vec::with_capacity(num_elements);
 
@@ -23,13 +32,24 @@ pub fn generate_random_multilinear_polynomial(num_vars: usize) -> Vec<FieldEleme
     // Get access to the uninitialized memory
     let spare = elements.spare_capacity_mut();
 
+    let seeds = (0..num_elements.div_ceil(CHUNK_SIZE))
+        .map(|_| {
+            let mut seed = <StdRng as SeedableRng>::Seed::default();
+            rng.fill_bytes(&mut seed);
+            seed
+        })
+        .collect::<Vec<_>>();
+
     // Fill the uninitialized memory in parallel using chunked approach
-    spare.par_chunks_mut(CHUNK_SIZE).for_each(|chunk| {
-        let mut rng = ark_std::rand::thread_rng();
-        for element in chunk {
-            element.write(FieldElement::rand(&mut rng));
-        }
-    });
+    spare
+        .par_chunks_mut(CHUNK_SIZE)
+        .zip(seeds)
+        .for_each(|(chunk, seed)| {
+            let mut rng = StdRng::from_seed(seed);
+            for element in chunk {
+                element.write(FieldElement::rand(&mut rng));
+            }
+        });
 
     unsafe {
         elements.set_len(num_elements);
@@ -15,6 +15,13 @@ use {
 pub type WhirConfig = GenericWhirConfig<FieldElement, SkyscraperMerkleConfig, SkyscraperPoW>;
 pub type IOPattern = DomainSeparator<SkyscraperSponge, FieldElement>;
 
+/// Sizes in bytes of the proof elements, used to estimate proof sizes
+const FIELD_ELEMENT_BYTES: usize = 32;
+const DIGEST_BYTES: usize = 32;
+const POW_NONCE_BYTES: usize = 8;
+/// A sumcheck round sends the evaluations of a quadratic and a PoW nonce
+const SUMCHECK_ROUND_BYTES: usize = 3 * FIELD_ELEMENT_BYTES + POW_NONCE_BYTES;
+
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct WhirR1CSScheme {
     pub m: usize,
@@ -22,12 +29,16 @@ pub struct WhirR1CSScheme {
     pub m_0: usize,
     pub a_num_terms: usize,
     pub num_challenges: usize,
-    pub has_public_inputs: bool,
+    pub num_public_inputs: usize,
     pub whir_witness: WhirConfig,
     pub whir_for_hiding_spartan: WhirConfig,
 }
 
 impl WhirR1CSScheme {
+    pub const fn has_public_inputs(&self) -> bool {
+        self.num_public_inputs > 0
+    }
+
     #[instrument(skip_all)]
     pub fn create_io_pattern(&self) -> IOPattern {
         let mut io = IOPattern::new("🌪️");
@@ -39,7 +50,7 @@ impl WhirR1CSScheme {
             // statement_2 has 3 constraints = 3, total = 7
             let num_witnesses = 2;
             let num_ood_constraints = num_witnesses * self.whir_witness.committment_ood_samples;
-            let num_statement_constraints = if self.has_public_inputs { 7 } else { 6 };
+            let num_statement_constraints = if self.has_public_inputs() { 7 } else { 6 };
             let num_constraints_total = num_ood_constraints + num_statement_constraints;
 
             io = io
@@ -70,6 +81,67 @@ impl WhirR1CSScheme {
 
         io
     }
+
+    /// Approximate size in bytes of the transcript of a proof for this
+    /// scheme, computed from the WHIR round parameters without proving.
+    ///
+    /// Counts the Merkle roots and paths, OOD answers, query openings, PoW
+    /// nonces and sumcheck messages. Useful to tune the security and folding
+    /// parameters for a size budget.
+    pub fn estimated_proof_size_bytes(&self) -> usize {
+        let num_witnesses = if self.num_challenges > 0 { 2 } else { 1 };
+        // Cubic Spartan sumcheck polynomials
+        let spartan_sumcheck = self.m_0 * 4 * FIELD_ELEMENT_BYTES;
+        // Claimed A, B and C evaluations of each witness and the public weights
+        let claimed_evaluations = (3 * num_witnesses + 1) * FIELD_ELEMENT_BYTES;
+
+        num_witnesses * estimated_commitment_size(&self.whir_witness)
+            + estimated_commitment_size(&self.whir_for_hiding_spartan)
+            + spartan_sumcheck
+            + estimated_whir_proof_size(&self.whir_for_hiding_spartan, 1)
+            + claimed_evaluations
+            + estimated_whir_proof_size(&self.whir_witness, num_witnesses)
+    }
+}
+
+/// Size in bytes of a WHIR commitment: its Merkle root and OOD answers.
+fn estimated_commitment_size(config: &WhirConfig) -> usize {
+    DIGEST_BYTES + config.committment_ood_samples * config.batch_size * FIELD_ELEMENT_BYTES
+}
+
+/// Approximate size in bytes of a WHIR proof opening `num_commitments`
+/// commitments of `config` at once.
+fn estimated_whir_proof_size(config: &WhirConfig, num_commitments: usize) -> usize {
+    let num_variables = config.mv_parameters.num_variables;
+    let (num_rounds, final_sumcheck_rounds) = config
+        .folding_factor
+        .compute_number_of_rounds(num_variables);
+
+    // Queries are neither deduplicated nor share their Merkle paths
+    let opening = |batch: usize, log_domain_size: usize, folding: usize| {
+        (batch << folding) * FIELD_ELEMENT_BYTES + (log_domain_size - folding) * DIGEST_BYTES
+    };
+
+    let mut size = config.folding_factor.at_round(0) * SUMCHECK_ROUND_BYTES;
+    let mut batch = num_commitments * config.batch_size;
+    let mut log_domain_size = num_variables + config.starting_log_inv_rate;
+    for (round, params) in config.round_parameters.iter().enumerate() {
+        let folding = config.folding_factor.at_round(round);
+        size += DIGEST_BYTES
+            + params.ood_samples * FIELD_ELEMENT_BYTES
+            + POW_NONCE_BYTES
+            + params.num_queries * opening(batch, log_domain_size, folding)
+            + config.folding_factor.at_round(round + 1) * SUMCHECK_ROUND_BYTES;
+        // Each round folds a single polynomial over a domain half the size
+        batch = 1;
+        log_domain_size -= 1;
+    }
+
+    let folding = config.folding_factor.at_round(num_rounds);
+    size + (1 << final_sumcheck_rounds) * FIELD_ELEMENT_BYTES
+        + POW_NONCE_BYTES
+        + config.final_queries * opening(batch, log_domain_size, folding)
+        + final_sumcheck_rounds * SUMCHECK_ROUND_BYTES
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
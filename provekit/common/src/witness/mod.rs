@@ -13,14 +13,16 @@ use {
         FieldElement,
     },
     ark_crypto_primitives::crh::CRHScheme,
-    ark_ff::One,
+    ark_ff::{One, PrimeField},
     serde::{Deserialize, Serialize},
 };
 pub use {
     binops::{BINOP_ATOMIC_BITS, BINOP_BITS, NUM_DIGITS},
     digits::{decompose_into_digits, DigitalDecompositionWitnesses},
     ram::{SpiceMemoryOperation, SpiceWitnesses},
-    scheduling::{Layer, LayerType, LayeredWitnessBuilders, SplitError, SplitWitnessBuilders},
+    scheduling::{
+        DependencyInfo, Layer, LayerType, LayeredWitnessBuilders, SplitError, SplitWitnessBuilders,
+    },
     witness_builder::{
         CombinedTableEntryInverseData, ConstantTerm, ProductLinearTerm, SumTerm, WitnessBuilder,
         WitnessCoefficient,
@@ -67,6 +69,9 @@ impl PublicInputs {
         self.0.is_empty()
     }
 
+    /// Hash of the public inputs, kept for proofs and verifiers that already
+    /// commit to it. Prefer [`PublicInputs::hash_v2`]: this hash folds the
+    /// inputs without their count, so e.g. `[a]` and `[a, 0]` collide.
     pub fn hash(&self) -> FieldElement {
         match self.0.len() {
             0 => FieldElement::from(0u64),
@@ -81,8 +86,49 @@ impl PublicInputs {
     }
 }
 
+impl PublicInputs {
+    /// Domain separator of [`PublicInputs::hash_v2`]
+    const HASH_DOMAIN: &'static [u8] = b"provekit-public-inputs-v2";
+
+    /// Domain separated hash of the public inputs, the recommended way to
+    /// commit to them. The domain tag and the number of inputs are hashed
+    /// first, so inputs of different lengths cannot alias.
+    pub fn hash_v2(&self) -> FieldElement {
+        let mut input = Vec::with_capacity(self.0.len() + 2);
+        input.push(FieldElement::from_le_bytes_mod_order(Self::HASH_DOMAIN));
+        input.push(FieldElement::from(self.0.len() as u64));
+        input.extend_from_slice(&self.0);
+        SkyscraperCRH::evaluate(&(), &input[..]).expect("hash should succeed")
+    }
+}
+
 impl Default for PublicInputs {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_v2_separates_lengths() {
+        let a = FieldElement::from(3u64);
+        let b = FieldElement::from(5u64);
+        let c = FieldElement::from(7u64);
+        let hash = |inputs: &[FieldElement]| PublicInputs::from_vec(inputs.to_vec()).hash();
+        let hash_v2 = |inputs: &[FieldElement]| PublicInputs::from_vec(inputs.to_vec()).hash_v2();
+
+        // Zero padding and folding make `hash` alias inputs of other lengths
+        let ab = SkyscraperCRH::evaluate(&(), &[a, b][..]).unwrap();
+        let zero = FieldElement::from(0u64);
+        assert_eq!(hash(&[a]), hash(&[a, zero]));
+        assert_eq!(hash(&[ab, c]), hash(&[a, b, c]));
+
+        assert_ne!(hash_v2(&[a]), hash_v2(&[a, zero]));
+        assert_ne!(hash_v2(&[ab, c]), hash_v2(&[a, b, c]));
+        assert_ne!(hash_v2(&[]), hash_v2(&[zero]));
+        assert_eq!(hash_v2(&[a, b]), hash_v2(&[a, b]));
+    }
+}
@@ -1,6 +1,7 @@
 use {
     crate::witness::WitnessBuilder,
     serde::{Deserialize, Serialize},
+    std::fmt::Write as _,
 };
 
 mod dependency;
@@ -82,3 +83,137 @@ pub struct SplitWitnessBuilders {
     /// Number of witnesses in w1 (used for indexing split).
     pub w1_size:   usize,
 }
+
+impl SplitWitnessBuilders {
+    /// Renders the dependency graph of the witness builders in Graphviz DOT
+    /// format.
+    ///
+    /// Builders are grouped in a cluster for w1 and one for w2, with a nested
+    /// cluster per layer, and labelled with their kind and the witnesses they
+    /// write. An edge goes from the builder writing a witness to each builder
+    /// reading it.
+    pub fn to_dot(&self) -> String {
+        let phases = [("w1", &self.w1_layers), ("w2", &self.w2_layers)];
+        let builders: Vec<WitnessBuilder> = phases
+            .iter()
+            .flat_map(|(_, plan)| &plan.layers)
+            .flat_map(|layer| layer.witness_builders.iter().cloned())
+            .collect();
+        let deps = DependencyInfo::new(&builders);
+
+        let mut out = String::from("digraph witness_builders {\n");
+        let mut node = 0;
+        for (name, plan) in phases {
+            let _ = writeln!(out, "  subgraph cluster_{name} {{\n    label=\"{name}\";");
+            for (i, layer) in plan.layers.iter().enumerate() {
+                let typ = match layer.typ {
+                    LayerType::Other => "",
+                    LayerType::Inverse => " (inverse)",
+                };
+                let _ = writeln!(
+                    out,
+                    "    subgraph cluster_{name}_{i} {{\n      label=\"layer {i}{typ}\";"
+                );
+                for builder in &layer.witness_builders {
+                    let _ = writeln!(
+                        out,
+                        "      b{node} [label=\"{}\\n{}\"];",
+                        builder_kind(builder),
+                        witness_range(&DependencyInfo::extract_writes(builder))
+                    );
+                    node += 1;
+                }
+                out.push_str("    }\n");
+            }
+            out.push_str("  }\n");
+        }
+
+        for (producer, consumers) in deps.adjacency_list.iter().enumerate() {
+            let mut consumers = consumers.clone();
+            consumers.sort_unstable();
+            consumers.dedup();
+            for consumer in consumers {
+                let _ = writeln!(out, "  b{producer} -> b{consumer};");
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Name of the variant of a witness builder
+fn builder_kind(builder: &WitnessBuilder) -> String {
+    let debug = format!("{builder:?}");
+    debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Compact list of witness indices, with contiguous runs as ranges
+fn witness_range(witnesses: &[usize]) -> String {
+    match witnesses {
+        [first, .., last] if last > first && last - first + 1 == witnesses.len() => {
+            format!("{first}..={last}")
+        }
+        _ => witnesses
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            witness::{ConstantTerm, WitnessCoefficient},
+            FieldElement, R1CS,
+        },
+        std::collections::HashSet,
+    };
+
+    #[test]
+    fn dot_graph_shows_split() {
+        let one = FieldElement::from(1u64);
+        let builders = [
+            WitnessBuilder::Constant(ConstantTerm(0, one)),
+            WitnessBuilder::Acir(1, 0),
+            WitnessBuilder::Challenge(2),
+            WitnessBuilder::LogUpInverse(3, 2, WitnessCoefficient(one, 1)),
+            WitnessBuilder::Product(4, 3, 3),
+        ];
+        let (split, ..) = WitnessBuilder::split_and_prepare_layers(
+            &builders,
+            R1CS::new(),
+            vec![],
+            HashSet::new(),
+        )
+        .unwrap();
+        let dot = split.to_dot();
+
+        let (w1, w2) = dot.split_once("subgraph cluster_w2 ").unwrap();
+        assert!(w1.contains("Constant\\n0\""));
+        assert!(w1.contains("Acir\\n1\""));
+        assert!(!w1.contains("Challenge"));
+        assert!(w2.contains("Challenge\\n2\""));
+        assert!(w2.contains("LogUpInverse\\n3\""));
+        assert!(w2.contains("Product\\n4\""));
+
+        // The lookup reads the w1 value and the challenge, the product reads
+        // the lookup twice but has a single edge.
+        let node = |label: &str| {
+            let line = dot.lines().find(|line| line.contains(label)).unwrap();
+            line.trim().split(' ').next().unwrap().to_string()
+        };
+        let (acir, challenge) = (node("Acir"), node("Challenge"));
+        let (lookup, product) = (node("LogUpInverse"), node("Product"));
+        assert!(dot.contains(&format!("{acir} -> {lookup};")));
+        assert!(dot.contains(&format!("{challenge} -> {lookup};")));
+        assert_eq!(dot.matches(&format!("{lookup} -> {product};")).count(), 1);
+        assert_eq!(dot.matches(" -> ").count(), 3);
+    }
+}
@@ -1,13 +1,21 @@
 use {
-    crate::{r1cs::R1CSSolver, whir_r1cs::WhirR1CSProver},
+    crate::{
+        r1cs::R1CSSolver,
+        whir_r1cs::WhirR1CSProver,
+        witness::{streaming::StreamingWitness, WitnessSlots},
+    },
     acir::native_types::WitnessMap,
-    anyhow::{Context, Result},
+    anyhow::{Context, Error, Result},
+    ark_std::rand::{rngs::StdRng, SeedableRng},
     bn254_blackbox_solver::Bn254BlackBoxSolver,
-    nargo::foreign_calls::DefaultForeignCallBuilder,
+    nargo::{
+        foreign_calls::{DefaultForeignCallBuilder, ForeignCallError},
+        NargoError,
+    },
     noir_artifact_cli::fs::inputs::read_inputs_from_file,
-    noirc_abi::InputMap,
+    noirc_abi::{input_parser::Format, InputMap},
     provekit_common::{FieldElement, IOPattern, NoirElement, NoirProof, Prover, PublicInputs},
-    std::path::Path,
+    std::path::{Path, PathBuf},
     tracing::instrument,
 };
 
@@ -15,129 +23,364 @@ mod r1cs;
 mod whir_r1cs;
 mod witness;
 
+/// Where the prover keeps the witness while solving it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WitnessMode {
+    /// Keep the whole witness in memory.
+    #[default]
+    InMemory,
+    /// Write solved witnesses to a temporary file layer by layer and only keep
+    /// the values still read by later layers in memory. Lowers peak memory on
+    /// large circuits at the cost of disk I/O. The file is created in
+    /// [`ProveOptions::spill_dir`].
+    Streaming,
+}
+
+/// Phase of a proof, reported to a [`ProgressSink`] when it starts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Executing the ACIR program.
+    WitnessGeneration,
+    /// Solving and committing to w1, or the whole witness without challenges.
+    CommitW1,
+    /// Solving and committing to w2.
+    CommitW2,
+    /// ZK sumcheck reducing the R1CS instance to evaluation claims.
+    Sumcheck,
+    /// WHIR openings of the witness commitments.
+    Whir,
+    /// The proof is complete.
+    Done,
+}
+
+impl Phase {
+    /// Rough fraction of the proving time spent before the phase starts.
+    pub const fn fraction(self) -> f64 {
+        match self {
+            Self::WitnessGeneration => 0.0,
+            Self::CommitW1 => 0.1,
+            Self::CommitW2 => 0.35,
+            Self::Sumcheck => 0.6,
+            Self::Whir => 0.7,
+            Self::Done => 1.0,
+        }
+    }
+}
+
+/// Receives the phases of a proof as they start, e.g. to render a progress
+/// bar. Closures taking a [`Phase`] and its [`Phase::fraction`] are sinks.
+pub trait ProgressSink {
+    fn report(&mut self, phase: Phase, fraction: f64);
+}
+
+impl<F: FnMut(Phase, f64)> ProgressSink for F {
+    fn report(&mut self, phase: Phase, fraction: f64) {
+        self(phase, fraction);
+    }
+}
+
+fn no_progress(_: Phase, _: f64) {}
+
+fn report(progress: &mut dyn ProgressSink, phase: Phase) {
+    progress.report(phase, phase.fraction());
+}
+
+/// Inputs of a proof, see [`Prove::prove_with`].
+#[derive(Clone, Debug)]
+pub enum ProveInputs {
+    /// Path of a `Prover.toml` file.
+    File(PathBuf),
+    /// Contents of a `Prover.toml` file, so that nothing is read from disk.
+    Toml(String),
+    /// Inputs parsed beforehand.
+    Map(InputMap),
+    /// ACIR witness solved beforehand, e.g. by [`Prove::generate_witness`],
+    /// skipping the ACIR execution.
+    Witness(WitnessMap<NoirElement>),
+}
+
+/// How to prove, see [`Prove::prove_with`]. The default keeps the witness in
+/// memory and reports no progress.
+#[derive(Default)]
+pub struct ProveOptions<'a> {
+    /// Where to keep the witness while solving it.
+    pub mode:      WitnessMode,
+    /// Receives each phase of the proof as it starts.
+    pub progress:  Option<&'a mut dyn ProgressSink>,
+    /// Directory of the witness spill file in [`WitnessMode::Streaming`],
+    /// the system temporary directory if unset.
+    pub spill_dir: Option<PathBuf>,
+    /// Draws the prover randomness from a generator seeded with this value,
+    /// so that the same inputs give byte-identical proofs. Such proofs are
+    /// not zero-knowledge, this is only meant for tests.
+    #[cfg(feature = "deterministic-transcript")]
+    pub seed:      Option<u64>,
+}
+
 pub trait Prove {
-    fn generate_witness(&mut self, input_map: InputMap) -> Result<WitnessMap<NoirElement>>;
+    fn generate_witness(&self, input_map: InputMap) -> Result<WitnessMap<NoirElement>>;
+
+    /// Like [`Prove::generate_witness`], resolving the oracles called by the
+    /// circuit with the JSON-RPC server at `resolver_url`.
+    fn generate_witness_with_resolver(
+        &self,
+        input_map: InputMap,
+        resolver_url: &str,
+    ) -> Result<WitnessMap<NoirElement>>;
 
-    fn prove(self, prover_toml: impl AsRef<Path>) -> Result<NoirProof>;
+    /// Proves the program on `inputs`.
+    fn prove_with(&self, inputs: ProveInputs, options: ProveOptions) -> Result<NoirProof>;
 }
 
 impl Prove for Prover {
     #[instrument(skip_all)]
-    fn generate_witness(&mut self, input_map: InputMap) -> Result<WitnessMap<NoirElement>> {
-        let solver = Bn254BlackBoxSolver::default();
-        let mut output_buffer = Vec::new();
-        let mut foreign_call_executor = DefaultForeignCallBuilder {
-            output:       &mut output_buffer,
-            enable_mocks: false,
-            resolver_url: None,
-            root_path:    None,
-            package_name: None,
-        }
-        .build();
+    fn generate_witness(&self, input_map: InputMap) -> Result<WitnessMap<NoirElement>> {
+        execute(self, input_map, None)
+    }
 
-        let initial_witness = self.witness_generator.abi().encode(&input_map, None)?;
+    #[instrument(skip_all)]
+    fn generate_witness_with_resolver(
+        &self,
+        input_map: InputMap,
+        resolver_url: &str,
+    ) -> Result<WitnessMap<NoirElement>> {
+        execute(self, input_map, Some(resolver_url.to_string()))
+    }
 
-        let mut witness_stack = nargo::ops::execute_program(
-            &self.program,
-            initial_witness,
-            &solver,
-            &mut foreign_call_executor,
-        )?;
+    #[instrument(skip_all)]
+    fn prove_with(&self, inputs: ProveInputs, options: ProveOptions) -> Result<NoirProof> {
+        let mut no_progress = no_progress;
+        let progress: &mut dyn ProgressSink = match options.progress {
+            Some(progress) => progress,
+            None => &mut no_progress,
+        };
+        #[cfg(feature = "deterministic-transcript")]
+        let rng = options
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        #[cfg(not(feature = "deterministic-transcript"))]
+        let rng = StdRng::from_entropy();
+
+        let spill_dir = match options.mode {
+            WitnessMode::InMemory => None,
+            WitnessMode::Streaming => Some(options.spill_dir.unwrap_or_else(std::env::temp_dir)),
+        };
+
+        let abi = self.witness_generator.abi();
+        let input_map = match inputs {
+            ProveInputs::File(prover_toml) => read_inputs_from_file(&prover_toml, abi)?.0,
+            ProveInputs::Toml(prover_toml) => Format::Toml
+                .parse(&prover_toml, abi)
+                .context("while parsing Prover.toml")?,
+            ProveInputs::Map(input_map) => input_map,
+            ProveInputs::Witness(witness_map) => {
+                return prove_from_witness_map(
+                    self,
+                    witness_map,
+                    spill_dir.as_deref(),
+                    rng,
+                    progress,
+                );
+            }
+        };
+        report(progress, Phase::WitnessGeneration);
+        let witness_map = self.generate_witness(input_map)?;
+        prove_from_witness_map(self, witness_map, spill_dir.as_deref(), rng, progress)
+    }
+}
+
+/// Executes the ACIR program on `input_map`, resolving oracles with the
+/// JSON-RPC server at `resolver_url` if any.
+///
+/// Returns the witness of the function the prover proves. For a function
+/// called more than once that is the witness of its last call.
+fn execute(
+    prover: &Prover,
+    input_map: InputMap,
+    resolver_url: Option<String>,
+) -> Result<WitnessMap<NoirElement>> {
+    let solver = Bn254BlackBoxSolver::default();
+    let mut output_buffer = Vec::new();
+    let mut foreign_call_executor = DefaultForeignCallBuilder {
+        output: &mut output_buffer,
+        enable_mocks: false,
+        resolver_url,
+        root_path: None,
+        package_name: None,
+    }
+    .build();
+
+    let initial_witness = prover.witness_generator.abi().encode(&input_map, None)?;
 
-        Ok(witness_stack
+    let mut witness_stack = nargo::ops::execute_program(
+        &prover.program,
+        initial_witness,
+        &solver,
+        &mut foreign_call_executor,
+    )
+    .map_err(|error| match error {
+        NargoError::ForeignCallError(ForeignCallError::NoHandler(ref name)) => {
+            let hint = format!(
+                "The circuit calls the oracle `{name}`, which has no handler. Resolve it with a \
+                 JSON-RPC server through `Prove::generate_witness_with_resolver`; mocks are only \
+                 available in `nargo test`"
+            );
+            Error::new(error).context(hint)
+        }
+        error => error.into(),
+    })?;
+
+    // The entry point finishes last and is on top of the stack, calls are below
+    // it in reverse order of completion.
+    loop {
+        let item = witness_stack
             .pop()
-            .context("Missing witness results")?
-            .witness)
+            .with_context(|| format!("Missing witness results for function {}", prover.function))?;
+        if item.index == prover.function {
+            return Ok(item.witness);
+        }
     }
+}
 
-    #[instrument(skip_all)]
-    fn prove(mut self, prover_toml: impl AsRef<Path>) -> Result<NoirProof> {
-        let (input_map, _expected_return) =
-            read_inputs_from_file(prover_toml.as_ref(), self.witness_generator.abi())?;
+/// Proves from the solved ACIR witness, keeping the R1CS witness in memory or,
+/// given a `spill_dir`, in a spill file there.
+fn prove_from_witness_map(
+    prover: &Prover,
+    acir_witness_idx_to_value_map: WitnessMap<NoirElement>,
+    spill_dir: Option<&Path>,
+    rng: StdRng,
+    progress: &mut dyn ProgressSink,
+) -> Result<NoirProof> {
+    match spill_dir {
+        None => {
+            let witness: Vec<Option<FieldElement>> = vec![None; prover.r1cs.num_witnesses()];
+            prove_with_storage(
+                prover,
+                acir_witness_idx_to_value_map,
+                witness,
+                rng,
+                progress,
+            )
+        }
+        Some(spill_dir) => {
+            let witness = StreamingWitness::new(
+                prover.r1cs.num_witnesses(),
+                &[
+                    &prover.split_witness_builders.w1_layers,
+                    &prover.split_witness_builders.w2_layers,
+                ],
+                spill_dir,
+            )?;
+            prove_with_storage(
+                prover,
+                acir_witness_idx_to_value_map,
+                witness,
+                rng,
+                progress,
+            )
+        }
+    }
+}
+
+/// Solves and commits to the witness using `witness` as storage, then proves
+/// the R1CS instance. The masks and blinding polynomials are drawn from `rng`.
+fn prove_with_storage<W: WitnessSlots>(
+    prover: &Prover,
+    acir_witness_idx_to_value_map: WitnessMap<NoirElement>,
+    mut witness: W,
+    mut rng: StdRng,
+    progress: &mut dyn ProgressSink,
+) -> Result<NoirProof> {
+    let acir_public_inputs = prover.program.functions[prover.function as usize]
+        .public_inputs()
+        .indices();
+    let num_witnesses = prover.r1cs.num_witnesses();
+    let w1_size = prover.whir_for_witness.w1_size;
+
+    // Set up transcript
+    let io: IOPattern = prover.whir_for_witness.create_io_pattern();
+    let mut merlin = io.to_prover_state();
+    drop(io);
 
-        let acir_witness_idx_to_value_map = self.generate_witness(input_map)?;
-        let acir_public_inputs = self.program.functions[0].public_inputs().indices();
+    // Solve w1 (or all witnesses if no challenges)
+    report(progress, Phase::CommitW1);
+    prover.r1cs.solve_witness_vec(
+        &mut witness,
+        &prover.split_witness_builders.w1_layers,
+        &acir_witness_idx_to_value_map,
+        &mut merlin,
+    )?;
 
-        // Set up transcript
-        let io: IOPattern = self.whir_for_witness.create_io_pattern();
-        let mut merlin = io.to_prover_state();
-        drop(io);
+    let w1 = witness.read(0..w1_size).context("While reading w1")?;
 
-        let mut witness: Vec<Option<FieldElement>> = vec![None; self.r1cs.num_witnesses()];
+    let commitment_1 = prover
+        .whir_for_witness
+        .commit(&mut merlin, &prover.r1cs, w1, true, &mut rng)
+        .context("While committing to w1")?;
 
-        // Solve w1 (or all witnesses if no challenges)
-        self.r1cs.solve_witness_vec(
+    // Build commitment list based on whether we have challenges
+    let commitments = if prover.whir_for_witness.num_challenges > 0 {
+        // Solve w2
+        report(progress, Phase::CommitW2);
+        prover.r1cs.solve_witness_vec(
             &mut witness,
-            self.split_witness_builders.w1_layers,
+            &prover.split_witness_builders.w2_layers,
             &acir_witness_idx_to_value_map,
             &mut merlin,
-        );
+        )?;
 
-        let w1 = witness[..self.whir_for_witness.w1_size]
-            .iter()
-            .map(|w| w.ok_or_else(|| anyhow::anyhow!("Some witnesses in w1 are missing")))
-            .collect::<Result<Vec<_>>>()?;
+        let w2 = witness
+            .read(w1_size..num_witnesses)
+            .context("While reading w2")?;
 
-        let commitment_1 = self
+        let commitment_2 = prover
             .whir_for_witness
-            .commit(&mut merlin, &self.r1cs, w1, true)
-            .context("While committing to w1")?;
-
-        // Build commitment list based on whether we have challenges
-        let commitments = if self.whir_for_witness.num_challenges > 0 {
-            // Solve w2
-            self.r1cs.solve_witness_vec(
-                &mut witness,
-                self.split_witness_builders.w2_layers,
-                &acir_witness_idx_to_value_map,
-                &mut merlin,
-            );
+            .commit(&mut merlin, &prover.r1cs, w2, false, &mut rng)
+            .context("While committing to w2")?;
 
-            let w2 = witness[self.whir_for_witness.w1_size..]
-                .iter()
-                .map(|w| w.ok_or_else(|| anyhow::anyhow!("Some witnesses in w2 are missing")))
-                .collect::<Result<Vec<_>>>()?;
+        vec![commitment_1, commitment_2]
+    } else {
+        vec![commitment_1]
+    };
+    drop(acir_witness_idx_to_value_map);
 
-            let commitment_2 = self
-                .whir_for_witness
-                .commit(&mut merlin, &self.r1cs, w2, false)
-                .context("While committing to w2")?;
+    #[cfg(test)]
+    prover
+        .r1cs
+        .test_witness_satisfaction(&witness.read(0..num_witnesses)?)
+        .context("While verifying R1CS instance")?;
 
-            vec![commitment_1, commitment_2]
-        } else {
-            vec![commitment_1]
-        };
-        drop(acir_witness_idx_to_value_map);
-
-        #[cfg(test)]
-        self.r1cs
-            .test_witness_satisfaction(&witness.iter().map(|w| w.unwrap()).collect::<Vec<_>>())
-            .context("While verifying R1CS instance")?;
-
-        // Gather public inputs from witness
-        let num_public_inputs = acir_public_inputs.len();
-        let public_inputs = if num_public_inputs == 0 {
-            PublicInputs::new()
-        } else {
-            PublicInputs::from_vec(
-                witness[1..=num_public_inputs]
-                    .iter()
-                    .map(|w| w.ok_or_else(|| anyhow::anyhow!("Missing public input witness")))
-                    .collect::<Result<Vec<FieldElement>>>()?,
-            )
-        };
-        drop(witness);
+    // Gather public inputs from witness
+    let num_public_inputs = acir_public_inputs.len();
+    let public_inputs = if num_public_inputs == 0 {
+        PublicInputs::new()
+    } else {
+        PublicInputs::from_vec(
+            witness
+                .read(1..num_public_inputs + 1)
+                .context("Missing public input witness")?,
+        )
+    };
+    drop(witness);
 
-        let whir_r1cs_proof = self
-            .whir_for_witness
-            .prove(merlin, self.r1cs, commitments, &public_inputs)
-            .context("While proving R1CS instance")?;
+    report(progress, Phase::Sumcheck);
+    let whir_r1cs_proof = prover
+        .whir_for_witness
+        .prove(
+            merlin,
+            &prover.r1cs,
+            commitments,
+            &public_inputs,
+            &mut rng,
+            progress,
+        )
+        .context("While proving R1CS instance")?;
+    report(progress, Phase::Done);
 
-        Ok(NoirProof {
-            public_inputs,
-            whir_r1cs_proof,
-        })
-    }
+    Ok(NoirProof {
+        public_inputs,
+        whir_r1cs_proof,
+    })
 }
 
 #[cfg(test)]
@@ -1,26 +1,26 @@
 #[cfg(test)]
-use anyhow::{ensure, Result};
+use anyhow::{bail, ensure};
 use {
-    crate::witness::witness_builder::WitnessBuilderSolver,
+    crate::witness::{witness_builder::WitnessBuilderSolver, WitnessSlots},
     acir::native_types::WitnessMap,
+    anyhow::Result,
     provekit_common::{
-        skyscraper::SkyscraperSponge,
+        transcript::ChallengeTranscript,
         utils::batch_inverse_montgomery,
         witness::{LayerType, LayeredWitnessBuilders, WitnessBuilder},
         FieldElement, NoirElement, R1CS,
     },
-    spongefish::ProverState,
     tracing::instrument,
 };
 
 pub trait R1CSSolver {
-    fn solve_witness_vec(
+    fn solve_witness_vec<W: WitnessSlots + ?Sized, T: ChallengeTranscript + ?Sized>(
         &self,
-        witness: &mut Vec<Option<FieldElement>>,
-        plan: LayeredWitnessBuilders,
+        witness: &mut W,
+        plan: &LayeredWitnessBuilders,
         acir_map: &WitnessMap<NoirElement>,
-        transcript: &mut ProverState<SkyscraperSponge, FieldElement>,
-    );
+        transcript: &mut T,
+    ) -> Result<()>;
 
     #[cfg(test)]
     fn test_witness_satisfaction(&self, witness: &[FieldElement]) -> Result<()>;
@@ -43,18 +43,22 @@ impl R1CSSolver for R1CS {
     /// 3. Perform batch inversion using Montgomery's algorithm
     /// 4. Write inverse results to witness vector
     ///
+    /// The storage is notified after every layer, which lets
+    /// [`StreamingWitness`](crate::witness::streaming::StreamingWitness) spill
+    /// finished values to disk.
+    ///
     /// # Panics
     ///
     /// Panics if a denominator witness is not set when needed for inversion.
     /// This indicates a bug in the layer scheduling algorithm.
     #[instrument(skip_all)]
-    fn solve_witness_vec(
+    fn solve_witness_vec<W: WitnessSlots + ?Sized, T: ChallengeTranscript + ?Sized>(
         &self,
-        witness: &mut Vec<Option<FieldElement>>,
-        plan: LayeredWitnessBuilders,
+        witness: &mut W,
+        plan: &LayeredWitnessBuilders,
         acir_map: &WitnessMap<NoirElement>,
-        transcript: &mut ProverState<SkyscraperSponge, FieldElement>,
-    ) {
+        transcript: &mut T,
+    ) -> Result<()> {
         for layer in &plan.layers {
             match layer.typ {
                 LayerType::Other => {
@@ -129,7 +133,9 @@ impl R1CSSolver for R1CS {
                     }
                 }
             }
+            witness.finish_layer(layer)?;
         }
+        Ok(())
     }
 
     // Tests R1CS Witness satisfaction given the constraints provided by the
@@ -142,18 +148,67 @@ impl R1CSSolver for R1CS {
             "Witness size does not match"
         );
 
-        // Verify
-        let a = self.a() * witness;
-        let b = self.b() * witness;
-        let c = self.c() * witness;
-        for (row, ((a, b), c)) in a
-            .into_iter()
-            .zip(b.into_iter())
-            .zip(c.into_iter())
-            .enumerate()
-        {
-            ensure!(a * b == c, "Constraint {row} failed");
+        if let Some(row) = self.par_first_unsatisfied_constraint(witness) {
+            bail!("Constraint {row} failed");
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        provekit_common::{
+            transcript::MockTranscript,
+            witness::{ConstantTerm, Layer},
+        },
+    };
+
+    #[test]
+    fn solve_with_mock_transcript() {
+        let one = FieldElement::from(1u64);
+        let mut r1cs = R1CS::new();
+        r1cs.add_witnesses(5);
+        // w1 * w2 = w3 and w4 * w1 = 1
+        r1cs.add_constraint(&[(one, 1)], &[(one, 2)], &[(one, 3)]);
+        r1cs.add_constraint(&[(one, 4)], &[(one, 1)], &[(one, 0)]);
+        let plan = LayeredWitnessBuilders {
+            layers: vec![
+                Layer {
+                    witness_builders: vec![
+                        WitnessBuilder::Constant(ConstantTerm(0, one)),
+                        WitnessBuilder::Challenge(1),
+                        WitnessBuilder::Challenge(2),
+                    ],
+                    typ:              LayerType::Other,
+                },
+                Layer {
+                    witness_builders: vec![WitnessBuilder::Product(3, 1, 2)],
+                    typ:              LayerType::Other,
+                },
+                Layer {
+                    witness_builders: vec![WitnessBuilder::Inverse(4, 1)],
+                    typ:              LayerType::Inverse,
+                },
+            ],
+        };
+
+        let mut witness = vec![None; r1cs.num_witnesses()];
+        r1cs.solve_witness_vec(
+            &mut witness,
+            &plan,
+            &WitnessMap::new(),
+            &mut MockTranscript::new(),
+        )
+        .unwrap();
+        let witness = witness.read(0..r1cs.num_witnesses()).unwrap();
+
+        assert_eq!(witness[1..4], [
+            one,
+            FieldElement::from(2u64),
+            FieldElement::from(2u64)
+        ]);
+        r1cs.test_witness_satisfaction(&witness).unwrap();
+    }
+}
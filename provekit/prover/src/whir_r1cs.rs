@@ -1,7 +1,8 @@
 use {
+    crate::{report, Phase, ProgressSink},
     anyhow::{ensure, Result},
     ark_ff::UniformRand,
-    ark_std::{One, Zero},
+    ark_std::{rand::rngs::StdRng, One, Zero},
     provekit_common::{
         skyscraper::{SkyscraperMerkleConfig, SkyscraperSponge},
         utils::{
@@ -47,14 +48,17 @@ pub trait WhirR1CSProver {
         r1cs: &R1CS,
         witness: Vec<FieldElement>,
         is_w1: bool,
+        rng: &mut StdRng,
     ) -> Result<WhirR1CSCommitment>;
 
     fn prove(
         &self,
         merlin: ProverState<SkyscraperSponge, FieldElement>,
-        r1cs: R1CS,
+        r1cs: &R1CS,
         commitments: Vec<WhirR1CSCommitment>,
         public_inputs: &PublicInputs,
+        rng: &mut StdRng,
+        progress: &mut dyn ProgressSink,
     ) -> Result<WhirR1CSProof>;
 }
 
@@ -66,6 +70,7 @@ impl WhirR1CSProver for WhirR1CSScheme {
         r1cs: &R1CS,
         witness: Vec<FieldElement>,
         is_w1: bool,
+        rng: &mut StdRng,
     ) -> Result<WhirR1CSCommitment> {
         let witness_size = if is_w1 {
             self.w1_size
@@ -106,6 +111,7 @@ impl WhirR1CSProver for WhirR1CSScheme {
                 &self.whir_witness,
                 witness_polynomial_evals,
                 merlin,
+                rng,
             );
 
         Ok(WhirR1CSCommitment {
@@ -120,9 +126,11 @@ impl WhirR1CSProver for WhirR1CSScheme {
     fn prove(
         &self,
         mut merlin: ProverState<SkyscraperSponge, FieldElement>,
-        r1cs: R1CS,
+        r1cs: &R1CS,
         mut commitments: Vec<WhirR1CSCommitment>,
         public_inputs: &PublicInputs,
+        rng: &mut StdRng,
+        progress: &mut dyn ProgressSink,
     ) -> Result<WhirR1CSProof> {
         ensure!(!commitments.is_empty(), "Need at least one commitment");
 
@@ -145,13 +153,15 @@ impl WhirR1CSProver for WhirR1CSScheme {
 
         // First round: ZK sumcheck to reduce R1CS to weighted evaluation
         let alpha = run_zk_sumcheck_prover(
-            &r1cs,
+            r1cs,
             &full_witness,
             &mut merlin,
             self.m_0,
             &self.whir_for_hiding_spartan,
+            rng,
         );
         drop(full_witness);
+        report(progress, Phase::Whir);
 
         // Compute weights from R1CS matrices
         let alphas = calculate_external_row_of_r1cs_matrices(alpha, r1cs);
@@ -348,17 +358,18 @@ pub fn batch_commit_to_polynomial(
     whir_config: &WhirConfig,
     witness: EvaluationsList<FieldElement>,
     merlin: &mut ProverState<SkyscraperSponge, FieldElement>,
+    rng: &mut StdRng,
 ) -> (
     Witness<FieldElement, SkyscraperMerkleConfig>,
     EvaluationsList<FieldElement>,
     EvaluationsList<FieldElement>,
 ) {
-    let mask = generate_random_multilinear_polynomial(witness.num_variables());
+    let mask = generate_random_multilinear_polynomial(witness.num_variables(), rng);
     let masked_polynomial_coeff = create_masked_polynomial(witness, &mask).to_coeffs();
     drop(mask);
 
     let random_polynomial_coeff =
-        EvaluationsList::new(generate_random_multilinear_polynomial(m)).to_coeffs();
+        EvaluationsList::new(generate_random_multilinear_polynomial(m, rng)).to_coeffs();
 
     let committer = CommitmentWriter::new(whir_config.clone());
     let witness_new = committer
@@ -375,16 +386,18 @@ pub fn batch_commit_to_polynomial(
     )
 }
 
-fn generate_blinding_spartan_univariate_polys(m_0: usize) -> Vec<[FieldElement; 4]> {
-    let mut rng = ark_std::rand::thread_rng();
+fn generate_blinding_spartan_univariate_polys(
+    m_0: usize,
+    rng: &mut StdRng,
+) -> Vec<[FieldElement; 4]> {
     let mut g_univariates = Vec::with_capacity(m_0);
 
     for _ in 0..m_0 {
         let coeffs: [FieldElement; 4] = [
-            FieldElement::rand(&mut rng),
-            FieldElement::rand(&mut rng),
-            FieldElement::rand(&mut rng),
-            FieldElement::rand(&mut rng),
+            FieldElement::rand(rng),
+            FieldElement::rand(rng),
+            FieldElement::rand(rng),
+            FieldElement::rand(rng),
         ];
         g_univariates.push(coeffs);
     }
@@ -413,6 +426,7 @@ pub fn run_zk_sumcheck_prover(
     merlin: &mut ProverState<SkyscraperSponge, FieldElement>,
     m_0: usize,
     whir_for_blinding_of_spartan_config: &WhirConfig,
+    rng: &mut StdRng,
 ) -> Vec<FieldElement> {
     // r is the combination randomness from the 2nd item of the interaction phase
     let mut r = vec![FieldElement::zero(); m_0];
@@ -433,7 +447,7 @@ pub fn run_zk_sumcheck_prover(
 
     let mut alpha = Vec::<FieldElement>::with_capacity(m_0);
 
-    let blinding_polynomial = generate_blinding_spartan_univariate_polys(m_0);
+    let blinding_polynomial = generate_blinding_spartan_univariate_polys(m_0, rng);
 
     // Spartan blinding: m = log2(domain), target_len = 2^(m-1).
     let blinding_num_vars = whir_for_blinding_of_spartan_config
@@ -460,6 +474,7 @@ pub fn run_zk_sumcheck_prover(
             whir_for_blinding_of_spartan_config,
             blinding_polynomial_for_committing,
             merlin,
+            rng,
         );
 
     let sum_g_reduce = sum_over_hypercube(&blinding_polynomial);
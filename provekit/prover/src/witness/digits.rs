@@ -1,14 +1,14 @@
-use provekit_common::{
-    witness::{decompose_into_digits, DigitalDecompositionWitnesses},
-    FieldElement,
+use {
+    crate::witness::WitnessSlots,
+    provekit_common::witness::{decompose_into_digits, DigitalDecompositionWitnesses},
 };
 
 pub(crate) trait DigitalDecompositionWitnessesSolver {
-    fn solve(&self, witness: &mut [Option<FieldElement>]);
+    fn solve<W: WitnessSlots + ?Sized>(&self, witness: &mut W);
 }
 
 impl DigitalDecompositionWitnessesSolver for DigitalDecompositionWitnesses {
-    fn solve(&self, witness: &mut [Option<FieldElement>]) {
+    fn solve<W: WitnessSlots + ?Sized>(&self, witness: &mut W) {
         self.witnesses_to_decompose
             .iter()
             .enumerate()
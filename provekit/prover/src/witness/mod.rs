@@ -1,3 +1,37 @@
+use {
+    anyhow::{anyhow, Result},
+    provekit_common::{witness::Layer, FieldElement},
+    std::ops::{IndexMut, Range},
+};
+
 mod digits;
 mod ram;
+pub(crate) mod streaming;
 pub(crate) mod witness_builder;
+
+/// Storage the witness solvers read from and write to, indexed by R1CS
+/// witness index.
+pub(crate) trait WitnessSlots: IndexMut<usize, Output = Option<FieldElement>> {
+    /// Called once every builder in `layer` has been solved.
+    fn finish_layer(&mut self, _layer: &Layer) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns the solved witnesses in `range`, failing if any is missing.
+    fn read(&mut self, range: Range<usize>) -> Result<Vec<FieldElement>>;
+}
+
+impl WitnessSlots for [Option<FieldElement>] {
+    fn read(&mut self, range: Range<usize>) -> Result<Vec<FieldElement>> {
+        self[range.clone()]
+            .iter()
+            .map(|w| w.ok_or_else(|| anyhow!("Some witnesses in {range:?} are missing")))
+            .collect()
+    }
+}
+
+impl WitnessSlots for Vec<Option<FieldElement>> {
+    fn read(&mut self, range: Range<usize>) -> Result<Vec<FieldElement>> {
+        self.as_mut_slice().read(range)
+    }
+}
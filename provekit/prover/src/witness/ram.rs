@@ -1,4 +1,5 @@
 use {
+    crate::witness::WitnessSlots,
     ark_ff::PrimeField,
     provekit_common::{
         witness::{SpiceMemoryOperation, SpiceWitnesses},
@@ -7,11 +8,11 @@ use {
 };
 
 pub(crate) trait SpiceWitnessesSolver {
-    fn solve(&self, witness: &mut [Option<FieldElement>]);
+    fn solve<W: WitnessSlots + ?Sized>(&self, witness: &mut W);
 }
 
 impl SpiceWitnessesSolver for SpiceWitnesses {
-    fn solve(&self, witness: &mut [Option<FieldElement>]) {
+    fn solve<W: WitnessSlots + ?Sized>(&self, witness: &mut W) {
         debug_assert_eq!(
             self.initial_value_witnesses.len(),
             self.memory_length,
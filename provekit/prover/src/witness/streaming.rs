@@ -0,0 +1,199 @@
+use {
+    crate::witness::WitnessSlots,
+    anyhow::{ensure, Context, Result},
+    ark_ff::{BigInteger, PrimeField, Zero},
+    provekit_common::{
+        witness::{DependencyInfo, Layer, LayeredWitnessBuilders},
+        FieldElement,
+    },
+    std::{
+        collections::HashMap,
+        fs::{self, File},
+        io::{BufWriter, Read, Write},
+        ops::{Index, IndexMut, Range},
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// Size of a spill file record: the witness index as a little endian `u64`
+/// followed by the serialized field element.
+const RECORD_SIZE: usize = 8 + 32;
+
+/// Number of records read from the spill file at once.
+const CHUNK_RECORDS: usize = 1 << 14;
+
+/// `last_use` of witnesses no layer reads.
+const NEVER_READ: u32 = u32::MAX;
+
+/// Distinguishes the spill files of concurrent provers in one process.
+static SPILL_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Witness storage that keeps only the values still read by a later layer in
+/// memory.
+///
+/// Every solved value is appended to a spill file as soon as its layer is
+/// finished and evicted from memory after its last read, so peak RAM while
+/// solving is bounded by the widest set of live witnesses rather than by the
+/// whole witness. The committed ranges are read back by scanning the file in
+/// chunks.
+pub(crate) struct StreamingWitness {
+    /// Values that are still read by a later layer.
+    live:     HashMap<usize, Option<FieldElement>>,
+    /// For each witness: the last layer reading it, or [`NEVER_READ`].
+    last_use: Vec<u32>,
+    /// For each layer: the witnesses read for the last time in that layer.
+    expiring: Vec<Vec<u32>>,
+    /// Whether each witness has been written to the spill file.
+    written:  Vec<bool>,
+    /// Number of records in the spill file.
+    records:  usize,
+    /// Number of layers finished so far, across all plans.
+    layer:    usize,
+    writer:   BufWriter<File>,
+    path:     PathBuf,
+}
+
+impl StreamingWitness {
+    /// Creates storage for `num_witnesses` witnesses solved by `plans`, in
+    /// the order the plans will be executed, spilling to a new file in
+    /// `spill_dir`.
+    ///
+    /// The file is only readable by its owner and is removed when the storage
+    /// is dropped, including when proving fails.
+    pub(crate) fn new(
+        num_witnesses: usize,
+        plans: &[&LayeredWitnessBuilders],
+        spill_dir: &Path,
+    ) -> Result<Self> {
+        let mut last_use = vec![NEVER_READ; num_witnesses];
+        let mut num_layers = 0;
+        for layer in plans.iter().flat_map(|plan| &plan.layers) {
+            for builder in &layer.witness_builders {
+                for witness in DependencyInfo::extract_reads(builder) {
+                    last_use[witness] = num_layers;
+                }
+            }
+            num_layers += 1;
+        }
+        let mut expiring = vec![Vec::new(); num_layers as usize];
+        for (witness, &layer) in last_use.iter().enumerate() {
+            if layer != NEVER_READ {
+                expiring[layer as usize].push(witness as u32);
+            }
+        }
+
+        // The random part keeps the name from being guessed, `create_new`
+        // refuses to follow a file or link someone else put there.
+        let path = spill_dir.join(format!(
+            "provekit-witness-{}-{}-{:016x}.bin",
+            std::process::id(),
+            SPILL_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+            rand::random::<u64>()
+        ));
+        let mut options = File::options();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = options
+            .open(&path)
+            .with_context(|| format!("while creating witness spill file {}", path.display()))?;
+
+        Ok(Self {
+            live: HashMap::new(),
+            last_use,
+            expiring,
+            written: vec![false; num_witnesses],
+            records: 0,
+            layer: 0,
+            writer: BufWriter::new(file),
+            path,
+        })
+    }
+
+    fn spill(&mut self, witness: usize, value: FieldElement) -> Result<()> {
+        self.writer.write_all(&(witness as u64).to_le_bytes())?;
+        self.writer.write_all(&value.into_bigint().to_bytes_le())?;
+        self.written[witness] = true;
+        self.records += 1;
+        Ok(())
+    }
+}
+
+impl Index<usize> for StreamingWitness {
+    type Output = Option<FieldElement>;
+
+    fn index(&self, witness: usize) -> &Self::Output {
+        self.live.get(&witness).unwrap_or(&None)
+    }
+}
+
+impl IndexMut<usize> for StreamingWitness {
+    fn index_mut(&mut self, witness: usize) -> &mut Self::Output {
+        self.live.entry(witness).or_insert(None)
+    }
+}
+
+impl WitnessSlots for StreamingWitness {
+    fn finish_layer(&mut self, layer: &Layer) -> Result<()> {
+        for builder in &layer.witness_builders {
+            for witness in DependencyInfo::extract_writes(builder) {
+                let Some(value) = self.live.get(&witness).copied().flatten() else {
+                    continue;
+                };
+                self.spill(witness, value)?;
+                // Values nobody reads after this layer are not kept around.
+                let last = self.last_use[witness];
+                if last == NEVER_READ || last as usize <= self.layer {
+                    self.live.remove(&witness);
+                }
+            }
+        }
+        for witness in std::mem::take(&mut self.expiring[self.layer]) {
+            self.live.remove(&(witness as usize));
+        }
+        self.layer += 1;
+        Ok(())
+    }
+
+    /// Reads back the witnesses in `range` from the spill file.
+    ///
+    /// The file is scanned front to back [`CHUNK_RECORDS`] records at a time,
+    /// so besides the result only one chunk is held in memory.
+    fn read(&mut self, range: Range<usize>) -> Result<Vec<FieldElement>> {
+        ensure!(
+            self.written[range.clone()].iter().all(|&written| written),
+            "Some witnesses in {range:?} are missing"
+        );
+        self.writer
+            .flush()
+            .context("while flushing witness spill file")?;
+        let mut file = File::open(&self.path)
+            .with_context(|| format!("while opening witness spill file {}", self.path.display()))?;
+
+        let mut values = vec![FieldElement::zero(); range.len()];
+        let mut chunk = vec![0u8; CHUNK_RECORDS.min(self.records) * RECORD_SIZE];
+        let mut remaining = self.records;
+        while remaining > 0 {
+            let count = remaining.min(CHUNK_RECORDS);
+            let bytes = &mut chunk[..count * RECORD_SIZE];
+            file.read_exact(bytes)
+                .context("while reading witness spill file")?;
+            for record in bytes.chunks_exact(RECORD_SIZE) {
+                let (index, value) = record.split_at(8);
+                let index = u64::from_le_bytes(index.try_into().unwrap()) as usize;
+                if range.contains(&index) {
+                    values[index - range.start] = FieldElement::from_le_bytes_mod_order(value);
+                }
+            }
+            remaining -= count;
+        }
+        Ok(values)
+    }
+}
+
+impl Drop for StreamingWitness {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
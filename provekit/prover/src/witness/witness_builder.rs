@@ -1,10 +1,12 @@
 use {
-    crate::witness::{digits::DigitalDecompositionWitnessesSolver, ram::SpiceWitnessesSolver},
+    crate::witness::{
+        digits::DigitalDecompositionWitnessesSolver, ram::SpiceWitnessesSolver, WitnessSlots,
+    },
     acir::native_types::WitnessMap,
     ark_ff::{BigInteger, PrimeField},
     ark_std::Zero,
     provekit_common::{
-        skyscraper::SkyscraperSponge,
+        transcript::ChallengeTranscript,
         utils::noir_to_native,
         witness::{
             ConstantOrR1CSWitness, ConstantTerm, ProductLinearTerm, SumTerm, WitnessBuilder,
@@ -12,24 +14,23 @@ use {
         },
         FieldElement, NoirElement,
     },
-    spongefish::{codecs::arkworks_algebra::UnitToField, ProverState},
 };
 
 pub trait WitnessBuilderSolver {
-    fn solve(
+    fn solve<W: WitnessSlots + ?Sized, T: ChallengeTranscript + ?Sized>(
         &self,
         acir_witness_idx_to_value_map: &WitnessMap<NoirElement>,
-        witness: &mut [Option<FieldElement>],
-        transcript: &mut ProverState<SkyscraperSponge, FieldElement>,
+        witness: &mut W,
+        transcript: &mut T,
     );
 }
 
 impl WitnessBuilderSolver for WitnessBuilder {
-    fn solve(
+    fn solve<W: WitnessSlots + ?Sized, T: ChallengeTranscript + ?Sized>(
         &self,
         acir_witness_idx_to_value_map: &WitnessMap<NoirElement>,
-        witness: &mut [Option<FieldElement>],
-        transcript: &mut ProverState<SkyscraperSponge, FieldElement>,
+        witness: &mut W,
+        transcript: &mut T,
     ) {
         match self {
             WitnessBuilder::Constant(ConstantTerm(witness_idx, c)) => {
@@ -93,9 +94,7 @@ impl WitnessBuilderSolver for WitnessBuilder {
                 }
             }
             WitnessBuilder::Challenge(witness_idx) => {
-                let mut one = [FieldElement::zero(); 1];
-                let _ = transcript.fill_challenge_scalars(&mut one);
-                witness[*witness_idx] = Some(one[0]);
+                witness[*witness_idx] = Some(transcript.challenge());
             }
             WitnessBuilder::LogUpDenominator(
                 witness_idx,
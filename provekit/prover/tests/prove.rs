@@ -0,0 +1,174 @@
+use {
+    noirc_artifacts::program::ProgramArtifact,
+    provekit_common::{NoirProofScheme, Prover, Verifier},
+    provekit_prover::{Phase, Prove, ProveInputs, ProveOptions, WitnessMode},
+    provekit_r1cs_compiler::NoirProofSchemeBuilder,
+    provekit_test_utils::{compile_workspace, prepare_program},
+    provekit_verifier::Verify,
+    std::{fs, path::Path},
+};
+
+#[test]
+fn streaming_witness() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/small-sha",
+        "basic",
+    );
+
+    // With the same randomness the witness mode must not change the proof
+    let [in_memory, streaming] = [WitnessMode::InMemory, WitnessMode::Streaming].map(|mode| {
+        let proof = Prover::from_noir_proof_scheme(schema.clone())
+            .prove_with(ProveInputs::Map(input_map.clone()), ProveOptions {
+                mode,
+                seed: Some(42),
+                ..ProveOptions::default()
+            })
+            .expect("While proving Noir program statement");
+        Verifier::from_noir_proof_scheme(schema.clone())
+            .verify(&proof)
+            .expect("Verifying proof");
+        proof
+    });
+    assert_eq!(in_memory.public_inputs, streaming.public_inputs);
+    assert!(!streaming.public_inputs.0.is_empty());
+    assert_eq!(
+        in_memory.whir_r1cs_proof.transcript,
+        streaming.whir_r1cs_proof.transcript
+    );
+}
+
+#[test]
+fn spill_file() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/small-sha",
+        "basic",
+    );
+    let spill_dir = std::env::temp_dir().join(format!("provekit-spill-{}", std::process::id()));
+    fs::create_dir_all(&spill_dir).expect("Creating spill directory");
+    let list = || {
+        fs::read_dir(&spill_dir)
+            .expect("Listing spill directory")
+            .map(|entry| entry.expect("Listing spill directory").metadata())
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Reading spill file metadata")
+    };
+
+    // The spill file exists from the start of the proof until the witness is
+    // committed
+    let mut while_solving = Vec::new();
+    let mut after_commit = Vec::new();
+    Prover::from_noir_proof_scheme(schema)
+        .prove_with(ProveInputs::Map(input_map), ProveOptions {
+            mode: WitnessMode::Streaming,
+            progress: Some(&mut |phase: Phase, _: f64| match phase {
+                Phase::CommitW1 => while_solving = list(),
+                Phase::Sumcheck => after_commit = list(),
+                _ => {}
+            }),
+            spill_dir: Some(spill_dir.clone()),
+            ..ProveOptions::default()
+        })
+        .expect("While proving Noir program statement");
+
+    assert_eq!(while_solving.len(), 1);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(while_solving[0].permissions().mode() & 0o777, 0o600);
+    }
+    assert!(after_commit.is_empty());
+    fs::remove_dir(&spill_dir).expect("Removing spill directory");
+}
+
+#[test]
+fn prove_with_cached_witness() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/read-write-memory",
+        "main",
+    );
+    let witness_map = Prover::from_noir_proof_scheme(schema.clone())
+        .generate_witness(input_map)
+        .expect("Generating witness");
+
+    for _ in 0..2 {
+        let proof = Prover::from_noir_proof_scheme(schema.clone())
+            .prove_with(
+                ProveInputs::Witness(witness_map.clone()),
+                ProveOptions::default(),
+            )
+            .expect("While proving from cached witness");
+        Verifier::from_noir_proof_scheme(schema.clone())
+            .verify(&proof)
+            .expect("Verifying proof");
+    }
+}
+
+#[test]
+fn prove_from_memory() {
+    let test_case_path =
+        Path::new("../../noir-examples/noir-r1cs-test-programs/simplest-read-only-memory");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+    let program = std::fs::read(test_case_path.join("target/main.json")).expect("Reading program");
+    let prover_toml =
+        std::fs::read_to_string(test_case_path.join("Prover.toml")).expect("Reading inputs");
+
+    // Only in-memory values from here on
+    let program: ProgramArtifact = serde_json::from_slice(&program).expect("Parsing program");
+    let schema = NoirProofScheme::from_program(program).expect("Compiling program");
+    let proof = Prover::from_noir_proof_scheme(schema.clone())
+        .prove_with(ProveInputs::Toml(prover_toml), ProveOptions::default())
+        .expect("While proving Noir program statement");
+    Verifier::from_noir_proof_scheme(schema)
+        .verify(&proof)
+        .expect("Verifying proof");
+}
+
+#[test]
+fn progress_phases() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/simplest-read-only-memory",
+        "main",
+    );
+    // ROM checking draws challenges, so the witness is committed in two parts
+    assert!(schema.whir_for_witness.num_challenges > 0);
+
+    let mut reports = Vec::new();
+    let proof = Prover::from_noir_proof_scheme(schema.clone())
+        .prove_with(ProveInputs::Map(input_map), ProveOptions {
+            progress: Some(&mut |phase: Phase, fraction: f64| reports.push((phase, fraction))),
+            ..ProveOptions::default()
+        })
+        .expect("While proving Noir program statement");
+    Verifier::from_noir_proof_scheme(schema)
+        .verify(&proof)
+        .expect("Verifying proof");
+
+    let phases = reports.iter().map(|&(phase, _)| phase).collect::<Vec<_>>();
+    assert_eq!(phases, [
+        Phase::WitnessGeneration,
+        Phase::CommitW1,
+        Phase::CommitW2,
+        Phase::Sumcheck,
+        Phase::Whir,
+        Phase::Done,
+    ]);
+    assert!(reports.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    assert_eq!(reports.last().map(|&(_, fraction)| fraction), Some(1.0));
+}
+
+#[test]
+fn unresolved_oracle_error() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/unresolved-oracle",
+        "main",
+    );
+    let error = Prover::from_noir_proof_scheme(schema)
+        .generate_witness(input_map)
+        .expect_err("Executing a program with an unresolved oracle");
+    let message = format!("{error:#}");
+    assert!(message.contains("`get_secret`"), "{message}");
+    assert!(
+        message.contains("generate_witness_with_resolver"),
+        "{message}"
+    );
+}
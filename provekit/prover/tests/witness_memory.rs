@@ -0,0 +1,87 @@
+//! Peak memory of the witness modes.
+//!
+//! The allocator counts every allocation of the process, so this lives in its
+//! own test binary with a single test: no other test allocates concurrently.
+
+use {
+    provekit_common::Prover,
+    provekit_prover::{Phase, Prove, ProveInputs, ProveOptions, WitnessMode},
+    provekit_test_utils::prepare_program,
+    std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// Allocator that tracks the current and the peak number of allocated bytes.
+struct MeasuringAllocator {
+    current: AtomicUsize,
+    peak:    AtomicUsize,
+}
+
+impl MeasuringAllocator {
+    /// Restarts the peak from the current allocation, returning it.
+    fn reset_peak(&self) -> usize {
+        let current = self.current.load(Ordering::SeqCst);
+        self.peak.store(current, Ordering::SeqCst);
+        current
+    }
+
+    fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe impl GlobalAlloc for MeasuringAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            self.peak.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: MeasuringAllocator = MeasuringAllocator {
+    current: AtomicUsize::new(0),
+    peak:    AtomicUsize::new(0),
+};
+
+#[test]
+fn streaming_witness_lowers_peak_memory() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/small-sha",
+        "basic",
+    );
+
+    // Measures from the start of the proof until the witness is committed,
+    // where the in-memory witness is dropped.
+    let [in_memory, streaming] = [WitnessMode::InMemory, WitnessMode::Streaming].map(|mode| {
+        let mut start = 0;
+        let mut peak = 0;
+        Prover::from_noir_proof_scheme(schema.clone())
+            .prove_with(ProveInputs::Map(input_map.clone()), ProveOptions {
+                mode,
+                progress: Some(&mut |phase: Phase, _: f64| match phase {
+                    Phase::WitnessGeneration => start = ALLOCATOR.reset_peak(),
+                    Phase::Sumcheck => peak = ALLOCATOR.peak() - start,
+                    _ => {}
+                }),
+                ..ProveOptions::default()
+            })
+            .expect("While proving Noir program statement");
+        peak
+    });
+    assert!(
+        streaming < in_memory,
+        "streaming peak {streaming}B, in-memory peak {in_memory}B"
+    );
+}
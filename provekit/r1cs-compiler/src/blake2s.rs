@@ -0,0 +1,255 @@
+use {
+    crate::{
+        noir_to_r1cs::{CompileError, NoirToR1CSCompiler},
+        sha256_compression::{add_u32_multi_addition, rotr_u32, xor_u32, PackCache},
+        uints::{U32, U8},
+    },
+    ark_ff::{Field, PrimeField},
+    provekit_common::{witness::ConstantOrR1CSWitness, FieldElement},
+    std::collections::BTreeMap,
+};
+
+/// Blake2s block size in bytes
+const BLAKE2S_BLOCK_BYTES: usize = 64;
+
+/// Blake2s initialization vector (same as the SHA256 initial hash values)
+const BLAKE2S_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Message word permutations for each of the 10 rounds
+const BLAKE2S_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Parameter block word 0 for an unkeyed hash with a 32-byte digest:
+/// digest length 32, key length 0, fanout 1, depth 1.
+const BLAKE2S_PARAM_0: u32 = 0x01010020;
+
+/// Blake2s mixing function G, updating `v[a]`, `v[b]`, `v[c]` and `v[d]`:
+///
+/// ```text
+/// a = a + b + x;  d = ROTR(d ⊕ a, 16);  c = c + d;  b = ROTR(b ⊕ c, 12)
+/// a = a + b + y;  d = ROTR(d ⊕ a, 8);   c = c + d;  b = ROTR(b ⊕ c, 7)
+/// ```
+#[allow(clippy::too_many_arguments)]
+fn add_blake2s_g(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    xor_ops: &mut Vec<(ConstantOrR1CSWitness, ConstantOrR1CSWitness, usize)>,
+    range_checks: &mut BTreeMap<u32, Vec<usize>>,
+    pack_cache: &mut PackCache,
+    v: &mut [U32; 16],
+    [a, b, c, d]: [usize; 4],
+    x: &U32,
+    y: &U32,
+) {
+    v[a] = add_u32_multi_addition(r1cs_compiler, range_checks, pack_cache, &[&v[a], &v[b], x]);
+    let t = xor_u32(r1cs_compiler, xor_ops, &v[d], &v[a]);
+    v[d] = rotr_u32(r1cs_compiler, range_checks, &t, 16);
+    v[c] = add_u32_multi_addition(r1cs_compiler, range_checks, pack_cache, &[&v[c], &v[d]]);
+    let t = xor_u32(r1cs_compiler, xor_ops, &v[b], &v[c]);
+    v[b] = rotr_u32(r1cs_compiler, range_checks, &t, 12);
+
+    v[a] = add_u32_multi_addition(r1cs_compiler, range_checks, pack_cache, &[&v[a], &v[b], y]);
+    let t = xor_u32(r1cs_compiler, xor_ops, &v[d], &v[a]);
+    v[d] = rotr_u32(r1cs_compiler, range_checks, &t, 8);
+    v[c] = add_u32_multi_addition(r1cs_compiler, range_checks, pack_cache, &[&v[c], &v[d]]);
+    let t = xor_u32(r1cs_compiler, xor_ops, &v[b], &v[c]);
+    v[b] = rotr_u32(r1cs_compiler, range_checks, &t, 7);
+}
+
+/// Blake2s compression function F.
+/// `bytes_compressed` is the offset counter `t` after this block and
+/// `is_last` sets the finalization flag. Both are known when compiling, so
+/// the initial work vector only depends on constants and `h`.
+fn add_blake2s_compression(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    xor_ops: &mut Vec<(ConstantOrR1CSWitness, ConstantOrR1CSWitness, usize)>,
+    range_checks: &mut BTreeMap<u32, Vec<usize>>,
+    h: [U32; 8],
+    m: &[U32; 16],
+    bytes_compressed: u64,
+    is_last: bool,
+) -> [U32; 8] {
+    let mut iv = BLAKE2S_IV;
+    iv[4] ^= bytes_compressed as u32;
+    iv[5] ^= (bytes_compressed >> 32) as u32;
+    if is_last {
+        iv[6] ^= u32::MAX;
+    }
+
+    let mut v = [h[0]; 16];
+    v[..8].copy_from_slice(&h);
+    for (i, word) in iv.into_iter().enumerate() {
+        v[8 + i] = U32::from_const(r1cs_compiler, word);
+    }
+
+    // Pack cache for this compression - avoids repacking same U32 values
+    let mut pack_cache = PackCache::new();
+
+    for sigma in &BLAKE2S_SIGMA {
+        // Column step
+        for i in 0..4 {
+            add_blake2s_g(
+                r1cs_compiler,
+                xor_ops,
+                range_checks,
+                &mut pack_cache,
+                &mut v,
+                [i, 4 + i, 8 + i, 12 + i],
+                &m[sigma[2 * i]],
+                &m[sigma[2 * i + 1]],
+            );
+        }
+        // Diagonal step
+        for i in 0..4 {
+            add_blake2s_g(
+                r1cs_compiler,
+                xor_ops,
+                range_checks,
+                &mut pack_cache,
+                &mut v,
+                [i, 4 + (i + 1) % 4, 8 + (i + 2) % 4, 12 + (i + 3) % 4],
+                &m[sigma[8 + 2 * i]],
+                &m[sigma[8 + 2 * i + 1]],
+            );
+        }
+    }
+
+    // h[i] = h[i] ⊕ v[i] ⊕ v[i + 8]
+    std::array::from_fn(|i| {
+        let t = xor_u32(r1cs_compiler, xor_ops, &v[i], &v[i + 8]);
+        xor_u32(r1cs_compiler, xor_ops, &h[i], &t)
+    })
+}
+
+/// Blake2s hash (unkeyed, 32-byte digest) of byte inputs.
+/// The message length is fixed by the circuit, so padding, the offset
+/// counter and the finalization flag are resolved at compile time.
+pub(crate) fn add_blake2s(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    xor_ops: &mut Vec<(ConstantOrR1CSWitness, ConstantOrR1CSWitness, usize)>,
+    range_checks: &mut BTreeMap<u32, Vec<usize>>,
+    inputs_and_outputs: Vec<(Vec<ConstantOrR1CSWitness>, Vec<usize>)>,
+) -> Result<(), CompileError> {
+    for (inputs, outputs) in inputs_and_outputs {
+        if outputs.len() != 32 {
+            return Err(CompileError::InvalidCircuit(format!(
+                "Blake2s with {} output bytes, expected 32",
+                outputs.len()
+            )));
+        }
+
+        // Convert inputs to U8, range checking witness inputs
+        let mut message: Vec<U8> = inputs
+            .iter()
+            .map(|input| match input {
+                ConstantOrR1CSWitness::Witness(idx) => {
+                    range_checks.entry(8).or_default().push(*idx);
+                    Ok(U8::new(*idx, true))
+                }
+                ConstantOrR1CSWitness::Constant(value) => {
+                    let value = value.into_bigint().0;
+                    if value[0] >= 256 || value[1..].iter().any(|&limb| limb != 0) {
+                        return Err(CompileError::InvalidCircuit(
+                            "Blake2s input constant exceeds 8 bits".to_string(),
+                        ));
+                    }
+                    Ok(U8::from_const(r1cs_compiler, value[0] as u8))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Pad with zeros to a whole number of blocks (at least one block)
+        let message_len = message.len() as u64;
+        let num_blocks = message.len().div_ceil(BLAKE2S_BLOCK_BYTES).max(1);
+        if message.len() < num_blocks * BLAKE2S_BLOCK_BYTES {
+            let zero = U8::zero(r1cs_compiler);
+            message.resize(num_blocks * BLAKE2S_BLOCK_BYTES, zero);
+        }
+
+        let mut h = BLAKE2S_IV;
+        h[0] ^= BLAKE2S_PARAM_0;
+        let mut h = h.map(|word| U32::from_const(r1cs_compiler, word));
+
+        for (i, block) in message.chunks_exact(BLAKE2S_BLOCK_BYTES).enumerate() {
+            let is_last = i + 1 == num_blocks;
+            let bytes_compressed = if is_last {
+                message_len
+            } else {
+                ((i + 1) * BLAKE2S_BLOCK_BYTES) as u64
+            };
+            let m: [U32; 16] = std::array::from_fn(|j| {
+                U32::new([
+                    block[4 * j],
+                    block[4 * j + 1],
+                    block[4 * j + 2],
+                    block[4 * j + 3],
+                ])
+            });
+            h = add_blake2s_compression(
+                r1cs_compiler,
+                xor_ops,
+                range_checks,
+                h,
+                &m,
+                bytes_compressed,
+                is_last,
+            );
+        }
+
+        // Constrain the little-endian digest bytes to the outputs
+        for (byte, &output) in h.iter().flat_map(|word| word.bytes.iter()).zip(&outputs) {
+            r1cs_compiler.r1cs.add_constraint(
+                &[(FieldElement::ONE, byte.idx)],
+                &[(FieldElement::ONE, r1cs_compiler.witness_one())],
+                &[(FieldElement::ONE, output)],
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(
+        inputs: Vec<ConstantOrR1CSWitness>,
+        outputs: Vec<usize>,
+    ) -> Result<(), CompileError> {
+        add_blake2s(
+            &mut NoirToR1CSCompiler::new(),
+            &mut Vec::new(),
+            &mut BTreeMap::new(),
+            vec![(inputs, outputs)],
+        )
+    }
+
+    #[test]
+    fn output_count() {
+        let byte = ConstantOrR1CSWitness::Constant(FieldElement::ZERO);
+        assert!(matches!(
+            compile(vec![byte; 3], vec![0; 31]),
+            Err(CompileError::InvalidCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn wide_input_constant() {
+        let byte = ConstantOrR1CSWitness::Constant(FieldElement::from(256u64));
+        assert!(matches!(
+            compile(vec![byte; 3], vec![0; 32]),
+            Err(CompileError::InvalidCircuit(_))
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+use {
+    crate::{
+        binops::{add_byte_binop, BinOp},
+        noir_to_r1cs::{CompileError, NoirToR1CSCompiler},
+        sha256_compression::partition_byte_witnesses,
+        uints::{U64, U8},
+    },
+    ark_ff::{Field, PrimeField},
+    provekit_common::{
+        witness::{ConstantOrR1CSWitness, SumTerm, WitnessBuilder},
+        FieldElement,
+    },
+    std::{collections::BTreeMap, ops::Neg},
+};
+
+/// Keccak-f[1600] round constants RC[0..23]
+const KECCAK_RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Keccak ρ rotation offsets, indexed as `KECCAK_ROTATIONS[x][y]`
+const KECCAK_ROTATIONS: [[u32; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+type BinOps = Vec<(ConstantOrR1CSWitness, ConstantOrR1CSWitness, usize)>;
+
+/// Byte-wise XOR of two 64-bit lanes.
+fn xor_u64(r1cs_compiler: &mut NoirToR1CSCompiler, xor_ops: &mut BinOps, a: &U64, b: &U64) -> U64 {
+    U64::new(std::array::from_fn(|i| {
+        add_byte_binop(r1cs_compiler, BinOp::Xor, xor_ops, a.bytes[i], b.bytes[i])
+    }))
+}
+
+/// XORs a 64-bit lane with a constant.
+/// Zero bytes of the constant are skipped, the others are looked up with a
+/// constant operand so no witness is needed for the constant.
+fn xor_u64_const(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    xor_ops: &mut BinOps,
+    a: &U64,
+    constant: u64,
+) -> U64 {
+    let constant = constant.to_le_bytes();
+    U64::new(std::array::from_fn(|i| {
+        if constant[i] == 0 {
+            return a.bytes[i];
+        }
+        let rhs = ConstantOrR1CSWitness::Constant(FieldElement::from(constant[i] as u64));
+        let result = r1cs_compiler.add_witness_builder(WitnessBuilder::Xor(
+            r1cs_compiler.num_witnesses(),
+            ConstantOrR1CSWitness::Witness(a.bytes[i].idx),
+            rhs,
+        ));
+        xor_ops.push((ConstantOrR1CSWitness::Witness(a.bytes[i].idx), rhs, result));
+        U8::new(result, true)
+    }))
+}
+
+/// Byte-wise `!a & b` of two 64-bit lanes.
+/// Uses the identity `!a & b = b - (a & b)`, so only one AND lookup and one
+/// linear constraint per byte are needed.
+fn andn_u64(r1cs_compiler: &mut NoirToR1CSCompiler, and_ops: &mut BinOps, a: &U64, b: &U64) -> U64 {
+    U64::new(std::array::from_fn(|i| {
+        let a_and_b = add_byte_binop(r1cs_compiler, BinOp::And, and_ops, a.bytes[i], b.bytes[i]);
+        let result = r1cs_compiler.num_witnesses();
+        r1cs_compiler.add_witness_builder(WitnessBuilder::Sum(result, vec![
+            SumTerm(None, b.bytes[i].idx),
+            SumTerm(Some(FieldElement::ONE.neg()), a_and_b.idx),
+        ]));
+        // Constraint: result = b - (a & b)
+        r1cs_compiler.r1cs.add_constraint(
+            &[
+                (FieldElement::ONE, b.bytes[i].idx),
+                (FieldElement::ONE.neg(), a_and_b.idx),
+            ],
+            &[(FieldElement::ONE, r1cs_compiler.witness_one())],
+            &[(FieldElement::ONE, result)],
+        );
+        // `a & b` is a bitwise subset of `b`, so the result is a valid byte.
+        U8::new(result, true)
+    }))
+}
+
+/// Left-rotates a 64-bit lane represented as 8 little-endian bytes.
+/// Implemented as ROTR(64 - n) using byte permutation + intra-byte bit
+/// recombination, with the same fused constraint as `rotr_u32`:
+/// `result[i] * 2^k + lo[i] = byte[i] + lo[(i+1)%8] * 256`
+fn rotl_u64(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    range_checks: &mut BTreeMap<u32, Vec<usize>>,
+    x: &U64,
+    n: u32,
+) -> U64 {
+    assert!(n < 64);
+    if n == 0 {
+        return *x;
+    }
+    let n = 64 - n;
+
+    let byte_rot = (n / 8) as usize;
+    let bit_rot = n % 8;
+
+    // Step 1: Byte-level rotation
+    let rot: [U8; 8] = std::array::from_fn(|i| x.bytes[(i + byte_rot) % 8]);
+
+    if bit_rot == 0 {
+        return U64::new(rot);
+    }
+
+    let two_pow_k = FieldElement::from(1u64 << bit_rot);
+    let two_pow_8 = FieldElement::from(256u64);
+    let shift_coeff = FieldElement::from(1u64 << (8 - bit_rot));
+
+    // Step 2: Create partition witnesses (lo, hi) with range check on lo only
+    let parts: [(U8, U8); 8] = std::array::from_fn(|i| {
+        partition_byte_witnesses(r1cs_compiler, range_checks, rot[i], bit_rot)
+    });
+
+    // Step 3: Create result witnesses with fused constraints
+    let out_bytes = std::array::from_fn(|i| {
+        let next = (i + 1) % 8;
+        let res_idx = r1cs_compiler.num_witnesses();
+
+        // Witness: result = hi[i] + lo[next] * 2^(8-k)
+        r1cs_compiler.add_witness_builder(WitnessBuilder::Sum(res_idx, vec![
+            SumTerm(None, parts[i].1.idx),
+            SumTerm(Some(shift_coeff), parts[next].0.idx),
+        ]));
+
+        // Fused constraint: result * 2^k + lo[i] = byte[i] + lo[next] * 256
+        r1cs_compiler.r1cs.add_constraint(
+            &[(two_pow_k, res_idx), (FieldElement::ONE, parts[i].0.idx)],
+            &[(FieldElement::ONE, r1cs_compiler.witness_one())],
+            &[
+                (FieldElement::ONE, rot[i].idx),
+                (two_pow_8, parts[next].0.idx),
+            ],
+        );
+
+        // Result is implicitly range-checked by the fused constraint.
+        U8::new(res_idx, true)
+    });
+
+    U64::new(out_bytes)
+}
+
+/// A single Keccak-f[1600] round on a state indexed as `state[x + 5 * y]`.
+fn add_keccak_round(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    and_ops: &mut BinOps,
+    xor_ops: &mut BinOps,
+    range_checks: &mut BTreeMap<u32, Vec<usize>>,
+    state: [U64; 25],
+    round_constant: u64,
+) -> [U64; 25] {
+    // θ: C[x] = A[x,0] ⊕ ... ⊕ A[x,4], D[x] = C[x-1] ⊕ ROTL(C[x+1], 1)
+    let c: [U64; 5] = std::array::from_fn(|x| {
+        (1..5).fold(state[x], |acc, y| {
+            xor_u64(r1cs_compiler, xor_ops, &acc, &state[x + 5 * y])
+        })
+    });
+    let d: [U64; 5] = std::array::from_fn(|x| {
+        let rotated = rotl_u64(r1cs_compiler, range_checks, &c[(x + 1) % 5], 1);
+        xor_u64(r1cs_compiler, xor_ops, &c[(x + 4) % 5], &rotated)
+    });
+    let state: [U64; 25] =
+        std::array::from_fn(|i| xor_u64(r1cs_compiler, xor_ops, &state[i], &d[i % 5]));
+
+    // ρ and π: B[y, 2x + 3y] = ROTL(A[x, y], r[x, y])
+    let mut b = state;
+    for x in 0..5 {
+        for y in 0..5 {
+            b[y + 5 * ((2 * x + 3 * y) % 5)] = rotl_u64(
+                r1cs_compiler,
+                range_checks,
+                &state[x + 5 * y],
+                KECCAK_ROTATIONS[x][y],
+            );
+        }
+    }
+
+    // χ: A[x, y] = B[x, y] ⊕ (¬B[x+1, y] ∧ B[x+2, y])
+    let mut state: [U64; 25] = std::array::from_fn(|i| {
+        let (x, y) = (i % 5, i / 5);
+        let t = andn_u64(
+            r1cs_compiler,
+            and_ops,
+            &b[(x + 1) % 5 + 5 * y],
+            &b[(x + 2) % 5 + 5 * y],
+        );
+        xor_u64(r1cs_compiler, xor_ops, &b[i], &t)
+    });
+
+    // ι: A[0, 0] ⊕= RC
+    state[0] = xor_u64_const(r1cs_compiler, xor_ops, &state[0], round_constant);
+    state
+}
+
+/// Keccak-f[1600] permutation on 25 little-endian 64-bit lanes.
+pub(crate) fn add_keccakf1600(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    and_ops: &mut BinOps,
+    xor_ops: &mut BinOps,
+    range_checks: &mut BTreeMap<u32, Vec<usize>>,
+    inputs_and_outputs: Vec<(Vec<ConstantOrR1CSWitness>, Vec<usize>)>,
+) -> Result<(), CompileError> {
+    for (inputs, outputs) in inputs_and_outputs {
+        if inputs.len() != 25 || outputs.len() != 25 {
+            return Err(CompileError::InvalidCircuit(format!(
+                "Keccak-f[1600] with {} input and {} output lanes, expected 25",
+                inputs.len(),
+                outputs.len()
+            )));
+        }
+
+        // Convert inputs to U64 (unpack from 64-bit witnesses to 8 bytes each)
+        let mut state: [U64; 25] = inputs
+            .iter()
+            .map(|input| match *input {
+                ConstantOrR1CSWitness::Witness(idx) => {
+                    Ok(U64::unpack_u64(r1cs_compiler, range_checks, idx))
+                }
+                ConstantOrR1CSWitness::Constant(value) => {
+                    let value = value.into_bigint().0;
+                    if value[1..].iter().any(|&limb| limb != 0) {
+                        return Err(CompileError::InvalidCircuit(
+                            "Keccak-f[1600] lane constant exceeds 64 bits".to_string(),
+                        ));
+                    }
+                    Ok(U64::from_const(r1cs_compiler, value[0]))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .try_into()
+            .unwrap();
+
+        for round_constant in KECCAK_RC {
+            state = add_keccak_round(
+                r1cs_compiler,
+                and_ops,
+                xor_ops,
+                range_checks,
+                state,
+                round_constant,
+            );
+        }
+
+        // Pack final lanes and constrain to outputs
+        for (lane, &output) in state.iter().zip(&outputs) {
+            lane.constrain_packed_eq(r1cs_compiler, output);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(
+        inputs: Vec<ConstantOrR1CSWitness>,
+        outputs: Vec<usize>,
+    ) -> Result<(), CompileError> {
+        add_keccakf1600(
+            &mut NoirToR1CSCompiler::new(),
+            &mut Vec::new(),
+            &mut Vec::new(),
+            &mut BTreeMap::new(),
+            vec![(inputs, outputs)],
+        )
+    }
+
+    #[test]
+    fn lane_count() {
+        let lane = ConstantOrR1CSWitness::Constant(FieldElement::ZERO);
+        assert!(matches!(
+            compile(vec![lane; 24], vec![0; 25]),
+            Err(CompileError::InvalidCircuit(_))
+        ));
+        assert!(matches!(
+            compile(vec![lane; 25], vec![0; 26]),
+            Err(CompileError::InvalidCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn wide_lane_constant() {
+        let lane = ConstantOrR1CSWitness::Constant(FieldElement::from(1u128 << 64));
+        assert!(matches!(
+            compile(vec![lane; 25], vec![0; 25]),
+            Err(CompileError::InvalidCircuit(_))
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 mod binops;
+mod blake2s;
 mod digits;
+mod keccak;
 mod memory;
 mod noir_proof_scheme;
 mod noir_to_r1cs;
@@ -12,8 +14,12 @@ mod witness_generator;
 
 pub use {
     noir_proof_scheme::NoirProofSchemeBuilder,
-    noir_to_r1cs::{noir_to_r1cs, noir_to_r1cs_with_breakdown, R1CSBreakdown},
-    whir_r1cs::WhirR1CSSchemeBuilder,
+    noir_to_r1cs::{
+        noir_to_r1cs, noir_to_r1cs_with_breakdown, noir_to_r1cs_with_params, CategoryCounts,
+        CompileError, R1CSBreakdown, R1CSCompilerParams,
+    },
+    poseidon2::{Poseidon2Builder, Poseidon2Params},
+    whir_r1cs::{WhirR1CSParams, WhirR1CSSchemeBuilder},
 };
 
 #[cfg(test)]
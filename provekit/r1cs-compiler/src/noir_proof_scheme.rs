@@ -19,39 +19,68 @@ pub trait NoirProofSchemeBuilder {
     where
         Self: Sized;
 
+    /// Reads the program at `path` and prepares a scheme for its ACIR
+    /// function at index `function`, see
+    /// [`from_program_function`](Self::from_program_function).
+    fn from_file_function(path: impl AsRef<Path> + std::fmt::Debug, function: u32) -> Result<Self>
+    where
+        Self: Sized;
+
     fn from_program(program: ProgramArtifact) -> Result<Self>
     where
         Self: Sized;
+
+    /// Prepares a scheme for the ACIR function at index `function` of
+    /// `program` instead of the entry point.
+    ///
+    /// Calls between ACIR functions (e.g. from `#[fold]`) are not linked, so
+    /// a function that calls another fails to compile. A called function can
+    /// be proven on its own: the prover executes the whole program from the
+    /// entry point's inputs and proves the witness of the last call to
+    /// `function`. ACIR witness indices are local to each function, so the
+    /// witness map of the scheme refers to the indices of `function`.
+    fn from_program_function(program: ProgramArtifact, function: u32) -> Result<Self>
+    where
+        Self: Sized;
 }
 
 impl NoirProofSchemeBuilder for NoirProofScheme {
-    #[instrument(fields(size = path.as_ref().metadata().map(|m| m.len()).ok()))]
     fn from_file(path: impl AsRef<Path> + std::fmt::Debug) -> Result<Self> {
+        Self::from_file_function(path, 0)
+    }
+
+    #[instrument(fields(size = path.as_ref().metadata().map(|m| m.len()).ok()))]
+    fn from_file_function(path: impl AsRef<Path> + std::fmt::Debug, function: u32) -> Result<Self> {
         let file = File::open(path).context("while opening Noir program")?;
         let program = serde_json::from_reader(file).context("while reading Noir program")?;
 
-        Self::from_program(program)
+        Self::from_program_function(program, function)
     }
 
-    #[instrument(skip_all)]
     fn from_program(program: ProgramArtifact) -> Result<Self> {
+        Self::from_program_function(program, 0)
+    }
+
+    #[instrument(skip(program))]
+    fn from_program_function(program: ProgramArtifact, function: u32) -> Result<Self> {
         info!("Program noir version: {}", program.noir_version);
         info!("Program entry point: fn main{};", PrintAbi(&program.abi));
+        let num_functions = program.bytecode.functions.len();
         ensure!(
-            program.bytecode.functions.len() == 1,
-            "Program must have one entry point."
+            (function as usize) < num_functions,
+            "ACIR function {function} does not exist, the program has {num_functions} functions"
         );
 
         // Extract bits from Program Artifact.
-        let main = &program.bytecode.functions[0];
+        let circuit = &program.bytecode.functions[function as usize];
         info!(
             "ACIR: {} witnesses, {} opcodes.",
-            main.current_witness_index,
-            main.opcodes.len()
+            circuit.current_witness_index,
+            circuit.opcodes.len()
         );
 
         // Compile to R1CS schemes
-        let (r1cs, witness_map, witness_builders) = noir_to_r1cs(main)?;
+        let (r1cs, witness_map, witness_builders) = noir_to_r1cs(circuit)?;
         info!(
             "R1CS {} constraints, {} witnesses, A {} entries, B {} entries, C {} entries",
             r1cs.num_constraints(),
@@ -63,9 +92,9 @@ impl NoirProofSchemeBuilder for NoirProofScheme {
 
         // Extract ACIR public input indices set
         let acir_public_inputs_indices_set: HashSet<u32> =
-            main.public_inputs().indices().iter().cloned().collect();
+            circuit.public_inputs().indices().iter().cloned().collect();
 
-        let has_public_inputs = !acir_public_inputs_indices_set.is_empty();
+        let num_public_inputs = acir_public_inputs_indices_set.len();
         // Split witness builders and remap indices for sound challenge generation
         let (split_witness_builders, remapped_r1cs, remapped_witness_map, num_challenges) =
             WitnessBuilder::split_and_prepare_layers(
@@ -92,11 +121,12 @@ impl NoirProofSchemeBuilder for NoirProofScheme {
             &remapped_r1cs,
             split_witness_builders.w1_size,
             num_challenges,
-            has_public_inputs,
+            num_public_inputs,
         );
 
         Ok(Self {
             program: program.bytecode,
+            function,
             r1cs: remapped_r1cs,
             split_witness_builders,
             witness_generator,
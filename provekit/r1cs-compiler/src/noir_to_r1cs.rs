@@ -1,10 +1,14 @@
 use {
     crate::{
         binops::add_combined_binop_constraints,
+        blake2s::add_blake2s,
         digits::{add_digital_decomposition, DigitalDecompositionWitnessesBuilder},
+        keccak::add_keccakf1600,
         memory::{add_ram_checking, add_rom_checking, MemoryBlock, MemoryOperation},
         poseidon2::add_poseidon2_permutation,
-        range_check::add_range_checks,
+        range_check::{
+            add_range_checks, MAX_BITS_FOR_DIGITAL_DECOMP, NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP,
+        },
         sha256_compression::add_sha256_compression,
     },
     acir::{
@@ -16,7 +20,6 @@ use {
         },
         native_types::{Expression, Witness as NoirWitness},
     },
-    anyhow::{bail, Result},
     ark_ff::PrimeField,
     ark_std::One,
     provekit_common::{
@@ -27,21 +30,69 @@ use {
         },
         FieldElement, NoirElement, R1CS,
     },
-    std::{collections::BTreeMap, num::NonZeroU32, ops::Neg},
+    serde::Serialize,
+    std::{collections::BTreeMap, fmt, num::NonZeroU32, ops::Neg},
 };
 
+/// Reason a circuit cannot be compiled to R1CS.
+///
+/// Returned by the compilation functions, and inside the [`anyhow::Error`] of
+/// the proof scheme builders, where callers can recover it with
+/// `downcast_ref`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    /// Black box function without an R1CS implementation, by name.
+    UnsupportedBlackBox(String),
+    /// ACIR opcode without an R1CS implementation.
+    UnsupportedOpcode(String),
+    /// Memory opcode in a form the memory checking does not handle.
+    MemoryUnsupported(String),
+    /// Black box call with an input in a form its R1CS implementation does
+    /// not handle.
+    UnsupportedInput(String),
+    /// Invalid compilation parameters.
+    InvalidParams(String),
+    /// Circuit breaking an invariant of ACIR, e.g. reading a memory block
+    /// before initializing it.
+    InvalidCircuit(String),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedBlackBox(name) => {
+                write!(f, "Circuit uses unsupported black box function {name}")
+            }
+            Self::UnsupportedOpcode(op) => write!(f, "Circuit uses unsupported opcode {op}"),
+            Self::MemoryUnsupported(reason) => write!(f, "Unsupported memory operation: {reason}"),
+            Self::UnsupportedInput(reason) => write!(f, "Unsupported black box input: {reason}"),
+            Self::InvalidParams(reason) => write!(f, "Invalid compiler parameters: {reason}"),
+            Self::InvalidCircuit(reason) => write!(f, "Invalid circuit: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
 /// Detailed breakdown of R1CS constraint and witness counts by circuit
 /// component.
 ///
 /// This struct tracks the exact contribution of each ACIR opcode type to the
 /// final R1CS size, enabling precise analysis of circuit complexity.
-#[derive(Debug, Clone, Default)]
+/// [`R1CSBreakdown::by_category`] groups the counts into a machine-readable
+/// form, e.g. for tracking regressions in CI.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct R1CSBreakdown {
     /// Constraints from ACIR AssertZero opcodes
     pub assert_zero_constraints: usize,
     /// Witnesses added for AssertZero intermediate products
     pub assert_zero_witnesses:   usize,
 
+    /// Constraints for the byte decomposition of AND/XOR black box operands
+    pub digits_constraints: usize,
+    /// Witnesses for the byte decomposition of AND/XOR black box operands
+    pub digits_witnesses:   usize,
+
     /// Constraints for ROM (read-only memory) checking
     pub memory_rom_constraints: usize,
     /// Witnesses for ROM checking
@@ -77,12 +128,121 @@ pub struct R1CSBreakdown {
     /// Number of range checks generated by SHA256
     pub sha256_range_ops:          usize,
 
+    /// Direct constraints from Keccak-f[1600] (excluding batched ops)
+    pub keccak_direct_constraints: usize,
+    /// Direct witnesses from Keccak-f[1600]
+    pub keccak_direct_witnesses:   usize,
+    /// Number of AND operations generated by Keccak-f[1600]
+    pub keccak_and_ops:            usize,
+    /// Number of XOR operations generated by Keccak-f[1600]
+    pub keccak_xor_ops:            usize,
+    /// Number of range checks generated by Keccak-f[1600]
+    pub keccak_range_ops:          usize,
+
+    /// Direct constraints from Blake2s (excluding batched ops)
+    pub blake2s_direct_constraints: usize,
+    /// Direct witnesses from Blake2s
+    pub blake2s_direct_witnesses:   usize,
+    /// Number of XOR operations generated by Blake2s
+    pub blake2s_xor_ops:            usize,
+    /// Number of range checks generated by Blake2s
+    pub blake2s_range_ops:          usize,
+
     /// Constraints from Poseidon2 permutation
     pub poseidon2_constraints: usize,
     /// Witnesses from Poseidon2 permutation
     pub poseidon2_witnesses:   usize,
 }
 
+/// Constraint and witness counts of a single [`R1CSBreakdown`] category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CategoryCounts {
+    pub constraints: usize,
+    pub witnesses:   usize,
+}
+
+impl R1CSBreakdown {
+    /// Category names used by [`R1CSBreakdown::by_category`].
+    pub const CATEGORIES: [&'static str; 10] = [
+        "assert_zero",
+        "memory_rom",
+        "memory_ram",
+        "digits",
+        "binops",
+        "range_checks",
+        "sha256",
+        "keccak",
+        "blake2s",
+        "poseidon2",
+    ];
+
+    /// Per-category constraint and witness counts, keyed by the names in
+    /// [`R1CSBreakdown::CATEGORIES`].
+    ///
+    /// The hash categories only count their direct constraints; their
+    /// byte-level lookups and range checks are included in `binops` and
+    /// `range_checks`. Summing all categories gives the number of R1CS
+    /// constraints.
+    pub fn by_category(&self) -> BTreeMap<&'static str, CategoryCounts> {
+        let counts = [
+            (self.assert_zero_constraints, self.assert_zero_witnesses),
+            (self.memory_rom_constraints, self.memory_rom_witnesses),
+            (self.memory_ram_constraints, self.memory_ram_witnesses),
+            (self.digits_constraints, self.digits_witnesses),
+            (self.binop_constraints, self.binop_witnesses),
+            (self.range_constraints, self.range_witnesses),
+            (self.sha256_direct_constraints, self.sha256_direct_witnesses),
+            (self.keccak_direct_constraints, self.keccak_direct_witnesses),
+            (
+                self.blake2s_direct_constraints,
+                self.blake2s_direct_witnesses,
+            ),
+            (self.poseidon2_constraints, self.poseidon2_witnesses),
+        ];
+        Self::CATEGORIES
+            .into_iter()
+            .zip(counts)
+            .map(|(name, (constraints, witnesses))| {
+                (name, CategoryCounts {
+                    constraints,
+                    witnesses,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parameters of the compilation of ACIR circuits to R1CS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct R1CSCompilerParams {
+    /// Width in bits of the digits that range checks are decomposed into, at
+    /// most 16. Wider digits need fewer digit witnesses per value, but a
+    /// lookup table of `2^range_check_digit_bits` entries. AND/XOR operations
+    /// keep their byte-wise tables (`BINOP_ATOMIC_BITS`), whose operands are
+    /// constrained to bytes by the lookup itself.
+    pub range_check_digit_bits: u32,
+}
+
+impl Default for R1CSCompilerParams {
+    fn default() -> Self {
+        Self {
+            range_check_digit_bits: NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP,
+        }
+    }
+}
+
+impl R1CSCompilerParams {
+    fn validate(&self) -> Result<(), CompileError> {
+        if !(1..=MAX_BITS_FOR_DIGITAL_DECOMP).contains(&self.range_check_digit_bits) {
+            return Err(CompileError::InvalidParams(format!(
+                "Range check digits must be 1 to {MAX_BITS_FOR_DIGITAL_DECOMP} bits wide, got {}",
+                self.range_check_digit_bits
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// Compiles an ACIR circuit into an [R1CS] instance, comprising of the A, B,
 /// and C R1CS matrices, along with the witness vector.
 pub(crate) struct NoirToR1CSCompiler {
@@ -104,28 +264,49 @@ pub(crate) struct NoirToR1CSCompiler {
 /// witness indices, and the witness builders for solving.
 pub fn noir_to_r1cs(
     circuit: &Circuit<NoirElement>,
-) -> Result<(R1CS, Vec<Option<NonZeroU32>>, Vec<WitnessBuilder>)> {
+) -> Result<(R1CS, Vec<Option<NonZeroU32>>, Vec<WitnessBuilder>), CompileError> {
     let mut compiler = NoirToR1CSCompiler::new();
     compiler.add_circuit(circuit)?;
     Ok(compiler.finalize())
 }
 
-pub fn noir_to_r1cs_with_breakdown(
+/// Like [`noir_to_r1cs_with_breakdown`], with the given compilation
+/// parameters instead of the defaults.
+pub fn noir_to_r1cs_with_params(
     circuit: &Circuit<NoirElement>,
-) -> Result<(
-    R1CS,
-    Vec<Option<NonZeroU32>>,
-    Vec<WitnessBuilder>,
-    R1CSBreakdown,
-)> {
+    params: &R1CSCompilerParams,
+) -> Result<
+    (
+        R1CS,
+        Vec<Option<NonZeroU32>>,
+        Vec<WitnessBuilder>,
+        R1CSBreakdown,
+    ),
+    CompileError,
+> {
+    params.validate()?;
     let mut compiler = NoirToR1CSCompiler::new();
-    let breakdown = compiler.add_circuit_with_breakdown(circuit)?;
+    let breakdown = compiler.add_circuit_with_breakdown(circuit, params)?;
     let (r1cs, map, builders) = compiler.finalize();
     Ok((r1cs, map, builders, breakdown))
 }
 
+pub fn noir_to_r1cs_with_breakdown(
+    circuit: &Circuit<NoirElement>,
+) -> Result<
+    (
+        R1CS,
+        Vec<Option<NonZeroU32>>,
+        Vec<WitnessBuilder>,
+        R1CSBreakdown,
+    ),
+    CompileError,
+> {
+    noir_to_r1cs_with_params(circuit, &R1CSCompilerParams::default())
+}
+
 impl NoirToR1CSCompiler {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         let mut r1cs = R1CS::new();
         // Grow the matrices to account for the constant one witness.
         r1cs.add_witnesses(1);
@@ -145,6 +326,25 @@ impl NoirToR1CSCompiler {
         }
     }
 
+    /// Resumes compilation on an existing R1CS instance, e.g. to append
+    /// gadgets to a circuit compiled earlier. The first witness must be the
+    /// constant one.
+    pub(crate) fn from_parts(r1cs: R1CS, witness_builders: Vec<WitnessBuilder>) -> Self {
+        assert!(
+            matches!(
+                witness_builders.first(),
+                Some(WitnessBuilder::Constant(ConstantTerm(0, one))) if one.is_one()
+            ),
+            "R1CS requires first witness to be 1"
+        );
+        Self {
+            r1cs,
+            witness_builders,
+            acir_to_r1cs_witness_map: BTreeMap::new(),
+            initial_memories: BTreeMap::new(),
+        }
+    }
+
     /// Returns the R1CS and the witness map
     pub fn finalize(self) -> (R1CS, Vec<Option<NonZeroU32>>, Vec<WitnessBuilder>) {
         // Convert witness map to vector
@@ -304,15 +504,16 @@ impl NoirToR1CSCompiler {
         self.r1cs.add_constraint(&a, &b, &linear);
     }
 
-    fn add_circuit(&mut self, circuit: &Circuit<NoirElement>) -> Result<()> {
-        self.add_circuit_with_breakdown(circuit)?;
+    fn add_circuit(&mut self, circuit: &Circuit<NoirElement>) -> Result<(), CompileError> {
+        self.add_circuit_with_breakdown(circuit, &R1CSCompilerParams::default())?;
         Ok(())
     }
 
     fn add_circuit_with_breakdown(
         &mut self,
         circuit: &Circuit<NoirElement>,
-    ) -> Result<R1CSBreakdown> {
+        params: &R1CSCompilerParams,
+    ) -> Result<R1CSBreakdown, CompileError> {
         // Read-only memory blocks (used for building the memory lookup constraints at
         // the end)
 
@@ -328,6 +529,8 @@ impl NoirToR1CSCompiler {
         let mut and_ops = vec![];
         let mut xor_ops = vec![];
         let mut sha256_compression_ops = vec![];
+        let mut keccakf1600_ops = vec![];
+        let mut blake2s_ops = vec![];
         let mut poseidon2_ops = vec![];
 
         let mut breakdown = R1CSBreakdown::default();
@@ -336,6 +539,8 @@ impl NoirToR1CSCompiler {
         let witnesses_before_assert = self.num_witnesses();
 
         for opcode in &circuit.opcodes {
+            let constraints_before_opcode = self.r1cs.num_constraints();
+            let witnesses_before_opcode = self.num_witnesses();
             match opcode {
                 Opcode::AssertZero(expr) => self.add_acir_assert_zero(expr),
                 // Brillig is only for witness generation and does not produce constraints.
@@ -346,14 +551,16 @@ impl NoirToR1CSCompiler {
                     block_type,
                 } => {
                     if *block_type != BlockType::Memory {
-                        panic!("MemoryInit block type must be Memory")
+                        return Err(CompileError::MemoryUnsupported(format!(
+                            "block type {block_type:?}, expected Memory"
+                        )));
                     }
                     let block_id = block_id.0 as usize;
-                    assert!(
-                        !memory_blocks.contains_key(&block_id),
-                        "Memory block {} already initialized",
-                        block_id
-                    );
+                    if memory_blocks.contains_key(&block_id) {
+                        return Err(CompileError::InvalidCircuit(format!(
+                            "memory block {block_id} already initialized"
+                        )));
+                    }
                     let acir_indices: Vec<usize> = init.iter().map(|w| w.0 as usize).collect();
                     self.initial_memories.insert(block_id, acir_indices);
                     let mut block = MemoryBlock::new();
@@ -368,36 +575,41 @@ impl NoirToR1CSCompiler {
                     op,
                     predicate,
                 } => {
-                    // Panic if the predicate is set (according to Noir developers, predicate is
+                    // Reject a set predicate (according to Noir developers, predicate is
                     // always None and will soon be removed).
-                    assert!(
-                        predicate.is_none(),
-                        "MemoryOp has unexpected predicate: {:?}",
-                        predicate
-                    );
+                    if let Some(predicate) = predicate {
+                        return Err(CompileError::MemoryUnsupported(format!(
+                            "MemoryOp has unexpected predicate: {predicate:?}"
+                        )));
+                    }
 
                     let block_id = block_id.0 as usize;
-                    assert!(
-                        memory_blocks.contains_key(&block_id),
-                        "Memory block {} not initialized before read",
-                        block_id
-                    );
-                    let block = memory_blocks.get_mut(&block_id).unwrap();
+                    let Some(block) = memory_blocks.get_mut(&block_id) else {
+                        return Err(CompileError::InvalidCircuit(format!(
+                            "memory block {block_id} not initialized before use"
+                        )));
+                    };
 
                     // `op.index` is _always_ just a single ACIR witness, not a more complicated
                     // expression, and not a constant. See [here](https://discord.com/channels/1113924620781883405/1356865341065531446)
                     // Static reads are hard-wired into the circuit, or instead rendered as a
                     // dummy dynamic read by introducing a new witness constrained to have the value
                     // of the static address.
-                    let addr = op.index.to_witness().map_or_else(
-                        || {
-                            unimplemented!(
-                                "MemoryOp index must be a single witness, not a more general \
-                                 Expression"
-                            )
-                        },
-                        |acir_witness| self.fetch_r1cs_witness_index(acir_witness),
-                    );
+                    let Some(index) = op.index.to_witness() else {
+                        return Err(CompileError::MemoryUnsupported(
+                            "MemoryOp index must be a single witness, not a more general \
+                             Expression"
+                                .to_string(),
+                        ));
+                    };
+                    let addr = self.fetch_r1cs_witness_index(index);
+                    let Some(value) = op.value.to_witness() else {
+                        return Err(CompileError::MemoryUnsupported(
+                            "MemoryOp value must be a single witness, not a more general \
+                             Expression"
+                                .to_string(),
+                        ));
+                    };
                     let op = if op.operation.is_zero() {
                         // Create a new (as yet unconstrained) witness `result_of_read` for the
                         // result of the read; it will be constrained by later memory block
@@ -407,12 +619,10 @@ impl NoirToR1CSCompiler {
                         // At R1CS solving time, only need to map over the value of the
                         // corresponding ACIR witness, whose value is already determined by the ACIR
                         // solver.
-                        let result_of_read =
-                            self.fetch_r1cs_witness_index(op.value.to_witness().unwrap());
+                        let result_of_read = self.fetch_r1cs_witness_index(value);
                         MemoryOperation::Load(addr, result_of_read)
                     } else {
-                        let new_value =
-                            self.fetch_r1cs_witness_index(op.value.to_witness().unwrap());
+                        let new_value = self.fetch_r1cs_witness_index(value);
                         MemoryOperation::Store(addr, new_value)
                     };
                     block.operations.push(op);
@@ -424,11 +634,11 @@ impl NoirToR1CSCompiler {
                         let input = function_input.input();
                         let num_bits = function_input.num_bits();
                         let input_witness = match input {
+                            // The Noir compiler already checks constants.
                             ConstantOrACIRWitness::Constant(_) => {
-                                panic!(
-                                    "We should never be range-checking a constant value, as this \
-                                     should already be done by the noir-ACIR compiler"
-                                );
+                                return Err(CompileError::UnsupportedInput(
+                                    "RANGE on a constant".to_string(),
+                                ));
                             }
                             ConstantOrACIRWitness::Witness(witness) => {
                                 self.fetch_r1cs_witness_index(witness)
@@ -444,7 +654,7 @@ impl NoirToR1CSCompiler {
                     // The inputs and outputs will have already been solved for by the ACIR solver.
                     // Noir blackbox AND/XOR operate on 32-bit values. We decompose into 4 bytes
                     // and add byte-level ops to leverage the combined byte-level lookup table.
-                    BlackBoxFuncCall::AND { lhs, rhs, output } => {
+                    BlackBoxFuncCall::AND { lhs, rhs, output } => 'binop: {
                         // Get the 32-bit witness indices
                         let lhs_witness = match lhs.input() {
                             ConstantOrACIRWitness::Witness(w) => self.fetch_r1cs_witness_index(w),
@@ -505,7 +715,7 @@ impl NoirToR1CSCompiler {
                                         }
                                     }
                                 }
-                                continue;
+                                break 'binop;
                             }
                         };
                         let rhs_witness = match rhs.input() {
@@ -537,7 +747,7 @@ impl NoirToR1CSCompiler {
                                     let out_byte = dd.get_digit_witness_index(byte_idx, 1);
                                     and_ops.push((lhs_byte, rhs_byte, out_byte));
                                 }
-                                continue;
+                                break 'binop;
                             }
                         };
                         let out_idx = self.fetch_r1cs_witness_index(*output);
@@ -562,7 +772,7 @@ impl NoirToR1CSCompiler {
                             and_ops.push((lhs_byte, rhs_byte, out_byte));
                         }
                     }
-                    BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+                    BlackBoxFuncCall::XOR { lhs, rhs, output } => 'binop: {
                         // Get the 32-bit witness indices
                         let lhs_witness = match lhs.input() {
                             ConstantOrACIRWitness::Witness(w) => self.fetch_r1cs_witness_index(w),
@@ -623,7 +833,7 @@ impl NoirToR1CSCompiler {
                                         }
                                     }
                                 }
-                                continue;
+                                break 'binop;
                             }
                         };
                         let rhs_witness = match rhs.input() {
@@ -654,7 +864,7 @@ impl NoirToR1CSCompiler {
                                     let out_byte = dd.get_digit_witness_index(byte_idx, 1);
                                     xor_ops.push((lhs_byte, rhs_byte, out_byte));
                                 }
-                                continue;
+                                break 'binop;
                             }
                         };
                         let out_idx = self.fetch_r1cs_witness_index(*output);
@@ -684,15 +894,22 @@ impl NoirToR1CSCompiler {
                         outputs,
                         len,
                     } => {
-                        assert_eq!(inputs.len() as u32, *len, "Poseidon2: inputs.len != len");
-                        assert_eq!(outputs.len() as u32, *len, "Poseidon2: outputs.len != len");
+                        if inputs.len() as u32 != *len || outputs.len() as u32 != *len {
+                            return Err(CompileError::InvalidCircuit(format!(
+                                "Poseidon2 permutation of width {len} with {} inputs and {} \
+                                 outputs",
+                                inputs.len(),
+                                outputs.len()
+                            )));
+                        }
                         let t = *len;
 
                         // Only these widths are allowed for Poseidon2
-                        assert!(
-                            matches!(t, 2 | 3 | 4 | 8 | 12 | 16),
-                            "Poseidon2: unsupported width {t}"
-                        );
+                        if !matches!(t, 2 | 3 | 4 | 8 | 12 | 16) {
+                            return Err(CompileError::UnsupportedInput(format!(
+                                "Poseidon2 permutation of width {t}"
+                            )));
+                        }
 
                         // Convert ACIR inputs to (Constant | Witness)
                         let in_wits: Vec<ConstantOrR1CSWitness> = inputs
@@ -728,16 +945,53 @@ impl NoirToR1CSCompiler {
                             output_witnesses,
                         ));
                     }
+                    BlackBoxFuncCall::Keccakf1600 { inputs, outputs } => {
+                        let input_witnesses: Vec<ConstantOrR1CSWitness> = inputs
+                            .iter()
+                            .map(|input| self.fetch_constant_or_r1cs_witness(input.input()))
+                            .collect();
+                        let output_witnesses: Vec<usize> = outputs
+                            .iter()
+                            .map(|&output| self.fetch_r1cs_witness_index(output))
+                            .collect();
+                        keccakf1600_ops.push((input_witnesses, output_witnesses));
+                    }
+                    BlackBoxFuncCall::Blake2s { inputs, outputs } => {
+                        let input_witnesses: Vec<ConstantOrR1CSWitness> = inputs
+                            .iter()
+                            .map(|input| self.fetch_constant_or_r1cs_witness(input.input()))
+                            .collect();
+                        let output_witnesses: Vec<usize> = outputs
+                            .iter()
+                            .map(|&output| self.fetch_r1cs_witness_index(output))
+                            .collect();
+                        blake2s_ops.push((input_witnesses, output_witnesses));
+                    }
                     _ => {
-                        unimplemented!("Other black box function: {:?}", black_box_func_call);
+                        return Err(CompileError::UnsupportedBlackBox(
+                            black_box_func_call.get_black_box_func().name().to_string(),
+                        ))
                     }
                 },
-                op => bail!("Unsupported Opcode {op}"),
+                op => return Err(CompileError::UnsupportedOpcode(op.to_string())),
+            }
+            // AND/XOR black box calls only decompose their operands into bytes here.
+            if matches!(
+                opcode,
+                Opcode::BlackBoxFuncCall(
+                    BlackBoxFuncCall::AND { .. } | BlackBoxFuncCall::XOR { .. }
+                )
+            ) {
+                breakdown.digits_constraints +=
+                    self.r1cs.num_constraints() - constraints_before_opcode;
+                breakdown.digits_witnesses += self.num_witnesses() - witnesses_before_opcode;
             }
         }
 
-        breakdown.assert_zero_constraints = self.r1cs.num_constraints() - constraints_before_assert;
-        breakdown.assert_zero_witnesses = self.num_witnesses() - witnesses_before_assert;
+        breakdown.assert_zero_constraints =
+            self.r1cs.num_constraints() - constraints_before_assert - breakdown.digits_constraints;
+        breakdown.assert_zero_witnesses =
+            self.num_witnesses() - witnesses_before_assert - breakdown.digits_witnesses;
 
         // Process ROM blocks first (read-only memory uses lookup constraints)
         let constraints_before_rom = self.r1cs.num_constraints();
@@ -783,7 +1037,7 @@ impl NoirToR1CSCompiler {
             &mut xor_ops,
             &mut range_checks,
             sha256_compression_ops,
-        );
+        )?;
 
         breakdown.sha256_direct_constraints =
             self.r1cs.num_constraints() - constraints_before_sha256;
@@ -793,6 +1047,46 @@ impl NoirToR1CSCompiler {
         breakdown.sha256_range_ops =
             range_checks.values().map(|v| v.len()).sum::<usize>() - range_ops_before;
 
+        // Track Keccak-f[1600]'s contribution to batched operations
+        let and_ops_before = and_ops.len();
+        let xor_ops_before = xor_ops.len();
+        let range_ops_before: usize = range_checks.values().map(|v| v.len()).sum();
+        let constraints_before_keccak = self.r1cs.num_constraints();
+        let witnesses_before_keccak = self.num_witnesses();
+
+        // Keccak-f[1600] also works on bytes, sharing the batched lookups with SHA256.
+        add_keccakf1600(
+            self,
+            &mut and_ops,
+            &mut xor_ops,
+            &mut range_checks,
+            keccakf1600_ops,
+        )?;
+
+        breakdown.keccak_direct_constraints =
+            self.r1cs.num_constraints() - constraints_before_keccak;
+        breakdown.keccak_direct_witnesses = self.num_witnesses() - witnesses_before_keccak;
+        breakdown.keccak_and_ops = and_ops.len() - and_ops_before;
+        breakdown.keccak_xor_ops = xor_ops.len() - xor_ops_before;
+        breakdown.keccak_range_ops =
+            range_checks.values().map(|v| v.len()).sum::<usize>() - range_ops_before;
+
+        // Track Blake2s's contribution to batched operations
+        let xor_ops_before = xor_ops.len();
+        let range_ops_before: usize = range_checks.values().map(|v| v.len()).sum();
+        let constraints_before_blake2s = self.r1cs.num_constraints();
+        let witnesses_before_blake2s = self.num_witnesses();
+
+        // Blake2s only needs XOR lookups; additions and rotations use range checks.
+        add_blake2s(self, &mut xor_ops, &mut range_checks, blake2s_ops)?;
+
+        breakdown.blake2s_direct_constraints =
+            self.r1cs.num_constraints() - constraints_before_blake2s;
+        breakdown.blake2s_direct_witnesses = self.num_witnesses() - witnesses_before_blake2s;
+        breakdown.blake2s_xor_ops = xor_ops.len() - xor_ops_before;
+        breakdown.blake2s_range_ops =
+            range_checks.values().map(|v| v.len()).sum::<usize>() - range_ops_before;
+
         breakdown.and_ops_total = and_ops.len();
         breakdown.xor_ops_total = xor_ops.len();
         let constraints_before_binop = self.r1cs.num_constraints();
@@ -809,10 +1103,89 @@ impl NoirToR1CSCompiler {
         breakdown.range_ops_total = range_checks.values().map(|v| v.len()).sum();
         let constraints_before_range = self.r1cs.num_constraints();
         let witnesses_before_range = self.num_witnesses();
-        add_range_checks(self, range_checks);
+        add_range_checks(self, range_checks, params.range_check_digit_bits);
         breakdown.range_constraints = self.r1cs.num_constraints() - constraints_before_range;
         breakdown.range_witnesses = self.num_witnesses() - witnesses_before_range;
 
         Ok(breakdown)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        acir::circuit::opcodes::{BlockId, FunctionInput, MemOp},
+    };
+
+    fn compile(opcodes: Vec<Opcode<NoirElement>>) -> Result<(), CompileError> {
+        let circuit = Circuit {
+            opcodes,
+            ..Circuit::default()
+        };
+        noir_to_r1cs(&circuit).map(|_| ())
+    }
+
+    fn memory_init(block: u32) -> Opcode<NoirElement> {
+        Opcode::MemoryInit {
+            block_id:   BlockId(block),
+            init:       vec![NoirWitness(0)],
+            block_type: BlockType::Memory,
+        }
+    }
+
+    fn poseidon2(num_inputs: u32, num_outputs: u32, len: u32) -> Opcode<NoirElement> {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Poseidon2Permutation {
+            inputs: (0..num_inputs)
+                .map(|i| FunctionInput::witness(NoirWitness(i), 254))
+                .collect(),
+            outputs: (0..num_outputs)
+                .map(|i| NoirWitness(num_inputs + i))
+                .collect(),
+            len,
+        })
+    }
+
+    #[test]
+    fn memory_block_initialized_twice() {
+        let result = compile(vec![memory_init(0), memory_init(0)]);
+        assert!(
+            matches!(result, Err(CompileError::InvalidCircuit(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn memory_block_read_before_initialization() {
+        let read = Opcode::MemoryOp {
+            block_id:  BlockId(1),
+            op:        MemOp::read_at_mem_index(NoirWitness(1).into(), NoirWitness(2)),
+            predicate: None,
+        };
+        let result = compile(vec![memory_init(0), read]);
+        assert!(
+            matches!(result, Err(CompileError::InvalidCircuit(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn poseidon2_length_mismatch() {
+        for (num_inputs, num_outputs) in [(3, 4), (4, 3)] {
+            let result = compile(vec![poseidon2(num_inputs, num_outputs, 4)]);
+            assert!(
+                matches!(result, Err(CompileError::InvalidCircuit(_))),
+                "{result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn poseidon2_unsupported_width() {
+        let result = compile(vec![poseidon2(5, 5, 5)]);
+        assert!(
+            matches!(result, Err(CompileError::UnsupportedInput(_))),
+            "{result:?}"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Public entry point for emitting Poseidon2 constraints into an R1CS instance
+//! outside of the Noir black box lowering.
+use {
+    super::permutation::{add_poseidon2_hash, add_poseidon2_sponge},
+    crate::noir_to_r1cs::NoirToR1CSCompiler,
+    provekit_common::{witness::WitnessBuilder, R1CS},
+    std::mem,
+};
+
+/// Poseidon2 parameter sets over BN254 supported by [`Poseidon2Builder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Poseidon2Params {
+    /// State width 2: 8 full rounds and 56 partial rounds.
+    T2,
+    /// State width 3: 8 full rounds and 56 partial rounds.
+    T3,
+}
+
+impl Poseidon2Params {
+    /// The state width `t`, which is also the number of permutation inputs.
+    pub const fn width(self) -> usize {
+        match self {
+            Poseidon2Params::T2 => 2,
+            Poseidon2Params::T3 => 3,
+        }
+    }
+}
+
+/// Emits the constraints of a Poseidon2 hash or permutation into an existing
+/// R1CS instance.
+///
+/// The instance must use the compiler's layout, with the constant one as
+/// witness 0, as produced by [`noir_to_r1cs`](crate::noir_to_r1cs). The
+/// witness builders are extended so the usual witness solver fills in the
+/// intermediate and output witnesses.
+#[derive(Clone, Copy, Debug)]
+pub struct Poseidon2Builder {
+    params: Poseidon2Params,
+}
+
+impl Poseidon2Builder {
+    pub const fn new(params: Poseidon2Params) -> Self {
+        Self { params }
+    }
+
+    /// Hashes any number of input witnesses and returns the index of the
+    /// output witness.
+    ///
+    /// The inputs are absorbed by a sponge with rate `t - 1`, as in Noir's
+    /// `poseidon2` hash: the capacity lane starts at `inputs.len() * 2^64` and
+    /// the last block is padded with zeros.
+    ///
+    /// # Panics
+    ///
+    /// Panics if witness 0 is not the constant one.
+    pub fn build(
+        &self,
+        r1cs: &mut R1CS,
+        witness_builders: &mut Vec<WitnessBuilder>,
+        inputs: &[usize],
+    ) -> usize {
+        self.emit(r1cs, witness_builders, |compiler, t| {
+            add_poseidon2_sponge(compiler, t, inputs)
+        })
+    }
+
+    /// Permutes the `t` input witnesses and returns the index of the witness
+    /// holding the first lane of the resulting state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` does not hold exactly `t` witnesses or if witness 0
+    /// is not the constant one.
+    pub fn permute(
+        &self,
+        r1cs: &mut R1CS,
+        witness_builders: &mut Vec<WitnessBuilder>,
+        inputs: &[usize],
+    ) -> usize {
+        assert_eq!(
+            inputs.len(),
+            self.params.width(),
+            "Poseidon2 expects one input per state lane"
+        );
+        self.emit(r1cs, witness_builders, |compiler, t| {
+            add_poseidon2_hash(compiler, t, inputs)
+        })
+    }
+
+    fn emit(
+        &self,
+        r1cs: &mut R1CS,
+        witness_builders: &mut Vec<WitnessBuilder>,
+        add: impl FnOnce(&mut NoirToR1CSCompiler, u32) -> usize,
+    ) -> usize {
+        let mut compiler =
+            NoirToR1CSCompiler::from_parts(mem::take(r1cs), mem::take(witness_builders));
+        let output = add(&mut compiler, self.params.width() as u32);
+        *r1cs = compiler.r1cs;
+        *witness_builders = compiler.witness_builders;
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::poseidon2::constants::fe,
+        ark_std::{One, Zero},
+        provekit_common::{
+            witness::{ConstantTerm, SumTerm},
+            FieldElement,
+        },
+    };
+
+    /// Solves the witness builders emitted by the permutation.
+    fn solve(num_witnesses: usize, witness_builders: &[WitnessBuilder]) -> Vec<FieldElement> {
+        let mut witness = vec![FieldElement::zero(); num_witnesses];
+        for builder in witness_builders {
+            match builder {
+                WitnessBuilder::Constant(ConstantTerm(idx, value)) => witness[*idx] = *value,
+                WitnessBuilder::Sum(idx, terms) => {
+                    witness[*idx] = terms
+                        .iter()
+                        .map(|SumTerm(coeff, w)| coeff.unwrap_or(FieldElement::one()) * witness[*w])
+                        .sum();
+                }
+                WitnessBuilder::Product(idx, a, b) => witness[*idx] = witness[*a] * witness[*b],
+                _ => unreachable!("unexpected witness builder {builder:?}"),
+            }
+        }
+        witness
+    }
+
+    /// Emits the constraints for `num_inputs` constant inputs `first, first +
+    /// 1, ...` with `emit`, checks that the solved witness satisfies them and
+    /// returns the output value.
+    fn run(
+        num_inputs: usize,
+        first: u64,
+        emit: impl FnOnce(&mut R1CS, &mut Vec<WitnessBuilder>, &[usize]) -> usize,
+    ) -> FieldElement {
+        let mut r1cs = R1CS::new();
+        let mut witness_builders = vec![];
+        for value in 0..=num_inputs {
+            // Witness 0 is the constant one, followed by the inputs.
+            let value = if value == 0 {
+                1
+            } else {
+                first + value as u64 - 1
+            };
+            witness_builders.push(WitnessBuilder::Constant(ConstantTerm(
+                r1cs.num_witnesses(),
+                FieldElement::from(value),
+            )));
+            r1cs.add_witnesses(1);
+        }
+        let inputs: Vec<usize> = (1..=num_inputs).collect();
+
+        let output = emit(&mut r1cs, &mut witness_builders, &inputs);
+
+        let witness = solve(r1cs.num_witnesses(), &witness_builders);
+        let a = r1cs.a() * witness.as_slice();
+        let b = r1cs.b() * witness.as_slice();
+        let c = r1cs.c() * witness.as_slice();
+        for (row, ((a, b), c)) in a.into_iter().zip(b).zip(c).enumerate() {
+            assert_eq!(a * b, c, "constraint {row} failed");
+        }
+        witness[output]
+    }
+
+    /// Permutes `0, 1, ..., t - 1` and checks the output against the first
+    /// lane of the HorizenLabs reference permutation.
+    fn check_permutation(params: Poseidon2Params, expected: &str) {
+        let output = run(params.width(), 0, |r1cs, witness_builders, inputs| {
+            Poseidon2Builder::new(params).permute(r1cs, witness_builders, inputs)
+        });
+        assert_eq!(output, fe(expected));
+    }
+
+    /// Hashes `1, 2, ..., n` for each `(n, expected)` pair. The expected
+    /// values chain the reference permutation above through the sponge.
+    fn check_sponge(params: Poseidon2Params, cases: &[(usize, &str)]) {
+        for &(num_inputs, expected) in cases {
+            let output = run(num_inputs, 1, |r1cs, witness_builders, inputs| {
+                Poseidon2Builder::new(params).build(r1cs, witness_builders, inputs)
+            });
+            assert_eq!(output, fe(expected), "{num_inputs} inputs");
+        }
+    }
+
+    #[test]
+    fn poseidon2_t2_matches_reference() {
+        check_permutation(
+            Poseidon2Params::T2,
+            "0x1d01e56f49579cec72319e145f06f6177f6c5253206e78c2689781452a31878b",
+        );
+    }
+
+    #[test]
+    fn poseidon2_t3_matches_reference() {
+        check_permutation(
+            Poseidon2Params::T3,
+            "0x0bb61d24daca55eebcb1929a82650f328134334da98ea4f847f760054f4a3033",
+        );
+    }
+
+    #[test]
+    fn poseidon2_t2_sponge() {
+        check_sponge(Poseidon2Params::T2, &[
+            (
+                1,
+                "0x117bdb59d7080bc3b908e9fff4b7a2b0af7cff59efead2270d817894be833c4b",
+            ),
+            (
+                2,
+                "0x1e02eb9a65a94959fd3343adc1337c4ccceb6196ac1c78e8a0ab2f7fbbbd6daa",
+            ),
+            (
+                3,
+                "0x08972d4863f9d027f5663520ac7ed5c1141ddf7a76efe1ff0be68900fa16fb4a",
+            ),
+            (
+                5,
+                "0x0110045d35b88e18a3149eebb68ae3880a6bd13ff9461d24083bce366e2db0c9",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn poseidon2_t3_sponge() {
+        check_sponge(Poseidon2Params::T3, &[
+            (
+                1,
+                "0x1e4a657b5631309b732f4762fdf6552c266e3b1c6ec84c01b65e88bceac60efb",
+            ),
+            (
+                2,
+                "0x0210752763833e0245ca2554e847d2e5b327da5ae77038be2c525059254e3bfd",
+            ),
+            (
+                3,
+                "0x15c5bbf1c4f643d14fdf940c3d79358b2e9fdaa2d9aee6c42555fe0a9f5ef653",
+            ),
+            (
+                5,
+                "0x2bccb67e442fa0985c8b374ae03ab4ec838eb46c9a945656be2938b6293d5bf8",
+            ),
+        ]);
+    }
+}
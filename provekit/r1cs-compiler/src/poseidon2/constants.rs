@@ -6,7 +6,7 @@ use {ark_ff::PrimeField, provekit_common::FieldElement as F};
 
 /// Parses a big-endian hex string into a field element `F`.
 #[inline]
-fn fe(hex: &str) -> F {
+pub(super) fn fe(hex: &str) -> F {
     fe_hex(hex)
 }
 
@@ -1,4 +1,6 @@
+mod builder;
 pub(crate) mod constants;
 mod permutation;
 
+pub use builder::{Poseidon2Builder, Poseidon2Params};
 pub(crate) use permutation::add_poseidon2_permutation;
@@ -611,93 +611,106 @@ fn internal_mds_forms_t_from_forms<FDiag: Fn(u32) -> Vec<FieldElement>>(
     }
 }
 
-/// Poseidon2 permutation: applies ext MDS -> 4 full -> pr partial -> 4 full;
-/// outputs constrained to final state.
-pub(crate) fn add_poseidon2_permutation(
+/// Poseidon2 permutation: applies ext MDS -> 4 full -> pr partial -> 4 full
+/// rounds and returns the final state as linear forms.
+fn permutation_forms(
     r1cs_compiler: &mut NoirToR1CSCompiler,
-    ops: Vec<(u32, Vec<ConstantOrR1CSWitness>, Vec<usize>)>,
-) {
-    for (t, inputs, outputs) in ops {
-        let t_usize = t as usize;
-        assert!(matches!(t, 2 | 3 | 4 | 8 | 12 | 16));
-        assert_eq!(inputs.len(), t_usize);
-        assert_eq!(outputs.len(), t_usize);
+    t: u32,
+    inputs: Vec<ConstantOrR1CSWitness>,
+) -> Vec<LinearForm> {
+    let t_usize = t as usize;
+    assert!(matches!(t, 2 | 3 | 4 | 8 | 12 | 16));
+    assert_eq!(inputs.len(), t_usize);
+
+    let pr = amount_partial_rounds(t);
+    let rc_full1 = load_rc_full1(t);
+    let rc_part = load_rc_partial(t);
+    let rc_full2 = load_rc_full2(t);
+    let load_diag_fn = |tt: u32| load_diag(tt);
+
+    // Current state as concrete witnesses and as symbolic linear forms.
+    let mut state_witnesses: Vec<ConstantOrR1CSWitness> = inputs;
+    let mut state_forms = mds_t_block_forms(&state_witnesses);
+
+    // Re-usable buffers to reduce allocations across rounds.
+    let mut temp_sbox_out: Vec<ConstantOrR1CSWitness> = Vec::with_capacity(t_usize);
+    let mut scratch_sum_terms: Vec<SumTerm> = Vec::with_capacity(t_usize);
+    let mut scratch_constraint_terms: Vec<(FieldElement, usize)> = Vec::with_capacity(t_usize);
+
+    // Poseidon2 round schedule: 4 full rounds -> pr partial rounds -> 4 full
+    // rounds. Matches the 4 + Rp + 4 design from the Poseidon2 spec and reference templates ("https://github.com/TaceoLabs/nullifier-oracle-service/tree/main/circom/poseidon2/poseidon2_constants.circom").
+
+    // First 4 full rounds.
+    for r in 0..4 {
+        apply_sbox_to_linear_forms_out(
+            r1cs_compiler,
+            state_forms.as_mut_slice(),
+            &rc_full1[r],
+            &mut temp_sbox_out,
+            &mut scratch_sum_terms,
+            &mut scratch_constraint_terms,
+        );
+
+        // Update state witnesses and recompute linear forms via external MDS.
+        std::mem::swap(&mut state_witnesses, &mut temp_sbox_out);
+        state_forms = mds_t_block_forms(&state_witnesses);
+    }
 
-        let pr = amount_partial_rounds(t);
-        let rc_full1 = load_rc_full1(t);
-        let rc_part = load_rc_partial(t);
-        let rc_full2 = load_rc_full2(t);
-        let load_diag_fn = |tt: u32| load_diag(tt);
-
-        // Current state as concrete witnesses and as symbolic linear forms.
-        let mut state_witnesses: Vec<ConstantOrR1CSWitness> = inputs.clone();
-        let mut state_forms = mds_t_block_forms(&state_witnesses);
-
-        // Re-usable buffers to reduce allocations across rounds.
-        let mut temp_sbox_out: Vec<ConstantOrR1CSWitness> = Vec::with_capacity(t_usize);
-        let mut scratch_sum_terms: Vec<SumTerm> = Vec::with_capacity(t_usize);
-        let mut scratch_constraint_terms: Vec<(FieldElement, usize)> = Vec::with_capacity(t_usize);
-
-        // Poseidon2 round schedule: 4 full rounds -> pr partial rounds -> 4 full
-        // rounds. Matches the 4 + Rp + 4 design from the Poseidon2 spec and reference templates ("https://github.com/TaceoLabs/nullifier-oracle-service/tree/main/circom/poseidon2/poseidon2_constants.circom").
-
-        // First 4 full rounds.
-        for r in 0..4 {
-            apply_sbox_to_linear_forms_out(
-                r1cs_compiler,
-                state_forms.as_mut_slice(),
-                &rc_full1[r],
-                &mut temp_sbox_out,
-                &mut scratch_sum_terms,
-                &mut scratch_constraint_terms,
-            );
+    let mut forms_for_next_round: Vec<LinearForm> = Vec::with_capacity(t_usize);
 
-            // Update state witnesses and recompute linear forms via external MDS.
-            std::mem::swap(&mut state_witnesses, &mut temp_sbox_out);
-            state_forms = mds_t_block_forms(&state_witnesses);
-        }
+    // Partial rounds: non-linear on lane 0, linear on the rest.
+    for r in 0..pr as usize {
+        forms_for_next_round.clear();
 
-        let mut forms_for_next_round: Vec<LinearForm> = Vec::with_capacity(t_usize);
+        // Apply RC + S-box only to the first limb.
+        let mut form_0 = state_forms[0].clone();
+        form_0.add_constant_mut(rc_part[r]);
 
-        // Partial rounds: non-linear on lane 0, linear on the rest.
-        for r in 0..pr as usize {
-            forms_for_next_round.clear();
+        let sboxed_witness_0 = linear_form_pow5(
+            r1cs_compiler,
+            &form_0,
+            &mut scratch_sum_terms,
+            &mut scratch_constraint_terms,
+        );
 
-            // Apply RC + S-box only to the first limb.
-            let mut form_0 = state_forms[0].clone();
-            form_0.add_constant_mut(rc_part[r]);
+        forms_for_next_round.push(LinearForm::from_witness(sboxed_witness_0));
 
-            let sboxed_witness_0 = linear_form_pow5(
-                r1cs_compiler,
-                &form_0,
-                &mut scratch_sum_terms,
-                &mut scratch_constraint_terms,
-            );
+        // Remaining limbs pass through unchanged for this step.
+        for i in 1..t_usize {
+            forms_for_next_round.push(state_forms[i].clone());
+        }
 
-            forms_for_next_round.push(LinearForm::from_witness(sboxed_witness_0));
+        // Apply internal MDS to mix all limbs.
+        state_forms = internal_mds_forms_t_from_forms(t, &forms_for_next_round, &load_diag_fn);
+    }
 
-            // Remaining limbs pass through unchanged for this step.
-            for i in 1..t_usize {
-                forms_for_next_round.push(state_forms[i].clone());
-            }
+    // Final 4 full rounds.
+    for r in 0..4 {
+        apply_sbox_to_linear_forms_out(
+            r1cs_compiler,
+            state_forms.as_mut_slice(),
+            &rc_full2[r],
+            &mut temp_sbox_out,
+            &mut scratch_sum_terms,
+            &mut scratch_constraint_terms,
+        );
+        std::mem::swap(&mut state_witnesses, &mut temp_sbox_out);
+        state_forms = mds_t_block_forms(&state_witnesses);
+    }
 
-            // Apply internal MDS to mix all limbs.
-            state_forms = internal_mds_forms_t_from_forms(t, &forms_for_next_round, &load_diag_fn);
-        }
+    state_forms
+}
 
-        // Final 4 full rounds.
-        for r in 0..4 {
-            apply_sbox_to_linear_forms_out(
-                r1cs_compiler,
-                state_forms.as_mut_slice(),
-                &rc_full2[r],
-                &mut temp_sbox_out,
-                &mut scratch_sum_terms,
-                &mut scratch_constraint_terms,
-            );
-            std::mem::swap(&mut state_witnesses, &mut temp_sbox_out);
-            state_forms = mds_t_block_forms(&state_witnesses);
-        }
+/// Poseidon2 permutation: outputs constrained to final state.
+pub(crate) fn add_poseidon2_permutation(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    ops: Vec<(u32, Vec<ConstantOrR1CSWitness>, Vec<usize>)>,
+) {
+    for (t, inputs, outputs) in ops {
+        let t_usize = t as usize;
+        assert_eq!(outputs.len(), t_usize);
+
+        let state_forms = permutation_forms(r1cs_compiler, t, inputs);
 
         // Constrain final linear forms to equal the requested output witnesses.
         // Output witnesses already have WitnessBuilder::Acir entries (from
@@ -742,3 +755,96 @@ pub(crate) fn add_poseidon2_permutation(
         }
     }
 }
+
+/// Returns `form` as a single witness, adding a `Sum` witness and its
+/// constraint unless the form already is a constant or a lone witness.
+fn materialize_form(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    form: &LinearForm,
+) -> ConstantOrR1CSWitness {
+    match form.terms.as_slice() {
+        [] => return ConstantOrR1CSWitness::Constant(form.get_constant()),
+        &[(coeff, w)] if coeff.is_one() && form.get_constant().is_zero() => {
+            return ConstantOrR1CSWitness::Witness(w)
+        }
+        _ => {}
+    }
+
+    let mut recipe: Vec<(FieldElement, usize)> = form.terms.to_vec();
+    if !form.get_constant().is_zero() {
+        recipe.push((form.get_constant(), r1cs_compiler.witness_one()));
+    }
+
+    // output = Σ coeff_i * witness_i (+ const)
+    let output = r1cs_compiler.add_witness_builder(WitnessBuilder::Sum(
+        r1cs_compiler.num_witnesses(),
+        recipe.iter().map(|&(c, w)| SumTerm(Some(c), w)).collect(),
+    ));
+    r1cs_compiler.r1cs.add_constraint(
+        &recipe,
+        &[(FieldElement::ONE, r1cs_compiler.witness_one())],
+        &[(FieldElement::ONE, output)],
+    );
+    ConstantOrR1CSWitness::Witness(output)
+}
+
+/// Materializes the first lane of a final permutation state as a witness.
+fn first_lane_witness(r1cs_compiler: &mut NoirToR1CSCompiler, state_forms: &[LinearForm]) -> usize {
+    let ConstantOrR1CSWitness::Witness(output) = materialize_form(r1cs_compiler, &state_forms[0])
+    else {
+        unreachable!("the permuted state depends on the S-box witnesses")
+    };
+    output
+}
+
+/// Poseidon2 permutation over the `inputs` witnesses: the first lane of the
+/// final state is materialized as a new witness, whose index is returned.
+pub(crate) fn add_poseidon2_hash(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    t: u32,
+    inputs: &[usize],
+) -> usize {
+    let inputs = inputs
+        .iter()
+        .map(|&w| ConstantOrR1CSWitness::Witness(w))
+        .collect();
+    let state_forms = permutation_forms(r1cs_compiler, t, inputs);
+    first_lane_witness(r1cs_compiler, &state_forms)
+}
+
+/// Poseidon2 sponge over any number of `inputs` witnesses, following Noir's
+/// `poseidon2` hash at width `t`: the first `t - 1` lanes are the rate, the
+/// last lane is the capacity and starts at `inputs.len() * 2^64`. The last
+/// block is zero-padded, and the first lane of the squeezed state is
+/// materialized as a new witness, whose index is returned.
+pub(crate) fn add_poseidon2_sponge(
+    r1cs_compiler: &mut NoirToR1CSCompiler,
+    t: u32,
+    inputs: &[usize],
+) -> usize {
+    let rate = t as usize - 1;
+    let mut state_forms = vec![LinearForm::zero(); t as usize];
+    state_forms[rate].add_constant_mut(
+        FieldElement::from(inputs.len() as u64) * FieldElement::from(1u128 << 64),
+    );
+
+    // An empty input still squeezes a permutation of the initial state.
+    let blocks: Vec<&[usize]> = if inputs.is_empty() {
+        vec![&[]]
+    } else {
+        inputs.chunks(rate).collect()
+    };
+    for block in blocks {
+        // Absorb into the rate lanes; a short block leaves the remaining lanes
+        // unchanged, which pads it with zeros.
+        for (form, &w) in state_forms.iter_mut().zip(block) {
+            form.add_term_mut(FieldElement::ONE, w);
+        }
+        let lanes = state_forms
+            .iter()
+            .map(|form| materialize_form(r1cs_compiler, form))
+            .collect();
+        state_forms = permutation_forms(r1cs_compiler, t, lanes);
+    }
+    first_lane_witness(r1cs_compiler, &state_forms)
+}
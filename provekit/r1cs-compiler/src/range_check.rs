@@ -15,37 +15,39 @@ use {
 };
 
 const NUM_WITNESS_THRESHOLD_FOR_LOOKUP_TABLE: usize = 5;
+/// Default width of the digits of range check decompositions
 pub const NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP: u32 = 8;
+/// Widest digits allowed, bounding the size of the lookup tables
+pub const MAX_BITS_FOR_DIGITAL_DECOMP: u32 = 16;
 
 /// Add witnesses and constraints that ensure that the values of the witness
-/// belong to a range 0..2^k (for some k). If k is larger than
-/// `NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP`, then a digital decomposition is
-/// performed: witnesses are allocated for the digits of the decomposition, a
-/// constraint is added that enforces the correctness of the digital
-/// decomposition, and then the digits themselves are range checked.
+/// belong to a range 0..2^k (for some k). If k is larger than `digit_bits`,
+/// then a digital decomposition is performed: witnesses are allocated for the
+/// digits of the decomposition, a constraint is added that enforces the
+/// correctness of the digital decomposition, and then the digits themselves
+/// are range checked.
 /// `range_checks` is a map from the number of bits k to the vector of witness
 /// indices that are to be constrained within the range [0..2^k].
 pub(crate) fn add_range_checks(
     r1cs: &mut NoirToR1CSCompiler,
     range_checks: BTreeMap<u32, Vec<usize>>,
+    digit_bits: u32,
 ) {
     if range_checks.is_empty() {
         return;
     }
 
     // Do a pass through everything that needs to be range checked,
-    // decomposing each value into digits that are at most
-    // [NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP] and creating a map
-    // `atomic_range_blocks` of each `num_bits` from 1 to the
-    // NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP (inclusive) to the vec of witness
-    // indices that are constrained to that range.
+    // decomposing each value into digits that are at most `digit_bits` and
+    // creating a map `atomic_range_blocks` of each `num_bits` from 1 to
+    // `digit_bits` (inclusive) to the vec of witness indices that are
+    // constrained to that range.
 
     // Mapping the log of the range size k to the vector of witness indices that
     // are to be constrained within the range [0..2^k].
     // The witnesses of all small range op codes are added to this map, along with
     // witnesses of digits for digital decompositions of larger range checks.
-    let mut atomic_range_checks: Vec<Vec<Vec<usize>>> =
-        vec![vec![vec![]]; NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP as usize + 1];
+    let mut atomic_range_checks: Vec<Vec<Vec<usize>>> = vec![vec![vec![]]; digit_bits as usize + 1];
 
     range_checks
         .into_iter()
@@ -59,11 +61,10 @@ pub(crate) fn add_range_checks(
                     .collect()
             };
 
-            if num_bits > NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP {
-                let num_big_digits = num_bits / NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP;
-                let logbase_of_remainder_digit = num_bits % NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP;
-                let mut log_bases =
-                    vec![NUM_BITS_THRESHOLD_FOR_DIGITAL_DECOMP as usize; num_big_digits as usize];
+            if num_bits > digit_bits {
+                let num_big_digits = num_bits / digit_bits;
+                let logbase_of_remainder_digit = num_bits % digit_bits;
+                let mut log_bases = vec![digit_bits as usize; num_big_digits as usize];
                 if logbase_of_remainder_digit != 0 {
                     log_bases.push(logbase_of_remainder_digit as usize);
                 }
@@ -1,7 +1,7 @@
 use {
     crate::{
         binops::{add_byte_binop, BinOp},
-        noir_to_r1cs::NoirToR1CSCompiler,
+        noir_to_r1cs::{CompileError, NoirToR1CSCompiler},
         uints::{U32, U8},
     },
     ark_ff::Field,
@@ -270,9 +270,9 @@ pub(crate) fn shr_u32(
 /// # Warning
 ///
 /// This function is **only sound when used with the fused constraints** in
-/// rotr_u32 and shr_u32. If reused elsewhere without equivalent constraints,
-/// an explicit range check on `hi` would be required.
-fn partition_byte_witnesses(
+/// rotr_u32, shr_u32 and `keccak::rotl_u64`. If reused elsewhere without
+/// equivalent constraints, an explicit range check on `hi` would be required.
+pub(crate) fn partition_byte_witnesses(
     r1cs_compiler: &mut NoirToR1CSCompiler,
     range_checks: &mut BTreeMap<u32, Vec<usize>>,
     byte: U8,
@@ -655,23 +655,17 @@ pub(crate) fn add_sha256_compression(
         Vec<ConstantOrR1CSWitness>,
         Vec<usize>,
     )>,
-) {
+) -> Result<(), CompileError> {
     for (inputs, hash_values, outputs) in inputs_and_outputs {
-        assert_eq!(
-            inputs.len(),
-            16,
-            "SHA256 requires exactly 16 input u32 words"
-        );
-        assert_eq!(
-            hash_values.len(),
-            8,
-            "SHA256 requires exactly 8 initial hash values"
-        );
-        assert_eq!(
-            outputs.len(),
-            8,
-            "SHA256 produces exactly 8 output u32 words"
-        );
+        if inputs.len() != 16 || hash_values.len() != 8 || outputs.len() != 8 {
+            return Err(CompileError::InvalidCircuit(format!(
+                "Sha256Compression with {} input words, {} hash values and {} output words, \
+                 expected 16, 8 and 8",
+                inputs.len(),
+                hash_values.len(),
+                outputs.len()
+            )));
+        }
 
         // Convert inputs to U32 (unpack from 32-bit witnesses to 4 bytes each)
         let input_u32s: [U32; 16] = inputs
@@ -679,13 +673,13 @@ pub(crate) fn add_sha256_compression(
             .map(|input| match input {
                 ConstantOrR1CSWitness::Witness(idx) => {
                     // Unpack to bytes (adds 4× 8-bit range checks internally)
-                    U32::unpack_u32(r1cs_compiler, range_checks, *idx)
-                }
-                ConstantOrR1CSWitness::Constant(_) => {
-                    panic!("Input constants not yet supported")
+                    Ok(U32::unpack_u32(r1cs_compiler, range_checks, *idx))
                 }
+                ConstantOrR1CSWitness::Constant(_) => Err(CompileError::UnsupportedInput(
+                    "Sha256Compression input constant".to_string(),
+                )),
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, _>>()?
             .try_into()
             .unwrap();
 
@@ -695,13 +689,13 @@ pub(crate) fn add_sha256_compression(
             .map(|hash_val| match hash_val {
                 ConstantOrR1CSWitness::Witness(idx) => {
                     // Unpack to bytes (adds 4× 8-bit range checks internally)
-                    U32::unpack_u32(r1cs_compiler, range_checks, *idx)
-                }
-                ConstantOrR1CSWitness::Constant(_) => {
-                    panic!("Hash value constants not yet supported")
+                    Ok(U32::unpack_u32(r1cs_compiler, range_checks, *idx))
                 }
+                ConstantOrR1CSWitness::Constant(_) => Err(CompileError::UnsupportedInput(
+                    "Sha256Compression hash value constant".to_string(),
+                )),
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, _>>()?
             .try_into()
             .unwrap();
 
@@ -755,4 +749,31 @@ pub(crate) fn add_sha256_compression(
             );
         }
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_counts() {
+        let word = ConstantOrR1CSWitness::Witness(0);
+        for (inputs, hash_values, outputs) in [(15, 8, 8), (16, 7, 8), (16, 8, 9)] {
+            let result = add_sha256_compression(
+                &mut NoirToR1CSCompiler::new(),
+                &mut Vec::new(),
+                &mut Vec::new(),
+                &mut BTreeMap::new(),
+                vec![(vec![word; inputs], vec![word; hash_values], vec![
+                    0;
+                    outputs
+                ])],
+            );
+            assert!(
+                matches!(result, Err(CompileError::InvalidCircuit(_))),
+                "{result:?}"
+            );
+        }
+    }
 }
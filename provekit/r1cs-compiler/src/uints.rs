@@ -161,3 +161,61 @@ impl U32 {
         Self { bytes }
     }
 }
+
+/// A 64-bit word represented as eight little-endian bytes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct U64 {
+    pub(crate) bytes: [U8; 8],
+}
+
+impl U64 {
+    /// Constructs a U64 from eight byte witnesses.
+    pub(crate) fn new(bytes: [U8; 8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Decomposes a field element witness into eight bytes.
+    /// Uses digital decomposition with base 2^8 and enforces
+    /// byte-range constraints on each resulting digit.
+    pub(crate) fn unpack_u64(
+        r1cs_compiler: &mut NoirToR1CSCompiler,
+        range_checks: &mut BTreeMap<u32, Vec<usize>>,
+        word: usize,
+    ) -> U64 {
+        let log_bases = vec![8usize; 8];
+        let dd = add_digital_decomposition(r1cs_compiler, log_bases, vec![word]);
+
+        let bytes = std::array::from_fn(|i| {
+            let idx = dd.get_digit_witness_index(i, 0);
+            range_checks.entry(8).or_default().push(idx);
+            U8::new(idx, true)
+        });
+
+        U64::new(bytes)
+    }
+
+    /// Constrains the packed value b0 + 256*b1 + ... + 256^7*b7 to equal the
+    /// given witness, without allocating an intermediate packed witness.
+    pub(crate) fn constrain_packed_eq(&self, r1cs_compiler: &mut NoirToR1CSCompiler, word: usize) {
+        let mut terms = vec![];
+        let mut multiplier = FieldElement::ONE;
+        for byte in self.bytes {
+            terms.push((multiplier, byte.idx));
+            multiplier *= FieldElement::from(256u64);
+        }
+        r1cs_compiler.r1cs.add_constraint(
+            &terms,
+            &[(FieldElement::ONE, r1cs_compiler.witness_one())],
+            &[(FieldElement::ONE, word)],
+        );
+    }
+
+    /// Constructs a constant 64-bit word.
+    /// Each byte is created independently and is trivially in range.
+    pub(crate) fn from_const(r1cs_compiler: &mut NoirToR1CSCompiler, value: u64) -> Self {
+        let bytes = value
+            .to_le_bytes()
+            .map(|byte| U8::from_const(r1cs_compiler, byte));
+        Self { bytes }
+    }
+}
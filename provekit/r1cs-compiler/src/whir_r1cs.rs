@@ -1,5 +1,7 @@
 use {
-    provekit_common::{utils::next_power_of_two, WhirConfig, WhirR1CSScheme, R1CS},
+    anyhow::{ensure, Result},
+    ark_ff::FftField,
+    provekit_common::{utils::next_power_of_two, FieldElement, WhirConfig, WhirR1CSScheme, R1CS},
     std::sync::Arc,
     whir::{
         ntt::RSDefault,
@@ -16,15 +18,51 @@ const MIN_WHIR_NUM_VARIABLES: usize = 12;
 // bound for m_0).
 const MIN_SUMCHECK_NUM_VARIABLES: usize = 1;
 
-pub trait WhirR1CSSchemeBuilder {
+/// Security and performance parameters for the WHIR commitments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WhirR1CSParams {
+    /// Target security level in bits.
+    pub security_level:        usize,
+    pub folding_factor:        FoldingFactor,
+    pub soundness_type:        SoundnessType,
+    /// Log2 of the inverse Reed-Solomon rate of the first round.
+    pub starting_log_inv_rate: usize,
+}
+
+impl Default for WhirR1CSParams {
+    fn default() -> Self {
+        Self {
+            security_level:        128,
+            folding_factor:        FoldingFactor::Constant(4),
+            soundness_type:        SoundnessType::ConjectureList,
+            starting_log_inv_rate: 1,
+        }
+    }
+}
+
+pub trait WhirR1CSSchemeBuilder: Sized {
     fn new_for_r1cs(
         r1cs: &R1CS,
         w1_size: usize,
         num_challenges: usize,
-        has_public_inputs: bool,
+        num_public_inputs: usize,
     ) -> Self;
 
+    fn new_for_r1cs_with_params(
+        r1cs: &R1CS,
+        w1_size: usize,
+        num_challenges: usize,
+        num_public_inputs: usize,
+        params: &WhirR1CSParams,
+    ) -> Result<Self>;
+
     fn new_whir_config_for_size(num_variables: usize, batch_size: usize) -> WhirConfig;
+
+    fn new_whir_config_for_size_with_params(
+        num_variables: usize,
+        batch_size: usize,
+        params: &WhirR1CSParams,
+    ) -> Result<WhirConfig>;
 }
 
 impl WhirR1CSSchemeBuilder for WhirR1CSScheme {
@@ -32,8 +70,25 @@ impl WhirR1CSSchemeBuilder for WhirR1CSScheme {
         r1cs: &R1CS,
         w1_size: usize,
         num_challenges: usize,
-        has_public_inputs: bool,
+        num_public_inputs: usize,
     ) -> Self {
+        Self::new_for_r1cs_with_params(
+            r1cs,
+            w1_size,
+            num_challenges,
+            num_public_inputs,
+            &WhirR1CSParams::default(),
+        )
+        .expect("Default WHIR parameters are valid for all sizes")
+    }
+
+    fn new_for_r1cs_with_params(
+        r1cs: &R1CS,
+        w1_size: usize,
+        num_challenges: usize,
+        num_public_inputs: usize,
+        params: &WhirR1CSParams,
+    ) -> Result<Self> {
         let total_witnesses = r1cs.num_witnesses();
         assert!(
             w1_size <= total_witnesses,
@@ -48,41 +103,99 @@ impl WhirR1CSSchemeBuilder for WhirR1CSScheme {
         let m_raw = m1_raw.max(m2_raw).max(MIN_WHIR_NUM_VARIABLES);
         let m_0 = m0_raw.max(MIN_SUMCHECK_NUM_VARIABLES);
 
-        Self {
+        Ok(Self {
             m: m_raw + 1,
             w1_size,
             m_0,
             a_num_terms: next_power_of_two(r1cs.a().iter().count()),
             num_challenges,
-            whir_witness: Self::new_whir_config_for_size(m_raw + 1, 2),
-            whir_for_hiding_spartan: Self::new_whir_config_for_size(
+            whir_witness: Self::new_whir_config_for_size_with_params(m_raw + 1, 2, params)?,
+            whir_for_hiding_spartan: Self::new_whir_config_for_size_with_params(
                 next_power_of_two(4 * m_0) + 1,
                 2,
-            ),
-            has_public_inputs,
-        }
+                params,
+            )?,
+            num_public_inputs,
+        })
     }
 
     fn new_whir_config_for_size(num_variables: usize, batch_size: usize) -> WhirConfig {
+        Self::new_whir_config_for_size_with_params(
+            num_variables,
+            batch_size,
+            &WhirR1CSParams::default(),
+        )
+        .expect("Default WHIR parameters are valid for all sizes")
+    }
+
+    fn new_whir_config_for_size_with_params(
+        num_variables: usize,
+        batch_size: usize,
+        params: &WhirR1CSParams,
+    ) -> Result<WhirConfig> {
         let nv = num_variables.max(MIN_WHIR_NUM_VARIABLES);
+        let initial_folding = params.folding_factor.at_round(0);
+        ensure!(
+            (1..=nv).contains(&initial_folding),
+            "Folding factor {initial_folding} is incompatible with {nv} variables"
+        );
+        ensure!(
+            params.starting_log_inv_rate >= 1
+                && nv + params.starting_log_inv_rate <= FieldElement::TWO_ADICITY as usize,
+            "Rate 2^-{} with {nv} variables exceeds the NTT domain of the field",
+            params.starting_log_inv_rate
+        );
 
         let mv_params = MultivariateParameters::new(nv);
         let whir_params = ProtocolParameters {
             initial_statement: true,
-            security_level: 128,
-            pow_bits: default_max_pow(nv, 1),
-            folding_factor: FoldingFactor::Constant(4),
+            security_level: params.security_level,
+            pow_bits: default_max_pow(nv, params.starting_log_inv_rate),
+            folding_factor: params.folding_factor,
             leaf_hash_params: (),
             two_to_one_params: (),
-            soundness_type: SoundnessType::ConjectureList,
+            soundness_type: params.soundness_type,
             _pow_parameters: Default::default(),
-            starting_log_inv_rate: 1,
+            starting_log_inv_rate: params.starting_log_inv_rate,
             batch_size,
             deduplication_strategy: DeduplicationStrategy::Disabled,
             merkle_proof_strategy: MerkleProofStrategy::Uncompressed,
         };
         let reed_solomon = Arc::new(RSDefault);
         let basefield_reed_solomon = reed_solomon.clone();
-        WhirConfig::new(reed_solomon, basefield_reed_solomon, mv_params, whir_params)
+        Ok(WhirConfig::new(
+            reed_solomon,
+            basefield_reed_solomon,
+            mv_params,
+            whir_params,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_params_match_new_whir_config_for_size() {
+        let config =
+            WhirR1CSScheme::new_whir_config_for_size_with_params(14, 2, &WhirR1CSParams::default())
+                .unwrap();
+        assert_eq!(config, WhirR1CSScheme::new_whir_config_for_size(14, 2));
+    }
+
+    #[test]
+    fn rejects_incompatible_params() {
+        let params = WhirR1CSParams {
+            folding_factor: FoldingFactor::Constant(MIN_WHIR_NUM_VARIABLES + 1),
+            ..WhirR1CSParams::default()
+        };
+        assert!(WhirR1CSScheme::new_whir_config_for_size_with_params(0, 2, &params).is_err());
+
+        let params = WhirR1CSParams {
+            starting_log_inv_rate: 20,
+            ..WhirR1CSParams::default()
+        };
+        assert!(WhirR1CSScheme::new_whir_config_for_size_with_params(20, 2, &params).is_err());
     }
 }
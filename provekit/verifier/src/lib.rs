@@ -2,22 +2,177 @@ mod whir_r1cs;
 
 use {
     crate::whir_r1cs::WhirR1CSVerifier,
-    anyhow::Result,
-    provekit_common::{NoirProof, Verifier},
+    anyhow::{ensure, Context, Result},
+    provekit_common::{FieldElement, NoirProof, PublicInputs, Verifier, WhirR1CSScheme},
+    std::fmt,
     tracing::instrument,
 };
 
 pub trait Verify {
+    /// Verifies `proof`. The verifier is not consumed, so one instance can
+    /// verify any number of proofs.
     fn verify(&mut self, proof: &NoirProof) -> Result<()>;
+
+    /// Verifies every proof of `proofs` against the same scheme, computing
+    /// the IO pattern only once.
+    ///
+    /// All proofs are checked, and the error is a [`BatchVerificationError`]
+    /// listing the ones that failed.
+    fn verify_batch(&mut self, proofs: &[NoirProof]) -> Result<()>;
+
+    /// Verifies `proof` like [`Verify::verify`], then checks that it was made
+    /// for the `expected` public inputs rather than trusting the ones it
+    /// carries.
+    ///
+    /// A valid proof for other public inputs fails with a
+    /// [`PublicInputsMismatch`].
+    fn verify_against(&mut self, proof: &NoirProof, expected: &PublicInputs) -> Result<()> {
+        self.verify(proof)?;
+        if proof.public_inputs != *expected {
+            return Err(PublicInputsMismatch {
+                expected: expected.clone(),
+                actual:   proof.public_inputs.clone(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Error of [`Verify::verify_batch`] when some proofs of the batch fail to
+/// verify.
+#[derive(Debug)]
+pub struct BatchVerificationError {
+    /// Number of proofs in the batch.
+    pub batch_size: usize,
+    /// Index in the batch and verification error of each failed proof.
+    pub failures:   Vec<(usize, anyhow::Error)>,
+}
+
+impl BatchVerificationError {
+    /// Indices in the batch of the proofs that failed to verify.
+    pub fn failed_indices(&self) -> Vec<usize> {
+        self.failures.iter().map(|(index, _)| *index).collect()
+    }
+}
+
+impl fmt::Display for BatchVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} proofs failed to verify",
+            self.failures.len(),
+            self.batch_size
+        )?;
+        for (index, error) in &self.failures {
+            write!(f, "\n  proof {index}: {error:#}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BatchVerificationError {}
+
+/// Error of [`Verify::verify_against`] when the proof is valid but was made
+/// for other public inputs.
+#[derive(Debug)]
+pub struct PublicInputsMismatch {
+    /// Public inputs the caller expected.
+    pub expected: PublicInputs,
+    /// Public inputs of the proof.
+    pub actual:   PublicInputs,
+}
+
+impl fmt::Display for PublicInputsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mismatch = self
+            .expected
+            .0
+            .iter()
+            .zip(&self.actual.0)
+            .position(|(expected, actual)| expected != actual);
+        match mismatch {
+            Some(index) => write!(
+                f,
+                "Proof public input {index} is {}, expected {}",
+                self.actual.0[index], self.expected.0[index]
+            ),
+            None => write!(
+                f,
+                "Proof has {} public inputs, expected {}",
+                self.actual.len(),
+                self.expected.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PublicInputsMismatch {}
+
+/// Cheap pre-filter checking that `proof` carries public inputs hashing to
+/// `expected_hash` (with [`PublicInputs::hash`]).
+///
+/// This is not a cryptographic check: the proof itself is not verified, so
+/// anyone can craft a proof passing it. Use it only to discard proofs early,
+/// and run [`Verify::verify`] on the ones it accepts.
+pub fn precheck_public_inputs(proof: &NoirProof, expected_hash: FieldElement) -> bool {
+    proof.public_inputs.hash() == expected_hash
+}
+
+fn check_public_input_count(scheme: &WhirR1CSScheme, proof: &NoirProof) -> Result<()> {
+    ensure!(
+        proof.public_inputs.len() == scheme.num_public_inputs,
+        "Proof has {} public inputs, but the circuit expects {}",
+        proof.public_inputs.len(),
+        scheme.num_public_inputs
+    );
+    Ok(())
 }
 
 impl Verify for Verifier {
     #[instrument(skip_all)]
     fn verify(&mut self, proof: &NoirProof) -> Result<()> {
-        self.whir_for_witness
-            .take()
-            .unwrap()
-            .verify(&proof.whir_r1cs_proof, &proof.public_inputs)?;
+        let whir_for_witness = self
+            .whir_for_witness
+            .as_ref()
+            .context("Verifier has no WHIR scheme")?;
+        check_public_input_count(whir_for_witness, proof)?;
+        whir_for_witness.verify(&proof.whir_r1cs_proof, &proof.public_inputs)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(proofs = proofs.len()))]
+    fn verify_batch(&mut self, proofs: &[NoirProof]) -> Result<()> {
+        let whir_for_witness = self
+            .whir_for_witness
+            .as_ref()
+            .context("Verifier has no WHIR scheme")?;
+        let io = whir_for_witness.create_io_pattern();
+
+        let failures: Vec<_> = proofs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, proof)| {
+                check_public_input_count(whir_for_witness, proof)
+                    .and_then(|()| {
+                        whir_for_witness.verify_with_io_pattern(
+                            &io,
+                            &proof.whir_r1cs_proof,
+                            &proof.public_inputs,
+                        )
+                    })
+                    .err()
+                    .map(|error| (index, error))
+            })
+            .collect();
+        if !failures.is_empty() {
+            return Err(BatchVerificationError {
+                batch_size: proofs.len(),
+                failures,
+            }
+            .into());
+        }
 
         Ok(())
     }
@@ -4,7 +4,7 @@ use {
     provekit_common::{
         skyscraper::SkyscraperSponge,
         utils::sumcheck::{calculate_eq, eval_cubic_poly},
-        FieldElement, PublicInputs, WhirConfig, WhirR1CSProof, WhirR1CSScheme,
+        FieldElement, IOPattern, PublicInputs, WhirConfig, WhirR1CSProof, WhirR1CSScheme,
     },
     spongefish::{
         codecs::arkworks_algebra::{FieldToUnitDeserialize, UnitToField},
@@ -30,13 +30,30 @@ pub struct DataFromSumcheckVerifier {
 
 pub trait WhirR1CSVerifier {
     fn verify(&self, proof: &WhirR1CSProof, public_inputs: &PublicInputs) -> Result<()>;
+
+    /// Same as [`WhirR1CSVerifier::verify`] with the IO pattern of the scheme
+    /// computed beforehand, so that it can be shared between proofs.
+    fn verify_with_io_pattern(
+        &self,
+        io: &IOPattern,
+        proof: &WhirR1CSProof,
+        public_inputs: &PublicInputs,
+    ) -> Result<()>;
 }
 
 impl WhirR1CSVerifier for WhirR1CSScheme {
+    fn verify(&self, proof: &WhirR1CSProof, public_inputs: &PublicInputs) -> Result<()> {
+        self.verify_with_io_pattern(&self.create_io_pattern(), proof, public_inputs)
+    }
+
     #[instrument(skip_all)]
     #[allow(unused)]
-    fn verify(&self, proof: &WhirR1CSProof, public_inputs: &PublicInputs) -> Result<()> {
-        let io = self.create_io_pattern();
+    fn verify_with_io_pattern(
+        &self,
+        io: &IOPattern,
+        proof: &WhirR1CSProof,
+        public_inputs: &PublicInputs,
+    ) -> Result<()> {
         let mut arthur = io.to_verifier_state(&proof.transcript);
 
         let commitment_reader = CommitmentReader::new(&self.whir_witness);
@@ -2,3 +2,39 @@ pub mod constants;
 pub mod load_store;
 pub mod scalar;
 pub mod simd;
+
+#[cfg(test)]
+mod test {
+    use {
+        crate::{scalar, simd},
+        hla::{
+            builder::{assemble, Interleaving, Setup},
+            codegen::generate_rust_includable_asm,
+            target::Target,
+        },
+    };
+
+    fn assemble_x86_64(setup: Setup) -> String {
+        assemble(
+            Interleaving::single(setup),
+            Target::X86_64,
+            |inputs, outputs, instructions| {
+                generate_rust_includable_asm(Target::X86_64, inputs, outputs, instructions)
+            },
+        )
+    }
+
+    #[test]
+    fn simd_lowers_to_x86_64() {
+        assemble_x86_64(simd::setup_single_step);
+        assemble_x86_64(simd::setup_square_single_step);
+    }
+
+    // The scalar kernels need more general purpose registers than x86_64
+    // leaves for allocation and are only generated for aarch64.
+    #[test]
+    #[should_panic(expected = "All register are in use")]
+    fn scalar_does_not_fit_x86_64() {
+        assemble_x86_64(scalar::setup_single_step);
+    }
+}
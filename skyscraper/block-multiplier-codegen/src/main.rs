@@ -1,15 +1,24 @@
 use {
     block_multiplier_codegen::{scalar, simd},
-    hla::builder::{build_includable, Interleaving},
+    hla::{
+        builder::{build_includable, Interleaving},
+        target::Target,
+    },
 };
 
 fn main() {
+    build_includable(
+        "./asm/montgomery_interleaved_2.s",
+        Interleaving::single(simd::setup_single_step),
+        Target::Aarch64,
+    );
     build_includable(
         "./asm/montgomery_interleaved_3.s",
         Interleaving::par(
             Interleaving::single(scalar::setup_single_step),
             Interleaving::single(simd::setup_single_step),
         ),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_square_interleaved_3.s",
@@ -17,6 +26,7 @@ fn main() {
             Interleaving::single(scalar::setup_square_single_step),
             Interleaving::single(simd::setup_square_single_step),
         ),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_square_log_interleaved_3.s",
@@ -24,6 +34,7 @@ fn main() {
             Interleaving::single(scalar::setup_square_log_jump),
             Interleaving::single(simd::setup_square_single_step),
         ),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_interleaved_4.s",
@@ -31,6 +42,7 @@ fn main() {
             Interleaving::seq(vec![scalar::setup_single_step, scalar::setup_single_step]),
             Interleaving::single(simd::setup_single_step),
         ),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_square_interleaved_4.s",
@@ -41,6 +53,7 @@ fn main() {
             ]),
             Interleaving::single(simd::setup_square_single_step),
         ),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_square_log_interleaved_4.s",
@@ -51,17 +64,21 @@ fn main() {
             ]),
             Interleaving::single(simd::setup_square_single_step),
         ),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery.s",
         Interleaving::single(scalar::setup_single_step),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_square.s",
         Interleaving::single(scalar::setup_square_single_step),
+        Target::Aarch64,
     );
     build_includable(
         "./asm/montgomery_log_jump.s",
         Interleaving::single(scalar::setup_log_jump),
+        Target::Aarch64,
     );
 }
@@ -41,6 +41,14 @@ mod mul {
             .bench_local_values(|(a, b, c, d)| block_multiplier::simd_mul(a, b, c, d));
     }
 
+    #[divan::bench]
+    fn simd_mul8(bencher: Bencher) {
+        bencher
+            //.counter(ItemsCount::new(8usize))
+            .with_inputs(|| rng().random())
+            .bench_local_values(|(a, b)| block_multiplier::simd_mul8(a, b));
+    }
+
     #[divan::bench]
     fn block_mul(bencher: Bencher) {
         let bencher = bencher
@@ -55,6 +63,25 @@ mod mul {
         }
     }
 
+    #[divan::bench]
+    fn montgomery_interleaved_2(bencher: Bencher) {
+        let bencher = bencher
+            //.counter(ItemsCount::new(2usize))
+            .with_inputs(|| {
+                (
+                    array::from_fn(|_| u64x2::from_array(rng().random())),
+                    array::from_fn(|_| u64x2::from_array(rng().random())),
+                )
+            });
+        unsafe {
+            with_rounding_mode((), |mode_guard, _| {
+                bencher.bench_local_values(|(a, b)| {
+                    block_multiplier::montgomery_interleaved_2(mode_guard, a, b)
+                });
+            });
+        }
+    }
+
     #[divan::bench]
     fn montgomery_interleaved_3(bencher: Bencher) {
         let bencher = bencher
@@ -98,6 +125,61 @@ mod mul {
             });
         }
     }
+
+    #[divan::bench]
+    fn montgomery_interleaved_8(bencher: Bencher) {
+        let bencher = bencher
+            //.counter(ItemsCount::new(8usize))
+            .with_inputs(|| {
+                (
+                    rng().random(),
+                    rng().random(),
+                    array::from_fn(|_| array::from_fn(|_| u64x2::from_array(rng().random()))),
+                    array::from_fn(|_| array::from_fn(|_| u64x2::from_array(rng().random()))),
+                )
+            });
+        unsafe {
+            with_rounding_mode((), |mode_guard, _| {
+                bencher.bench_local_values(|(a, b, c, d)| {
+                    block_multiplier::montgomery_interleaved_8(mode_guard, a, b, c, d)
+                });
+            });
+        }
+    }
+}
+
+mod mul_many {
+    use super::*;
+
+    const SIZES: &[usize] = &[12, 1200, 120000];
+
+    fn inputs(size: usize) -> (Vec<[u64; 4]>, Vec<[u64; 4]>) {
+        (
+            (0..size).map(|_| rng().random()).collect(),
+            (0..size).map(|_| rng().random()).collect(),
+        )
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn scalar_mul(bencher: Bencher, size: usize) {
+        bencher
+            .counter(divan::counter::ItemsCount::new(size))
+            .with_inputs(|| inputs(size))
+            .bench_local_refs(|(a, b)| {
+                a.iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| block_multiplier::scalar_mul(*a, *b))
+                    .collect::<Vec<_>>()
+            });
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn montgomery_mul_many(bencher: Bencher, size: usize) {
+        bencher
+            .counter(divan::counter::ItemsCount::new(size))
+            .with_inputs(|| inputs(size))
+            .bench_local_refs(|(a, b)| block_multiplier::montgomery_mul_many(a, b));
+    }
 }
 
 // #[divan::bench_group]
@@ -1,10 +1,21 @@
 use {
     block_multiplier_codegen::{scalar, simd},
-    hla::builder::{build_includable, Interleaving},
+    hla::{
+        builder::{build_includable, Interleaving},
+        target::Target,
+    },
     std::path::Path,
 };
 
 fn main() {
+    let path = Path::new("./src/aarch64/montgomery_interleaved_2.s");
+    if !path.exists() {
+        build_includable(
+            path,
+            Interleaving::single(simd::setup_single_step),
+            Target::Aarch64,
+        );
+    }
     let path = Path::new("./src/aarch64/montgomery_interleaved_3.s");
     if !path.exists() {
         build_includable(
@@ -13,6 +24,7 @@ fn main() {
                 Interleaving::single(scalar::setup_single_step),
                 Interleaving::single(simd::setup_single_step),
             ),
+            Target::Aarch64,
         );
     }
     let path = Path::new("./src/aarch64/montgomery_interleaved_4.s");
@@ -23,6 +35,7 @@ fn main() {
                 Interleaving::seq(vec![scalar::setup_single_step, scalar::setup_single_step]),
                 Interleaving::single(simd::setup_single_step),
             ),
+            Target::Aarch64,
         );
     }
     let path = Path::new("./src/aarch64/montgomery_square_interleaved_3.s");
@@ -33,6 +46,7 @@ fn main() {
                 Interleaving::single(scalar::setup_square_single_step),
                 Interleaving::single(simd::setup_square_single_step),
             ),
+            Target::Aarch64,
         );
     }
     let path = Path::new("./src/aarch64/montgomery_square_interleaved_4.s");
@@ -46,6 +60,7 @@ fn main() {
                 ]),
                 Interleaving::single(simd::setup_square_single_step),
             ),
+            Target::Aarch64,
         );
     }
 }
@@ -14,6 +14,30 @@ use {
     fp_rounding::{RoundingGuard, Zero},
 };
 
+/// A block multiplier with 2 concurrent multiplications.
+///
+/// Only uses the SIMD unit, which gives the lowest latency when there are too
+/// few independent multiplications to fill the wider blocks.
+#[inline]
+pub fn montgomery_interleaved_2(
+    _rtz: &RoundingGuard<Zero>,
+    av: [Simd<u64, 2>; 4],
+    bv: [Simd<u64, 2>; 4],
+) -> [Simd<u64, 2>; 4] {
+    let mut outv = [Simd::splat(0); 4];
+    unsafe {
+        asm!(include_str!("montgomery_interleaved_2.s"),
+        in("v0") av[0], in("v1") av[1], in("v2") av[2], in("v3") av[3],
+        in("v4") bv[0], in("v5") bv[1], in("v6") bv[2], in("v7") bv[3],
+        lateout("v0") outv[0], lateout("v1") outv[1], lateout("v2") outv[2], lateout("v3") outv[3],
+        lateout("x0") _, lateout("x1") _, lateout("x2") _, lateout("x3") _, lateout("v4") _, lateout("v5") _, lateout("v6") _, lateout("v7") _, lateout("v8") _, lateout("v9") _, lateout("v10") _, lateout("v11") _, lateout("v12") _, lateout("v13") _, lateout("v14") _, lateout("v15") _, lateout("v16") _, lateout("v17") _, lateout("v18") _, lateout("v19") _, lateout("v20") _, lateout("v21") _, lateout("v22") _, lateout("v23") _, lateout("v24") _,
+        lateout("lr") _,
+        options(nomem, nostack)
+        )
+    };
+    outv
+}
+
 /// A block multiplier with 3 concurrent multiplications.
 #[inline]
 pub fn montgomery_interleaved_3(
@@ -139,6 +163,25 @@ pub fn montgomery_interleaved_4(
     (out, out1, outv)
 }
 
+/// A block multiplier with 8 concurrent multiplications.
+///
+/// A single assembly block of this width does not fit in the register file,
+/// so this issues two [`montgomery_interleaved_4`] blocks back to back and
+/// relies on out-of-order execution to overlap them. Useful for large batches
+/// as the rounding mode only has to be set once.
+#[inline]
+pub fn montgomery_interleaved_8(
+    rtz: &RoundingGuard<Zero>,
+    a: [[u64; 4]; 4],
+    b: [[u64; 4]; 4],
+    av: [[Simd<u64, 2>; 4]; 2],
+    bv: [[Simd<u64, 2>; 4]; 2],
+) -> ([[u64; 4]; 4], [[Simd<u64, 2>; 4]; 2]) {
+    let (out0, out1, outv0) = montgomery_interleaved_4(rtz, a[0], b[0], a[1], b[1], av[0], bv[0]);
+    let (out2, out3, outv1) = montgomery_interleaved_4(rtz, a[2], b[2], a[3], b[3], av[1], bv[1]);
+    ([out0, out1, out2, out3], [outv0, outv1])
+}
+
 #[inline]
 /// A block multiplier with 4 concurrent multiplications.
 ///
@@ -229,6 +272,59 @@ mod tests {
         std::array,
     };
 
+    /// test that compares interleaved_2 with ark_ff
+    #[test]
+    fn test_montgomery_interleaved_2() {
+        proptest!(|(
+            a in safe_bn254_montgomery_input(),
+            b in safe_bn254_montgomery_input(),
+            c in safe_bn254_montgomery_input(),
+            d in safe_bn254_montgomery_input(),
+        )| {
+            let av = array::from_fn(|i| Simd::from_array([a[i], b[i]]));
+            let bv = array::from_fn(|i| Simd::from_array([c[i], d[i]]));
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    let outv = montgomery_interleaved_2(rtz, av, bv);
+                    assert_eq!(Fr::new(BigInt(outv.map(|e| e[0]))), ark_ff_reference(a, c));
+                    assert_eq!(Fr::new(BigInt(outv.map(|e| e[1]))), ark_ff_reference(b, d));
+                });
+            }
+        });
+    }
+
+    /// test that compares interleaved_8 with ark_ff
+    #[test]
+    fn test_montgomery_interleaved_8() {
+        proptest!(|(
+            a in proptest::array::uniform8(safe_bn254_montgomery_input()),
+            b in proptest::array::uniform8(safe_bn254_montgomery_input()),
+        )| {
+            // The first four multiplications go to the scalar unit, the
+            // remaining four are split over two SIMD blocks.
+            let split = |x: [[u64; 4]; 8]| {
+                let s: [[u64; 4]; 4] = array::from_fn(|k| x[k]);
+                let v: [[Simd<u64, 2>; 4]; 2] = array::from_fn(|j| {
+                    array::from_fn(|i| Simd::from_array([x[4 + 2 * j][i], x[5 + 2 * j][i]]))
+                });
+                (s, v)
+            };
+            let (a_s, a_v) = split(a);
+            let (b_s, b_v) = split(b);
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    let (out, outv) = montgomery_interleaved_8(rtz, a_s, b_s, a_v, b_v);
+                    let results = out.into_iter().chain(
+                        outv.into_iter().flat_map(|v| [v.map(|e| e[0]), v.map(|e| e[1])]),
+                    );
+                    for (i, result) in results.enumerate() {
+                        assert_eq!(Fr::new(BigInt(result)), ark_ff_reference(a[i], b[i]));
+                    }
+                });
+            }
+        });
+    }
+
     /// test that compares square interleaved with ark_ff
     #[test]
     fn test_montgomery_square() {
@@ -280,4 +376,60 @@ mod tests {
             }
         });
     }
+
+    /// test that repeated squarings with square log interleaved 3, fed back
+    /// their unreduced outputs, match ark_ff
+    #[test]
+    fn test_montgomery_square_log_3_repeated() {
+        proptest!(|(
+            a in safe_bn254_montgomery_input(),
+            b in safe_bn254_montgomery_input(),
+            c in safe_bn254_montgomery_input(),
+        )| {
+            let (mut a, mut b, mut c) = (a, b, c);
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    for _ in 0..8 {
+                        let av = array::from_fn(|i| Simd::from_array([b[i], c[i]]));
+                        let (a_sqr, sqr_v) = montgomery_square_log_interleaved_3(rtz, a, av);
+                        let b_sqr = sqr_v.map(|e| e[0]);
+                        let c_sqr = sqr_v.map(|e| e[1]);
+                        assert_eq!(Fr::new(BigInt(a_sqr)), ark_ff_reference(a, a));
+                        assert_eq!(Fr::new(BigInt(b_sqr)), ark_ff_reference(b, b));
+                        assert_eq!(Fr::new(BigInt(c_sqr)), ark_ff_reference(c, c));
+                        (a, b, c) = (a_sqr, b_sqr, c_sqr);
+                    }
+                });
+            }
+        });
+    }
+
+    /// test that repeated squarings with square log interleaved 4, fed back
+    /// their unreduced outputs, match ark_ff
+    #[test]
+    fn test_montgomery_square_log_4_repeated() {
+        proptest!(|(
+            a in safe_bn254_montgomery_input(),
+            b in safe_bn254_montgomery_input(),
+            c in safe_bn254_montgomery_input(),
+            d in safe_bn254_montgomery_input(),
+        )| {
+            let (mut a, mut b, mut c, mut d) = (a, b, c, d);
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    for _ in 0..8 {
+                        let av = array::from_fn(|i| Simd::from_array([c[i], d[i]]));
+                        let (a_sqr, b_sqr, sqr_v) = montgomery_square_log_interleaved_4(rtz, a, b, av);
+                        let c_sqr = sqr_v.map(|e| e[0]);
+                        let d_sqr = sqr_v.map(|e| e[1]);
+                        assert_eq!(Fr::new(BigInt(a_sqr)), ark_ff_reference(a, a));
+                        assert_eq!(Fr::new(BigInt(b_sqr)), ark_ff_reference(b, b));
+                        assert_eq!(Fr::new(BigInt(c_sqr)), ark_ff_reference(c, c));
+                        assert_eq!(Fr::new(BigInt(d_sqr)), ark_ff_reference(d, d));
+                        (a, b, c, d) = (a_sqr, b_sqr, c_sqr, d_sqr);
+                    }
+                });
+            }
+        });
+    }
 }
@@ -0,0 +1,528 @@
+// GENERATED FILE, DO NOT EDIT!
+// in("v0") av[0], in("v1") av[1], in("v2") av[2], in("v3") av[3],
+// in("v4") bv[0], in("v5") bv[1], in("v6") bv[2], in("v7") bv[3],
+// lateout("v0") outv[0], lateout("v1") outv[1], lateout("v2") outv[2], lateout("v3") outv[3],
+// lateout("x0") _, lateout("x1") _, lateout("x2") _, lateout("x3") _, lateout("v4") _, lateout("v5") _, lateout("v6") _, lateout("v7") _, lateout("v8") _, lateout("v9") _, lateout("v10") _, lateout("v11") _, lateout("v12") _, lateout("v13") _, lateout("v14") _, lateout("v15") _, lateout("v16") _, lateout("v17") _, lateout("v18") _, lateout("v19") _, lateout("v20") _, lateout("v21") _, lateout("v22") _, lateout("v23") _, lateout("v24") _,
+// lateout("lr") _
+  mov x0, #4503599627370495
+  dup.2d v8, x0
+  mov x1, #5075556780046548992
+  dup.2d v9, x1
+  mov x1, #1
+  movk x1, #18032, lsl 48
+  dup.2d v10, x1
+  shl.2d v11, v1, #14
+  shl.2d v12, v2, #26
+  shl.2d v13, v3, #38
+  ushr.2d v3, v3, #14
+  shl.2d v14, v0, #2
+  usra.2d v11, v0, #50
+  usra.2d v12, v1, #38
+  usra.2d v13, v2, #26
+  and.16b v0, v14, v8
+  and.16b v1, v11, v8
+  and.16b v2, v12, v8
+  and.16b v11, v13, v8
+  shl.2d v12, v5, #14
+  shl.2d v13, v6, #26
+  shl.2d v14, v7, #38
+  ushr.2d v7, v7, #14
+  shl.2d v15, v4, #2
+  usra.2d v12, v4, #50
+  usra.2d v13, v5, #38
+  usra.2d v14, v6, #26
+  and.16b v4, v15, v8
+  and.16b v5, v12, v8
+  and.16b v6, v13, v8
+  and.16b v12, v14, v8
+  mov x1, #13605374474286268416
+  dup.2d v13, x1
+  mov x1, #6440147467139809280
+  dup.2d v14, x1
+  mov x1, #3688448094816436224
+  dup.2d v15, x1
+  mov x1, #9209861237972664320
+  dup.2d v16, x1
+  mov x1, #12218265789056155648
+  dup.2d v17, x1
+  mov x1, #17739678932212383744
+  dup.2d v18, x1
+  mov x1, #2301339409586323456
+  dup.2d v19, x1
+  mov x1, #7822752552742551552
+  dup.2d v20, x1
+  mov x1, #5071053180419178496
+  dup.2d v21, x1
+  mov x1, #16352570246982270976
+  dup.2d v22, x1
+  ucvtf.2d v0, v0
+  ucvtf.2d v1, v1
+  ucvtf.2d v2, v2
+  ucvtf.2d v11, v11
+  ucvtf.2d v3, v3
+  ucvtf.2d v4, v4
+  ucvtf.2d v5, v5
+  ucvtf.2d v6, v6
+  ucvtf.2d v12, v12
+  ucvtf.2d v7, v7
+  mov.16b v23, v9
+  fmla.2d v23, v0, v4
+  fsub.2d v24, v10, v23
+  fmla.2d v24, v0, v4
+  add.2d v15, v15, v23
+  add.2d v13, v13, v24
+  mov.16b v23, v9
+  fmla.2d v23, v0, v5
+  fsub.2d v24, v10, v23
+  fmla.2d v24, v0, v5
+  add.2d v17, v17, v23
+  add.2d v15, v15, v24
+  mov.16b v23, v9
+  fmla.2d v23, v0, v6
+  fsub.2d v24, v10, v23
+  fmla.2d v24, v0, v6
+  add.2d v19, v19, v23
+  add.2d v17, v17, v24
+  mov.16b v23, v9
+  fmla.2d v23, v0, v12
+  fsub.2d v24, v10, v23
+  fmla.2d v24, v0, v12
+  add.2d v21, v21, v23
+  add.2d v19, v19, v24
+  mov.16b v23, v9
+  fmla.2d v23, v0, v7
+  fsub.2d v24, v10, v23
+  fmla.2d v24, v0, v7
+  add.2d v0, v22, v23
+  add.2d v21, v21, v24
+  mov.16b v22, v9
+  fmla.2d v22, v1, v4
+  fsub.2d v23, v10, v22
+  fmla.2d v23, v1, v4
+  add.2d v17, v17, v22
+  add.2d v15, v15, v23
+  mov.16b v22, v9
+  fmla.2d v22, v1, v5
+  fsub.2d v23, v10, v22
+  fmla.2d v23, v1, v5
+  add.2d v19, v19, v22
+  add.2d v17, v17, v23
+  mov.16b v22, v9
+  fmla.2d v22, v1, v6
+  fsub.2d v23, v10, v22
+  fmla.2d v23, v1, v6
+  add.2d v21, v21, v22
+  add.2d v19, v19, v23
+  mov.16b v22, v9
+  fmla.2d v22, v1, v12
+  fsub.2d v23, v10, v22
+  fmla.2d v23, v1, v12
+  add.2d v0, v0, v22
+  add.2d v21, v21, v23
+  mov.16b v22, v9
+  fmla.2d v22, v1, v7
+  fsub.2d v23, v10, v22
+  fmla.2d v23, v1, v7
+  add.2d v1, v20, v22
+  add.2d v0, v0, v23
+  mov.16b v20, v9
+  fmla.2d v20, v2, v4
+  fsub.2d v22, v10, v20
+  fmla.2d v22, v2, v4
+  add.2d v19, v19, v20
+  add.2d v17, v17, v22
+  mov.16b v20, v9
+  fmla.2d v20, v2, v5
+  fsub.2d v22, v10, v20
+  fmla.2d v22, v2, v5
+  add.2d v20, v21, v20
+  add.2d v19, v19, v22
+  mov.16b v21, v9
+  fmla.2d v21, v2, v6
+  fsub.2d v22, v10, v21
+  fmla.2d v22, v2, v6
+  add.2d v0, v0, v21
+  add.2d v20, v20, v22
+  mov.16b v21, v9
+  fmla.2d v21, v2, v12
+  fsub.2d v22, v10, v21
+  fmla.2d v22, v2, v12
+  add.2d v1, v1, v21
+  add.2d v0, v0, v22
+  mov.16b v21, v9
+  fmla.2d v21, v2, v7
+  fsub.2d v22, v10, v21
+  fmla.2d v22, v2, v7
+  add.2d v2, v18, v21
+  add.2d v1, v1, v22
+  mov.16b v18, v9
+  fmla.2d v18, v11, v4
+  fsub.2d v21, v10, v18
+  fmla.2d v21, v11, v4
+  add.2d v18, v20, v18
+  add.2d v19, v19, v21
+  mov.16b v20, v9
+  fmla.2d v20, v11, v5
+  fsub.2d v21, v10, v20
+  fmla.2d v21, v11, v5
+  add.2d v0, v0, v20
+  add.2d v18, v18, v21
+  mov.16b v20, v9
+  fmla.2d v20, v11, v6
+  fsub.2d v21, v10, v20
+  fmla.2d v21, v11, v6
+  add.2d v1, v1, v20
+  add.2d v0, v0, v21
+  mov.16b v20, v9
+  fmla.2d v20, v11, v12
+  fsub.2d v21, v10, v20
+  fmla.2d v21, v11, v12
+  add.2d v2, v2, v20
+  add.2d v1, v1, v21
+  mov.16b v20, v9
+  fmla.2d v20, v11, v7
+  fsub.2d v21, v10, v20
+  fmla.2d v21, v11, v7
+  add.2d v11, v16, v20
+  add.2d v2, v2, v21
+  mov.16b v16, v9
+  fmla.2d v16, v3, v4
+  fsub.2d v20, v10, v16
+  fmla.2d v20, v3, v4
+  add.2d v0, v0, v16
+  add.2d v4, v18, v20
+  mov.16b v16, v9
+  fmla.2d v16, v3, v5
+  fsub.2d v18, v10, v16
+  fmla.2d v18, v3, v5
+  add.2d v1, v1, v16
+  add.2d v0, v0, v18
+  mov.16b v5, v9
+  fmla.2d v5, v3, v6
+  fsub.2d v16, v10, v5
+  fmla.2d v16, v3, v6
+  add.2d v2, v2, v5
+  add.2d v1, v1, v16
+  mov.16b v5, v9
+  fmla.2d v5, v3, v12
+  fsub.2d v6, v10, v5
+  fmla.2d v6, v3, v12
+  add.2d v5, v11, v5
+  add.2d v2, v2, v6
+  mov.16b v6, v9
+  fmla.2d v6, v3, v7
+  fsub.2d v11, v10, v6
+  fmla.2d v11, v3, v7
+  add.2d v3, v14, v6
+  add.2d v5, v5, v11
+  usra.2d v15, v13, #52
+  usra.2d v17, v15, #52
+  usra.2d v19, v17, #52
+  usra.2d v4, v19, #52
+  and.16b v6, v13, v8
+  and.16b v7, v15, v8
+  and.16b v11, v17, v8
+  and.16b v8, v19, v8
+  ucvtf.2d v6, v6
+  mov x1, #37864
+  movk x1, #1815, lsl 16
+  movk x1, #28960, lsl 32
+  movk x1, #17153, lsl 48
+  dup.2d v12, x1
+  mov.16b v13, v9
+  fmla.2d v13, v6, v12
+  fsub.2d v14, v10, v13
+  fmla.2d v14, v6, v12
+  add.2d v0, v0, v13
+  add.2d v4, v4, v14
+  mov x1, #46128
+  movk x1, #29964, lsl 16
+  movk x1, #7587, lsl 32
+  movk x1, #17161, lsl 48
+  dup.2d v12, x1
+  mov.16b v13, v9
+  fmla.2d v13, v6, v12
+  fsub.2d v14, v10, v13
+  fmla.2d v14, v6, v12
+  add.2d v1, v1, v13
+  add.2d v0, v0, v14
+  mov x1, #52826
+  movk x1, #57790, lsl 16
+  movk x1, #55431, lsl 32
+  movk x1, #17196, lsl 48
+  dup.2d v12, x1
+  mov.16b v13, v9
+  fmla.2d v13, v6, v12
+  fsub.2d v14, v10, v13
+  fmla.2d v14, v6, v12
+  add.2d v2, v2, v13
+  add.2d v1, v1, v14
+  mov x1, #31276
+  movk x1, #21262, lsl 16
+  movk x1, #2304, lsl 32
+  movk x1, #17182, lsl 48
+  dup.2d v12, x1
+  mov.16b v13, v9
+  fmla.2d v13, v6, v12
+  fsub.2d v14, v10, v13
+  fmla.2d v14, v6, v12
+  add.2d v5, v5, v13
+  add.2d v2, v2, v14
+  mov x1, #28672
+  movk x1, #24515, lsl 16
+  movk x1, #54929, lsl 32
+  movk x1, #17064, lsl 48
+  dup.2d v12, x1
+  mov.16b v13, v9
+  fmla.2d v13, v6, v12
+  fsub.2d v14, v10, v13
+  fmla.2d v14, v6, v12
+  add.2d v3, v3, v13
+  add.2d v5, v5, v14
+  ucvtf.2d v6, v7
+  mov x1, #44768
+  movk x1, #51919, lsl 16
+  movk x1, #6346, lsl 32
+  movk x1, #17133, lsl 48
+  dup.2d v7, x1
+  mov.16b v12, v9
+  fmla.2d v12, v6, v7
+  fsub.2d v13, v10, v12
+  fmla.2d v13, v6, v7
+  add.2d v0, v0, v12
+  add.2d v4, v4, v13
+  mov x1, #47492
+  movk x1, #23630, lsl 16
+  movk x1, #49985, lsl 32
+  movk x1, #17168, lsl 48
+  dup.2d v7, x1
+  mov.16b v12, v9
+  fmla.2d v12, v6, v7
+  fsub.2d v13, v10, v12
+  fmla.2d v13, v6, v7
+  add.2d v1, v1, v12
+  add.2d v0, v0, v13
+  mov x1, #57936
+  movk x1, #54828, lsl 16
+  movk x1, #18292, lsl 32
+  movk x1, #17197, lsl 48
+  dup.2d v7, x1
+  mov.16b v12, v9
+  fmla.2d v12, v6, v7
+  fsub.2d v13, v10, v12
+  fmla.2d v13, v6, v7
+  add.2d v2, v2, v12
+  add.2d v1, v1, v13
+  mov x1, #17708
+  movk x1, #43915, lsl 16
+  movk x1, #64348, lsl 32
+  movk x1, #17188, lsl 48
+  dup.2d v7, x1
+  mov.16b v12, v9
+  fmla.2d v12, v6, v7
+  fsub.2d v13, v10, v12
+  fmla.2d v13, v6, v7
+  add.2d v5, v5, v12
+  add.2d v2, v2, v13
+  mov x1, #29184
+  movk x1, #20789, lsl 16
+  movk x1, #19197, lsl 32
+  movk x1, #17083, lsl 48
+  dup.2d v7, x1
+  mov.16b v12, v9
+  fmla.2d v12, v6, v7
+  fsub.2d v13, v10, v12
+  fmla.2d v13, v6, v7
+  add.2d v3, v3, v12
+  add.2d v5, v5, v13
+  ucvtf.2d v6, v11
+  mov x1, #58856
+  movk x1, #14953, lsl 16
+  movk x1, #15155, lsl 32
+  movk x1, #17181, lsl 48
+  dup.2d v7, x1
+  mov.16b v11, v9
+  fmla.2d v11, v6, v7
+  fsub.2d v12, v10, v11
+  fmla.2d v12, v6, v7
+  add.2d v0, v0, v11
+  add.2d v4, v4, v12
+  mov x1, #35392
+  movk x1, #12477, lsl 16
+  movk x1, #56780, lsl 32
+  movk x1, #17142, lsl 48
+  dup.2d v7, x1
+  mov.16b v11, v9
+  fmla.2d v11, v6, v7
+  fsub.2d v12, v10, v11
+  fmla.2d v12, v6, v7
+  add.2d v1, v1, v11
+  add.2d v0, v0, v12
+  mov x1, #9848
+  movk x1, #54501, lsl 16
+  movk x1, #31540, lsl 32
+  movk x1, #17170, lsl 48
+  dup.2d v7, x1
+  mov.16b v11, v9
+  fmla.2d v11, v6, v7
+  fsub.2d v12, v10, v11
+  fmla.2d v12, v6, v7
+  add.2d v2, v2, v11
+  add.2d v1, v1, v12
+  mov x1, #9584
+  movk x1, #63883, lsl 16
+  movk x1, #18253, lsl 32
+  movk x1, #17190, lsl 48
+  dup.2d v7, x1
+  mov.16b v11, v9
+  fmla.2d v11, v6, v7
+  fsub.2d v12, v10, v11
+  fmla.2d v12, v6, v7
+  add.2d v5, v5, v11
+  add.2d v2, v2, v12
+  mov x1, #51712
+  movk x1, #16093, lsl 16
+  movk x1, #30633, lsl 32
+  movk x1, #17068, lsl 48
+  dup.2d v7, x1
+  mov.16b v11, v9
+  fmla.2d v11, v6, v7
+  fsub.2d v12, v10, v11
+  fmla.2d v12, v6, v7
+  add.2d v3, v3, v11
+  add.2d v5, v5, v12
+  ucvtf.2d v6, v8
+  mov x1, #34724
+  movk x1, #40393, lsl 16
+  movk x1, #23752, lsl 32
+  movk x1, #17184, lsl 48
+  dup.2d v7, x1
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v0, v0, v8
+  add.2d v4, v4, v11
+  mov x1, #25532
+  movk x1, #31025, lsl 16
+  movk x1, #10002, lsl 32
+  movk x1, #17199, lsl 48
+  dup.2d v7, x1
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v1, v1, v8
+  add.2d v0, v0, v11
+  mov x1, #18830
+  movk x1, #2465, lsl 16
+  movk x1, #36348, lsl 32
+  movk x1, #17194, lsl 48
+  dup.2d v7, x1
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v2, v2, v8
+  add.2d v1, v1, v11
+  mov x1, #21566
+  movk x1, #43708, lsl 16
+  movk x1, #57685, lsl 32
+  movk x1, #17185, lsl 48
+  dup.2d v7, x1
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v5, v5, v8
+  add.2d v2, v2, v11
+  mov x1, #3072
+  movk x1, #8058, lsl 16
+  movk x1, #46097, lsl 32
+  movk x1, #17047, lsl 48
+  dup.2d v7, x1
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v3, v3, v8
+  add.2d v5, v5, v11
+  mov x1, #65535
+  movk x1, #61439, lsl 16
+  movk x1, #62867, lsl 32
+  movk x1, #1, lsl 48
+  umov x2, v4.d[0]
+  umov x3, v4.d[1]
+  mul x2, x2, x1
+  mul x1, x3, x1
+  and x2, x2, x0
+  and x0, x1, x0
+  ins v6.d[0], x2
+  ins v6.d[1], x0
+  ucvtf.2d v6, v6
+  mov x0, #16
+  movk x0, #22847, lsl 32
+  movk x0, #17151, lsl 48
+  dup.2d v7, x0
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v0, v0, v8
+  add.2d v4, v4, v11
+  mov x0, #20728
+  movk x0, #23588, lsl 16
+  movk x0, #7790, lsl 32
+  movk x0, #17170, lsl 48
+  dup.2d v7, x0
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v1, v1, v8
+  add.2d v0, v0, v11
+  mov x0, #16000
+  movk x0, #53891, lsl 16
+  movk x0, #5509, lsl 32
+  movk x0, #17144, lsl 48
+  dup.2d v7, x0
+  mov.16b v8, v9
+  fmla.2d v8, v6, v7
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v7
+  add.2d v2, v2, v8
+  add.2d v7, v1, v11
+  mov x0, #46800
+  movk x0, #2568, lsl 16
+  movk x0, #1335, lsl 32
+  movk x0, #17188, lsl 48
+  dup.2d v1, x0
+  mov.16b v8, v9
+  fmla.2d v8, v6, v1
+  fsub.2d v11, v10, v8
+  fmla.2d v11, v6, v1
+  add.2d v1, v5, v8
+  add.2d v5, v2, v11
+  mov x0, #39040
+  movk x0, #14704, lsl 16
+  movk x0, #12839, lsl 32
+  movk x0, #17096, lsl 48
+  dup.2d v2, x0
+  mov.16b v8, v9
+  fmla.2d v8, v6, v2
+  fsub.2d v9, v10, v8
+  fmla.2d v9, v6, v2
+  add.2d v6, v3, v8
+  add.2d v8, v1, v9
+  ssra.2d v0, v4, #52
+  ssra.2d v7, v0, #52
+  ssra.2d v5, v7, #52
+  ssra.2d v8, v5, #52
+  ssra.2d v6, v8, #52
+  ushr.2d v1, v7, #12
+  ushr.2d v2, v5, #24
+  ushr.2d v3, v8, #36
+  sli.2d v0, v7, #52
+  sli.2d v1, v5, #40
+  sli.2d v2, v8, #28
+  sli.2d v3, v6, #16
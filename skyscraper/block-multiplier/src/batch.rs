@@ -0,0 +1,229 @@
+//! Batched Montgomery arithmetic.
+//!
+//! These functions take care of setting the floating-point rounding mode and
+//! of picking the interleaved kernels, so callers do not need to depend on
+//! `fp-rounding` themselves.
+use crate::{
+    constants::{U64_P, U64_R},
+    scalar::{scalar_mul, scalar_sqr},
+    utils::sub,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    crate::{montgomery_interleaved_3, montgomery_interleaved_4},
+    core::simd::Simd,
+    fp_rounding::with_rounding_mode,
+};
+
+/// The least common multiple of the interleaved kernel widths.
+///
+/// Batches whose length is a multiple of this are processed by the kernels
+/// alone, whichever widths they are split into.
+pub const WIDTH_LCM: usize = 12;
+
+/// Montgomery multiplies `a[i]` with `b[i]` for all `i`.
+///
+/// The products are computed four at a time by `montgomery_interleaved_4`, a
+/// remainder of three by `montgomery_interleaved_3` and any other remainder by
+/// [`scalar_mul`]. Targets without the assembly kernels use their portable
+/// versions, wasm32 only uses [`scalar_mul`].
+///
+/// The rounding mode is set once for the whole batch. Inputs must be in the
+/// range `[0, 2ˆ256-2p]`. Like the underlying kernels the outputs are not
+/// necessarily fully reduced.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` differ in length.
+pub fn montgomery_mul_many(a: &[[u64; 4]], b: &[[u64; 4]]) -> Vec<[u64; 4]> {
+    assert_eq!(a.len(), b.len(), "Input lengths differ");
+    let mut out = Vec::with_capacity(a.len());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let (a, b) = {
+        let split = a.len() - a.len() % 4;
+        let (a, a_rest) = a.split_at(split);
+        let (b, b_rest) = b.split_at(split);
+        let three = a_rest.len() == 3;
+        unsafe {
+            with_rounding_mode(&mut out, |rtz, out| {
+                for (a, b) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+                    let av = transpose(a[2], a[3]);
+                    let bv = transpose(b[2], b[3]);
+                    let (s0, s1, v) = montgomery_interleaved_4(rtz, a[0], b[0], a[1], b[1], av, bv);
+                    out.extend([s0, s1, v.map(|e| e[0]), v.map(|e| e[1])]);
+                }
+                if three {
+                    let av = transpose(a_rest[1], a_rest[2]);
+                    let bv = transpose(b_rest[1], b_rest[2]);
+                    let (s0, v) = montgomery_interleaved_3(rtz, a_rest[0], b_rest[0], av, bv);
+                    out.extend([s0, v.map(|e| e[0]), v.map(|e| e[1])]);
+                }
+            });
+        }
+        if three {
+            (&a_rest[3..], &b_rest[3..])
+        } else {
+            (a_rest, b_rest)
+        }
+    };
+
+    out.extend(a.iter().zip(b).map(|(a, b)| scalar_mul(*a, *b)));
+    out
+}
+
+/// Montgomery form inverse of `a` using Fermat's little theorem.
+///
+/// Returns a fully reduced result. Zero is mapped to zero.
+pub fn inv_mod(a: [u64; 4]) -> [u64; 4] {
+    let exponent = sub(U64_P, [2, 0, 0, 0]);
+    let mut result = U64_R;
+    for bit in (0..256).rev() {
+        result = scalar_sqr(result);
+        if (exponent[bit / 64] >> (bit % 64)) & 1 == 1 {
+            result = scalar_mul(result, a);
+        }
+    }
+    reduce_full(result)
+}
+
+/// Replaces every element by its Montgomery form inverse.
+///
+/// Uses Montgomery's trick to compute all inverses with a single [`inv_mod`].
+/// The elements are split over [`WIDTH_LCM`] lanes so the running products of
+/// all lanes can be computed with one [`montgomery_mul_many`] call per row.
+/// Zero elements are skipped by the running products and stay zero. Results
+/// are fully reduced.
+pub fn batch_inverse(elements: &mut [[u64; 4]]) {
+    let lanes = WIDTH_LCM;
+    for element in elements.iter_mut() {
+        *element = reduce_full(*element);
+    }
+    // Zeros are replaced by one so they do not affect the running products.
+    let factor = |element: &[u64; 4]| if *element == [0; 4] { U64_R } else { *element };
+
+    // Forward pass: per lane prefix products excluding the current row.
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut products = vec![U64_R; lanes];
+    for row in elements.chunks(lanes) {
+        let n = row.len();
+        prefix.extend_from_slice(&products[..n]);
+        let factors = row.iter().map(factor).collect::<Vec<_>>();
+        let next = montgomery_mul_many(&products[..n], &factors);
+        products[..n]
+            .iter_mut()
+            .zip(next)
+            .for_each(|(product, next)| *product = reduce_full(next));
+    }
+
+    // Invert the lane products using the same trick on a single lane.
+    let mut inverses = vec![U64_R; lanes];
+    let mut acc = U64_R;
+    for (inverse, product) in inverses.iter_mut().zip(&products) {
+        *inverse = acc;
+        acc = reduce_full(scalar_mul(acc, *product));
+    }
+    let mut inv = inv_mod(acc);
+    for (inverse, product) in inverses.iter_mut().zip(&products).rev() {
+        *inverse = reduce_full(scalar_mul(*inverse, inv));
+        inv = reduce_full(scalar_mul(inv, *product));
+    }
+
+    // Backward pass: `inverses` holds the inverse of the lane products up to
+    // and including the current row.
+    for (row, prefix) in elements.chunks_mut(lanes).zip(prefix.chunks(lanes)).rev() {
+        let n = row.len();
+        let lhs = [&inverses[..n], &inverses[..n]].concat();
+        let rhs = [prefix, &row.iter().map(factor).collect::<Vec<_>>()].concat();
+        let out = montgomery_mul_many(&lhs, &rhs);
+        for (i, element) in row.iter_mut().enumerate() {
+            if *element != [0; 4] {
+                *element = reduce_full(out[i]);
+                inverses[i] = reduce_full(out[n + i]);
+            }
+        }
+    }
+}
+
+/// Reduces a value in `[0, 2ˆ256)` to `[0, p)`.
+#[inline]
+fn reduce_full(mut a: [u64; 4]) -> [u64; 4] {
+    while a.iter().rev().cmp(U64_P.iter().rev()).is_ge() {
+        a = sub(a, U64_P);
+    }
+    a
+}
+
+/// Packs two scalars into the limb-wise SIMD layout of the kernels.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline(always)]
+fn transpose(x: [u64; 4], y: [u64; 4]) -> [Simd<u64, 2>; 4] {
+    core::array::from_fn(|i| Simd::from_array([x[i], y[i]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{ark_ff_reference, safe_bn254_montgomery_input},
+        ark_bn254::Fr,
+        ark_ff::{AdditiveGroup, BigInt},
+        proptest::{collection, prelude::any, proptest},
+    };
+
+    #[test]
+    fn test_montgomery_mul_many() {
+        proptest!(|(
+            pairs in collection::vec(
+                (safe_bn254_montgomery_input(), safe_bn254_montgomery_input()),
+                0..3 * WIDTH_LCM,
+            ),
+        )| {
+            let (a, b): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+            let out = montgomery_mul_many(&a, &b);
+            assert_eq!(out.len(), a.len());
+            for ((a, b), out) in a.iter().zip(&b).zip(out) {
+                assert_eq!(Fr::new(BigInt(out)), ark_ff_reference(*a, *b));
+            }
+        });
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        proptest!(|(
+            values in collection::vec(
+                (safe_bn254_montgomery_input(), any::<bool>()),
+                0..3 * WIDTH_LCM,
+            ),
+        )| {
+            // Roughly half of the elements are zero, some in non-canonical form.
+            let input = values
+                .into_iter()
+                .map(|(value, zero)| if zero { U64_P } else { value })
+                .collect::<Vec<_>>();
+            let mut inverses = input.clone();
+            batch_inverse(&mut inverses);
+            let one = Fr::new(BigInt(U64_R));
+            for (value, inverse) in input.iter().zip(&inverses) {
+                if Fr::new(BigInt(*value)) == Fr::ZERO {
+                    assert_eq!(*inverse, [0; 4]);
+                } else {
+                    assert_eq!(ark_ff_reference(*value, *inverse), one);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_inv_mod() {
+        proptest!(|(value in safe_bn254_montgomery_input())| {
+            let inverse = inv_mod(value);
+            if Fr::new(BigInt(value)) == Fr::ZERO {
+                assert_eq!(inverse, [0; 4]);
+            } else {
+                assert_eq!(ark_ff_reference(value, inverse), Fr::new(BigInt(U64_R)));
+            }
+        });
+        assert_eq!(inv_mod([0; 4]), [0; 4]);
+    }
+}
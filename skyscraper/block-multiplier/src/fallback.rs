@@ -0,0 +1,410 @@
+//! Portable fallbacks for the aarch64 block multipliers.
+//!
+//! These compute every lane with [`scalar_mul`] and [`scalar_sqr`], so they
+//! produce the same field elements as the SIMD versions without relying on
+//! NEON or the floating-point rounding mode. They exist for compatibility on
+//! other targets and are not optimized.
+//!
+//! On aarch64 this module is only built by the tests, which check it against
+//! the assembly kernels.
+#![cfg_attr(target_arch = "aarch64", allow(dead_code))]
+#[cfg(not(target_arch = "wasm32"))]
+use fp_rounding::{RoundingGuard, Zero};
+use {
+    crate::scalar::{scalar_mul, scalar_sqr},
+    core::simd::Simd,
+};
+
+/// Splits the limb-wise SIMD layout of the kernels into its two elements.
+#[inline]
+fn unpack(v: [Simd<u64, 2>; 4]) -> ([u64; 4], [u64; 4]) {
+    (v.map(|limb| limb[0]), v.map(|limb| limb[1]))
+}
+
+/// Packs two elements into the limb-wise SIMD layout of the kernels.
+#[inline]
+fn pack(x: [u64; 4], y: [u64; 4]) -> [Simd<u64, 2>; 4] {
+    core::array::from_fn(|i| Simd::from_array([x[i], y[i]]))
+}
+
+/// Portable version of the aarch64 `montgomery_interleaved_2`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_interleaved_2(
+    _rtz: &RoundingGuard<Zero>,
+    av: [Simd<u64, 2>; 4],
+    bv: [Simd<u64, 2>; 4],
+) -> [Simd<u64, 2>; 4] {
+    let (a0, a1) = unpack(av);
+    let (b0, b1) = unpack(bv);
+    let (out0, out1) = simd_mul(a0, b0, a1, b1);
+    pack(out0, out1)
+}
+
+/// Portable version of the aarch64 `montgomery_interleaved_3`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_interleaved_3(
+    rtz: &RoundingGuard<Zero>,
+    a: [u64; 4],
+    b: [u64; 4],
+    av: [Simd<u64, 2>; 4],
+    bv: [Simd<u64, 2>; 4],
+) -> ([u64; 4], [Simd<u64, 2>; 4]) {
+    (scalar_mul(a, b), montgomery_interleaved_2(rtz, av, bv))
+}
+
+/// Portable version of the aarch64 `montgomery_interleaved_4`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_interleaved_4(
+    rtz: &RoundingGuard<Zero>,
+    a: [u64; 4],
+    b: [u64; 4],
+    a1: [u64; 4],
+    b1: [u64; 4],
+    av: [Simd<u64, 2>; 4],
+    bv: [Simd<u64, 2>; 4],
+) -> ([u64; 4], [u64; 4], [Simd<u64, 2>; 4]) {
+    (
+        scalar_mul(a, b),
+        scalar_mul(a1, b1),
+        montgomery_interleaved_2(rtz, av, bv),
+    )
+}
+
+/// Portable version of the aarch64 `montgomery_interleaved_8`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_interleaved_8(
+    rtz: &RoundingGuard<Zero>,
+    a: [[u64; 4]; 4],
+    b: [[u64; 4]; 4],
+    av: [[Simd<u64, 2>; 4]; 2],
+    bv: [[Simd<u64, 2>; 4]; 2],
+) -> ([[u64; 4]; 4], [[Simd<u64, 2>; 4]; 2]) {
+    (
+        core::array::from_fn(|i| scalar_mul(a[i], b[i])),
+        core::array::from_fn(|i| montgomery_interleaved_2(rtz, av[i], bv[i])),
+    )
+}
+
+/// Portable version of the aarch64 `montgomery_square_interleaved_3`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_square_interleaved_3(
+    _rtz: &RoundingGuard<Zero>,
+    a: [u64; 4],
+    av: [Simd<u64, 2>; 4],
+) -> ([u64; 4], [Simd<u64, 2>; 4]) {
+    let (v0, v1) = unpack(av);
+    let (out0, out1) = simd_sqr(v0, v1);
+    (scalar_sqr(a), pack(out0, out1))
+}
+
+/// Portable version of the aarch64 `montgomery_square_log_interleaved_3`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_square_log_interleaved_3(
+    rtz: &RoundingGuard<Zero>,
+    a: [u64; 4],
+    av: [Simd<u64, 2>; 4],
+) -> ([u64; 4], [Simd<u64, 2>; 4]) {
+    montgomery_square_interleaved_3(rtz, a, av)
+}
+
+/// Portable version of the aarch64 `montgomery_square_interleaved_4`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_square_interleaved_4(
+    rtz: &RoundingGuard<Zero>,
+    a: [u64; 4],
+    a1: [u64; 4],
+    av: [Simd<u64, 2>; 4],
+) -> ([u64; 4], [u64; 4], [Simd<u64, 2>; 4]) {
+    let (out, outv) = montgomery_square_interleaved_3(rtz, a, av);
+    (out, scalar_sqr(a1), outv)
+}
+
+/// Portable version of the aarch64 `montgomery_square_log_interleaved_4`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn montgomery_square_log_interleaved_4(
+    rtz: &RoundingGuard<Zero>,
+    a: [u64; 4],
+    a1: [u64; 4],
+    av: [Simd<u64, 2>; 4],
+) -> ([u64; 4], [u64; 4], [Simd<u64, 2>; 4]) {
+    montgomery_square_interleaved_4(rtz, a, a1, av)
+}
+
+/// Portable version of the aarch64 `block_sqr`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn block_sqr(
+    _rtz: &RoundingGuard<Zero>,
+    s0_a: [u64; 4],
+    v0_a: [u64; 4],
+    v1_a: [u64; 4],
+) -> ([u64; 4], [u64; 4], [u64; 4]) {
+    let (v0, v1) = simd_sqr(v0_a, v1_a);
+    (scalar_sqr(s0_a), v0, v1)
+}
+
+/// Portable version of the aarch64 `block_mul`.
+#[cfg(not(target_arch = "wasm32"))]
+#[inline]
+pub fn block_mul(
+    _rtz: &RoundingGuard<Zero>,
+    s0_a: [u64; 4],
+    s0_b: [u64; 4],
+    v0_a: [u64; 4],
+    v0_b: [u64; 4],
+    v1_a: [u64; 4],
+    v1_b: [u64; 4],
+) -> ([u64; 4], [u64; 4], [u64; 4]) {
+    let (v0, v1) = simd_mul(v0_a, v0_b, v1_a, v1_b);
+    (scalar_mul(s0_a, s0_b), v0, v1)
+}
+
+/// Portable version of the aarch64 `simd_sqr`.
+#[inline]
+pub fn simd_sqr(v0_a: [u64; 4], v1_a: [u64; 4]) -> ([u64; 4], [u64; 4]) {
+    (scalar_sqr(v0_a), scalar_sqr(v1_a))
+}
+
+/// Portable version of the aarch64 `simd_mul`.
+#[inline]
+pub fn simd_mul(
+    v0_a: [u64; 4],
+    v0_b: [u64; 4],
+    v1_a: [u64; 4],
+    v1_b: [u64; 4],
+) -> ([u64; 4], [u64; 4]) {
+    (scalar_mul(v0_a, v0_b), scalar_mul(v1_a, v1_b))
+}
+
+/// Portable version of the aarch64 `simd_mul8`.
+#[inline]
+pub fn simd_mul8(a: [[u64; 4]; 8], b: [[u64; 4]; 8]) -> [[u64; 4]; 8] {
+    core::array::from_fn(|i| scalar_mul(a[i], b[i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{ark_ff_reference, safe_bn254_montgomery_input},
+        ark_bn254::Fr,
+        ark_ff::BigInt,
+        fp_rounding::with_rounding_mode,
+        proptest::{array::uniform8, proptest},
+    };
+
+    /// Field element of a kernel output.
+    fn fr(out: [u64; 4]) -> Fr {
+        Fr::new(BigInt(out))
+    }
+
+    /// Field elements of a kernel output in the SIMD layout.
+    fn fr_pair(outv: [Simd<u64, 2>; 4]) -> [Fr; 2] {
+        let (out0, out1) = unpack(outv);
+        [fr(out0), fr(out1)]
+    }
+
+    #[test]
+    fn test_block_mul() {
+        proptest!(|(
+            a in safe_bn254_montgomery_input(),
+            b in safe_bn254_montgomery_input(),
+            c in safe_bn254_montgomery_input(),
+            d in safe_bn254_montgomery_input(),
+            e in safe_bn254_montgomery_input(),
+            f in safe_bn254_montgomery_input(),
+        )| {
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    let (s0, v0, v1) = block_mul(rtz, a, b, c, d, e, f);
+                    assert_eq!(Fr::new(BigInt(s0)), ark_ff_reference(a, b));
+                    assert_eq!(Fr::new(BigInt(v0)), ark_ff_reference(c, d));
+                    assert_eq!(Fr::new(BigInt(v1)), ark_ff_reference(e, f));
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_block_sqr() {
+        proptest!(|(
+            a in safe_bn254_montgomery_input(),
+            b in safe_bn254_montgomery_input(),
+            c in safe_bn254_montgomery_input(),
+        )| {
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    let (s0, v0, v1) = block_sqr(rtz, a, b, c);
+                    assert_eq!(Fr::new(BigInt(s0)), ark_ff_reference(a, a));
+                    assert_eq!(Fr::new(BigInt(v0)), ark_ff_reference(b, b));
+                    assert_eq!(Fr::new(BigInt(v1)), ark_ff_reference(c, c));
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_montgomery_interleaved() {
+        proptest!(|(
+            a in uniform8(safe_bn254_montgomery_input()),
+            b in uniform8(safe_bn254_montgomery_input()),
+        )| {
+            let expected: [Fr; 8] = core::array::from_fn(|i| ark_ff_reference(a[i], b[i]));
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    let outv = montgomery_interleaved_2(rtz, pack(a[0], a[1]), pack(b[0], b[1]));
+                    assert_eq!(fr_pair(outv), [expected[0], expected[1]]);
+
+                    let (out, outv) =
+                        montgomery_interleaved_3(rtz, a[0], b[0], pack(a[1], a[2]), pack(b[1], b[2]));
+                    assert_eq!(fr(out), expected[0]);
+                    assert_eq!(fr_pair(outv), [expected[1], expected[2]]);
+
+                    let (out, out1, outv) = montgomery_interleaved_4(
+                        rtz,
+                        a[0],
+                        b[0],
+                        a[1],
+                        b[1],
+                        pack(a[2], a[3]),
+                        pack(b[2], b[3]),
+                    );
+                    assert_eq!([fr(out), fr(out1)], [expected[0], expected[1]]);
+                    assert_eq!(fr_pair(outv), [expected[2], expected[3]]);
+
+                    let (out, outv) = montgomery_interleaved_8(
+                        rtz,
+                        [a[0], a[1], a[2], a[3]],
+                        [b[0], b[1], b[2], b[3]],
+                        [pack(a[4], a[5]), pack(a[6], a[7])],
+                        [pack(b[4], b[5]), pack(b[6], b[7])],
+                    );
+                    let results = out
+                        .into_iter()
+                        .map(fr)
+                        .chain(outv.into_iter().flat_map(fr_pair))
+                        .collect::<Vec<_>>();
+                    assert_eq!(results, expected);
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_montgomery_square_interleaved() {
+        proptest!(|(
+            a in safe_bn254_montgomery_input(),
+            b in safe_bn254_montgomery_input(),
+            c in safe_bn254_montgomery_input(),
+            d in safe_bn254_montgomery_input(),
+        )| {
+            let [a2, b2, c2, d2] = [a, b, c, d].map(|x| ark_ff_reference(x, x));
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    for square in [montgomery_square_interleaved_3, montgomery_square_log_interleaved_3] {
+                        let (out, outv) = square(rtz, a, pack(b, c));
+                        assert_eq!(fr(out), a2);
+                        assert_eq!(fr_pair(outv), [b2, c2]);
+                    }
+                    for square in [montgomery_square_interleaved_4, montgomery_square_log_interleaved_4] {
+                        let (out, out1, outv) = square(rtz, a, b, pack(c, d));
+                        assert_eq!([fr(out), fr(out1)], [a2, b2]);
+                        assert_eq!(fr_pair(outv), [c2, d2]);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Runs the fallbacks and the assembly kernels on the same inputs.
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn test_matches_aarch64() {
+        use crate::aarch64;
+
+        type Square3 =
+            fn(&RoundingGuard<Zero>, [u64; 4], [Simd<u64, 2>; 4]) -> ([u64; 4], [Simd<u64, 2>; 4]);
+        type Square4 = fn(
+            &RoundingGuard<Zero>,
+            [u64; 4],
+            [u64; 4],
+            [Simd<u64, 2>; 4],
+        ) -> ([u64; 4], [u64; 4], [Simd<u64, 2>; 4]);
+
+        proptest!(|(
+            a in uniform8(safe_bn254_montgomery_input()),
+            b in uniform8(safe_bn254_montgomery_input()),
+        )| {
+            let av = [pack(a[4], a[5]), pack(a[6], a[7])];
+            let bv = [pack(b[4], b[5]), pack(b[6], b[7])];
+            unsafe {
+                with_rounding_mode((), |rtz, _| {
+                    assert_eq!(
+                        fr_pair(montgomery_interleaved_2(rtz, av[0], bv[0])),
+                        fr_pair(aarch64::montgomery_interleaved_2(rtz, av[0], bv[0]))
+                    );
+
+                    let (out, outv) = montgomery_interleaved_3(rtz, a[0], b[0], av[0], bv[0]);
+                    let (expected, expectedv) =
+                        aarch64::montgomery_interleaved_3(rtz, a[0], b[0], av[0], bv[0]);
+                    assert_eq!(fr(out), fr(expected));
+                    assert_eq!(fr_pair(outv), fr_pair(expectedv));
+
+                    let (out, out1, outv) =
+                        montgomery_interleaved_4(rtz, a[0], b[0], a[1], b[1], av[0], bv[0]);
+                    let (expected, expected1, expectedv) =
+                        aarch64::montgomery_interleaved_4(rtz, a[0], b[0], a[1], b[1], av[0], bv[0]);
+                    assert_eq!([fr(out), fr(out1)], [fr(expected), fr(expected1)]);
+                    assert_eq!(fr_pair(outv), fr_pair(expectedv));
+
+                    let scalars = [a[0], a[1], a[2], a[3]];
+                    let scalars_b = [b[0], b[1], b[2], b[3]];
+                    let (out, outv) = montgomery_interleaved_8(rtz, scalars, scalars_b, av, bv);
+                    let (expected, expectedv) =
+                        aarch64::montgomery_interleaved_8(rtz, scalars, scalars_b, av, bv);
+                    assert_eq!(out.map(fr), expected.map(fr));
+                    assert_eq!(outv.map(fr_pair), expectedv.map(fr_pair));
+
+                    for (square, expected) in [
+                        (
+                            montgomery_square_interleaved_3 as Square3,
+                            aarch64::montgomery_square_interleaved_3 as Square3,
+                        ),
+                        (
+                            montgomery_square_log_interleaved_3 as Square3,
+                            aarch64::montgomery_square_log_interleaved_3 as Square3,
+                        ),
+                    ] {
+                        let (out, outv) = square(rtz, a[0], av[0]);
+                        let (expected, expectedv) = expected(rtz, a[0], av[0]);
+                        assert_eq!(fr(out), fr(expected));
+                        assert_eq!(fr_pair(outv), fr_pair(expectedv));
+                    }
+                    for (square, expected) in [
+                        (
+                            montgomery_square_interleaved_4 as Square4,
+                            aarch64::montgomery_square_interleaved_4 as Square4,
+                        ),
+                        (
+                            montgomery_square_log_interleaved_4 as Square4,
+                            aarch64::montgomery_square_log_interleaved_4 as Square4,
+                        ),
+                    ] {
+                        let (out, out1, outv) = square(rtz, a[0], a[1], av[0]);
+                        let (expected, expected1, expectedv) = expected(rtz, a[0], a[1], av[0]);
+                        assert_eq!([fr(out), fr(out1)], [fr(expected), fr(expected1)]);
+                        assert_eq!(fr_pair(outv), fr_pair(expectedv));
+                    }
+                });
+            }
+        });
+    }
+}
@@ -15,19 +15,35 @@ mod portable_simd;
 #[cfg(target_arch = "aarch64")]
 mod simd_utils;
 
+mod batch;
 pub mod constants;
+#[cfg(any(test, not(target_arch = "aarch64")))]
+mod fallback;
 mod scalar;
 mod test_utils;
 mod utils;
 
-pub use crate::scalar::{scalar_mul, scalar_sqr};
+// Portable fallbacks so downstream crates compile on all targets.
+#[cfg(all(not(target_arch = "aarch64"), not(target_arch = "wasm32")))]
+pub use crate::fallback::{
+    block_mul, block_sqr, montgomery_interleaved_2, montgomery_interleaved_3,
+    montgomery_interleaved_4, montgomery_interleaved_8, montgomery_square_interleaved_3,
+    montgomery_square_interleaved_4, montgomery_square_log_interleaved_3,
+    montgomery_square_log_interleaved_4,
+};
+#[cfg(not(target_arch = "aarch64"))]
+pub use crate::fallback::{simd_mul, simd_mul8, simd_sqr};
 #[cfg(target_arch = "aarch64")]
 pub use crate::{
     aarch64::{
-        montgomery_interleaved_3, montgomery_interleaved_4, montgomery_square_interleaved_3,
-        montgomery_square_interleaved_4, montgomery_square_log_interleaved_3,
-        montgomery_square_log_interleaved_4,
+        montgomery_interleaved_2, montgomery_interleaved_3, montgomery_interleaved_4,
+        montgomery_interleaved_8, montgomery_square_interleaved_3, montgomery_square_interleaved_4,
+        montgomery_square_log_interleaved_3, montgomery_square_log_interleaved_4,
     },
     block_simd::{block_mul, block_sqr},
-    portable_simd::{simd_mul, simd_sqr},
+    portable_simd::{simd_mul, simd_mul8, simd_sqr},
+};
+pub use crate::{
+    batch::{batch_inverse, inv_mod, montgomery_mul_many, WIDTH_LCM},
+    scalar::{scalar_mul, scalar_sqr},
 };
@@ -8,6 +8,7 @@ use {
     },
     core::{
         arch::aarch64::vcvtq_f64_u64,
+        array,
         ops::BitAnd,
         simd::{num::SimdFloat, Simd},
     },
@@ -165,31 +166,7 @@ pub fn simd_sqr(v0_a: [u64; 4], v1_a: [u64; 4]) -> ([u64; 4], [u64; 4]) {
     t[4 + 4 + 1] += p_hi.to_bits();
     t[4 + 4] += p_lo.to_bits();
 
-    t[1] += t[0] >> 52;
-    t[2] += t[1] >> 52;
-    t[3] += t[2] >> 52;
-    t[4] += t[3] >> 52;
-
-    let r0 = smult_noinit_simd(t[0].bitand(Simd::splat(MASK52)), RHO_4);
-    let r1 = smult_noinit_simd(t[1].bitand(Simd::splat(MASK52)), RHO_3);
-    let r2 = smult_noinit_simd(t[2].bitand(Simd::splat(MASK52)), RHO_2);
-    let r3 = smult_noinit_simd(t[3].bitand(Simd::splat(MASK52)), RHO_1);
-
-    let s = [
-        r0[0] + r1[0] + r2[0] + r3[0] + t[4],
-        r0[1] + r1[1] + r2[1] + r3[1] + t[5],
-        r0[2] + r1[2] + r2[2] + r3[2] + t[6],
-        r0[3] + r1[3] + r2[3] + r3[3] + t[7],
-        r0[4] + r1[4] + r2[4] + r3[4] + t[8],
-        r0[5] + r1[5] + r2[5] + r3[5] + t[9],
-    ];
-
-    let m = (s[0] * Simd::splat(U52_NP0)).bitand(Simd::splat(MASK52));
-    let mp = smult_noinit_simd(m, U52_P);
-
-    let reduced = reduce_ct_simd(addv_simd(s, mp));
-    let u256_result = u260_to_u256_simd(reduced);
-    let v = transpose_simd_to_u256(u256_result);
+    let v = reduce_simd(t);
     (v[0], v[1])
 }
 
@@ -350,6 +327,65 @@ pub fn simd_mul(
     t[4 + 4 + 1] += p_hi.to_bits();
     t[4 + 4] += p_lo.to_bits();
 
+    let v = reduce_simd(t);
+    (v[0], v[1])
+}
+
+/// Number of [`simd_mul`] pairs interleaved by [`simd_mul8`]
+const PAIRS: usize = 4;
+
+/// 8 multiplications on the SIMD unit only, as 4 interleaved [`simd_mul`]
+/// pairs.
+///
+/// Unlike `block_mul` and `montgomery_interleaved_4`, nothing runs on the
+/// scalar unit. This trades the scalar throughput for more independent vector
+/// work, which suits cores whose NEON pipes are underused by the mixed blocks.
+#[inline]
+pub fn simd_mul8(a: [[u64; 4]; 8], b: [[u64; 4]; 8]) -> [[u64; 4]; 8] {
+    let av: [[Simd<f64, 2>; 5]; PAIRS] = array::from_fn(|k| {
+        u256_to_u260_shl2_simd(transpose_u256_to_simd([a[2 * k], a[2 * k + 1]]))
+            .map(|limb| unsafe { vcvtq_f64_u64(limb.into()).into() })
+    });
+    let bv: [[Simd<f64, 2>; 5]; PAIRS] = array::from_fn(|k| {
+        u256_to_u260_shl2_simd(transpose_u256_to_simd([b[2 * k], b[2 * k + 1]]))
+            .map(|limb| unsafe { vcvtq_f64_u64(limb.into()).into() })
+    });
+
+    let initial: [Simd<u64, 2>; 10] = [
+        Simd::splat(make_initial(1, 0)),
+        Simd::splat(make_initial(2, 1)),
+        Simd::splat(make_initial(3, 2)),
+        Simd::splat(make_initial(4, 3)),
+        Simd::splat(make_initial(10, 4)),
+        Simd::splat(make_initial(9, 10)),
+        Simd::splat(make_initial(8, 9)),
+        Simd::splat(make_initial(7, 8)),
+        Simd::splat(make_initial(6, 7)),
+        Simd::splat(make_initial(0, 6)),
+    ];
+    let mut t = [initial; PAIRS];
+
+    // The innermost loop runs over independent pairs, so consecutive FMAs do
+    // not depend on each other.
+    for i in 0..5 {
+        for j in 0..5 {
+            for k in 0..PAIRS {
+                let p_hi = av[k][i].mul_add(bv[k][j], Simd::splat(C1));
+                let p_lo = av[k][i].mul_add(bv[k][j], Simd::splat(C2) - p_hi);
+                t[k][i + j + 1] += p_hi.to_bits();
+                t[k][i + j] += p_lo.to_bits();
+            }
+        }
+    }
+
+    let v = t.map(reduce_simd);
+    array::from_fn(|i| v[i / 2][i % 2])
+}
+
+/// Montgomery reduction of the column sums of a [`simd_mul`] or [`simd_sqr`]
+/// product
+#[inline(always)]
+fn reduce_simd(mut t: [Simd<u64, 2>; 10]) -> [[u64; 4]; 2] {
     t[1] += t[0] >> 52;
     t[2] += t[1] >> 52;
     t[3] += t[2] >> 52;
@@ -374,6 +410,30 @@ pub fn simd_mul(
 
     let reduced = reduce_ct_simd(addv_simd(s, mp));
     let u256_result = u260_to_u256_simd(reduced);
-    let v = transpose_simd_to_u256(u256_result);
-    (v[0], v[1])
+    transpose_simd_to_u256(u256_result)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{ark_ff_reference, safe_bn254_montgomery_input},
+        ark_bn254::Fr,
+        ark_ff::BigInt,
+        proptest::proptest,
+    };
+
+    /// test that compares simd_mul8 with ark_ff
+    #[test]
+    fn test_simd_mul8() {
+        proptest!(|(
+            a in proptest::array::uniform8(safe_bn254_montgomery_input()),
+            b in proptest::array::uniform8(safe_bn254_montgomery_input()),
+        )| {
+            let out = simd_mul8(a, b);
+            for i in 0..8 {
+                assert_eq!(Fr::new(BigInt(out[i])), ark_ff_reference(a[i], b[i]));
+            }
+        });
+    }
 }
@@ -38,6 +38,20 @@ mod reduce {
             .bench_values(skyscraper::reduce::reduce)
     }
 
+    #[divan::bench]
+    fn reduce_loop(bencher: Bencher) {
+        bencher
+            .with_inputs(|| array::from_fn(|_| rng().random()))
+            .bench_values(skyscraper::reduce::reduce_loop)
+    }
+
+    #[divan::bench]
+    fn reduce_binary(bencher: Bencher) {
+        bencher
+            .with_inputs(|| array::from_fn(|_| rng().random()))
+            .bench_values(skyscraper::reduce::reduce_binary)
+    }
+
     #[divan::bench]
     fn reduce_add_rc(bencher: Bencher) {
         bencher
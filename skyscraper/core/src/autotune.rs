@@ -0,0 +1,174 @@
+//! Runtime selection of the fastest [`CompressManyFn`] implementation.
+//!
+//! Only implementations that compute the Skyscraper v2 compression are
+//! candidates. [`crate::v1`] implements a different permutation and is
+//! therefore excluded.
+
+use {
+    crate::{simple, CompressManyFn, WIDTH_LCM},
+    core::{fmt, str::FromStr},
+    std::{
+        hint::black_box,
+        time::{Duration, Instant},
+    },
+    tracing::{debug, info, info_span},
+};
+
+/// Number of compressions per benchmark iteration.
+const BATCH_SIZE: usize = WIDTH_LCM * 16;
+
+/// Identifies a [`CompressManyFn`] implementation.
+///
+/// The [`fmt::Display`] and [`FromStr`] implementations use stable names, so
+/// the result of [`autotune`] can be stored in a file and reloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompressImpl {
+    /// [`crate::simple`], portable scalar implementation.
+    Simple,
+    /// `block3`, three-way interleaved.
+    #[cfg(target_arch = "aarch64")]
+    Block3,
+    /// `block4`, four-way interleaved.
+    #[cfg(target_arch = "aarch64")]
+    Block4,
+}
+
+impl CompressImpl {
+    /// All implementations available on the current target.
+    pub const ALL: &[Self] = &[
+        Self::Simple,
+        #[cfg(target_arch = "aarch64")]
+        Self::Block3,
+        #[cfg(target_arch = "aarch64")]
+        Self::Block4,
+    ];
+
+    /// The compression function of this implementation.
+    pub fn dispatch(self) -> CompressManyFn {
+        match self {
+            Self::Simple => simple::compress_many,
+            #[cfg(target_arch = "aarch64")]
+            Self::Block3 => crate::block3::compress_many,
+            #[cfg(target_arch = "aarch64")]
+            Self::Block4 => crate::block4::compress_many,
+        }
+    }
+
+    /// Stable name of the implementation.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Simple => "simple",
+            #[cfg(target_arch = "aarch64")]
+            Self::Block3 => "block3",
+            #[cfg(target_arch = "aarch64")]
+            Self::Block4 => "block4",
+        }
+    }
+}
+
+impl fmt::Display for CompressImpl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing an unknown or unavailable [`CompressImpl`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownCompressImpl(pub String);
+
+impl fmt::Display for UnknownCompressImpl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown or unavailable compression implementation: {}",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownCompressImpl {}
+
+impl FromStr for CompressImpl {
+    type Err = UnknownCompressImpl;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|imp| imp.name() == s.trim())
+            .ok_or_else(|| UnknownCompressImpl(s.to_owned()))
+    }
+}
+
+/// Benchmarks all available implementations and returns the fastest.
+///
+/// Each implementation is run for about `sample_duration` on batches of
+/// `WIDTH_LCM * 16` compressions.
+pub fn autotune(sample_duration: Duration) -> CompressImpl {
+    let _span = info_span!("autotune", ?sample_duration).entered();
+    let messages = (0..BATCH_SIZE * 64)
+        .map(|i| (i * 0x9e) as u8)
+        .collect::<Vec<u8>>();
+    let mut hashes = vec![0_u8; BATCH_SIZE * 32];
+
+    let mut best = (CompressImpl::Simple, 0.0);
+    for &candidate in CompressImpl::ALL {
+        let _span = info_span!("candidate", name = candidate.name()).entered();
+        let compress_many = candidate.dispatch();
+
+        // Warm up caches and the rounding mode machinery.
+        compress_many(&messages, &mut hashes);
+
+        let start = Instant::now();
+        let mut iterations = 0_u64;
+        while iterations == 0 || start.elapsed() < sample_duration {
+            compress_many(black_box(&messages), black_box(&mut hashes));
+            iterations += 1;
+        }
+        let rate = (iterations as usize * BATCH_SIZE) as f64 / start.elapsed().as_secs_f64();
+        debug!(iterations, rate, "compressions per second");
+
+        if rate > best.1 {
+            best = (candidate, rate);
+        }
+    }
+    info!(
+        best = best.0.name(),
+        rate = best.1,
+        "selected implementation"
+    );
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for &imp in CompressImpl::ALL {
+            assert_eq!(imp.to_string().parse(), Ok(imp));
+        }
+        assert!("v1".parse::<CompressImpl>().is_err());
+    }
+
+    #[test]
+    fn test_dispatch_eq_ref() {
+        let messages = (0..WIDTH_LCM * 3 * 64)
+            .map(|i| i as u8)
+            .collect::<Vec<u8>>();
+        let mut expected = vec![0_u8; messages.len() / 2];
+        crate::reference::compress_many(&messages, &mut expected);
+        for &imp in CompressImpl::ALL {
+            let mut hashes = vec![0_u8; messages.len() / 2];
+            imp.dispatch()(&messages, &mut hashes);
+            assert_eq!(hashes, expected, "{imp}");
+        }
+    }
+
+    #[test]
+    fn test_autotune() {
+        let best = autotune(Duration::from_millis(5));
+        assert!(CompressImpl::ALL.contains(&best));
+    }
+}
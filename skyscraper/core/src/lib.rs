@@ -2,7 +2,14 @@
 #![feature(bigint_helper_methods)]
 #![feature(cold_path)]
 
+use {
+    crate::autotune::CompressImpl,
+    std::{cell::Cell, marker::PhantomData, sync::OnceLock},
+    zerocopy::{FromBytes, IntoBytes},
+};
+
 pub mod arithmetic;
+pub mod autotune;
 pub mod bar;
 pub mod constants;
 pub mod generic;
@@ -28,6 +35,127 @@ pub const WIDTH_LCM: usize = 12;
 
 pub type CompressManyFn = fn(&[u8], &mut [u8]);
 
-// TODO: Some autotune method that does a small benchmark on target hardware and
-// finds the optimal implementation. It should also have an enum that can be
-// stored in a file. And the benchmarking should be instrumented.
+/// Implementation used by [`compress_many`], selected on first use.
+static SELECTED: OnceLock<CompressImpl> = OnceLock::new();
+
+thread_local! {
+    /// Implementation forced on this thread by [`override_compress_impl`].
+    static OVERRIDE: Cell<Option<CompressImpl>> = const { Cell::new(None) };
+}
+
+/// Restores the previous implementation of [`compress_many`] on drop, see
+/// [`override_compress_impl`].
+#[must_use = "the override ends when the guard is dropped"]
+pub struct CompressImplGuard {
+    previous:  Option<CompressImpl>,
+    // The override is per thread, so it must end on the thread it started on.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for CompressImplGuard {
+    fn drop(&mut self) {
+        OVERRIDE.set(self.previous);
+    }
+}
+
+/// Batch compression using the best implementation for the current target.
+///
+/// Compresses each 64 byte message in `input` to a 32 byte hash in `output`.
+///
+/// # Panics
+///
+/// Panics if `input.len()` is not a multiple of 64, `output.len()` is not a
+/// multiple of 32 or the number of messages and hashes differ.
+pub fn compress_many(input: &[u8], output: &mut [u8]) {
+    assert_eq!(input.len() % 64, 0, "Message length not a multiple of 64");
+    assert_eq!(output.len() % 32, 0, "Hashes length not a multiple of 32");
+    assert_eq!(
+        input.len() / 64,
+        output.len() / 32,
+        "Messages and hashes length mismatch"
+    );
+    compress_impl().dispatch()(input, output);
+}
+
+/// Compresses a single 64 byte message to a 32 byte hash, like
+/// [`compress_many`] does for each message.
+///
+/// Uses the scalar implementation: the SIMD ones only pay off on several
+/// messages and need to switch the rounding mode around each call.
+pub fn compress(input: &[u8; 64]) -> [u8; 32] {
+    let [l, r] = <[[u64; 4]; 2]>::read_from_bytes(input).unwrap();
+    simple::compress(l, r).as_bytes().try_into().unwrap()
+}
+
+/// The implementation currently used by [`compress_many`].
+pub fn compress_impl() -> CompressImpl {
+    OVERRIDE.get().unwrap_or_else(|| {
+        *SELECTED.get_or_init(|| {
+            if cfg!(target_arch = "aarch64") {
+                *CompressImpl::ALL.last().unwrap()
+            } else {
+                CompressImpl::Simple
+            }
+        })
+    })
+}
+
+/// Force [`compress_many`] to use `implementation` on the current thread
+/// until the returned guard is dropped.
+///
+/// This is intended for tests and benchmarks. Other threads keep the
+/// automatic selection, so tests running in parallel are not affected.
+pub fn override_compress_impl(implementation: CompressImpl) -> CompressImplGuard {
+    CompressImplGuard {
+        previous:  OVERRIDE.replace(Some(implementation)),
+        _not_send: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_many_eq_ref() {
+        let input = (0..WIDTH_LCM * 5 * 64)
+            .map(|i| (i * 7) as u8)
+            .collect::<Vec<u8>>();
+        let mut expected = vec![0_u8; input.len() / 2];
+        reference::compress_many(&input, &mut expected);
+
+        let mut output = vec![0_u8; input.len() / 2];
+        compress_many(&input, &mut output);
+        assert_eq!(output, expected);
+
+        let selected = compress_impl();
+        for &implementation in CompressImpl::ALL {
+            let _guard = override_compress_impl(implementation);
+            assert_eq!(compress_impl(), implementation);
+            let mut output = vec![0_u8; input.len() / 2];
+            compress_many(&input, &mut output);
+            assert_eq!(output, expected, "{implementation}");
+        }
+        assert_eq!(compress_impl(), selected);
+    }
+
+    #[test]
+    fn test_compress_eq_compress_many() {
+        let input: [u8; 64] = std::array::from_fn(|i| (i * 7) as u8);
+        let mut expected = [0_u8; 32];
+        compress_many(&input, &mut expected);
+        assert_eq!(compress(&input), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of 64")]
+    fn test_compress_many_bad_input() {
+        compress_many(&[0; 63], &mut [0; 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of 32")]
+    fn test_compress_many_bad_output() {
+        compress_many(&[0; 64], &mut [0; 31]);
+    }
+}
@@ -16,6 +16,36 @@ pub fn reduce(x: [u64; 4]) -> [u64; 4] {
     reduce_1(reduce_partial(x))
 }
 
+/// Fully reduce any input to [0, M) by subtracting M until it underflows.
+///
+/// Alternative to [`reduce`] for comparison, takes up to five subtractions.
+#[inline(always)]
+pub fn reduce_loop(mut x: [u64; 4]) -> [u64; 4] {
+    loop {
+        let (r, borrow) = overflowing_sub(x, MODULUS[1]);
+        if borrow {
+            return x;
+        }
+        x = r;
+    }
+}
+
+/// Fully reduce any input to [0, M) by conditionally subtracting 4M, 2M and
+/// then M.
+///
+/// Alternative to [`reduce`] for comparison, without the quotient estimate.
+#[inline(always)]
+pub fn reduce_binary(x: [u64; 4]) -> [u64; 4] {
+    [4, 2, 1].into_iter().fold(x, |x, multiple| {
+        let (r, borrow) = overflowing_sub(x, MODULUS[multiple]);
+        if borrow {
+            x
+        } else {
+            r
+        }
+    })
+}
+
 /// Reduces an input by at most M.
 /// Accepts inputs in the range [0, 2^256)
 /// Optimized for likely x < M, e.g. a partially reduced input.
@@ -69,9 +99,40 @@ mod tests {
         crate::{arithmetic::add, constants::ROUND_CONSTANTS},
         ark_bn254::Fr,
         ark_ff::{BigInt, PrimeField},
+        num_bigint::BigUint,
         proptest::proptest,
     };
 
+    fn to_biguint(x: [u64; 4]) -> BigUint {
+        x.iter()
+            .rev()
+            .fold(BigUint::default(), |acc, &limb| (acc << 64) + limb)
+    }
+
+    /// Checks every full reduction against `num-bigint`
+    fn check_reductions(x: [u64; 4]) {
+        let expected = to_biguint(x) % to_biguint(MODULUS[1]);
+        for (name, reduction) in [
+            ("reduce", reduce as fn([u64; 4]) -> [u64; 4]),
+            ("reduce_loop", reduce_loop),
+            ("reduce_binary", reduce_binary),
+        ] {
+            assert_eq!(to_biguint(reduction(x)), expected, "{name}({x:?})");
+        }
+    }
+
+    #[test]
+    fn test_reductions_agree() {
+        proptest!(|(x: [u64; 4])| check_reductions(x));
+        check_reductions([u64::MAX; 4]);
+        for i in 0..6 {
+            check_reductions(MODULUS[i]);
+            let mut x = [u64::MAX; 4];
+            x[3] = MODULUS[i][3] + 1;
+            check_reductions(x);
+        }
+    }
+
     #[test]
     fn test_reduce() {
         proptest!(|(x: [u64; 4])| {
@@ -2,11 +2,16 @@
 //! Floating point rounding mode control for aarch64 architecture.
 //!
 //! See <https://developer.arm.com/documentation/ddi0595/2021-06/AArch64-Registers/FPCR--Floating-point-Control-Register>
-use {crate::RoundingDirection, core::arch::asm};
+use {
+    crate::{Denormals, RoundingDirection},
+    core::arch::asm,
+};
 
 /// Layout of the floating point control register (FPCR).
 const SHIFT: u32 = 22;
 const BIT_MASK: u64 = 0b11 << SHIFT;
+/// Flush-to-zero bit of the FPCR.
+const FZ_MASK: u64 = 1 << 24;
 
 #[must_use]
 fn from_bits(bits: u64) -> RoundingDirection {
@@ -29,8 +34,8 @@ const fn to_bits(mode: RoundingDirection) -> u64 {
     }
 }
 
-/// Read the rounding mode bits from the FPCR register
-pub fn read_rounding_mode() -> RoundingDirection {
+/// Read the FPCR register
+fn read_fpcr() -> u64 {
     let mut bits: u64;
     unsafe {
         asm!(
@@ -39,25 +44,58 @@ pub fn read_rounding_mode() -> RoundingDirection {
             options(nomem, nostack, preserves_flags)
         );
     }
-    from_bits(bits)
+    bits
 }
 
-/// Update the rounding mode bits in the FPCR register
-pub unsafe fn write_rounding_mode(mode: RoundingDirection) {
+/// Replace the bits selected by `mask` in the FPCR register with `bits`
+unsafe fn update_fpcr(mask: u64, bits: u64) {
     unsafe {
         asm!(
             "mrs {tmp}, fpcr", // Read Floating Point Control Register into tmp
-            "bic {tmp}, {tmp}, {mask}", // Clear the rounding mode bits in tmp
-            "orr {tmp}, {tmp}, {bits}", // Set the rounding mode bits in tmp
+            "bic {tmp}, {tmp}, {mask}", // Clear the masked bits in tmp
+            "orr {tmp}, {tmp}, {bits}", // Set the new bits in tmp
             "msr fpcr, {tmp}", // Write the modified FPCR value back to the register
             tmp = out(reg) _,
-            mask = in(reg) BIT_MASK,
-            bits = in(reg) to_bits(mode),
+            mask = in(reg) mask,
+            bits = in(reg) bits,
             options(nomem, nostack, preserves_flags)
         );
     }
 }
 
+/// Read the whole FPCR register
+pub fn read_raw() -> u64 {
+    read_fpcr()
+}
+
+/// Read the rounding mode bits from the FPCR register
+pub fn read_rounding_mode() -> RoundingDirection {
+    from_bits(read_fpcr())
+}
+
+/// Update the rounding mode bits in the FPCR register
+pub unsafe fn write_rounding_mode(mode: RoundingDirection) {
+    unsafe { update_fpcr(BIT_MASK, to_bits(mode)) };
+}
+
+/// Read the flush-to-zero bit from the FPCR register
+pub fn read_denormals() -> Denormals {
+    if read_fpcr() & FZ_MASK == 0 {
+        Denormals::Preserve
+    } else {
+        Denormals::Flush
+    }
+}
+
+/// Update the flush-to-zero bit in the FPCR register
+pub unsafe fn write_denormals(denormals: Denormals) {
+    let bits = match denormals {
+        Denormals::Preserve => 0,
+        Denormals::Flush => FZ_MASK,
+    };
+    unsafe { update_fpcr(FZ_MASK, bits) };
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::test_rounding_mode};
@@ -74,4 +112,16 @@ mod tests {
             test_rounding_mode(mode);
         }
     }
+
+    #[test]
+    fn test_read_write_denormals() {
+        assert_eq!(read_denormals(), Denormals::Preserve);
+        for denormals in [Denormals::Flush, Denormals::Preserve] {
+            unsafe {
+                write_denormals(denormals);
+            }
+            assert_eq!(read_denormals(), denormals);
+            assert_eq!(read_rounding_mode(), RoundingDirection::Nearest);
+        }
+    }
 }
@@ -3,17 +3,26 @@
 //! Floating point rounding mode control for x86_64 architecture.
 //!
 //! See <https://www.intel.com/content/www/us/en/developer/articles/technical/intel-sdm.html> Volume 1, Chapter 10.
-use {crate::RoundingDirection, core::arch::asm};
+use {
+    crate::{Denormals, RoundingDirection},
+    core::arch::asm,
+};
 
+/// Layout of the rounding control (RC) field in the MXCSR register.
+///
+/// Note that the encoding differs from aarch64: on x86 `0b01` rounds down and
+/// `0b10` rounds up.
 const SHIFT: u32 = 13;
 const BIT_MASK: u32 = 0b11 << SHIFT;
+/// Flush-to-zero (bit 15) and denormals-are-zero (bit 6) flags of the MXCSR.
+const FTZ_DAZ_MASK: u32 = (1 << 15) | (1 << 6);
 
 #[must_use]
 fn from_bits(bits: u32) -> RoundingDirection {
     match (bits & BIT_MASK) >> SHIFT {
         0b00 => RoundingDirection::Nearest,
-        0b01 => RoundingDirection::Positive,
-        0b10 => RoundingDirection::Negative,
+        0b01 => RoundingDirection::Negative,
+        0b10 => RoundingDirection::Positive,
         0b11 => RoundingDirection::Zero,
         _ => unreachable!(),
     }
@@ -23,13 +32,14 @@ fn from_bits(bits: u32) -> RoundingDirection {
 const fn to_bits(mode: RoundingDirection) -> u32 {
     match mode {
         RoundingDirection::Nearest => 0b00 << SHIFT,
-        RoundingDirection::Positive => 0b01 << SHIFT,
-        RoundingDirection::Negative => 0b10 << SHIFT,
+        RoundingDirection::Negative => 0b01 << SHIFT,
+        RoundingDirection::Positive => 0b10 << SHIFT,
         RoundingDirection::Zero => 0b11 << SHIFT,
     }
 }
 
-pub fn read_rounding_mode() -> RoundingDirection {
+/// Read the MXCSR register
+fn read_mxcsr() -> u32 {
     let mut mxcsr = 0_u32;
     unsafe {
         asm!(
@@ -38,20 +48,12 @@ pub fn read_rounding_mode() -> RoundingDirection {
             options(nostack, preserves_flags)
         );
     }
-    from_bits(mxcsr)
+    mxcsr
 }
 
-pub unsafe fn write_rounding_mode(mode: RoundingDirection) {
-    // Update the rounding mode bits in the FPCR register
-    let mut mxcsr = 0_u32;
-    unsafe {
-        asm!(
-            "stmxcsr [{ptr}]", // Store MXCSR register value into memory.
-            ptr = in(reg) &mut mxcsr,
-            options(nostack, preserves_flags)
-        );
-    }
-    mxcsr = (mxcsr & !BIT_MASK) | to_bits(mode);
+/// Replace the bits selected by `mask` in the MXCSR register with `bits`
+unsafe fn update_mxcsr(mask: u32, bits: u32) {
+    let mxcsr = (read_mxcsr() & !mask) | bits;
     unsafe {
         asm!(
             "ldmxcsr [{ptr}]", // Load MXCSR from memory into register.
@@ -61,9 +63,46 @@ pub unsafe fn write_rounding_mode(mode: RoundingDirection) {
     }
 }
 
+/// Read the whole MXCSR register, zero-extended
+pub fn read_raw() -> u64 {
+    read_mxcsr().into()
+}
+
+/// Read the rounding mode bits from the MXCSR register
+pub fn read_rounding_mode() -> RoundingDirection {
+    from_bits(read_mxcsr())
+}
+
+/// Update the rounding mode bits in the MXCSR register
+pub unsafe fn write_rounding_mode(mode: RoundingDirection) {
+    unsafe { update_mxcsr(BIT_MASK, to_bits(mode)) };
+}
+
+/// Read the flush-to-zero and denormals-are-zero bits from the MXCSR register
+pub fn read_denormals() -> Denormals {
+    if read_mxcsr() & FTZ_DAZ_MASK == 0 {
+        Denormals::Preserve
+    } else {
+        Denormals::Flush
+    }
+}
+
+/// Update the flush-to-zero and denormals-are-zero bits in the MXCSR register
+pub unsafe fn write_denormals(denormals: Denormals) {
+    let bits = match denormals {
+        Denormals::Preserve => 0,
+        Denormals::Flush => FTZ_DAZ_MASK,
+    };
+    unsafe { update_mxcsr(FTZ_DAZ_MASK, bits) };
+}
+
 #[cfg(test)]
 mod tests {
-    use {super::*, crate::test_rounding_mode};
+    use {
+        super::*,
+        crate::{test_rounding_mode, utils::fence, with_rounding_mode, RoundingGuard, Zero},
+        proptest::proptest,
+    };
 
     #[test]
     fn test_read_write() {
@@ -77,4 +116,75 @@ mod tests {
             test_rounding_mode(mode);
         }
     }
+
+    #[test]
+    fn test_read_write_denormals() {
+        assert_eq!(read_denormals(), Denormals::Preserve);
+        for denormals in [Denormals::Flush, Denormals::Preserve] {
+            unsafe {
+                write_denormals(denormals);
+            }
+            assert_eq!(read_denormals(), denormals);
+            assert_eq!(read_rounding_mode(), RoundingDirection::Nearest);
+        }
+    }
+
+    #[test]
+    fn test_bits_roundtrip() {
+        use RoundingDirection::*;
+        for mode in [Nearest, Positive, Negative, Zero] {
+            assert_eq!(from_bits(to_bits(mode)), mode);
+        }
+        // Round up is 0b10 and round down is 0b01 in MXCSR.RC
+        assert_eq!(to_bits(Positive), 0x4000);
+        assert_eq!(to_bits(Negative), 0x2000);
+    }
+
+    #[test]
+    fn test_guard_restores() {
+        unsafe {
+            with_rounding_mode((), |_: &RoundingGuard<Zero>, ()| {
+                assert_eq!(read_rounding_mode(), RoundingDirection::Zero);
+            });
+        }
+        assert_eq!(read_rounding_mode(), RoundingDirection::Nearest);
+    }
+
+    /// Check the rounding of `a ± b` where `b` is less than half an ULP of `a`.
+    #[allow(clippy::float_cmp)]
+    fn check_rounding(mode: RoundingDirection, a: f64) {
+        let b = a * 2.0_f64.powi(-54);
+        let (a, b) = fence((a, b));
+        let (sum, diff) = fence((a + b, a - b));
+        match mode {
+            RoundingDirection::Nearest => {
+                assert_eq!(sum, a);
+                assert_eq!(diff, a);
+            }
+            RoundingDirection::Positive => {
+                assert!(sum > a);
+                assert_eq!(diff, a);
+            }
+            RoundingDirection::Negative => {
+                assert_eq!(sum, a);
+                assert!(diff < a);
+            }
+            RoundingDirection::Zero => {
+                assert_eq!(sum, a);
+                assert!(diff < a);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_rounding(a in 1.0_f64..2.0) {
+            use RoundingDirection::*;
+            for mode in [Negative, Positive, Zero, Nearest] {
+                unsafe { write_rounding_mode(mode) };
+                check_rounding(mode, a);
+            }
+            assert_eq!(read_rounding_mode(), Nearest);
+        }
+    }
 }
@@ -0,0 +1,13 @@
+/// Treatment of subnormal (denormal) floating-point values.
+///
+/// When flushing, subnormal inputs and results are replaced by a zero of the
+/// same sign. This is controlled by FPCR.FZ on aarch64 and by MXCSR.FTZ and
+/// MXCSR.DAZ on x86_64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Denormals {
+    /// IEEE 754 gradual underflow.
+    #[default]
+    Preserve,
+    /// Flush subnormal values to zero.
+    Flush,
+}
@@ -1,25 +1,46 @@
 #![allow(unsafe_code)]
 #![no_std]
+#![feature(thread_local)]
 //! Round Toward Zero (RTZ) floating-point rounding mode control
 //!
 //! Rust/LLVM does not support different float point mode rounding modes and
 //! this module provides abstractions to able to control the aarch64
-//! FPCR (Floating-point Control Register) rounding mode. For how this module
-//! provides a safe abstraction see the documentation of [`RoundingDirection`].
+//! FPCR (Floating-point Control Register) and x86_64 MXCSR rounding mode. For
+//! how this module provides a safe abstraction see the documentation of
+//! [`RoundingDirection`].
 
 mod arch;
+mod denormals;
 mod rounding_direction;
 mod rounding_guard;
 mod utils;
 
-use crate::utils::fence;
+use crate::{arch::read_rounding_mode, rounding_guard::guard_active, utils::fence};
 pub use crate::{
+    denormals::Denormals,
     rounding_direction::{
         Nearest, Negative, Positive, RoundingDirection, RoundingDirectionMarker, Zero,
     },
-    rounding_guard::RoundingGuard,
+    rounding_guard::{RoundingBusy, RoundingGuard},
 };
 
+/// Returns the floating-point rounding mode active on the current thread.
+///
+/// This only reads the control register (FPCR on aarch64, MXCSR on x86_64)
+/// and does not interfere with an active [`RoundingGuard`]. Useful for
+/// assertions like
+///
+/// ```rust
+/// use fp_rounding::{current_rounding_mode, RoundingDirection};
+///
+/// debug_assert_eq!(current_rounding_mode(), RoundingDirection::Nearest);
+/// ```
+#[must_use]
+#[inline]
+pub fn current_rounding_mode() -> RoundingDirection {
+    read_rounding_mode()
+}
+
 /// Call closure with a specific rounding mode.
 ///
 ///  `with_rounding_mode` provides a safe-ish abstraction (see Safety
@@ -55,16 +76,44 @@ pub use crate::{
 ///    has been set. This means that when you nest two call to
 ///    `with_rounding_mode` with different rounding modes the closest
 ///    `with_rounding_mode` in the call stack determines the rounding mode.
+///    Nesting is not detected; use [`try_with_rounding_mode`] if that is a
+///    concern.
 pub unsafe fn with_rounding_mode<Mode: RoundingDirectionMarker, Input, Result>(
     input: Input,
     f: impl for<'a> FnOnce(&'a RoundingGuard<Mode>, Input) -> Result,
+) -> Result {
+    unsafe { with_guard(input, || RoundingGuard::new(), f) }
+}
+
+/// Call closure with a specific rounding mode and subnormals flushed to zero.
+///
+/// Behaves like [`with_rounding_mode`], but additionally enables
+/// flush-to-zero ([`Denormals::Flush`]) for the duration of the call. Both
+/// the rounding mode and the denormal handling are restored afterwards.
+///
+/// # Safety
+///
+/// See [`with_rounding_mode`].
+pub unsafe fn with_rounding_mode_and_flush<Mode: RoundingDirectionMarker, Input, Result>(
+    input: Input,
+    f: impl for<'a> FnOnce(&'a RoundingGuard<Mode>, Input) -> Result,
+) -> Result {
+    unsafe { with_guard(input, || RoundingGuard::with_denormals(Denormals::Flush), f) }
+}
+
+/// Run `f` with the guard created by `new_guard`.
+#[inline(always)]
+unsafe fn with_guard<Mode: RoundingDirectionMarker, Input, Result>(
+    input: Input,
+    new_guard: impl FnOnce() -> RoundingGuard<Mode>,
+    f: impl for<'a> FnOnce(&'a RoundingGuard<Mode>, Input) -> Result,
 ) -> Result {
     let input = fence(input);
     // - The for 'a prevents the Mode token from being leaked by the closure.
     // - The function f is marked !Send + !Sync due to Mode
     // - The function f takes a reference to [`Mode`] such that on nested calls the
     //   drop order is still correct
-    let guard = RoundingGuard::new();
+    let guard = new_guard();
     // Tie the mode to the input such that mode will be seated before the first use
     // of input by f.
     // Tieing the input to the Mode does not seem to be strictly necessary, but it's
@@ -78,6 +127,25 @@ pub unsafe fn with_rounding_mode<Mode: RoundingDirectionMarker, Input, Result>(
     fence(result)
 }
 
+/// Call closure with a specific rounding mode unless one is already active.
+///
+/// Like [`with_rounding_mode`], but returns [`RoundingBusy`] instead of
+/// silently nesting when a [`RoundingGuard`] is already alive on the current
+/// thread.
+///
+/// # Safety
+///
+/// See [`with_rounding_mode`].
+pub unsafe fn try_with_rounding_mode<Mode: RoundingDirectionMarker, Input, Output>(
+    input: Input,
+    f: impl for<'a> FnOnce(&'a RoundingGuard<Mode>, Input) -> Output,
+) -> Result<Output, RoundingBusy> {
+    if guard_active() {
+        return Err(RoundingBusy);
+    }
+    Ok(unsafe { with_rounding_mode(input, f) })
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 fn test_rounding_mode(mode: RoundingDirection) {
@@ -125,6 +193,84 @@ mod test {
         test_rounding_mode(RoundingDirection::Zero);
     }
 
+    #[test]
+    fn test_current_rounding_mode() {
+        assert_eq!(current_rounding_mode(), RoundingDirection::Nearest);
+        unsafe {
+            with_rounding_mode((), |_: &RoundingGuard<Zero>, ()| {
+                assert_eq!(current_rounding_mode(), RoundingDirection::Zero);
+                with_rounding_mode((), |_: &RoundingGuard<Positive>, ()| {
+                    assert_eq!(current_rounding_mode(), RoundingDirection::Positive);
+                });
+                assert_eq!(current_rounding_mode(), RoundingDirection::Zero);
+            });
+        }
+        assert_eq!(current_rounding_mode(), RoundingDirection::Nearest);
+    }
+
+    #[test]
+    fn test_previous_raw() {
+        let default = arch::read_raw();
+        unsafe {
+            with_rounding_mode((), |zero: &RoundingGuard<Zero>, ()| {
+                assert_eq!(zero.previous_raw(), default);
+                let in_zero = arch::read_raw();
+                assert_ne!(in_zero, default);
+                with_rounding_mode((), |nearest: &RoundingGuard<Nearest>, ()| {
+                    assert_eq!(nearest.previous_raw(), in_zero);
+                    assert_eq!(arch::read_raw(), default);
+                });
+            });
+            with_rounding_mode_and_flush((), |nearest: &RoundingGuard<Nearest>, ()| {
+                assert_eq!(nearest.previous_raw(), default);
+            });
+        }
+    }
+
+    #[test]
+    fn test_try_with_rounding_mode() {
+        unsafe {
+            let outer = try_with_rounding_mode((), |_: &RoundingGuard<Zero>, ()| {
+                let nested = try_with_rounding_mode((), |_: &RoundingGuard<Positive>, ()| ());
+                assert_eq!(nested, Err(RoundingBusy));
+                assert_eq!(current_rounding_mode(), RoundingDirection::Zero);
+                // The fast path still nests silently.
+                with_rounding_mode((), requires_round_to_positive);
+                let nested = try_with_rounding_mode((), |_: &RoundingGuard<Positive>, ()| ());
+                assert_eq!(nested, Err(RoundingBusy));
+                42
+            });
+            assert_eq!(outer, Ok(42));
+            assert_eq!(
+                try_with_rounding_mode((), requires_round_to_negative),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn test_flush_to_zero() {
+        fn add_zero(x: f64) -> f64 {
+            let (x, zero) = fence((x, 0.0));
+            fence(x + zero)
+        }
+
+        let denormal = f64::MIN_POSITIVE / 4.0;
+        assert!(denormal.is_subnormal());
+        assert_eq!(add_zero(denormal), denormal);
+        unsafe {
+            with_rounding_mode_and_flush((), |_: &RoundingGuard<Zero>, ()| {
+                assert_eq!(current_rounding_mode(), RoundingDirection::Zero);
+                assert_eq!(add_zero(denormal), 0.0);
+            });
+            with_rounding_mode((), |_: &RoundingGuard<Zero>, ()| {
+                assert_eq!(add_zero(denormal), denormal);
+            });
+        }
+        assert_eq!(add_zero(denormal), denormal);
+        assert_eq!(arch::read_denormals(), Denormals::Preserve);
+    }
+
     #[test]
     fn test() {
         unsafe {
@@ -1,12 +1,36 @@
 use {
     crate::{
-        arch::{read_rounding_mode, write_rounding_mode},
+        arch::{
+            read_denormals, read_raw, read_rounding_mode, write_denormals, write_rounding_mode,
+        },
         utils::NotSendSync,
-        RoundingDirection, RoundingDirectionMarker,
+        Denormals, RoundingDirection, RoundingDirectionMarker,
     },
-    core::marker::PhantomData,
+    core::{cell::Cell, fmt, marker::PhantomData},
 };
 
+/// Whether a [`RoundingGuard`] is alive on the current OS-thread.
+#[thread_local]
+static ACTIVE: Cell<bool> = Cell::new(false);
+
+/// Returns true if a [`RoundingGuard`] is alive on the current thread.
+pub(crate) fn guard_active() -> bool {
+    ACTIVE.get()
+}
+
+/// Error returned when a rounding mode is requested while another
+/// [`RoundingGuard`] is already active on the current thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RoundingBusy;
+
+impl fmt::Display for RoundingBusy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a rounding mode guard is already active on this thread")
+    }
+}
+
+impl core::error::Error for RoundingBusy {}
+
 /// Guard to mark that the floating-point rounding mode has been set.
 ///
 /// This struct must to be passed as a (unused) reference to any function that
@@ -17,24 +41,53 @@ use {
 ///
 /// This type is marked !Send + !Sync because FPCR is a per-core / per OS-thread
 /// register.
-// TODO: Force one per thread.
 pub struct RoundingGuard<M: RoundingDirectionMarker> {
-    previous: RoundingDirection,
-    mode:     PhantomData<M>,
-    _marker:  NotSendSync,
+    previous:     RoundingDirection,
+    /// Control register before this guard changed it.
+    previous_raw: u64,
+    /// Denormal handling to restore, if it was changed by this guard.
+    denormals:    Option<Denormals>,
+    was_active:   bool,
+    mode:         PhantomData<M>,
+    _marker:      NotSendSync,
 }
 
 impl<M: RoundingDirectionMarker> RoundingGuard<M> {
     /// Create a new mode guard.
     pub(crate) unsafe fn new() -> Self {
+        let previous_raw = read_raw();
         let previous = read_rounding_mode();
+        let was_active = ACTIVE.replace(true);
         unsafe { write_rounding_mode(M::MODE) };
         Self {
             previous,
+            previous_raw,
+            denormals: None,
+            was_active,
             mode: PhantomData,
             _marker: NotSendSync::default(),
         }
     }
+
+    /// Create a new mode guard that also sets the denormal handling.
+    pub(crate) unsafe fn with_denormals(denormals: Denormals) -> Self {
+        let previous_raw = read_raw();
+        let previous = read_denormals();
+        unsafe { write_denormals(denormals) };
+        let mut guard = unsafe { Self::new() };
+        guard.previous_raw = previous_raw;
+        guard.denormals = Some(previous);
+        guard
+    }
+
+    /// Value of the control register (FPCR on aarch64, MXCSR on x86_64)
+    /// before this guard was created, e.g. to check that the caller was not
+    /// already in a non-default mode. The guard restores the rounding mode and
+    /// denormal bits of this value on drop.
+    #[must_use]
+    pub fn previous_raw(&self) -> u64 {
+        self.previous_raw
+    }
 }
 
 /// Implement the Drop trait for `RoundingGuard` to make sure the rounding mode
@@ -42,5 +95,9 @@ impl<M: RoundingDirectionMarker> RoundingGuard<M> {
 impl<M: RoundingDirectionMarker> Drop for RoundingGuard<M> {
     fn drop(&mut self) {
         unsafe { write_rounding_mode(self.previous) };
+        if let Some(denormals) = self.denormals {
+            unsafe { write_denormals(denormals) };
+        }
+        ACTIVE.set(self.was_active);
     }
 }
@@ -3,6 +3,7 @@ use {
         ir::{FreshRegister, HardwareRegister, Instruction, TypedHardwareRegister, Variable},
         liveness::{Lifetime, Lifetimes},
         reification::{Index, RegisterType, ReifiedRegister},
+        target::Target,
         FreshVariable,
     },
     std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
@@ -177,20 +178,22 @@ impl Default for RegisterBank {
 }
 
 impl RegisterBank {
-    /// Creates a new RegisterBank with default register pools.
+    /// Creates a new RegisterBank with the aarch64 register pools.
+    pub fn new() -> Self {
+        Self::for_target(Target::Aarch64)
+    }
+
+    /// Creates a new RegisterBank with the register pools of `target`.
     ///
     /// # Returns
     ///
     /// A new RegisterBank with general-purpose and vector register pools.
-    /// Certain registers are excluded:
-    /// - Register 18 (reserved by OS)
-    /// - Register 19 (reserved by LLVM)
-    /// - Register 30 (reserved for link register)
-    /// - Register 31 (reserved for stack pointer)
-    pub fn new() -> Self {
+    /// Registers reserved by the OS, LLVM or the ABI and the scratch registers
+    /// of the lowering are excluded.
+    pub fn for_target(target: Target) -> Self {
         Self {
-            general_purpose: RegisterAllocator::new((0..=17).chain(20..29)),
-            vector:          RegisterAllocator::new(0..=31),
+            general_purpose: RegisterAllocator::new(target.general_purpose_registers().into_iter()),
+            vector:          RegisterAllocator::new(target.vector_registers().into_iter()),
         }
     }
 
@@ -8,8 +8,10 @@ use {
             generate_rust_global_asm, generate_rust_includable_asm, generate_rust_inline_asm,
         },
         frontend::{Assembler, FreshAllocator, FreshVariable},
-        ir::{HardwareRegister, Instruction, Variable},
+        ir::{Allocation, HardwareRegister, Instruction, Ir, Variable},
         liveness::liveness_analysis,
+        target::Target,
+        validation::validate,
         AtomicInstructionBlock,
     },
     std::{
@@ -30,8 +32,9 @@ pub type Setup =
 /// * `path` - The path where the assembly file will be written
 /// * `label` - The label for the assembly function
 /// * `f` - The setup function that creates the assembly
-pub fn build_single<P: AsRef<Path>>(path: P, label: &str, f: Setup) {
-    build_standalone(path, label, Interleaving::single(f));
+/// * `target` - The architecture to generate assembly for
+pub fn build_single<P: AsRef<Path>>(path: P, label: &str, f: Setup, target: Target) {
+    build_standalone(path, label, Interleaving::single(f), target);
 }
 
 /// Builds one or more interleaved assembly functions.
@@ -48,21 +51,31 @@ pub fn build_single<P: AsRef<Path>>(path: P, label: &str, f: Setup) {
 /// * `path` - The path where the assembly file will be written
 /// * `label` - The label for the assembly function
 /// * `algos` - The interleaved setup functions
-pub fn build_standalone<P: AsRef<Path>>(path: P, label: &str, algos: Interleaving<Setup>) {
-    build(path, algos, |inputs, outputs, instructions| {
-        generate_rust_global_asm(label, inputs, outputs, instructions)
+/// * `target` - The architecture to generate assembly for
+pub fn build_standalone<P: AsRef<Path>>(
+    path: P,
+    label: &str,
+    algos: Interleaving<Setup>,
+    target: Target,
+) {
+    build(path, algos, target, |inputs, outputs, instructions| {
+        generate_rust_global_asm(target, label, inputs, outputs, instructions)
     })
 }
 
-pub fn build_includable<P: AsRef<Path>>(path: P, algos: Interleaving<Setup>) {
-    build(path, algos, generate_rust_includable_asm)
+pub fn build_includable<P: AsRef<Path>>(path: P, algos: Interleaving<Setup>, target: Target) {
+    build(path, algos, target, |inputs, outputs, instructions| {
+        generate_rust_includable_asm(target, inputs, outputs, instructions)
+    })
 }
 
-pub fn build_inline<P: AsRef<Path>>(path: P, algos: Interleaving<Setup>) {
-    build(path, algos, generate_rust_inline_asm)
+pub fn build_inline<P: AsRef<Path>>(path: P, algos: Interleaving<Setup>, target: Target) {
+    build(path, algos, target, |inputs, outputs, instructions| {
+        generate_rust_inline_asm(target, inputs, outputs, instructions)
+    })
 }
 
-pub fn build<P, C>(path: P, algos: Interleaving<Setup>, codegen: C)
+pub fn build<P, C>(path: P, algos: Interleaving<Setup>, target: Target, codegen: C)
 where
     P: AsRef<Path>,
     C: FnOnce(
@@ -70,10 +83,32 @@ where
         &[AllocatedVariable],
         &[Instruction<HardwareRegister>],
     ) -> String,
+{
+    let assembly = assemble(algos, target, codegen);
+
+    use std::io::Write;
+    let mut file = std::fs::File::create(&path)
+        .unwrap_or_else(|_| panic!("Unable to create file: {:#?}", path.as_ref()));
+    file.write_all(assembly.as_bytes())
+        .unwrap_or_else(|_| panic!("Unable to write assembly to file: {:#?}", path.as_ref()));
+}
+
+/// Runs the pipeline of [`build`] and returns the generated assembly instead
+/// of writing it to a file.
+///
+/// Set `HLA_DUMP_IR=1` to print the IR, the live ranges and the assigned
+/// hardware registers to stderr before generating the assembly.
+pub fn assemble<C>(algos: Interleaving<Setup>, target: Target, codegen: C) -> String
+where
+    C: FnOnce(
+        &[AllocatedVariable],
+        &[AllocatedVariable],
+        &[Instruction<HardwareRegister>],
+    ) -> String,
 {
     let mut alloc = FreshAllocator::new();
     let mut mapping = RegisterMapping::new();
-    let mut register_bank = RegisterBank::new();
+    let mut register_bank = RegisterBank::for_target(target);
 
     let (input_hw_registers, output_hw_registers, instructions) = run_setups(&mut alloc, algos);
 
@@ -84,12 +119,20 @@ where
 
     let instructions: Vec<_> = instructions.into_iter().flatten().collect();
 
+    let mut ir = Ir {
+        inputs:       &input_hw_registers,
+        outputs:      &output_hw_registers,
+        instructions: &instructions,
+        allocation:   None,
+    };
+    validate(&ir).unwrap_or_else(|error| panic!("Invalid IR: {error}"));
+
     let (releases, lifetimes) = liveness_analysis(&alloc, &output_hw_registers, &instructions);
 
-    let input_hw_registers = allocate_input_variable(
+    let allocated_inputs = allocate_input_variable(
         &mut mapping,
         &mut register_bank,
-        input_hw_registers,
+        input_hw_registers.clone(),
         &lifetimes,
     );
 
@@ -100,28 +143,31 @@ where
     let hardware_instructions = hardware_register_allocation(
         &mut mapping,
         &mut register_bank,
-        instructions,
+        instructions.clone(),
         releases,
         lifetimes,
     );
 
+    ir.allocation = Some(Allocation {
+        inputs:       &allocated_inputs,
+        instructions: &hardware_instructions,
+    });
+    validate(&ir).unwrap_or_else(|error| panic!("Invalid register allocation: {error}"));
+
+    if std::env::var("HLA_DUMP_IR").is_ok_and(|value| value == "1") {
+        eprintln!("{ir}");
+    }
+
     let output_hw_registers: Vec<_> = output_hw_registers
         .iter()
         .map(|fresh_variable| mapping.get_allocated_variable(fresh_variable))
         .collect();
 
-    // Write this info in the assembly file
-    let assembly = codegen(
-        &input_hw_registers,
+    codegen(
+        &allocated_inputs,
         &output_hw_registers,
         &hardware_instructions,
-    );
-
-    use std::io::Write;
-    let mut file = std::fs::File::create(&path)
-        .unwrap_or_else(|_| panic!("Unable to create file: {:#?}", path.as_ref()));
-    file.write_all(assembly.as_bytes())
-        .unwrap_or_else(|_| panic!("Unable to write assembly to file: {:#?}", path.as_ref()));
+    )
 }
 
 /// Runs setup functions according to their interleaving pattern.
@@ -332,7 +378,144 @@ fn interleave<T>(lhs: Vec<T>, rhs: Vec<T>) -> Vec<T> {
 
 #[cfg(test)]
 mod test {
-    use quickcheck_macros::quickcheck;
+    use {
+        super::{assemble, Interleaving, Setup},
+        crate::{codegen::generate_rust_includable_asm, target::Target, *},
+        quickcheck_macros::quickcheck,
+    };
+
+    /// Computes the 128-bit `a * b + a`.
+    fn setup_mul_add(
+        alloc: &mut FreshAllocator,
+        asm: &mut Assembler,
+    ) -> (Vec<FreshVariable>, FreshVariable) {
+        let a = alloc.fresh();
+        let b = alloc.fresh();
+        let lo = mul(alloc, asm, &a, &b);
+        let hi = umulh(alloc, asm, &a, &b);
+
+        let out: [_; 2] = alloc.fresh_array();
+        asm.append_instruction(vec![
+            adds_inst(&out[0], &lo, &a),
+            cinc_inst(&out[1], &hi, "hs".to_string()),
+        ]);
+
+        (
+            vec![FreshVariable::new("a", &[a]), FreshVariable::new("b", &[b])],
+            FreshVariable::new("out", &out),
+        )
+    }
+
+    /// Computes `a - b` and `a + b + carry`, where the carry of the addition
+    /// is the one left by the subtraction.
+    fn setup_sub_then_add(
+        alloc: &mut FreshAllocator,
+        asm: &mut Assembler,
+    ) -> (Vec<FreshVariable>, FreshVariable) {
+        let a = alloc.fresh();
+        let b = alloc.fresh();
+
+        let out: [_; 2] = alloc.fresh_array();
+        asm.append_instruction(vec![subs_inst(&out[0], &a, &b), adcs_inst(&out[1], &a, &b)]);
+
+        (
+            vec![FreshVariable::new("a", &[a]), FreshVariable::new("b", &[b])],
+            FreshVariable::new("out", &out),
+        )
+    }
+
+    /// Reads the carry of an addition after an `and`.
+    fn setup_and_in_carry_chain(
+        alloc: &mut FreshAllocator,
+        asm: &mut Assembler,
+    ) -> (Vec<FreshVariable>, FreshVariable) {
+        let a = alloc.fresh();
+        let b = alloc.fresh();
+
+        let out: [_; 3] = alloc.fresh_array();
+        asm.append_instruction(vec![
+            adds_inst(&out[0], &a, &b),
+            and_inst(&out[1], &a, &b),
+            cinc_inst(&out[2], &b, "hs".to_string()),
+        ]);
+
+        (
+            vec![FreshVariable::new("a", &[a]), FreshVariable::new("b", &[b])],
+            FreshVariable::new("out", &out),
+        )
+    }
+
+    fn assemble_setup(setup: Setup, target: Target) -> String {
+        assemble(
+            Interleaving::single(setup),
+            target,
+            |inputs, outputs, instructions| {
+                generate_rust_includable_asm(target, inputs, outputs, instructions)
+            },
+        )
+    }
+
+    fn assemble_mul_add(target: Target) -> String {
+        assemble_setup(setup_mul_add, target)
+    }
+
+    #[test]
+    fn snapshot_aarch64() {
+        let expected = r#"// GENERATED FILE, DO NOT EDIT!
+// in("x0") a,
+// in("x1") b,
+// lateout("x0") out[0], lateout("x1") out[1],
+// lateout("x2") _,
+// lateout("lr") _
+  mul x2, x0, x1
+  umulh x1, x0, x1
+  adds x0, x2, x0
+  cinc x1, x1, hs
+"#;
+        assert_eq!(assemble_mul_add(Target::Aarch64), expected);
+    }
+
+    #[test]
+    fn snapshot_x86_64() {
+        let expected = r#"// GENERATED FILE, DO NOT EDIT!
+// in("rax") a,
+// in("rcx") b,
+// lateout("rax") out[0], lateout("rcx") out[1],
+// lateout("rsi") _,
+// lateout("rdx") _, lateout("r11") _, lateout("xmm31") _, lateout("k1") _
+  mov rdx, rax
+  mulx r11, rsi, rcx
+  mov rdx, rax
+  mulx rcx, r11, rcx
+  add rax, rsi
+  lea r11, [rcx + 1]
+  cmovc rcx, r11
+"#;
+        assert_eq!(assemble_mul_add(Target::X86_64), expected);
+    }
+
+    #[test]
+    fn x86_64_carry_after_borrow() {
+        // The subtraction leaves a borrow in CF, which is flipped into a carry
+        // for the addition.
+        let asm = assemble_setup(setup_sub_then_add, Target::X86_64);
+        let lines: Vec<_> = asm.lines().map(str::trim).collect();
+        let sub = lines
+            .iter()
+            .position(|line| line.starts_with("sub "))
+            .unwrap();
+        let adc = lines
+            .iter()
+            .position(|line| line.starts_with("adc "))
+            .unwrap();
+        assert!(lines[sub..adc].contains(&"cmc"), "{asm}");
+    }
+
+    #[test]
+    #[should_panic(expected = "after the flags were clobbered")]
+    fn x86_64_and_clobbers_carry() {
+        assemble_setup(setup_and_in_carry_chain, Target::X86_64);
+    }
 
     #[quickcheck]
     fn interleave(lhs: Vec<u64>, rhs: Vec<u64>) -> bool {
@@ -7,25 +7,33 @@ use {
     crate::{
         backend::AllocatedVariable,
         ir::{HardwareRegister, Instruction, TypedHardwareRegister},
+        target::Target,
     },
     std::collections::BTreeSet,
 };
 
 pub fn generate_standalone_asm(
+    target: Target,
     label: &str,
     instructions: &[Instruction<HardwareRegister>],
 ) -> String {
     let label = format!("_{label}");
 
-    let formatted_instructions: String = instructions
+    let formatted_instructions: String = target
+        .lower(instructions)
         .iter()
         // tab instructions by two spaces
-        .map(|instruction| format!("  {instruction}"))
+        .map(|line| format!("  {line}"))
         .collect::<Vec<_>>()
         .join("\n");
 
+    let syntax = match target {
+        Target::Aarch64 => "",
+        Target::X86_64 => "\n.intel_syntax noprefix",
+    };
+
     format!(
-        r#"
+        r#"{syntax}
 .global {label}
 .align 4
 .text
@@ -35,10 +43,14 @@ pub fn generate_standalone_asm(
     )
 }
 
-pub fn format_instructions_rust_inline(instructions: &[Instruction<HardwareRegister>]) -> String {
-    instructions
+pub fn format_instructions_rust_inline(
+    target: Target,
+    instructions: &[Instruction<HardwareRegister>],
+) -> String {
+    target
+        .lower(instructions)
         .iter()
-        .map(|instruction| format!("\"{instruction}\""))
+        .map(|line| format!("\"{line}\""))
         .collect::<Vec<_>>()
         .join(",\n")
 }
@@ -46,13 +58,14 @@ pub fn format_instructions_rust_inline(instructions: &[Instruction<HardwareRegis
 /// Generate a standalone file to be used with global_asm!. The top of file will
 /// include a comment that can be used as basis for the operands in global_asm!.
 pub fn generate_rust_global_asm(
+    target: Target,
     label: &str,
     inputs_registers: &[AllocatedVariable],
     outputs_registers: &[AllocatedVariable],
     instructions: &[Instruction<HardwareRegister>],
 ) -> String {
-    let operands = generate_asm_operands(inputs_registers, outputs_registers, instructions);
-    let standalone = generate_standalone_asm(label, instructions);
+    let operands = generate_asm_operands(target, inputs_registers, outputs_registers, instructions);
+    let standalone = generate_standalone_asm(target, label, instructions);
 
     let operands_with_comments: String = operands
         .lines()
@@ -69,15 +82,17 @@ pub fn generate_rust_global_asm(
 /// file will include a comment that can be used as basis for the operands in
 /// asm!.
 pub fn generate_rust_includable_asm(
+    target: Target,
     inputs_registers: &[AllocatedVariable],
     outputs_registers: &[AllocatedVariable],
     instructions: &[Instruction<HardwareRegister>],
 ) -> String {
-    let operands = generate_asm_operands(inputs_registers, outputs_registers, instructions);
-    let formatted_instructions: String = instructions
+    let operands = generate_asm_operands(target, inputs_registers, outputs_registers, instructions);
+    let formatted_instructions: String = target
+        .lower(instructions)
         .iter()
         // tab instructions by two spaces
-        .map(|instruction| format!("  {instruction}"))
+        .map(|line| format!("  {line}"))
         .collect::<Vec<_>>()
         .join("\n");
 
@@ -96,12 +111,13 @@ pub fn generate_rust_includable_asm(
 }
 
 pub fn generate_rust_inline_asm(
+    target: Target,
     inputs_registers: &[AllocatedVariable],
     outputs_registers: &[AllocatedVariable],
     instructions: &[Instruction<HardwareRegister>],
 ) -> String {
-    let inst = format_instructions_rust_inline(instructions);
-    let operands = generate_asm_operands(inputs_registers, outputs_registers, instructions);
+    let inst = format_instructions_rust_inline(target, instructions);
+    let operands = generate_asm_operands(target, inputs_registers, outputs_registers, instructions);
 
     format!(
         r#"
@@ -113,23 +129,24 @@ unsafe {{ asm!(
 }
 
 pub fn generate_asm_operands(
+    target: Target,
     inputs: &[AllocatedVariable],
     outputs: &[AllocatedVariable],
     instructions: &[Instruction<HardwareRegister>],
 ) -> String {
-    let input_operands = format_operands(inputs, "in");
-    let output_operands = format_operands(outputs, "lateout");
+    let input_operands = format_operands(target, inputs, "in");
+    let output_operands = format_operands(target, outputs, "lateout");
     let clobber_registers = get_clobber_registers(outputs, instructions);
 
-    let clobbers = format_clobbers(&clobber_registers);
+    let clobbers = format_clobbers(target, &clobber_registers);
+    let scratch = target
+        .scratch_registers()
+        .iter()
+        .map(|register| format!("lateout(\"{register}\") _"))
+        .collect::<Vec<_>>()
+        .join(", ");
 
-    [
-        input_operands,
-        output_operands,
-        clobbers,
-        "lateout(\"lr\") _".to_string(),
-    ]
-    .join(",\n")
+    [input_operands, output_operands, clobbers, scratch].join(",\n")
 }
 
 /// Clobber registers are all the registers that have been used in the assembly
@@ -167,6 +184,7 @@ fn get_clobber_registers(
 ///
 /// # Arguments
 ///
+/// * `target` - The target whose register names are used
 /// * `clobbered_registers` - The list of registers that need to be marked as
 ///   clobbered
 ///
@@ -174,10 +192,10 @@ fn get_clobber_registers(
 ///
 /// An iterator that produces formatted strings for each clobbered register with
 /// separators
-fn format_clobbers(clobbered_registers: &[TypedHardwareRegister]) -> String {
+fn format_clobbers(target: Target, clobbered_registers: &[TypedHardwareRegister]) -> String {
     clobbered_registers
         .iter()
-        .map(|register| format!("lateout(\"{register}\") _"))
+        .map(|register| format!("lateout(\"{}\") _", target.register_name(*register)))
         .collect::<Vec<_>>()
         .join(", ")
 }
@@ -191,6 +209,7 @@ fn format_clobbers(clobbered_registers: &[TypedHardwareRegister]) -> String {
 ///
 /// # Arguments
 ///
+/// * `target` - The target whose register names are used
 /// * `variables` - A slice of register vectors, where each vector represents a
 ///   logical group (e.g., all input registers for a particular operation)
 /// * `formatter` - A function that formats a single register with its group
@@ -200,7 +219,7 @@ fn format_clobbers(clobbered_registers: &[TypedHardwareRegister]) -> String {
 ///
 /// An iterator that produces formatted strings for each register group with
 /// appropriate separators
-fn format_operands(variables: &[AllocatedVariable], direction: &str) -> String {
+fn format_operands(target: Target, variables: &[AllocatedVariable], direction: &str) -> String {
     // Process each register group (with its index)
     variables
         .iter()
@@ -213,7 +232,8 @@ fn format_operands(variables: &[AllocatedVariable], direction: &str) -> String {
                     .enumerate()
                     .map(move |(variable_index, register)| {
                         format!(
-                            "{direction}(\"{register}\") {}[{variable_index}]",
+                            "{direction}(\"{}\") {}[{variable_index}]",
+                            target.register_name(*register),
                             variable.label
                         )
                     })
@@ -223,7 +243,8 @@ fn format_operands(variables: &[AllocatedVariable], direction: &str) -> String {
             } else {
                 format!(
                     "{direction}(\"{}\") {}",
-                    variable.registers[0], variable.label
+                    target.register_name(variable.registers[0]),
+                    variable.label
                 )
             }
         })
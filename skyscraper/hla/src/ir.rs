@@ -1,4 +1,11 @@
-use crate::reification::ReifiedRegister;
+use {
+    crate::{
+        backend::AllocatedVariable,
+        frontend::FreshVariable,
+        reification::{RegisterType, ReifiedRegister},
+    },
+    std::collections::{BTreeMap, HashMap},
+};
 
 #[derive(Clone, Debug)]
 pub struct Variable<R> {
@@ -17,7 +24,7 @@ pub struct Variable<R> {
 ///
 /// * `R` - The register type is either `FreshRegister` for virtual registers or
 ///   `HardwareRegister` for physical machine registers.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Instruction<R> {
     pub(crate) opcode:    String,
     // Result is a vector because:
@@ -30,7 +37,7 @@ pub struct Instruction<R> {
     pub(crate) modifiers: Modifier,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) enum Modifier {
     None,
     Imm(u64),
@@ -70,12 +77,128 @@ impl<R> Instruction<R> {
     }
 }
 
+/// A program as seen by the backend.
+pub struct Ir<'a> {
+    pub(crate) inputs:       &'a [FreshVariable],
+    pub(crate) outputs:      &'a [FreshVariable],
+    pub(crate) instructions: &'a [Instruction<FreshRegister>],
+    /// The hardware registers assigned to the inputs and instructions, once
+    /// register allocation has run.
+    pub(crate) allocation:   Option<Allocation<'a>>,
+}
+
+pub struct Allocation<'a> {
+    pub(crate) inputs:       &'a [AllocatedVariable],
+    pub(crate) instructions: &'a [Instruction<HardwareRegister>],
+}
+
+impl std::fmt::Display for Ir<'_> {
+    /// Lists the program with the virtual registers, their live ranges and,
+    /// once allocated, their hardware registers.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut assigned: HashMap<FreshRegister, TypedHardwareRegister> = HashMap::new();
+        if let Some(allocation) = &self.allocation {
+            for (fresh, hardware) in self.inputs.iter().zip(allocation.inputs) {
+                for (fresh, &hardware) in fresh.registers.iter().zip(&hardware.registers) {
+                    assigned.insert(fresh.reg, hardware);
+                }
+            }
+            for (fresh, hardware) in self.instructions.iter().zip(allocation.instructions) {
+                for (fresh, hardware) in fresh.extract_registers().zip(hardware.extract_registers())
+                {
+                    assigned.insert(fresh.reg, hardware.to_basic_register());
+                }
+            }
+        }
+        let physical = |register: &ReifiedRegister<FreshRegister>| {
+            assigned
+                .get(&register.reg)
+                .map_or("?".to_string(), |hardware| hardware.to_string())
+        };
+        let variable = |f: &mut std::fmt::Formatter<'_>, variable: &FreshVariable| {
+            let registers = |name: &dyn Fn(&ReifiedRegister<FreshRegister>) -> String| {
+                variable
+                    .registers
+                    .iter()
+                    .map(name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writeln!(
+                f,
+                "  {}: {} -> {}",
+                variable.label,
+                registers(&|register| format!("{}{}", register.r#type, register.reg)),
+                registers(&physical)
+            )
+        };
+
+        // Live ranges from the defining line, `in` for inputs, till the last use, `out`
+        // for outputs.
+        let mut ranges: BTreeMap<FreshRegister, (RegisterType, Option<usize>, Option<usize>)> =
+            BTreeMap::new();
+        for register in self.inputs.iter().flat_map(|variable| &variable.registers) {
+            ranges.insert(register.reg, (register.r#type, None, None));
+        }
+        for (line, instruction) in self.instructions.iter().enumerate() {
+            for register in &instruction.results {
+                ranges
+                    .entry(register.reg)
+                    .or_insert((register.r#type, Some(line), None));
+            }
+            for register in instruction.extract_registers() {
+                if let Some(range) = ranges.get_mut(&register.reg) {
+                    range.2 = Some(line);
+                }
+            }
+        }
+        for register in self.outputs.iter().flat_map(|variable| &variable.registers) {
+            if let Some(range) = ranges.get_mut(&register.reg) {
+                range.2 = None;
+            }
+        }
+
+        writeln!(f, "inputs:")?;
+        for input in self.inputs {
+            variable(f, input)?;
+        }
+
+        writeln!(f, "instructions:")?;
+        for (line, instruction) in self.instructions.iter().enumerate() {
+            let hardware = self
+                .allocation
+                .as_ref()
+                .map_or(String::new(), |allocation| {
+                    allocation.instructions[line].to_string()
+                });
+            let listing = format!("{line:>4}: {:<40} {hardware}", instruction.to_string());
+            writeln!(f, "  {}", listing.trim_end())?;
+        }
+
+        writeln!(f, "outputs:")?;
+        for output in self.outputs {
+            variable(f, output)?;
+        }
+
+        writeln!(f, "live ranges:")?;
+        for (register, (kind, begin, end)) in ranges {
+            let begin = begin.map_or("in".to_string(), |line| line.to_string());
+            let end = end.map_or("out".to_string(), |line| line.to_string());
+            let hardware = assigned
+                .get(&register)
+                .map_or("?".to_string(), |hardware| hardware.to_string());
+            writeln!(f, "  {kind}{register}: {begin}..{end} -> {hardware}")?;
+        }
+        Ok(())
+    }
+}
+
 /// A virtual register identifier used before hardware register allocation.
 ///
 /// FreshRegister represents a unique label for a variable in the intermediate
 /// representation. It serves as a placeholder for a hardware register that will
 /// be assigned during the register allocation phase.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FreshRegister(pub(crate) u64);
 
 impl std::fmt::Display for FreshRegister {
@@ -132,3 +255,70 @@ impl std::fmt::Display for TypedHardwareRegister {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use {
+        super::{
+            Allocation, HardwareRegister, Instruction, Ir, Modifier, TypedHardwareRegister,
+            Variable,
+        },
+        crate::{
+            frontend::{FreshAllocator, FreshVariable, Reg},
+            instructions::{mul_inst, umulh_inst},
+            reification::ReifyRegister,
+        },
+    };
+
+    #[test]
+    fn dump() {
+        let mut alloc = FreshAllocator::new();
+        let [a, b, lo, hi]: [Reg<u64>; 4] = alloc.fresh_array();
+        let instructions = [mul_inst(&lo, &a, &b), umulh_inst(&hi, &a, &b)];
+
+        let hardware =
+            |register: &Reg<u64>, reg| register.reify().into_hardware(HardwareRegister(reg));
+        let allocated_inputs = [("a", 5), ("b", 6)].map(|(label, reg)| Variable {
+            label:     label.to_string(),
+            registers: vec![TypedHardwareRegister::General(HardwareRegister(reg))],
+        });
+        let allocated_instructions = [
+            Instruction {
+                opcode:    "mul".to_string(),
+                results:   vec![hardware(&lo, 0)],
+                operands:  vec![hardware(&a, 5), hardware(&b, 6)],
+                modifiers: Modifier::None,
+            },
+            Instruction {
+                opcode:    "umulh".to_string(),
+                results:   vec![hardware(&hi, 1)],
+                operands:  vec![hardware(&a, 5), hardware(&b, 6)],
+                modifiers: Modifier::None,
+            },
+        ];
+
+        let inputs = [
+            FreshVariable::new("a", std::slice::from_ref(&a)),
+            FreshVariable::new("b", std::slice::from_ref(&b)),
+        ];
+        let outputs = [FreshVariable::new("out", &[lo, hi])];
+        let ir = Ir {
+            inputs:       &inputs,
+            outputs:      &outputs,
+            instructions: &instructions,
+            allocation:   Some(Allocation {
+                inputs:       &allocated_inputs,
+                instructions: &allocated_instructions,
+            }),
+        };
+        let dump = ir.to_string();
+
+        assert!(dump.contains("mul x2, x0, x1"), "{dump}");
+        assert!(dump.contains("mul x0, x5, x6"), "{dump}");
+        assert!(dump.contains("umulh x3, x0, x1"), "{dump}");
+        assert!(dump.contains("umulh x1, x5, x6"), "{dump}");
+        assert!(dump.contains("out: x2, x3 -> x0, x1"), "{dump}");
+        assert!(dump.contains("x0: in..1 -> x5"), "{dump}");
+        assert!(dump.contains("x3: 1..out -> x1"), "{dump}");
+    }
+}
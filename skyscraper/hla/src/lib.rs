@@ -6,5 +6,8 @@ pub mod instructions;
 pub mod ir;
 pub mod liveness;
 pub mod reification;
+pub mod target;
+pub mod validation;
+mod x86_64;
 
 pub use {frontend::*, instructions::*};
@@ -0,0 +1,72 @@
+//! The architectures HLA can generate assembly for.
+//!
+//! The frontend and IR model aarch64 instructions. A [`Target`] decides which
+//! hardware registers the backend may allocate and how the allocated
+//! instructions are written out.
+use crate::{
+    ir::{HardwareRegister, Instruction, TypedHardwareRegister},
+    x86_64,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Target {
+    /// ARM64 with NEON.
+    #[default]
+    Aarch64,
+    /// x86_64 with BMI2 and AVX-512F/VL/DQ, in Intel syntax.
+    ///
+    /// Only 11 general purpose registers are available for allocation, enough
+    /// for the SIMD Montgomery kernels but not for the scalar ones.
+    X86_64,
+}
+
+impl Target {
+    /// The general purpose registers available for allocation.
+    ///
+    /// aarch64 excludes x18 (reserved by the OS), x19 (reserved by LLVM), the
+    /// link register and the stack pointer. x86_64 excludes rbx (reserved by
+    /// LLVM), rsp and rbp, rdx as the implicit operand of `mulx` and r11 as
+    /// scratch register.
+    pub(crate) fn general_purpose_registers(self) -> Vec<u64> {
+        match self {
+            Target::Aarch64 => (0..=17).chain(20..29).collect(),
+            Target::X86_64 => vec![0, 1, 6, 7, 8, 9, 10, 12, 13, 14, 15],
+        }
+    }
+
+    /// The vector registers available for allocation. x86_64 keeps xmm31 as
+    /// scratch register.
+    pub(crate) fn vector_registers(self) -> Vec<u64> {
+        match self {
+            Target::Aarch64 => (0..=31).collect(),
+            Target::X86_64 => (0..=30).collect(),
+        }
+    }
+
+    /// Registers clobbered by the lowering on top of the allocated registers.
+    pub(crate) fn scratch_registers(self) -> &'static [&'static str] {
+        match self {
+            Target::Aarch64 => &["lr"],
+            Target::X86_64 => &["rdx", "r11", "xmm31", "k1"],
+        }
+    }
+
+    /// The name of the register as used in the `asm!` operands.
+    pub(crate) fn register_name(self, register: TypedHardwareRegister) -> String {
+        match self {
+            Target::Aarch64 => register.to_string(),
+            Target::X86_64 => x86_64::register_name(register),
+        }
+    }
+
+    /// Writes out the instructions, one assembly line per entry.
+    pub(crate) fn lower(self, instructions: &[Instruction<HardwareRegister>]) -> Vec<String> {
+        match self {
+            Target::Aarch64 => instructions
+                .iter()
+                .map(|instruction| instruction.to_string())
+                .collect(),
+            Target::X86_64 => x86_64::lower(instructions),
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Validation of the IR before it reaches codegen.
+//!
+//! A malformed frontend program otherwise either panics deep inside liveness
+//! analysis and register allocation, or silently produces wrong assembly.
+use {
+    crate::ir::{Allocation, FreshRegister, Ir, TypedHardwareRegister},
+    std::collections::{BTreeMap, HashMap, HashSet},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum IrError {
+    /// The opcode is not part of the instruction set.
+    UnknownOpcode { line: usize, opcode: String },
+    /// The number of (results, operands) does not match the opcode.
+    OperandCount {
+        line:     usize,
+        opcode:   String,
+        expected: (usize, usize),
+        found:    (usize, usize),
+    },
+    /// A register is read before any instruction or input defined it.
+    UseBeforeDef {
+        line:     usize,
+        register: FreshRegister,
+    },
+    /// An output register is never defined.
+    UndefinedOutput {
+        label:    String,
+        register: FreshRegister,
+    },
+    /// A hardware register is assigned while it still holds a live register.
+    DoubleAllocation {
+        line:     usize,
+        register: TypedHardwareRegister,
+        live:     FreshRegister,
+        assigned: FreshRegister,
+    },
+}
+
+impl std::fmt::Display for IrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IrError::UnknownOpcode { line, opcode } => write!(f, "{line}: unknown opcode {opcode}"),
+            IrError::OperandCount {
+                line,
+                opcode,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{line}: {opcode} expects {} results and {} operands, found {} and {}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            IrError::UseBeforeDef { line, register } => {
+                write!(
+                    f,
+                    "{line}: register {register} is used before it is defined"
+                )
+            }
+            IrError::UndefinedOutput { label, register } => {
+                write!(f, "output {label} register {register} is never defined")
+            }
+            IrError::DoubleAllocation {
+                line,
+                register,
+                live,
+                assigned,
+            } => write!(
+                f,
+                "{line}: {register} is assigned to {assigned} while still holding {live}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IrError {}
+
+/// The shape of an opcode.
+struct Signature {
+    results:      usize,
+    operands:     usize,
+    // The instruction updates its result in place, so the result needs to be
+    // defined beforehand.
+    reads_result: bool,
+}
+
+fn signature(opcode: &str) -> Option<Signature> {
+    let (results, operands, reads_result) = match opcode {
+        "mov" => (1, 0, false),
+        "movk" => (1, 0, true),
+        "tst" => (0, 1, false),
+        "cmn" => (0, 2, false),
+        "cinc" => (1, 1, false),
+        "csel" | "adds" | "adcs" | "adc" | "subs" | "sbcs" | "mul" | "umulh" | "add" | "and" => {
+            (1, 2, false)
+        }
+        "ldr" => (1, 1, false),
+        "ldp" => (2, 1, false),
+        "stp" => (0, 3, false),
+        "ucvtf.2d" | "dup.2d" | "ucvtf" | "ins" | "umov" | "cmeq.2d" | "mov.16b" | "shl.2d"
+        | "ushr.2d" => (1, 1, false),
+        "and.16b" | "bic.16b" | "add.2d" | "sub.2d" | "fsub.2d" | "orr.16b" => (1, 2, false),
+        "sli.2d" | "usra.2d" | "ssra.2d" => (1, 1, true),
+        "fmla.2d" => (1, 2, true),
+        _ => return None,
+    };
+    Some(Signature {
+        results,
+        operands,
+        reads_result,
+    })
+}
+
+/// Validates the program.
+///
+/// Checks that every opcode is known with the right number of results and
+/// operands, and that every register is defined before it is used. When the
+/// program has been allocated, also checks that no hardware register is
+/// reassigned while it still holds a register that is used later on.
+pub fn validate(ir: &Ir) -> Result<(), IrError> {
+    let mut defined: HashSet<FreshRegister> = ir
+        .inputs
+        .iter()
+        .flat_map(|variable| variable.registers.iter().map(|register| register.reg))
+        .collect();
+
+    for (line, instruction) in ir.instructions.iter().enumerate() {
+        let opcode = &instruction.opcode;
+        let Some(signature) = signature(opcode) else {
+            return Err(IrError::UnknownOpcode {
+                line,
+                opcode: opcode.clone(),
+            });
+        };
+
+        let found = (instruction.results.len(), instruction.operands.len());
+        if found != (signature.results, signature.operands) {
+            return Err(IrError::OperandCount {
+                line,
+                opcode: opcode.clone(),
+                expected: (signature.results, signature.operands),
+                found,
+            });
+        }
+
+        let reads = instruction.operands.iter().chain(
+            instruction
+                .results
+                .iter()
+                .filter(|_| signature.reads_result),
+        );
+        for register in reads {
+            if !defined.contains(&register.reg) {
+                return Err(IrError::UseBeforeDef {
+                    line,
+                    register: register.reg,
+                });
+            }
+        }
+
+        defined.extend(instruction.results.iter().map(|register| register.reg));
+    }
+
+    for variable in ir.outputs {
+        for register in &variable.registers {
+            if !defined.contains(&register.reg) {
+                return Err(IrError::UndefinedOutput {
+                    label:    variable.label.clone(),
+                    register: register.reg,
+                });
+            }
+        }
+    }
+
+    match &ir.allocation {
+        Some(allocation) => validate_allocation(ir, allocation),
+        None => Ok(()),
+    }
+}
+
+fn validate_allocation(ir: &Ir, allocation: &Allocation) -> Result<(), IrError> {
+    assert_eq!(
+        ir.instructions.len(),
+        allocation.instructions.len(),
+        "Allocation does not match the instructions"
+    );
+
+    // The line of the last use of each register. Outputs, as well as registers
+    // that are never used, hold on to their hardware register till the end.
+    let mut last_use = HashMap::new();
+    for (line, instruction) in ir.instructions.iter().enumerate() {
+        for register in instruction.extract_registers() {
+            last_use.insert(register.reg, line);
+        }
+    }
+    for variable in ir.outputs {
+        for register in &variable.registers {
+            last_use.insert(register.reg, usize::MAX);
+        }
+    }
+    let is_live_after = |register: &FreshRegister, line: usize| {
+        last_use.get(register).is_none_or(|&end| end > line)
+    };
+
+    let mut holds: BTreeMap<TypedHardwareRegister, FreshRegister> = BTreeMap::new();
+
+    let inputs = ir.inputs.iter().zip(allocation.inputs);
+    for (fresh, hardware) in inputs {
+        for (fresh, &hardware) in fresh.registers.iter().zip(&hardware.registers) {
+            if let Some(live) = holds
+                .insert(hardware, fresh.reg)
+                .filter(|&live| live != fresh.reg)
+            {
+                return Err(IrError::DoubleAllocation {
+                    line: 0,
+                    register: hardware,
+                    live,
+                    assigned: fresh.reg,
+                });
+            }
+        }
+    }
+
+    let instructions = ir.instructions.iter().zip(allocation.instructions);
+    for (line, (fresh, hardware)) in instructions.enumerate() {
+        for (fresh, hardware) in fresh.results.iter().zip(&hardware.results) {
+            let hardware = hardware.to_basic_register();
+            match holds.insert(hardware, fresh.reg) {
+                Some(live) if live != fresh.reg && is_live_after(&live, line) => {
+                    return Err(IrError::DoubleAllocation {
+                        line,
+                        register: hardware,
+                        live,
+                        assigned: fresh.reg,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::{validate, IrError},
+        crate::{
+            frontend::{FreshAllocator, FreshVariable, Reg},
+            instructions::{add_inst, mov_inst, movk_inst, mul_inst},
+            ir::{
+                Allocation, HardwareRegister, Instruction, Ir, Modifier, TypedHardwareRegister,
+                Variable,
+            },
+            reification::ReifyRegister,
+        },
+    };
+
+    fn var(label: &str, register: &Reg<u64>) -> FreshVariable {
+        FreshVariable::new(label, std::slice::from_ref(register))
+    }
+
+    fn ir<'a>(
+        inputs: &'a [FreshVariable],
+        outputs: &'a [FreshVariable],
+        instructions: &'a [Instruction<crate::ir::FreshRegister>],
+    ) -> Ir<'a> {
+        Ir {
+            inputs,
+            outputs,
+            instructions,
+            allocation: None,
+        }
+    }
+
+    #[test]
+    fn valid() {
+        let mut alloc = FreshAllocator::new();
+        let [a, b, c]: [Reg<u64>; 3] = alloc.fresh_array();
+        let inputs = [var("a", &a), var("b", &b)];
+        let outputs = [var("c", &c)];
+        let instructions = [add_inst(&c, &a, &b)];
+
+        assert_eq!(validate(&ir(&inputs, &outputs, &instructions)), Ok(()));
+    }
+
+    #[test]
+    fn use_before_def() {
+        let mut alloc = FreshAllocator::new();
+        let [a, b, c]: [Reg<u64>; 3] = alloc.fresh_array();
+        let inputs = [var("a", &a)];
+        let outputs = [var("c", &c)];
+        let instructions = [add_inst(&c, &a, &b)];
+
+        assert_eq!(
+            validate(&ir(&inputs, &outputs, &instructions)),
+            Err(IrError::UseBeforeDef {
+                line:     0,
+                register: b.reify().reg,
+            })
+        );
+    }
+
+    #[test]
+    fn in_place_update_before_def() {
+        let mut alloc = FreshAllocator::new();
+        let a: Reg<u64> = alloc.fresh();
+        let outputs = [var("a", &a)];
+        let instructions = [movk_inst(&a, 1, 16)];
+
+        assert_eq!(
+            validate(&ir(&[], &outputs, &instructions)),
+            Err(IrError::UseBeforeDef {
+                line:     0,
+                register: a.reify().reg,
+            })
+        );
+    }
+
+    #[test]
+    fn undefined_output() {
+        let mut alloc = FreshAllocator::new();
+        let [a, b]: [Reg<u64>; 2] = alloc.fresh_array();
+        let inputs = [var("a", &a)];
+        let outputs = [var("b", &b)];
+
+        assert_eq!(
+            validate(&ir(&inputs, &outputs, &[])),
+            Err(IrError::UndefinedOutput {
+                label:    "b".to_string(),
+                register: b.reify().reg,
+            })
+        );
+    }
+
+    #[test]
+    fn operand_count() {
+        let mut alloc = FreshAllocator::new();
+        let [a, b]: [Reg<u64>; 2] = alloc.fresh_array();
+        let inputs = [var("a", &a)];
+        let outputs = [var("b", &b)];
+        let instructions = [Instruction {
+            opcode:    "mul".to_string(),
+            results:   vec![b.reify()],
+            operands:  vec![a.reify()],
+            modifiers: Modifier::None,
+        }];
+
+        assert_eq!(
+            validate(&ir(&inputs, &outputs, &instructions)),
+            Err(IrError::OperandCount {
+                line:     0,
+                opcode:   "mul".to_string(),
+                expected: (1, 2),
+                found:    (1, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_opcode() {
+        let mut alloc = FreshAllocator::new();
+        let a: Reg<u64> = alloc.fresh();
+        let outputs = [var("a", &a)];
+        let instructions = [Instruction {
+            opcode:    "madd".to_string(),
+            results:   vec![a.reify()],
+            operands:  vec![],
+            modifiers: Modifier::None,
+        }];
+
+        assert_eq!(
+            validate(&ir(&[], &outputs, &instructions)),
+            Err(IrError::UnknownOpcode {
+                line:   0,
+                opcode: "madd".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn double_allocation() {
+        let mut alloc = FreshAllocator::new();
+        let [a, b, c]: [Reg<u64>; 3] = alloc.fresh_array();
+        let inputs = [var("a", &a)];
+        let outputs = [var("c", &c)];
+        let instructions = [mov_inst(&b, 1), mul_inst(&c, &a, &b)];
+
+        // b is placed in x0 while a still needs it
+        let x0 = HardwareRegister(0);
+        let x1 = HardwareRegister(1);
+        let allocated_inputs = [Variable {
+            label:     "a".to_string(),
+            registers: vec![TypedHardwareRegister::General(x0)],
+        }];
+        let allocated_instructions = [
+            Instruction {
+                opcode:    "mov".to_string(),
+                results:   vec![b.reify().into_hardware(x0)],
+                operands:  vec![],
+                modifiers: Modifier::Imm(1),
+            },
+            Instruction {
+                opcode:    "mul".to_string(),
+                results:   vec![c.reify().into_hardware(x1)],
+                operands:  vec![a.reify().into_hardware(x0), b.reify().into_hardware(x0)],
+                modifiers: Modifier::None,
+            },
+        ];
+
+        let mut ir = ir(&inputs, &outputs, &instructions);
+        ir.allocation = Some(Allocation {
+            inputs:       &allocated_inputs,
+            instructions: &allocated_instructions,
+        });
+        assert_eq!(
+            validate(&ir),
+            Err(IrError::DoubleAllocation {
+                line:     0,
+                register: TypedHardwareRegister::General(x0),
+                live:     a.reify().reg,
+                assigned: b.reify().reg,
+            })
+        );
+    }
+}
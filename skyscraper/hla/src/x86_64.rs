@@ -0,0 +1,405 @@
+//! Lowering of the (aarch64 flavoured) IR to x86_64.
+//!
+//! Instructions are written in Intel syntax, which is the default of Rust's
+//! `asm!`. The scalar instructions need BMI2 for `mulx` and `rorx`. The vector
+//! instructions need AVX-512F/VL/DQ as AVX2 lacks unsigned 64-bit conversions,
+//! 64-bit arithmetic shifts and xmm16-xmm31.
+//!
+//! aarch64 instructions have three operands where most x86_64 instructions have
+//! two, so the destination is first set to the left operand. Only flag-free
+//! instructions (`mov`, `lea`, `cmov`, `mulx`, `rorx`) are used next to the
+//! flag setting ones to keep carry chains intact. The exceptions are `and` and
+//! `adc`, which leave the flags alone on aarch64 but clobber them on x86_64.
+//! Reading the flags after either panics instead of silently using the wrong
+//! carry.
+//!
+//! Only kernels that keep at most 11 general purpose registers live can be
+//! lowered, which covers the SIMD Montgomery kernels but not the scalar ones.
+use crate::{
+    ir::{HardwareRegister, Instruction, Modifier, TypedHardwareRegister},
+    reification::{Index, RegisterType, ReifiedRegister},
+};
+
+const GENERAL_PURPOSE: [&str; 16] = [
+    "rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13",
+    "r14", "r15",
+];
+
+const GENERAL_PURPOSE_16: [&str; 16] = [
+    "ax", "cx", "dx", "bx", "sp", "bp", "si", "di", "r8w", "r9w", "r10w", "r11w", "r12w", "r13w",
+    "r14w", "r15w",
+];
+
+/// Scratch general purpose register, not available for allocation.
+const SCRATCH: &str = "r11";
+/// Scratch vector register, not available for allocation.
+const VECTOR_SCRATCH: &str = "xmm31";
+
+pub(crate) fn register_name(register: TypedHardwareRegister) -> String {
+    match register {
+        TypedHardwareRegister::General(reg) => GENERAL_PURPOSE[reg.0 as usize].to_string(),
+        TypedHardwareRegister::Vector(reg) => format!("xmm{}", reg.0),
+    }
+}
+
+/// Lowers the instructions to x86_64, one assembly line per entry.
+///
+/// # Panics
+///
+/// Panics on instructions that have no x86_64 lowering.
+pub(crate) fn lower(instructions: &[Instruction<HardwareRegister>]) -> Vec<String> {
+    let mut lowering = Lowering::default();
+    instructions
+        .iter()
+        .for_each(|instruction| lowering.instruction(instruction));
+    lowering.lines
+}
+
+/// What the x86_64 flags hold in terms of the aarch64 flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Flags {
+    /// Overwritten by an instruction that leaves the aarch64 flags alone, or
+    /// not set yet.
+    #[default]
+    Clobbered,
+    /// CF is the aarch64 carry, as set by additions.
+    Carry,
+    /// CF is the negated aarch64 carry. aarch64 sets the carry flag on a
+    /// subtraction without borrow, x86_64 sets it on a subtraction with
+    /// borrow.
+    Borrow,
+}
+
+#[derive(Default)]
+struct Lowering {
+    lines: Vec<String>,
+    flags: Flags,
+}
+
+impl Lowering {
+    fn emit(&mut self, line: String) {
+        self.lines.push(line);
+    }
+
+    fn instruction(&mut self, instruction: &Instruction<HardwareRegister>) {
+        let modifiers = &instruction.modifiers;
+        match (
+            instruction.opcode.as_str(),
+            instruction.results.as_slice(),
+            instruction.operands.as_slice(),
+        ) {
+            // Scalar
+            ("adds", [d], [a, b]) => {
+                self.binary("add", d, a, b, true);
+                self.flags = Flags::Carry;
+            }
+            ("adcs", [d], [a, b]) => {
+                self.carry_in(Flags::Carry);
+                self.binary("adc", d, a, b, true);
+                self.flags = Flags::Carry;
+            }
+            ("adc", [d], [a, b]) => {
+                self.carry_in(Flags::Carry);
+                self.binary("adc", d, a, b, true);
+                self.flags = Flags::Clobbered;
+            }
+            ("subs", [d], [a, b]) => {
+                self.binary("sub", d, a, b, false);
+                self.flags = Flags::Borrow;
+            }
+            ("sbcs", [d], [a, b]) => {
+                self.carry_in(Flags::Borrow);
+                self.binary("sbb", d, a, b, false);
+                self.flags = Flags::Borrow;
+            }
+            ("add", [d], [a, b]) => self.emit(format!("lea {}, [{} + {}]", gp(d), gp(a), gp(b))),
+            ("and", [d], [a, b]) => {
+                self.binary("and", d, a, b, true);
+                self.flags = Flags::Clobbered;
+            }
+            ("mul", [d], [a, b]) => {
+                self.emit(format!("mov rdx, {}", gp(a)));
+                self.emit(format!("mulx {SCRATCH}, {}, {}", gp(d), gp(b)));
+            }
+            ("umulh", [d], [a, b]) => {
+                self.emit(format!("mov rdx, {}", gp(a)));
+                self.emit(format!("mulx {}, {SCRATCH}, {}", gp(d), gp(b)));
+            }
+            ("mov", [d], []) => self.emit(format!("mov {}, {}", gp(d), imm(modifiers))),
+            ("movk", [d], []) => {
+                let Modifier::ImmLsl(imm, shift) = *modifiers else {
+                    panic!("movk requires an immediate with shift")
+                };
+                let low = GENERAL_PURPOSE_16[d.reg.0 as usize];
+                if shift == 0 {
+                    self.emit(format!("mov {low}, {imm}"));
+                } else {
+                    // Rotate the 16 bits into place as a 16-bit mov leaves the other bits intact.
+                    self.emit(format!("rorx {0}, {0}, {shift}", gp(d)));
+                    self.emit(format!("mov {low}, {imm}"));
+                    self.emit(format!("rorx {0}, {0}, {1}", gp(d), 64 - shift));
+                }
+            }
+            ("tst", [], [a]) => {
+                let imm = imm(modifiers);
+                // test only takes sign-extended 32-bit immediates
+                if imm <= i32::MAX as u64 {
+                    self.emit(format!("test {}, {imm}", gp(a)));
+                } else {
+                    self.emit(format!("mov {SCRATCH}, {imm}"));
+                    self.emit(format!("test {}, {SCRATCH}", gp(a)));
+                }
+                // Both clear the carry flag
+                self.flags = Flags::Carry;
+            }
+            ("cmn", [], [a, b]) => {
+                self.emit(format!("mov {SCRATCH}, {}", gp(a)));
+                self.emit(format!("add {SCRATCH}, {}", gp(b)));
+                self.flags = Flags::Carry;
+            }
+            ("csel", [d], [a, b]) => {
+                let cond = self.condition(modifiers);
+                if d.reg == a.reg {
+                    self.emit(format!("cmov{} {}, {}", negate(cond), gp(d), gp(b)));
+                } else {
+                    if d.reg != b.reg {
+                        self.emit(format!("mov {}, {}", gp(d), gp(b)));
+                    }
+                    self.emit(format!("cmov{cond} {}, {}", gp(d), gp(a)));
+                }
+            }
+            ("cinc", [d], [a]) => {
+                let cond = self.condition(modifiers);
+                self.emit(format!("lea {SCRATCH}, [{} + 1]", gp(a)));
+                if d.reg != a.reg {
+                    self.emit(format!("mov {}, {}", gp(d), gp(a)));
+                }
+                self.emit(format!("cmov{cond} {}, {SCRATCH}", gp(d)));
+            }
+
+            // Load/store
+            ("ldr", [d], [ptr]) => self.emit(format!("mov {}, {}", gp(d), memory(ptr, 0))),
+            ("ldp", [d0, d1], [ptr]) => {
+                // Don't overwrite the pointer before the second load
+                let loads = [(d0, 0), (d1, 8)];
+                let loads = if d0.reg == ptr.reg {
+                    [loads[1], loads[0]]
+                } else {
+                    loads
+                };
+                for (d, offset) in loads {
+                    self.emit(format!("mov {}, {}", gp(d), memory(ptr, offset)));
+                }
+            }
+            ("stp", [], [s0, s1, ptr]) => {
+                self.emit(format!("mov {}, {}", memory(ptr, 0), gp(s0)));
+                self.emit(format!("mov {}, {}", memory(ptr, 8), gp(s1)));
+            }
+
+            // SIMD
+            ("ucvtf.2d", [d], [a]) => self.emit(format!("vcvtuqq2pd {}, {}", xmm(d), xmm(a))),
+            ("dup.2d", [d], [a]) => self.emit(format!("vpbroadcastq {}, {}", xmm(d), gp(a))),
+            ("ucvtf", [d], [a]) => self.emit(format!("vcvtusi2sd {0}, {0}, {1}", xmm(d), gp(a))),
+            ("and.16b", [d], [a, b]) => self.vector("vpandq", d, a, b),
+            // vpandn negates the first operand
+            ("bic.16b", [d], [a, b]) => self.vector("vpandnq", d, b, a),
+            ("add.2d", [d], [a, b]) => self.vector("vpaddq", d, a, b),
+            ("sub.2d", [d], [a, b]) => self.vector("vpsubq", d, a, b),
+            ("fsub.2d", [d], [a, b]) => self.vector("vsubpd", d, a, b),
+            ("orr.16b", [d], [a, b]) => self.vector("vporq", d, a, b),
+            ("ins", [d], [a]) => {
+                self.emit(format!(
+                    "vpinsrq {0}, {0}, {1}, {2}",
+                    xmm(d),
+                    gp(a),
+                    lane(d)
+                ));
+            }
+            ("umov", [d], [a]) => self.emit(format!("vpextrq {}, {}, {}", gp(d), xmm(a), lane(a))),
+            ("cmeq.2d", [d], [a]) => {
+                assert_eq!(imm(modifiers), 0, "cmeq.2d only compares against zero");
+                self.emit(format!("vptestnmq k1, {0}, {0}", xmm(a)));
+                self.emit(format!("vpmovm2q {}, k1", xmm(d)));
+            }
+            ("mov.16b", [d], [a]) => self.emit(format!("vmovdqa64 {}, {}", xmm(d), xmm(a))),
+            ("sli.2d", [d], [a]) => {
+                let shift = shift(modifiers);
+                // Keep the low `shift` bits of the destination and insert the shifted source
+                self.emit(format!("vpsllq {VECTOR_SCRATCH}, {}, {shift}", xmm(a)));
+                self.emit(format!("vpsllq {0}, {0}, {1}", xmm(d), 64 - shift));
+                self.emit(format!("vpsrlq {0}, {0}, {1}", xmm(d), 64 - shift));
+                self.emit(format!("vporq {0}, {0}, {VECTOR_SCRATCH}", xmm(d)));
+            }
+            ("fmla.2d", [d], [a, b]) => {
+                let b = match b.idx {
+                    Index::None => xmm(b),
+                    Index::Lane(0) | Index::LaneSized(_, 0) => {
+                        self.emit(format!("vmovddup {VECTOR_SCRATCH}, {}", xmm(b)));
+                        VECTOR_SCRATCH.to_string()
+                    }
+                    Index::Lane(1) | Index::LaneSized(_, 1) => {
+                        self.emit(format!("vpermilpd {VECTOR_SCRATCH}, {}, 3", xmm(b)));
+                        VECTOR_SCRATCH.to_string()
+                    }
+                    _ => panic!("fmla.2d lane out of range: {b}"),
+                };
+                self.emit(format!("vfmadd231pd {}, {}, {b}", xmm(d), xmm(a)));
+            }
+            ("shl.2d", [d], [a]) => {
+                self.emit(format!(
+                    "vpsllq {}, {}, {}",
+                    xmm(d),
+                    xmm(a),
+                    shift(modifiers)
+                ));
+            }
+            ("ushr.2d", [d], [a]) => {
+                self.emit(format!(
+                    "vpsrlq {}, {}, {}",
+                    xmm(d),
+                    xmm(a),
+                    shift(modifiers)
+                ));
+            }
+            ("usra.2d", [d], [a]) => self.shift_accumulate("vpsrlq", d, a, shift(modifiers)),
+            ("ssra.2d", [d], [a]) => self.shift_accumulate("vpsraq", d, a, shift(modifiers)),
+            _ => panic!("No x86_64 lowering for: {instruction}"),
+        }
+    }
+
+    /// Lowers a three operand instruction to a two operand instruction.
+    fn binary(
+        &mut self,
+        opcode: &str,
+        d: &ReifiedRegister<HardwareRegister>,
+        a: &ReifiedRegister<HardwareRegister>,
+        b: &ReifiedRegister<HardwareRegister>,
+        commutative: bool,
+    ) {
+        let (d, a, b) = (gp(d), gp(a), gp(b));
+        if d == a {
+            self.emit(format!("{opcode} {d}, {b}"));
+        } else if d == b && commutative {
+            self.emit(format!("{opcode} {d}, {a}"));
+        } else if d == b {
+            self.emit(format!("mov {SCRATCH}, {a}"));
+            self.emit(format!("{opcode} {SCRATCH}, {b}"));
+            self.emit(format!("mov {d}, {SCRATCH}"));
+        } else {
+            self.emit(format!("mov {d}, {a}"));
+            self.emit(format!("{opcode} {d}, {b}"));
+        }
+    }
+
+    fn vector(
+        &mut self,
+        opcode: &str,
+        d: &ReifiedRegister<HardwareRegister>,
+        a: &ReifiedRegister<HardwareRegister>,
+        b: &ReifiedRegister<HardwareRegister>,
+    ) {
+        self.emit(format!("{opcode} {}, {}, {}", xmm(d), xmm(a), xmm(b)));
+    }
+
+    fn shift_accumulate(
+        &mut self,
+        opcode: &str,
+        d: &ReifiedRegister<HardwareRegister>,
+        a: &ReifiedRegister<HardwareRegister>,
+        shift: u8,
+    ) {
+        self.emit(format!("{opcode} {VECTOR_SCRATCH}, {}, {shift}", xmm(a)));
+        self.emit(format!("vpaddq {0}, {0}, {VECTOR_SCRATCH}", xmm(d)));
+    }
+
+    /// Brings CF into the sense `expected` by the next carry consuming
+    /// instruction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flags were clobbered since the last flag setting
+    /// instruction.
+    fn carry_in(&mut self, expected: Flags) {
+        match self.flags {
+            Flags::Clobbered => panic!("The carry flag is read after being clobbered on x86_64"),
+            flags if flags != expected => self.emit("cmc".to_string()),
+            _ => {}
+        }
+    }
+
+    /// Translates an aarch64 condition to the x86_64 condition code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flags were clobbered since the last flag setting
+    /// instruction.
+    fn condition(&self, modifiers: &Modifier) -> &'static str {
+        let Modifier::Cond(cond) = modifiers else {
+            panic!("Expected a condition, got {modifiers:?}")
+        };
+        let borrow = match self.flags {
+            Flags::Clobbered => panic!("Condition {cond} is read after the flags were clobbered"),
+            Flags::Carry => false,
+            Flags::Borrow => true,
+        };
+        match (cond.as_str(), borrow) {
+            ("eq", _) => "e",
+            ("ne", _) => "ne",
+            ("hs" | "cs", false) | ("lo" | "cc", true) => "c",
+            ("lo" | "cc", false) | ("hs" | "cs", true) => "nc",
+            _ => panic!("No x86_64 lowering for condition: {cond}"),
+        }
+    }
+}
+
+fn negate(cond: &str) -> &'static str {
+    match cond {
+        "e" => "ne",
+        "ne" => "e",
+        "c" => "nc",
+        "nc" => "c",
+        _ => unreachable!("condition() only returns e, ne, c and nc"),
+    }
+}
+
+fn gp(register: &ReifiedRegister<HardwareRegister>) -> &'static str {
+    assert_eq!(
+        register.r#type,
+        RegisterType::X,
+        "Expected a general purpose register"
+    );
+    GENERAL_PURPOSE[register.reg.0 as usize]
+}
+
+fn xmm(register: &ReifiedRegister<HardwareRegister>) -> String {
+    format!("xmm{}", register.reg.0)
+}
+
+/// Memory operand at `extra` bytes past the pointer's offset.
+fn memory(pointer: &ReifiedRegister<HardwareRegister>, extra: usize) -> String {
+    let Index::Pointer(offset) = pointer.idx else {
+        panic!("Expected a pointer, got {pointer}")
+    };
+    format!("qword ptr [{} + {}]", gp(pointer), offset + extra)
+}
+
+fn lane(register: &ReifiedRegister<HardwareRegister>) -> u8 {
+    match register.idx {
+        Index::Lane(lane) | Index::LaneSized(_, lane) => lane,
+        _ => panic!("Expected a lane, got {register}"),
+    }
+}
+
+fn imm(modifiers: &Modifier) -> u64 {
+    let Modifier::Imm(imm) = *modifiers else {
+        panic!("Expected an immediate, got {modifiers:?}")
+    };
+    imm
+}
+
+fn shift(modifiers: &Modifier) -> u8 {
+    let Modifier::Lsl(shift) = *modifiers else {
+        panic!("Expected a shift, got {modifiers:?}")
+    };
+    shift
+}
@@ -0,0 +1,135 @@
+use {
+    super::Command,
+    anyhow::{ensure, Context, Result},
+    argh::FromArgs,
+    provekit_common::{utils::human, NoirProofScheme, Prover, Verifier},
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
+    provekit_r1cs_compiler::NoirProofSchemeBuilder,
+    provekit_verifier::Verify,
+    serde::Serialize,
+    std::{
+        path::PathBuf,
+        time::{Duration, Instant},
+    },
+    tracing::{info, info_span, instrument},
+};
+
+/// Benchmark proving and verifying a Noir program
+#[derive(FromArgs, PartialEq, Eq, Debug)]
+#[argh(subcommand, name = "bench")]
+pub struct Args {
+    /// path to the compiled Noir program
+    #[argh(positional)]
+    program_path: PathBuf,
+
+    /// path to the input values
+    #[argh(positional)]
+    input_path: PathBuf,
+
+    /// number of times to prove and verify
+    #[argh(option, short = 'n', default = "10")]
+    iterations: usize,
+
+    /// print the results as JSON on stdout
+    #[argh(switch)]
+    json: bool,
+}
+
+/// Wall time statistics in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+struct Timings {
+    min:    f64,
+    median: f64,
+    mean:   f64,
+}
+
+impl Timings {
+    fn from_durations(durations: &[Duration]) -> Self {
+        let mut seconds = durations
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        seconds.sort_by(f64::total_cmp);
+        let n = seconds.len();
+        let median = if n % 2 == 0 {
+            (seconds[n / 2 - 1] + seconds[n / 2]) / 2.0
+        } else {
+            seconds[n / 2]
+        };
+        Self {
+            min: seconds[0],
+            median,
+            mean: seconds.iter().sum::<f64>() / n as f64,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    constraints: usize,
+    witnesses:   usize,
+    iterations:  usize,
+    prove:       Timings,
+    verify:      Timings,
+}
+
+impl Command for Args {
+    #[instrument(skip_all)]
+    fn run(&self) -> Result<()> {
+        ensure!(self.iterations > 0, "--iterations must be at least 1");
+
+        let scheme = NoirProofScheme::from_file(&self.program_path)
+            .context("while compiling Noir program")?;
+        let (constraints, witnesses) = scheme.size();
+        info!(constraints, witnesses, "Read Noir proof scheme");
+
+        let prover = Prover::from_noir_proof_scheme(scheme.clone());
+        let mut verifier = Verifier::from_noir_proof_scheme(scheme);
+        let mut prove_times = Vec::with_capacity(self.iterations);
+        let mut verify_times = Vec::with_capacity(self.iterations);
+        for iteration in 0..self.iterations {
+            let _span = info_span!("iteration", iteration).entered();
+            let start = Instant::now();
+            let proof = prover
+                .prove_with(
+                    ProveInputs::File(self.input_path.clone()),
+                    ProveOptions::default(),
+                )
+                .context("While proving Noir program statement")?;
+            prove_times.push(start.elapsed());
+
+            let start = Instant::now();
+            verifier
+                .verify(&proof)
+                .context("While verifying Noir proof")?;
+            verify_times.push(start.elapsed());
+        }
+
+        let report = Report {
+            constraints,
+            witnesses,
+            iterations: self.iterations,
+            prove: Timings::from_durations(&prove_times),
+            verify: Timings::from_durations(&verify_times),
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!(
+                "{} iterations, {constraints} constraints, {witnesses} witnesses",
+                report.iterations
+            );
+            for (name, timings) in [("prove", report.prove), ("verify", report.verify)] {
+                println!(
+                    "{name:>6}: min {}s, median {}s, mean {}s",
+                    human(timings.min),
+                    human(timings.median),
+                    human(timings.mean)
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
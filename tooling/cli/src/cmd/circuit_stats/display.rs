@@ -250,6 +250,13 @@ fn collect_r1cs_components(
         ));
     }
 
+    if breakdown.digits_constraints > 0 || breakdown.digits_witnesses > 0 {
+        components.push(format!(
+            "AND/XOR Byte Decomposition:          {:>8} constraints {:>8} witnesses",
+            breakdown.digits_constraints, breakdown.digits_witnesses
+        ));
+    }
+
     if let Some(&count) = stats.blackbox_func_counts.get("Sha256Compression") {
         if count > 0 {
             components.push(format!(
@@ -259,6 +266,24 @@ fn collect_r1cs_components(
         }
     }
 
+    if let Some(&count) = stats.blackbox_func_counts.get("Keccakf1600") {
+        if count > 0 {
+            components.push(format!(
+                "Keccakf1600 Direct ({} calls):       {:>8} constraints {:>8} witnesses",
+                count, breakdown.keccak_direct_constraints, breakdown.keccak_direct_witnesses
+            ));
+        }
+    }
+
+    if let Some(&count) = stats.blackbox_func_counts.get("Blake2s") {
+        if count > 0 {
+            components.push(format!(
+                "Blake2s Direct ({} calls):           {:>8} constraints {:>8} witnesses",
+                count, breakdown.blake2s_direct_constraints, breakdown.blake2s_direct_witnesses
+            ));
+        }
+    }
+
     if let Some(&count) = stats.blackbox_func_counts.get("Poseidon2Permutation") {
         if count > 0 {
             components.push(format!(
@@ -314,6 +339,9 @@ fn print_batched_operations(stats: &CircuitStats, breakdown: &R1CSBreakdown) {
         && range_count == 0
         && breakdown.sha256_and_ops == 0
         && breakdown.sha256_xor_ops == 0
+        && breakdown.keccak_and_ops == 0
+        && breakdown.keccak_xor_ops == 0
+        && breakdown.blake2s_xor_ops == 0
     {
         return;
     }
@@ -428,17 +456,23 @@ fn print_batched_operations(stats: &CircuitStats, breakdown: &R1CSBreakdown) {
 
 fn print_r1cs_totals(r1cs: &R1CS, breakdown: &R1CSBreakdown) {
     let total_tracked_constraints = breakdown.assert_zero_constraints
+        + breakdown.digits_constraints
         + breakdown.memory_rom_constraints
         + breakdown.memory_ram_constraints
         + breakdown.sha256_direct_constraints
+        + breakdown.keccak_direct_constraints
+        + breakdown.blake2s_direct_constraints
         + breakdown.poseidon2_constraints
         + breakdown.binop_constraints
         + breakdown.range_constraints;
 
     let total_tracked_witnesses = breakdown.assert_zero_witnesses
+        + breakdown.digits_witnesses
         + breakdown.memory_rom_witnesses
         + breakdown.memory_ram_witnesses
         + breakdown.sha256_direct_witnesses
+        + breakdown.keccak_direct_witnesses
+        + breakdown.blake2s_direct_witnesses
         + breakdown.poseidon2_witnesses
         + breakdown.binop_witnesses
         + breakdown.range_witnesses;
@@ -0,0 +1,128 @@
+//! Machine-readable JSON output for circuit statistics.
+//!
+//! Maps are ordered by key, so the output is stable between runs and can be
+//! diffed or ingested by dashboards.
+
+use {
+    super::stats_collector::CircuitStats,
+    acir::{circuit::Circuit, FieldElement},
+    anyhow::Result,
+    provekit_common::R1CS,
+    provekit_r1cs_compiler::{CategoryCounts, R1CSBreakdown},
+    serde::Serialize,
+    std::{collections::BTreeMap, path::Path},
+};
+
+#[derive(Serialize)]
+struct Report<'a> {
+    circuit:       String,
+    function:      usize,
+    num_functions: usize,
+    io:            IoSummary,
+    acir:          AcirSummary,
+    r1cs:          R1csSummary,
+    categories:    BTreeMap<&'static str, CategoryCounts>,
+    breakdown:     &'a R1CSBreakdown,
+}
+
+#[derive(Serialize)]
+struct IoSummary {
+    private_inputs: usize,
+    public_inputs:  usize,
+    return_values:  usize,
+    acir_witnesses: u32,
+}
+
+#[derive(Serialize)]
+struct AcirSummary {
+    opcodes:              usize,
+    assert_zero_opcodes:  usize,
+    mul_terms:            usize,
+    /// Calls per black box function, including unused functions.
+    blackbox_calls:       BTreeMap<String, usize>,
+    /// Number of range checks per bit size.
+    range_checks:         BTreeMap<u32, usize>,
+    memory:               MemorySummary,
+    brillig_calls:        usize,
+    unique_brillig_calls: usize,
+    calls:                usize,
+    unique_calls:         usize,
+}
+
+#[derive(Serialize)]
+struct MemorySummary {
+    blocks:           usize,
+    read_only_blocks: usize,
+    allocated_words:  usize,
+    reads:            usize,
+    writes:           usize,
+}
+
+/// R1CS dimensions: `constraints` rows by `witnesses` columns.
+#[derive(Serialize)]
+struct R1csSummary {
+    constraints: usize,
+    witnesses:   usize,
+    a_entries:   usize,
+    b_entries:   usize,
+    c_entries:   usize,
+}
+
+pub(super) fn print_report(
+    path: &Path,
+    function: usize,
+    num_functions: usize,
+    circuit: &Circuit<FieldElement>,
+    stats: &CircuitStats,
+    r1cs: &R1CS,
+    breakdown: &R1CSBreakdown,
+) -> Result<()> {
+    let report = Report {
+        circuit: path.display().to_string(),
+        function,
+        num_functions,
+        io: IoSummary {
+            private_inputs: circuit.private_parameters.len(),
+            public_inputs:  circuit.public_parameters.0.len(),
+            return_values:  circuit.return_values.0.len(),
+            acir_witnesses: circuit.current_witness_index,
+        },
+        acir: AcirSummary {
+            opcodes:              circuit.opcodes.len(),
+            assert_zero_opcodes:  stats.num_assert_zero_opcodes,
+            mul_terms:            stats.num_mul_terms,
+            blackbox_calls:       stats
+                .blackbox_func_counts
+                .iter()
+                .map(|(name, count)| (name.clone(), *count))
+                .collect(),
+            range_checks:         stats
+                .range_check_bit_counts
+                .iter()
+                .map(|(bits, count)| (*bits, *count))
+                .collect(),
+            memory:               MemorySummary {
+                blocks:           stats.memory.total_blocks(),
+                read_only_blocks: stats.memory.read_only_block_count(),
+                allocated_words:  stats.memory.total_allocated(),
+                reads:            stats.memory.total_reads(),
+                writes:           stats.memory.total_writes(),
+            },
+            brillig_calls:        stats.num_brillig_calls,
+            unique_brillig_calls: stats.unique_brillig_calls.len(),
+            calls:                stats.num_calls,
+            unique_calls:         stats.unique_calls.len(),
+        },
+        r1cs: R1csSummary {
+            constraints: r1cs.num_constraints(),
+            witnesses:   r1cs.num_witnesses(),
+            a_entries:   r1cs.a.num_entries(),
+            b_entries:   r1cs.b.num_entries(),
+            c_entries:   r1cs.c.num_entries(),
+        },
+        categories: breakdown.by_category(),
+        breakdown,
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
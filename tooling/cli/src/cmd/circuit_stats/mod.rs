@@ -5,8 +5,18 @@
 //! ```sh
 //! cargo run --release --bin provekit-cli circuit_stats ./target/basic.json
 //! ```
+//!
+//! Pass `--format json` to print the statistics as JSON instead of tables.
+//!
+//! `--range-check-digit-bits` sets the width of the digits range checks are
+//! decomposed into, to compare the R1CS size for different lookup table sizes.
+//!
+//! Programs with several ACIR functions (for example when using `#[fold]`) are
+//! analyzed one function at a time, selected with `--function`. Witness
+//! indices are local to each function.
 
 mod display;
+mod json;
 mod memory;
 mod stats_collector;
 
@@ -16,11 +26,12 @@ use {
     anyhow::{Context, Result},
     argh::FromArgs,
     base64::Engine,
-    provekit_r1cs_compiler::noir_to_r1cs_with_breakdown,
+    provekit_r1cs_compiler::{noir_to_r1cs_with_params, R1CSCompilerParams},
     stats_collector::CircuitStats,
     std::{
         fs,
         path::{Path, PathBuf},
+        str::FromStr,
     },
     tracing::instrument,
 };
@@ -34,13 +45,62 @@ use {
 pub struct Args {
     #[argh(positional, description = "path to the ACIR circuit file (.json)")]
     circuit_path: PathBuf,
+
+    #[argh(
+        option,
+        default = "0",
+        description = "index of the ACIR function to analyze (default: 0, the entry point)"
+    )]
+    function: usize,
+
+    #[argh(
+        option,
+        default = "Format::Table",
+        description = "output format: table (default) or json"
+    )]
+    format: Format,
+
+    #[argh(
+        option,
+        default = "R1CSCompilerParams::default().range_check_digit_bits",
+        description = "width in bits of the digits range checks are decomposed into (default: 8)"
+    )]
+    range_check_digit_bits: u32,
+}
+
+/// Output format of the statistics.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Format {
+    Table,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format {s:?}, expected table or json")),
+        }
+    }
 }
 
 impl Command for Args {
     #[instrument(skip_all)]
     fn run(&self) -> Result<()> {
         let program = load_program(&self.circuit_path)?;
-        analyze_circuit(program, &self.circuit_path)
+        let params = R1CSCompilerParams {
+            range_check_digit_bits: self.range_check_digit_bits,
+        };
+        analyze_circuit(
+            program,
+            &self.circuit_path,
+            self.function,
+            self.format,
+            &params,
+        )
     }
 }
 
@@ -62,10 +122,16 @@ fn load_program(path: &Path) -> Result<Program<FieldElement>> {
     Program::deserialize_program(&bytecode).context("Failed to deserialize ACIR program")
 }
 
-fn analyze_circuit(program: Program<FieldElement>, path: &Path) -> Result<()> {
+fn analyze_circuit(
+    program: Program<FieldElement>,
+    path: &Path,
+    function: usize,
+    format: Format,
+    params: &R1CSCompilerParams,
+) -> Result<()> {
     anyhow::ensure!(
-        program.functions.len() == 1,
-        "Only single-function programs are currently supported (found {} functions)",
+        function < program.functions.len(),
+        "Function index {function} out of range (program has {} functions)",
         program.functions.len()
     );
 
@@ -73,23 +139,39 @@ fn analyze_circuit(program: Program<FieldElement>, path: &Path) -> Result<()> {
         mut functions,
         unconstrained_functions: _,
     } = program;
-    let circuit = functions.pop().unwrap();
-
-    println!("\n╔═══════════════════════════════════════════════════════════════╗");
-    println!("║                   ACIR Circuit Analysis                       ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝");
-    println!("Circuit: {}", path.display());
-
-    display::print_io_summary(&circuit);
+    let num_functions = functions.len();
+    let circuit = functions.swap_remove(function);
 
     let stats = CircuitStats::from_circuit(&circuit);
 
-    display::print_acir_stats(&stats);
+    if format == Format::Table {
+        println!("\n╔═══════════════════════════════════════════════════════════════╗");
+        println!("║                   ACIR Circuit Analysis                       ║");
+        println!("╚═══════════════════════════════════════════════════════════════╝");
+        println!("Circuit: {}", path.display());
+        if num_functions > 1 {
+            println!("Function: {function} ({num_functions} functions in program)");
+        }
+
+        display::print_io_summary(&circuit);
+        display::print_acir_stats(&stats);
+    }
 
     let (r1cs, _witness_map, _witness_builders, breakdown) =
-        noir_to_r1cs_with_breakdown(&circuit).context("Failed to compile circuit to R1CS")?;
-
-    display::print_r1cs_breakdown(&stats, &circuit, &r1cs, &breakdown);
+        noir_to_r1cs_with_params(&circuit, params).context("Failed to compile circuit to R1CS")?;
+
+    match format {
+        Format::Table => display::print_r1cs_breakdown(&stats, &circuit, &r1cs, &breakdown),
+        Format::Json => json::print_report(
+            path,
+            function,
+            num_functions,
+            &circuit,
+            &stats,
+            &r1cs,
+            &breakdown,
+        )?,
+    }
 
     Ok(())
 }
@@ -3,7 +3,7 @@ use {
     anyhow::{Context, Result},
     argh::FromArgs,
     provekit_common::{file::read, NoirProof, Prover},
-    provekit_gnark::write_gnark_parameters_to_file,
+    provekit_gnark::{write_gnark_parameters_to_file, write_recursion_inputs_to_file},
     std::{fs::File, io::Write, path::PathBuf},
     tracing::{info, instrument},
 };
@@ -39,6 +39,10 @@ pub struct Args {
     /// path to the r1cs file
     #[argh(option, long = "r1cs", default = "String::from(\"./r1cs.json\")")]
     r1cs_path: String,
+
+    /// path to also write the inputs of a Groth16 recursion circuit to
+    #[argh(option, long = "recursion")]
+    recursion_inputs_path: Option<PathBuf>,
 }
 
 impl Command for Args {
@@ -55,17 +59,22 @@ impl Command for Args {
         write_gnark_parameters_to_file(
             &prover.whir_for_witness.whir_witness,
             &prover.whir_for_witness.whir_for_hiding_spartan,
-            &proof.whir_r1cs_proof.transcript,
+            proof.transcript_bytes(),
             &prover.whir_for_witness.create_io_pattern(),
             prover.whir_for_witness.m_0,
             prover.whir_for_witness.m,
             prover.whir_for_witness.a_num_terms,
             prover.whir_for_witness.num_challenges,
             prover.whir_for_witness.w1_size,
-            &proof.public_inputs,
+            proof.public_inputs(),
             &self.params_for_recursive_verifier,
         );
 
+        if let Some(path) = &self.recursion_inputs_path {
+            write_recursion_inputs_to_file(&prover.whir_for_witness, &proof, path)
+                .context("while writing recursion inputs")?;
+        }
+
         let json = serde_json::to_string_pretty(&prover.r1cs).unwrap(); // Or `to_string` for compact
         let mut file = File::create(&self.r1cs_path)?;
         file.write_all(json.as_bytes())?;
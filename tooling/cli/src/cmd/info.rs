@@ -0,0 +1,186 @@
+use {
+    super::Command,
+    anyhow::{bail, Result},
+    argh::FromArgs,
+    provekit_common::{
+        file::{read, FileFormat},
+        NoirProofScheme, Prover, Verifier, WhirConfig, WhirR1CSScheme, R1CS,
+    },
+    serde::Serialize,
+    std::{ffi::OsStr, path::PathBuf},
+    tracing::instrument,
+};
+
+/// Print the parameters of a prepared proof scheme
+#[derive(FromArgs, PartialEq, Eq, Debug)]
+#[argh(subcommand, name = "info")]
+pub struct Args {
+    /// path to the proof scheme (.nps), prover (.pkp) or verifier (.pkv)
+    #[argh(positional)]
+    scheme_path: PathBuf,
+
+    /// print the parameters as JSON on stdout
+    #[argh(switch)]
+    json: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct WhirInfo {
+    num_variables:         usize,
+    folding_factor:        usize,
+    security_level:        usize,
+    starting_log_inv_rate: usize,
+}
+
+impl WhirInfo {
+    fn new(config: &WhirConfig) -> Self {
+        Self {
+            num_variables:         config.mv_parameters.num_variables,
+            folding_factor:        config.folding_factor.at_round(0),
+            security_level:        config.security_level,
+            starting_log_inv_rate: config.starting_log_inv_rate,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Info {
+    /// Format version of the file, as `major.minor`.
+    version:        String,
+    /// R1CS dimensions, absent for verifier files.
+    constraints:    Option<usize>,
+    witnesses:      Option<usize>,
+    public_inputs:  usize,
+    w1_size:        usize,
+    num_challenges: usize,
+    m:              usize,
+    m_0:            usize,
+    witness_whir:   WhirInfo,
+    spartan_whir:   WhirInfo,
+}
+
+impl Info {
+    fn new<T: FileFormat>(r1cs: Option<&R1CS>, whir: &WhirR1CSScheme) -> Self {
+        let (major, minor) = T::VERSION;
+        Self {
+            version:        format!("{major}.{minor}"),
+            constraints:    r1cs.map(R1CS::num_constraints),
+            witnesses:      r1cs.map(R1CS::num_witnesses),
+            public_inputs:  whir.num_public_inputs,
+            w1_size:        whir.w1_size,
+            num_challenges: whir.num_challenges,
+            m:              whir.m,
+            m_0:            whir.m_0,
+            witness_whir:   WhirInfo::new(&whir.whir_witness),
+            spartan_whir:   WhirInfo::new(&whir.whir_for_hiding_spartan),
+        }
+    }
+}
+
+impl Args {
+    fn info(&self) -> Result<Info> {
+        // JSON files carry no header, so the extension before `.json` decides
+        // the type.
+        let path = &self.scheme_path;
+        let stem = PathBuf::from(path.file_stem().unwrap_or_default());
+        let extension = match path.extension().and_then(OsStr::to_str) {
+            Some("json") => stem.extension().and_then(OsStr::to_str),
+            extension => extension,
+        };
+        Ok(match extension {
+            Some(ext) if ext == NoirProofScheme::EXTENSION => {
+                let scheme: NoirProofScheme = read(path)?;
+                Info::new::<NoirProofScheme>(Some(&scheme.r1cs), &scheme.whir_for_witness)
+            }
+            Some(ext) if ext == Prover::EXTENSION => {
+                let prover: Prover = read(path)?;
+                Info::new::<Prover>(Some(&prover.r1cs), &prover.whir_for_witness)
+            }
+            Some(ext) if ext == Verifier::EXTENSION => {
+                let verifier: Verifier = read(path)?;
+                let Some(whir) = &verifier.whir_for_witness else {
+                    bail!("Verifier file has no WHIR scheme");
+                };
+                Info::new::<Verifier>(None, whir)
+            }
+            _ => bail!(
+                "Unsupported file extension, please specify .{}, .{} or .{}",
+                NoirProofScheme::EXTENSION,
+                Prover::EXTENSION,
+                Verifier::EXTENSION
+            ),
+        })
+    }
+}
+
+impl Command for Args {
+    #[instrument(skip_all)]
+    fn run(&self) -> Result<()> {
+        let info = self.info()?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&info)?);
+            return Ok(());
+        }
+
+        println!("format version {}", info.version);
+        if let (Some(constraints), Some(witnesses)) = (info.constraints, info.witnesses) {
+            println!("{constraints} constraints, {witnesses} witnesses");
+        }
+        println!(
+            "{} public inputs, w1 size {}, {} challenges, m = {}, m_0 = {}",
+            info.public_inputs, info.w1_size, info.num_challenges, info.m, info.m_0
+        );
+        for (name, whir) in [
+            ("witness", &info.witness_whir),
+            ("spartan", &info.spartan_whir),
+        ] {
+            println!(
+                "{name} WHIR: {} variables, folding factor {}, {} bits security, rate 2^-{}",
+                whir.num_variables,
+                whir.folding_factor,
+                whir.security_level,
+                whir.starting_log_inv_rate
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, provekit_common::file::write, provekit_r1cs_compiler::NoirProofSchemeBuilder};
+
+    #[test]
+    fn prepared_prover_info() {
+        let scheme =
+            NoirProofScheme::from_file("../provekit-bench/benches/poseidon_rounds.json").unwrap();
+        let scheme_path = std::env::temp_dir().join(format!(
+            "provekit-info-{}.{}",
+            std::process::id(),
+            Prover::EXTENSION
+        ));
+        write(
+            &Prover::from_noir_proof_scheme(scheme.clone()),
+            &scheme_path,
+        )
+        .unwrap();
+
+        let info = Args {
+            scheme_path: scheme_path.clone(),
+            json:        false,
+        }
+        .info()
+        .unwrap();
+        std::fs::remove_file(&scheme_path).unwrap();
+
+        let (constraints, witnesses) = scheme.size();
+        assert_eq!(info.constraints, Some(constraints));
+        assert_eq!(info.witnesses, Some(witnesses));
+        assert_eq!(info.public_inputs, scheme.public_input_count());
+        assert_eq!(info.m, scheme.whir_m());
+        assert_eq!(info.m_0, scheme.whir_m_0());
+        assert_eq!(info.witness_whir.security_level, 128);
+    }
+}
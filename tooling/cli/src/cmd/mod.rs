@@ -1,5 +1,7 @@
+mod bench;
 mod circuit_stats;
 mod generate_gnark_inputs;
+mod info;
 mod prepare;
 mod prove;
 mod verify;
@@ -41,6 +43,8 @@ enum Commands {
     CircuitStats(circuit_stats::Args),
     Verify(verify::Args),
     GenerateGnarkInputs(generate_gnark_inputs::Args),
+    Bench(bench::Args),
+    Info(info::Args),
 }
 
 impl Command for Args {
@@ -57,6 +61,8 @@ impl Command for Commands {
             Self::CircuitStats(args) => args.run(),
             Self::Verify(args) => args.run(),
             Self::GenerateGnarkInputs(args) => args.run(),
+            Self::Bench(args) => args.run(),
+            Self::Info(args) => args.run(),
         }
     }
 }
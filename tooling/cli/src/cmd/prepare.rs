@@ -2,9 +2,15 @@ use {
     super::Command,
     anyhow::{Context, Result},
     argh::FromArgs,
-    provekit_common::{file::write, NoirProofScheme, Prover, Verifier},
+    provekit_common::{
+        file::{write, FileFormat},
+        NoirProofScheme, Prover, Verifier,
+    },
     provekit_r1cs_compiler::NoirProofSchemeBuilder,
-    std::path::PathBuf,
+    std::{
+        ffi::OsStr,
+        path::{Path, PathBuf},
+    },
     tracing::instrument,
 };
 
@@ -33,20 +39,82 @@ pub struct Args {
         default = "PathBuf::from(\"noir_proof_scheme.pkv\")"
     )]
     pkv_path: PathBuf,
+
+    /// also write each output as JSON next to it, e.g. `prover.pkp.json`
+    #[argh(switch)]
+    also_json: bool,
+
+    /// index of the ACIR function to prove (default: 0, the entry point)
+    #[argh(option, default = "0")]
+    function: u32,
+}
+
+/// Write `value` to `path`, and also as JSON to `path` + `.json` if asked
+fn write_outputs<T: FileFormat>(value: &T, path: &Path, also_json: bool) -> Result<()> {
+    write(value, path)?;
+    if also_json && path.extension() != Some(OsStr::new("json")) {
+        let mut json_path = path.as_os_str().to_owned();
+        json_path.push(".json");
+        write(value, Path::new(&json_path))?;
+    }
+    Ok(())
 }
 
 impl Command for Args {
     #[instrument(skip_all)]
     fn run(&self) -> Result<()> {
-        let scheme = NoirProofScheme::from_file(&self.program_path)
+        let scheme = NoirProofScheme::from_file_function(&self.program_path, self.function)
             .context("while compiling Noir program")?;
-        write(
+        write_outputs(
             &Prover::from_noir_proof_scheme(scheme.clone()),
             &self.pkp_path,
+            self.also_json,
+        )
+        .context("while writing Noir proof scheme")?;
+        write_outputs(
+            &Verifier::from_noir_proof_scheme(scheme),
+            &self.pkv_path,
+            self.also_json,
         )
         .context("while writing Noir proof scheme")?;
-        write(&Verifier::from_noir_proof_scheme(scheme), &self.pkv_path)
-            .context("while writing Noir proof scheme")?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, provekit_common::file::read};
+
+    #[test]
+    fn prepare_also_json() {
+        let dir = std::env::temp_dir().join(format!("provekit-prepare-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pkp_path = dir.join("prover.pkp");
+        let pkv_path = dir.join("verifier.pkv");
+        Args {
+            program_path: PathBuf::from("../provekit-bench/benches/poseidon_rounds.json"),
+            pkp_path:     pkp_path.clone(),
+            pkv_path:     pkv_path.clone(),
+            also_json:    true,
+            function:     0,
+        }
+        .run()
+        .unwrap();
+
+        let prover: Prover = read(&pkp_path).unwrap();
+        let prover_json: Prover = read(&dir.join("prover.pkp.json")).unwrap();
+        let verifier: Verifier = read(&pkv_path).unwrap();
+        let verifier_json: Verifier = read(&dir.join("verifier.pkv.json")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Neither type implements `PartialEq`, so compare their serializations
+        assert_eq!(
+            serde_json::to_value(&prover).unwrap(),
+            serde_json::to_value(&prover_json).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&verifier).unwrap(),
+            serde_json::to_value(&verifier_json).unwrap()
+        );
+    }
+}
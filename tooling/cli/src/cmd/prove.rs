@@ -1,13 +1,15 @@
 use {
     super::Command,
-    anyhow::{Context, Result},
+    anyhow::{ensure, Context, Result},
     argh::FromArgs,
+    noirc_artifacts::program::ProgramArtifact,
     provekit_common::{
-        file::{read, write},
+        file::{read, serialized_size, write},
+        utils::human,
         Prover,
     },
-    provekit_prover::Prove,
-    std::path::PathBuf,
+    provekit_prover::{Prove, ProveInputs, ProveOptions, WitnessMode},
+    std::{fs::File, path::PathBuf},
     tracing::{info, instrument},
 };
 #[cfg(test)]
@@ -38,6 +40,25 @@ pub struct Args {
         default = "PathBuf::from(\"./proof.np\")"
     )]
     proof_path: PathBuf,
+
+    /// spill the witness to a temporary file while solving to lower peak
+    /// memory
+    #[argh(switch)]
+    streaming_witness: bool,
+
+    /// directory of the spill file of --streaming-witness, the system
+    /// temporary directory by default
+    #[argh(option)]
+    spill_dir: Option<PathBuf>,
+
+    /// fail without writing the proof if its file would be larger than this
+    /// many bytes
+    #[argh(option)]
+    max_proof_size: Option<usize>,
+
+    /// compiled Noir program (.json) the prover must have been prepared from
+    #[argh(option)]
+    program: Option<PathBuf>,
 }
 
 impl Command for Args {
@@ -48,12 +69,32 @@ impl Command for Args {
         let (constraints, witnesses) = prover.size();
         info!(constraints, witnesses, "Read Noir proof scheme");
 
+        if let Some(program_path) = &self.program {
+            let file = File::open(program_path).context("while opening Noir program")?;
+            let program: ProgramArtifact =
+                serde_json::from_reader(file).context("while reading Noir program")?;
+            ensure!(
+                prover.is_compatible_with(&program.bytecode),
+                "Prover was not prepared from {}, prepare it again",
+                program_path.display()
+            );
+        }
+
         // // Read the input toml
         // let input_map = scheme.read_witness(&self.input_path)?;
 
         // Generate the proof
+        let mode = if self.streaming_witness {
+            WitnessMode::Streaming
+        } else {
+            WitnessMode::InMemory
+        };
         let proof = prover
-            .prove(&self.input_path)
+            .prove_with(ProveInputs::File(self.input_path.clone()), ProveOptions {
+                mode,
+                spill_dir: self.spill_dir.clone(),
+                ..ProveOptions::default()
+            })
             .context("While proving Noir program statement")?;
 
         // Verify the proof (not in release build)
@@ -66,6 +107,16 @@ impl Command for Args {
                 .context("While verifying Noir proof")?;
         }
 
+        // Check the proof size before storing it
+        let size = serialized_size(&proof, &self.proof_path).context("while sizing proof")?;
+        info!(size, "Proof is {}B", human(size as f64));
+        if let Some(max_proof_size) = self.max_proof_size {
+            ensure!(
+                size <= max_proof_size,
+                "Proof is {size} bytes, above the maximum of {max_proof_size} bytes"
+            );
+        }
+
         // Store the proof to file
         write(&proof, &self.proof_path).context("while writing proof")?;
 
@@ -4,7 +4,7 @@ use {
     core::hint::black_box,
     divan::Bencher,
     provekit_common::{file::read, NoirProof, Prover, Verifier},
-    provekit_prover::Prove,
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
     provekit_verifier::Verify,
     std::path::Path,
 };
@@ -29,8 +29,8 @@ fn prove_poseidon_1000(bencher: Bencher) {
 
     bencher.bench_local(|| {
         let prover = black_box(prover.clone());
-        let witness_path = black_box(&witness_path);
-        prover.prove(witness_path)
+        let witness_path = black_box(witness_path.clone());
+        prover.prove_with(ProveInputs::File(witness_path), ProveOptions::default())
     });
 }
 
@@ -52,7 +52,10 @@ fn prove_poseidon_1000_with_io(bencher: Bencher) {
             })
             .expect("Reading prover failed");
         let prover = black_box(prover);
-        prover.prove(black_box(&witness_path))
+        prover.prove_with(
+            ProveInputs::File(black_box(witness_path.clone())),
+            ProveOptions::default(),
+        )
     });
 }
 
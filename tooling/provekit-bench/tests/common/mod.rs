@@ -0,0 +1,18 @@
+use {
+    provekit_common::{NoirProof, NoirProofScheme, Prover},
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
+    provekit_test_utils::prepare_program,
+};
+
+/// Proves the `simplest-read-only-memory` program, which returns one public
+/// output.
+pub fn prove_simplest_read_only_memory() -> (NoirProofScheme, NoirProof) {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/simplest-read-only-memory",
+        "main",
+    );
+    let proof = Prover::from_noir_proof_scheme(schema.clone())
+        .prove_with(ProveInputs::Map(input_map), ProveOptions::default())
+        .expect("While proving Noir program statement");
+    (schema, proof)
+}
@@ -1,15 +1,19 @@
+mod common;
+
 use {
-    anyhow::Result,
-    nargo::workspace::Workspace,
-    nargo_cli::cli::compile_cmd::compile_workspace_full,
-    nargo_toml::{resolve_workspace_from_toml, PackageSelection},
-    noirc_driver::CompileOptions,
-    provekit_common::{NoirProofScheme, Prover, Verifier},
-    provekit_prover::Prove,
-    provekit_r1cs_compiler::NoirProofSchemeBuilder,
+    crate::common::prove_simplest_read_only_memory,
+    noirc_artifacts::program::ProgramArtifact,
+    provekit_common::{FieldElement, NoirProofScheme, Prover, Verifier},
+    provekit_gnark::{RecursionInputs, RECURSION_INPUTS_VERSION, TRANSCRIPT_LIMB_BYTES},
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
+    provekit_r1cs_compiler::{
+        noir_to_r1cs, noir_to_r1cs_with_breakdown, noir_to_r1cs_with_params, CompileError,
+        NoirProofSchemeBuilder, R1CSBreakdown, R1CSCompilerParams,
+    },
+    provekit_test_utils::{compile_workspace, prepare_program},
     provekit_verifier::Verify,
     serde::Deserialize,
-    std::path::Path,
+    std::{collections::BTreeSet, fs::File, path::Path},
     test_case::test_case,
 };
 
@@ -43,31 +47,220 @@ fn test_compiler(test_case_path: impl AsRef<Path>) {
     let mut verifier = Verifier::from_noir_proof_scheme(schema.clone());
 
     let proof = prover
-        .prove(&witness_file_path)
+        .prove_with(
+            ProveInputs::File(witness_file_path),
+            ProveOptions::default(),
+        )
         .expect("While proving Noir program statement");
 
     verifier.verify(&proof).expect("Verifying proof");
 }
 
-pub fn compile_workspace(workspace_path: impl AsRef<Path>) -> Result<Workspace> {
-    let workspace_path = workspace_path.as_ref();
-    let workspace_path = if workspace_path.ends_with("Nargo.toml") {
-        workspace_path.to_owned()
-    } else {
-        workspace_path.join("Nargo.toml")
+#[test]
+fn estimated_proof_size() {
+    let test_case_path = Path::new("../../noir-examples/noir-r1cs-test-programs/small-sha");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+
+    let schema = NoirProofScheme::from_file(test_case_path.join("target/basic.json"))
+        .expect("Reading proof scheme");
+    let estimate = schema.whir_for_witness.estimated_proof_size_bytes();
+    let proof = Prover::from_noir_proof_scheme(schema)
+        .prove_with(
+            ProveInputs::File(test_case_path.join("Prover.toml")),
+            ProveOptions::default(),
+        )
+        .expect("While proving Noir program statement");
+
+    let actual = proof.whir_r1cs_proof.transcript.len();
+    assert!(
+        actual / 2 <= estimate && estimate <= 2 * actual,
+        "estimated {estimate} bytes, the proof has {actual}"
+    );
+}
+
+#[test]
+fn unsupported_black_box_error() {
+    let test_case_path =
+        Path::new("../../noir-examples/noir-r1cs-test-programs/unsupported-black-box");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+
+    let file = File::open(test_case_path.join("target/main.json")).expect("Opening circuit");
+    let program: ProgramArtifact = serde_json::from_reader(file).expect("Reading circuit");
+    assert_eq!(
+        noir_to_r1cs(&program.bytecode.functions[0]).err(),
+        Some(CompileError::UnsupportedBlackBox("blake3".to_string()))
+    );
+
+    let error = NoirProofScheme::from_file(test_case_path.join("target/main.json"))
+        .expect_err("Compiling a program with an unsupported black box");
+    assert_eq!(
+        error.downcast_ref::<CompileError>(),
+        Some(&CompileError::UnsupportedBlackBox("blake3".to_string())),
+        "{error:#}"
+    );
+}
+
+#[test]
+fn prove_folded_function() {
+    let test_case_path = Path::new("../../noir-examples/noir-r1cs-test-programs/fold-call");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+    let read_program = || -> ProgramArtifact {
+        let file = File::open(test_case_path.join("target/main.json")).expect("Opening program");
+        serde_json::from_reader(file).expect("Reading program")
+    };
+
+    // The entry point calls `double`, and calls between functions are not
+    // linked.
+    let error = NoirProofScheme::from_program(read_program())
+        .expect_err("Compiling a program that calls another function");
+    assert!(
+        matches!(
+            error.downcast_ref::<CompileError>(),
+            Some(CompileError::UnsupportedOpcode(_))
+        ),
+        "{error:#}"
+    );
+    NoirProofScheme::from_program_function(read_program(), 2)
+        .expect_err("Selecting a function that does not exist");
+
+    // `double` is proven on its own, from the witness of its call by `main`.
+    let schema =
+        NoirProofScheme::from_program_function(read_program(), 1).expect("Reading proof scheme");
+    let proof = Prover::from_noir_proof_scheme(schema.clone())
+        .prove_with(
+            ProveInputs::File(test_case_path.join("Prover.toml")),
+            ProveOptions::default(),
+        )
+        .expect("While proving Noir program statement");
+    Verifier::from_noir_proof_scheme(schema)
+        .verify(&proof)
+        .expect("Verifying proof");
+}
+
+#[test]
+fn scheme_compatibility() {
+    let (schema, _) = prove_simplest_read_only_memory();
+    let read_program = |path: &Path| -> ProgramArtifact {
+        compile_workspace(path).expect("Compiling workspace");
+        let file = File::open(path.join("target/main.json")).expect("Opening program");
+        serde_json::from_reader(file).expect("Reading program")
     };
 
-    // `resolve_workspace_from_toml` calls .normalize() under the hood which messes
-    // up path resolution
-    let workspace_path = workspace_path.canonicalize()?;
+    let same = read_program(Path::new(
+        "../../noir-examples/noir-r1cs-test-programs/simplest-read-only-memory",
+    ));
+    assert!(schema.is_compatible_with(&same.bytecode));
+
+    let other = read_program(Path::new(
+        "../../noir-examples/noir-r1cs-test-programs/range-check-u8",
+    ));
+    assert!(!schema.is_compatible_with(&other.bytecode));
+    assert!(!Prover::from_noir_proof_scheme(schema).is_compatible_with(&other.bytecode));
+}
+
+#[test]
+fn recursion_inputs_schema() {
+    let (schema, proof) = prove_simplest_read_only_memory();
+    let inputs = RecursionInputs::new(&schema.whir_for_witness, &proof);
+    let json = serde_json::to_value(&inputs).expect("Serializing recursion inputs");
+
+    assert_eq!(json["version"], RECURSION_INPUTS_VERSION);
+    let field_elements = |key: &str| {
+        json[key]
+            .as_array()
+            .unwrap_or_else(|| panic!("{key} is an array"))
+            .iter()
+            .map(|value| {
+                value
+                    .as_str()
+                    .unwrap_or_else(|| panic!("{key} holds strings"))
+                    .parse::<FieldElement>()
+                    .unwrap_or_else(|_| panic!("{key} holds decimal field elements"))
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(field_elements("public_inputs"), proof.public_inputs.0);
+    let hash = json["public_inputs_hash"]
+        .as_str()
+        .expect("Public input hash is a string");
+    assert_eq!(
+        hash.parse::<FieldElement>().ok(),
+        Some(proof.public_inputs.hash())
+    );
+
+    let transcript = &proof.whir_r1cs_proof.transcript;
+    assert_eq!(json["transcript_len"], transcript.len());
+    assert_eq!(
+        field_elements("transcript_limbs").len(),
+        transcript.len().div_ceil(TRANSCRIPT_LIMB_BYTES)
+    );
+    assert_eq!(inputs.transcript().as_ref(), Some(transcript));
+
+    for key in ["whir_config_witness", "whir_config_hiding_spartan"] {
+        let config = json[key].as_object().expect("WHIR config is an object");
+        let rounds = config["n_rounds"].as_u64().expect("Number of rounds") as usize;
+        assert_eq!(
+            config["folding_factor"].as_array().map(Vec::len),
+            Some(rounds)
+        );
+        assert!(config["domain_generator"]
+            .as_str()
+            .is_some_and(|generator| generator.parse::<FieldElement>().is_ok()));
+    }
+    for key in [
+        "log_num_constraints",
+        "log_num_variables",
+        "log_a_num_terms",
+        "num_challenges",
+        "w1_size",
+    ] {
+        assert!(json[key].is_u64(), "{key} is an integer");
+    }
+}
+
+#[test]
+fn seeded_proofs_are_identical() {
+    let (schema, input_map) = prepare_program(
+        "../../noir-examples/noir-r1cs-test-programs/simplest-read-only-memory",
+        "main",
+    );
 
-    let workspace =
-        resolve_workspace_from_toml(&workspace_path, PackageSelection::DefaultOrAll, None)?;
-    let compile_options = CompileOptions::default();
+    let [first, second] = [(); 2].map(|_| {
+        Prover::from_noir_proof_scheme(schema.clone())
+            .prove_with(ProveInputs::Map(input_map.clone()), ProveOptions {
+                seed: Some(42),
+                ..ProveOptions::default()
+            })
+            .expect("While proving Noir program statement")
+    });
+    Verifier::from_noir_proof_scheme(schema)
+        .verify(&first)
+        .expect("Verifying proof");
+    assert_eq!(
+        serde_json::to_vec(&first).expect("Serializing proof"),
+        serde_json::to_vec(&second).expect("Serializing proof")
+    );
+}
 
-    compile_workspace_full(&workspace, &compile_options, None)?;
+#[test]
+fn scheme_stats() {
+    let test_case_path = Path::new("../../noir-examples/noir-r1cs-test-programs/small-sha");
+    compile_workspace(test_case_path).expect("Compiling workspace");
 
-    Ok(workspace)
+    let file = File::open(test_case_path.join("target/basic.json")).expect("Opening circuit");
+    let program: ProgramArtifact = serde_json::from_reader(file).expect("Reading circuit");
+    // The same compilation `circuit_stats` reports on.
+    let main = &program.bytecode.functions[0];
+    let (r1cs, ..) = noir_to_r1cs_with_breakdown(main).expect("Compiling circuit to R1CS");
+    let public_inputs = main.public_inputs().indices().len();
+
+    let scheme = NoirProofScheme::from_program(program).expect("Preparing proof scheme");
+    assert_eq!(scheme.num_constraints(), r1cs.num_constraints());
+    assert_eq!(scheme.num_witnesses(), r1cs.num_witnesses());
+    assert_eq!(scheme.public_input_count(), public_inputs);
+    assert_eq!(scheme.public_input_count(), 32);
+    assert!(1 << scheme.whir_m_0() >= scheme.num_constraints());
+    assert!(1 << (scheme.whir_m() - 1) >= scheme.split_witness_builders.w1_size);
 }
 
 #[test_case("../../noir-examples/noir-r1cs-test-programs/acir_assert_zero")]
@@ -77,12 +270,126 @@ pub fn compile_workspace(workspace_path: impl AsRef<Path>) -> Result<Workspace>
 #[test_case("../../noir-examples/noir-r1cs-test-programs/range-check-u16")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/range-check-mixed-bases")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/read-write-memory")]
+#[test_case("../../noir-examples/noir-r1cs-test-programs/lookup-table")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/conditional-write")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/bin-opcode")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/small-sha")]
+#[test_case("../../noir-examples/noir-r1cs-test-programs/keccakf1600")]
+#[test_case("../../noir-examples/noir-r1cs-test-programs/blake2s")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/bounded-vec")]
 #[test_case("../../noir-examples/noir-r1cs-test-programs/brillig-unconstrained")]
 #[test_case("../../noir-examples/noir-passport-examples/complete_age_check"; "complete_age_check")]
 fn case(path: &str) {
     test_compiler(path);
 }
+
+#[test]
+fn compile_missing_workspace() {
+    let Err(error) = compile_workspace("../../noir-examples/does-not-exist") else {
+        panic!("Compiling a missing workspace succeeded");
+    };
+    let message = format!("{error:#}");
+    assert!(
+        message.contains("noir-examples/does-not-exist/Nargo.toml"),
+        "{message}"
+    );
+}
+
+#[test]
+fn rom_lookups_cheaper_than_ram() {
+    let test_case_path = Path::new("../../noir-examples/noir-r1cs-test-programs/lookup-table");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+
+    let file = File::open(test_case_path.join("target/main.json")).expect("Opening circuit");
+    let program: ProgramArtifact = serde_json::from_reader(file).expect("Reading circuit");
+    let (.., breakdown) = noir_to_r1cs_with_breakdown(&program.bytecode.functions[0])
+        .expect("Compiling circuit to R1CS");
+
+    // The circuit reads the same table at the same indices as ROM and as RAM.
+    // RAM timestamps are also range checked, which is not counted here.
+    assert!(breakdown.memory_rom_constraints > 0);
+    assert!(
+        breakdown.memory_rom_constraints < breakdown.memory_ram_constraints,
+        "ROM: {} constraints, RAM: {} constraints",
+        breakdown.memory_rom_constraints,
+        breakdown.memory_ram_constraints
+    );
+    assert!(breakdown.memory_rom_witnesses < breakdown.memory_ram_witnesses);
+}
+
+#[test]
+fn range_check_digit_widths() {
+    let test_case_path =
+        Path::new("../../noir-examples/noir-r1cs-test-programs/range-check-mixed-bases");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+
+    let file = File::open(test_case_path.join("target/main.json")).expect("Opening circuit");
+    let program: ProgramArtifact = serde_json::from_reader(file).expect("Reading circuit");
+    let main = &program.bytecode.functions[0];
+    let breakdown = |range_check_digit_bits| {
+        let params = R1CSCompilerParams {
+            range_check_digit_bits,
+        };
+        noir_to_r1cs_with_params(main, &params)
+            .expect("Compiling circuit to R1CS")
+            .3
+    };
+
+    // The 238, 16 and 17 bit range checks need 33 byte digits, so their
+    // lookup table of 256 entries outweighs the extra 4-bit digits.
+    let (bytes, nibbles) = (breakdown(8), breakdown(4));
+    assert_eq!(
+        bytes.range_constraints,
+        noir_to_r1cs_with_breakdown(main)
+            .expect("Compiling circuit to R1CS")
+            .3
+            .range_constraints
+    );
+    assert!(
+        nibbles.range_constraints < bytes.range_constraints,
+        "4-bit digits: {} constraints, 8-bit digits: {} constraints",
+        nibbles.range_constraints,
+        bytes.range_constraints
+    );
+    assert_eq!(nibbles.range_ops_total, bytes.range_ops_total);
+
+    for range_check_digit_bits in [0, 17] {
+        let params = R1CSCompilerParams {
+            range_check_digit_bits,
+        };
+        assert!(matches!(
+            noir_to_r1cs_with_params(main, &params),
+            Err(CompileError::InvalidParams(_))
+        ));
+    }
+}
+
+#[test]
+fn breakdown_json() {
+    let test_case_path = Path::new("../../noir-examples/noir-r1cs-test-programs/blake2s");
+    compile_workspace(test_case_path).expect("Compiling workspace");
+
+    let file = File::open(test_case_path.join("target/main.json")).expect("Opening circuit");
+    let program: ProgramArtifact = serde_json::from_reader(file).expect("Reading circuit");
+    let (r1cs, _, _, breakdown) = noir_to_r1cs_with_breakdown(&program.bytecode.functions[0])
+        .expect("Compiling circuit to R1CS");
+
+    let json = serde_json::to_value(breakdown.by_category()).expect("Serializing breakdown");
+    let categories = json.as_object().expect("Breakdown is a JSON object");
+    let keys = categories
+        .keys()
+        .map(String::as_str)
+        .collect::<BTreeSet<_>>();
+    assert_eq!(keys, BTreeSet::from(R1CSBreakdown::CATEGORIES));
+    for counts in categories.values() {
+        assert!(counts["constraints"].is_u64());
+        assert!(counts["witnesses"].is_u64());
+    }
+    assert!(categories["blake2s"]["constraints"].as_u64().unwrap() > 0);
+
+    let total_constraints: u64 = categories
+        .values()
+        .map(|counts| counts["constraints"].as_u64().unwrap())
+        .sum();
+    assert_eq!(total_constraints, r1cs.num_constraints() as u64);
+}
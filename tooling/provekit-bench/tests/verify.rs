@@ -0,0 +1,122 @@
+mod common;
+
+use {
+    crate::common::prove_simplest_read_only_memory,
+    provekit_common::{FieldElement, NoirProof, Verifier, WhirR1CSProof},
+    provekit_verifier::{
+        precheck_public_inputs, BatchVerificationError, PublicInputsMismatch, Verify,
+    },
+};
+
+#[test]
+fn proof_accessors_hold_verifier_inputs() {
+    let (schema, proof) = prove_simplest_read_only_memory();
+
+    // A proof rebuilt from the accessors alone must verify
+    let rebuilt = NoirProof {
+        public_inputs:   proof.public_inputs().clone(),
+        whir_r1cs_proof: WhirR1CSProof {
+            transcript: proof.transcript_bytes().to_vec(),
+        },
+    };
+    assert_eq!(rebuilt, proof);
+    Verifier::from_noir_proof_scheme(schema)
+        .verify(&rebuilt)
+        .expect("Verifying rebuilt proof");
+}
+
+#[test]
+fn verify_public_input_count_mismatch() {
+    let (schema, mut proof) = prove_simplest_read_only_memory();
+    proof.public_inputs.0.push(FieldElement::from(0u64));
+
+    let mut verifier = Verifier::from_noir_proof_scheme(schema);
+    let error = verifier
+        .verify(&proof)
+        .expect_err("Verifying tampered proof");
+    assert_eq!(
+        error.to_string(),
+        "Proof has 2 public inputs, but the circuit expects 1"
+    );
+}
+
+#[test]
+fn verify_many_with_one_verifier() {
+    let (schema, proof) = prove_simplest_read_only_memory();
+    let mut proofs = vec![proof];
+    proofs.extend((0..2).map(|_| prove_simplest_read_only_memory().1));
+
+    let mut verifier = Verifier::from_noir_proof_scheme(schema);
+    for proof in &proofs {
+        verifier.verify(proof).expect("Verifying proof");
+    }
+    verifier.verify(&proofs[0]).expect("Verifying proof again");
+
+    let mut tampered = proofs[0].clone();
+    tampered.public_inputs.0[0] += FieldElement::from(1u64);
+    verifier
+        .verify(&tampered)
+        .expect_err("Verifying tampered proof");
+    verifier
+        .verify(&proofs[1])
+        .expect("Verifying after a failure");
+}
+
+#[test]
+fn verify_batch_reports_tampered_proof() {
+    let (schema, proof) = prove_simplest_read_only_memory();
+    let mut proofs = vec![proof];
+    proofs.extend((0..2).map(|_| prove_simplest_read_only_memory().1));
+
+    let mut verifier = Verifier::from_noir_proof_scheme(schema);
+    verifier.verify_batch(&proofs).expect("Verifying batch");
+
+    proofs[1].public_inputs.0[0] += FieldElement::from(1u64);
+    let error = verifier
+        .verify_batch(&proofs)
+        .expect_err("Verifying batch with a tampered proof");
+    let error = error
+        .downcast_ref::<BatchVerificationError>()
+        .expect("Batch verification error");
+    assert_eq!(error.batch_size, 3);
+    assert_eq!(error.failed_indices(), vec![1]);
+}
+
+#[test]
+fn verify_against_expected_public_inputs() {
+    let (schema, proof) = prove_simplest_read_only_memory();
+
+    let mut verifier = Verifier::from_noir_proof_scheme(schema.clone());
+    verifier
+        .verify_against(&proof, &proof.public_inputs.clone())
+        .expect("Verifying against the proof's public inputs");
+
+    let mut expected = proof.public_inputs.clone();
+    expected.0[0] += FieldElement::from(1u64);
+    let mut verifier = Verifier::from_noir_proof_scheme(schema);
+    let error = verifier
+        .verify_against(&proof, &expected)
+        .expect_err("Verifying against other public inputs");
+    let error = error
+        .downcast_ref::<PublicInputsMismatch>()
+        .expect("Public inputs mismatch");
+    assert_eq!(error.expected, expected);
+    assert_eq!(error.actual, proof.public_inputs);
+}
+
+#[test]
+fn precheck_public_inputs_hash() {
+    let (_, mut proof) = prove_simplest_read_only_memory();
+    let hash = proof.public_inputs.hash();
+    assert!(precheck_public_inputs(&proof, hash));
+    assert!(!precheck_public_inputs(
+        &proof,
+        hash + FieldElement::from(1u64)
+    ));
+
+    // Only the public inputs are looked at, an invalid WHIR proof still passes
+    proof.whir_r1cs_proof.transcript.clear();
+    assert!(precheck_public_inputs(&proof, hash));
+    proof.public_inputs.0[0] += FieldElement::from(1u64);
+    assert!(!precheck_public_inputs(&proof, hash));
+}
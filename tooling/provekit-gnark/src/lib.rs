@@ -1,6 +1,13 @@
 mod gnark_config;
+mod recursion;
 
-pub use gnark_config::write_gnark_parameters_to_file;
+pub use {
+    gnark_config::{write_gnark_parameters_to_file, WHIRConfigGnark},
+    recursion::{
+        write_recursion_inputs_to_file, RecursionInputs, RECURSION_INPUTS_VERSION,
+        TRANSCRIPT_LIMB_BYTES,
+    },
+};
 
 #[cfg(test)]
 mod tests {}
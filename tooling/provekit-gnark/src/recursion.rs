@@ -0,0 +1,118 @@
+use {
+    crate::gnark_config::WHIRConfigGnark,
+    ark_ff::{BigInteger, PrimeField},
+    provekit_common::{FieldElement, NoirProof, WhirR1CSScheme},
+    serde::{Deserialize, Serialize},
+    std::{fs::File, io, path::Path},
+    tracing::instrument,
+};
+
+/// Version of the [`RecursionInputs`] format, bumped on incompatible changes.
+pub const RECURSION_INPUTS_VERSION: u32 = 1;
+
+/// Bytes packed into each transcript limb, so that every limb is below the
+/// BN254 scalar field modulus.
+pub const TRANSCRIPT_LIMB_BYTES: usize = 31;
+
+/// Inputs of a Groth16 circuit verifying a ProveKit proof, e.g. in gnark.
+/// Field elements are BN254 scalars in decimal.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecursionInputs {
+    /// Format version, [`RECURSION_INPUTS_VERSION`]
+    pub version:                    u32,
+    /// Commitment to the public inputs (`PublicInputs::hash`), the public
+    /// input of the outer circuit
+    pub public_inputs_hash:         String,
+    /// Public inputs of the proof
+    pub public_inputs:              Vec<String>,
+    /// Transcript bytes packed big-endian into field elements of
+    /// [`TRANSCRIPT_LIMB_BYTES`] bytes, the last one possibly shorter
+    pub transcript_limbs:           Vec<String>,
+    /// Length of the transcript in bytes
+    pub transcript_len:             usize,
+    /// nimue input output pattern
+    pub io_pattern:                 String,
+    /// WHIR parameters for witness
+    pub whir_config_witness:        WHIRConfigGnark,
+    /// WHIR parameters for hiding spartan
+    pub whir_config_hiding_spartan: WHIRConfigGnark,
+    /// log of number of constraints in R1CS
+    pub log_num_constraints:        usize,
+    /// log of number of variables in R1CS
+    pub log_num_variables:          usize,
+    /// log of number of non-zero terms matrix A
+    pub log_a_num_terms:            usize,
+    /// number of logup challenges (0 = single commitment mode)
+    pub num_challenges:             usize,
+    /// size of w1
+    pub w1_size:                    usize,
+}
+
+impl RecursionInputs {
+    pub fn new(scheme: &WhirR1CSScheme, proof: &NoirProof) -> Self {
+        let transcript = proof.transcript_bytes();
+        Self {
+            version:                    RECURSION_INPUTS_VERSION,
+            public_inputs_hash:         decimal(proof.public_inputs.hash()),
+            public_inputs:              proof
+                .public_inputs
+                .0
+                .iter()
+                .copied()
+                .map(decimal)
+                .collect(),
+            transcript_limbs:           transcript
+                .chunks(TRANSCRIPT_LIMB_BYTES)
+                .map(|limb| decimal(FieldElement::from_be_bytes_mod_order(limb)))
+                .collect(),
+            transcript_len:             transcript.len(),
+            io_pattern:                 String::from_utf8_lossy(
+                scheme.create_io_pattern().as_bytes(),
+            )
+            .into_owned(),
+            whir_config_witness:        WHIRConfigGnark::new(&scheme.whir_witness),
+            whir_config_hiding_spartan: WHIRConfigGnark::new(&scheme.whir_for_hiding_spartan),
+            log_num_constraints:        scheme.m_0,
+            log_num_variables:          scheme.m,
+            log_a_num_terms:            scheme.a_num_terms,
+            num_challenges:             scheme.num_challenges,
+            w1_size:                    scheme.w1_size,
+        }
+    }
+
+    /// Transcript bytes packed in [`RecursionInputs::transcript_limbs`]
+    pub fn transcript(&self) -> Option<Vec<u8>> {
+        let mut transcript = Vec::with_capacity(self.transcript_len);
+        for (i, limb) in self.transcript_limbs.iter().enumerate() {
+            let len = TRANSCRIPT_LIMB_BYTES
+                .min(self.transcript_len.checked_sub(i * TRANSCRIPT_LIMB_BYTES)?);
+            let bytes = limb
+                .parse::<FieldElement>()
+                .ok()?
+                .into_bigint()
+                .to_bytes_be();
+            let (padding, limb) = bytes.split_at(bytes.len().checked_sub(len)?);
+            if padding.iter().any(|&byte| byte != 0) {
+                return None;
+            }
+            transcript.extend_from_slice(limb);
+        }
+        (transcript.len() == self.transcript_len).then_some(transcript)
+    }
+}
+
+fn decimal(value: FieldElement) -> String {
+    value.into_bigint().to_string()
+}
+
+/// Writes the inputs of a Groth16 recursion circuit for `proof` to a JSON file
+#[instrument(skip_all)]
+pub fn write_recursion_inputs_to_file(
+    scheme: &WhirR1CSScheme,
+    proof: &NoirProof,
+    file_path: impl AsRef<Path>,
+) -> io::Result<()> {
+    let file = File::create(file_path)?;
+    serde_json::to_writer(file, &RecursionInputs::new(scheme, proof))?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Helpers shared by the tests of the workspace crates to compile the Noir
+//! test programs.
+
+use {
+    anyhow::{ensure, Context, Result},
+    nargo::workspace::Workspace,
+    nargo_cli::cli::compile_cmd::compile_workspace_full,
+    nargo_toml::{resolve_workspace_from_toml, PackageSelection},
+    noir_artifact_cli::fs::inputs::read_inputs_from_file,
+    noirc_abi::InputMap,
+    noirc_driver::CompileOptions,
+    provekit_common::NoirProofScheme,
+    provekit_r1cs_compiler::NoirProofSchemeBuilder,
+    std::path::Path,
+};
+
+/// Compiles the Noir workspace at `workspace_path`, writing its programs to
+/// its `target` directory.
+pub fn compile_workspace(workspace_path: impl AsRef<Path>) -> Result<Workspace> {
+    let workspace_path = workspace_path.as_ref();
+    let workspace_path = if workspace_path.ends_with("Nargo.toml") {
+        workspace_path.to_owned()
+    } else {
+        workspace_path.join("Nargo.toml")
+    };
+    ensure!(
+        workspace_path.is_file(),
+        "No Nargo.toml found at {}",
+        workspace_path.display()
+    );
+
+    // `resolve_workspace_from_toml` calls .normalize() under the hood which messes
+    // up path resolution
+    let workspace_path = workspace_path
+        .canonicalize()
+        .with_context(|| format!("while canonicalizing {}", workspace_path.display()))?;
+
+    let workspace =
+        resolve_workspace_from_toml(&workspace_path, PackageSelection::DefaultOrAll, None)
+            .with_context(|| format!("while resolving workspace {}", workspace_path.display()))?;
+    let compile_options = CompileOptions::default();
+
+    compile_workspace_full(&workspace, &compile_options, None)
+        .with_context(|| format!("while compiling workspace {}", workspace_path.display()))?;
+
+    Ok(workspace)
+}
+
+/// Compiles the Noir workspace at `test_case_path` and prepares its program
+/// `name` for proving.
+pub fn compile_scheme(test_case_path: impl AsRef<Path>, name: &str) -> NoirProofScheme {
+    let test_case_path = test_case_path.as_ref();
+    compile_workspace(test_case_path).expect("Compiling workspace");
+    NoirProofScheme::from_file(test_case_path.join(format!("target/{name}.json")))
+        .expect("Reading proof scheme")
+}
+
+/// Like [`compile_scheme`], also reading the inputs of the `Prover.toml` of
+/// the workspace.
+pub fn prepare_program(
+    test_case_path: impl AsRef<Path>,
+    name: &str,
+) -> (NoirProofScheme, InputMap) {
+    let test_case_path = test_case_path.as_ref();
+    let schema = compile_scheme(test_case_path, name);
+    let (input_map, _) = read_inputs_from_file(
+        &test_case_path.join("Prover.toml"),
+        schema.witness_generator.abi(),
+    )
+    .expect("Reading inputs");
+    (schema, input_map)
+}
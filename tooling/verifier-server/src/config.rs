@@ -25,10 +25,24 @@ pub struct ServerConfig {
     pub port: u16,
     /// Maximum request body size in bytes
     pub max_request_size: usize,
+    /// Maximum `/prove` request body size in bytes
+    pub max_prove_request_size: usize,
     /// Request timeout duration
     pub request_timeout: Duration,
     /// Maximum number of concurrent verifications
     pub verification_semaphore_limit: u32,
+    /// Maximum number of concurrent proof generations
+    pub proving_semaphore_limit: u32,
+    /// Requests per second allowed for each client IP (0 disables limiting)
+    pub rate_limit_per_second: u32,
+    /// Requests a client IP may make in a burst above its rate
+    pub rate_limit_burst: u32,
+    /// How long finished proving jobs are kept for polling
+    pub job_ttl: Duration,
+    /// Maximum number of pending proving jobs, further jobs are rejected
+    pub max_pending_jobs: usize,
+    /// How long a proving job may stay pending before it is dropped
+    pub job_max_pending_age: Duration,
 }
 
 /// Verification-specific configuration
@@ -38,6 +52,8 @@ pub struct VerificationConfig {
     pub verifier_binary_path:     String,
     /// Timeout for external verifier binary execution in seconds
     pub verifier_timeout_seconds: u64,
+    /// Number of deserialized verifiers to keep in memory (0 disables caching)
+    pub verifier_cache_capacity:  usize,
 }
 
 /// Artifact management configuration
@@ -59,6 +75,16 @@ impl Config {
 }
 
 impl ServerConfig {
+    /// Maximum size a binary request body may decompress to, as a multiple of
+    /// [`Self::max_request_size`]
+    pub const MAX_DECOMPRESSION_RATIO: usize = 4;
+
+    /// Maximum size in bytes a binary request body may decompress to
+    pub fn max_decompressed_size(&self) -> usize {
+        self.max_request_size
+            .saturating_mul(Self::MAX_DECOMPRESSION_RATIO)
+    }
+
     fn from_env() -> Self {
         Self {
             host: env::var("VERIFIER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
@@ -70,6 +96,10 @@ impl ServerConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(10 * 1024 * 1024), // 10MB
+            max_prove_request_size: env::var("VERIFIER_MAX_PROVE_REQUEST_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(256 * 1024 * 1024), // 256MB
             request_timeout: Duration::from_secs(
                 env::var("VERIFIER_REQUEST_TIMEOUT")
                     .ok()
@@ -80,6 +110,34 @@ impl ServerConfig {
                 .ok()
                 .and_then(|t| t.parse().ok())
                 .unwrap_or(1),
+            proving_semaphore_limit: env::var("VERIFIER_PROVING_SEMAPHORE_LIMIT")
+                .ok()
+                .and_then(|t| t.parse().ok())
+                .unwrap_or(1),
+            rate_limit_per_second: env::var("VERIFIER_RATE_LIMIT_PER_SECOND")
+                .ok()
+                .and_then(|r| r.parse().ok())
+                .unwrap_or(10),
+            rate_limit_burst: env::var("VERIFIER_RATE_LIMIT_BURST")
+                .ok()
+                .and_then(|b| b.parse().ok())
+                .unwrap_or(20),
+            job_ttl: Duration::from_secs(
+                env::var("VERIFIER_JOB_TTL")
+                    .ok()
+                    .and_then(|t| t.parse().ok())
+                    .unwrap_or(3600), // 1 hour
+            ),
+            max_pending_jobs: env::var("VERIFIER_MAX_PENDING_JOBS")
+                .ok()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(16),
+            job_max_pending_age: Duration::from_secs(
+                env::var("VERIFIER_JOB_MAX_PENDING_AGE")
+                    .ok()
+                    .and_then(|t| t.parse().ok())
+                    .unwrap_or(7200), // 2 hours
+            ),
         }
     }
 }
@@ -90,8 +148,15 @@ impl Default for ServerConfig {
             host: "0.0.0.0".to_string(),
             port: 3000,
             max_request_size: 10 * 1024 * 1024,         // 10MB
+            max_prove_request_size: 256 * 1024 * 1024,  // 256MB
             request_timeout: Duration::from_secs(1200), // 20 minutes
             verification_semaphore_limit: 1,
+            proving_semaphore_limit: 1,
+            rate_limit_per_second: 10,
+            rate_limit_burst: 20,
+            job_ttl: Duration::from_secs(3600), // 1 hour
+            max_pending_jobs: 16,
+            job_max_pending_age: Duration::from_secs(7200), // 2 hours
         }
     }
 }
@@ -105,6 +170,10 @@ impl VerificationConfig {
                 .ok()
                 .and_then(|t| t.parse().ok())
                 .unwrap_or(1200), // 20 minutes
+            verifier_cache_capacity:  env::var("VERIFIER_CACHE_CAPACITY")
+                .ok()
+                .and_then(|c| c.parse().ok())
+                .unwrap_or(16),
         }
     }
 }
@@ -114,6 +183,7 @@ impl Default for VerificationConfig {
         Self {
             verifier_binary_path:     "./verifier".to_string(),
             verifier_timeout_seconds: 1200, // 20 minutes
+            verifier_cache_capacity:  16,
         }
     }
 }
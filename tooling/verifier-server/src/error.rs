@@ -1,11 +1,11 @@
 use {
     axum::{
-        http::StatusCode,
+        http::{header::RETRY_AFTER, StatusCode},
         response::{IntoResponse, Response},
         Json,
     },
     serde_json::json,
-    std::fmt,
+    std::{fmt, time::Duration},
     tracing::error,
 };
 
@@ -16,6 +16,10 @@ pub enum AppError {
     InvalidInput(String),
     /// Verification failed
     VerificationFailed(String),
+    /// Proof generation failed (unsatisfiable inputs, etc.)
+    ProvingFailed(String),
+    /// Requested resource does not exist
+    NotFound(String),
     /// Download failed (404, network issues, etc.)
     DownloadFailed(String),
     /// Internal server error
@@ -24,6 +28,10 @@ pub enum AppError {
     Timeout,
     /// Request was cancelled
     Cancelled,
+    /// Client exceeded its rate limit, retry after the given delay
+    RateLimited(Duration),
+    /// Too many proving jobs are pending
+    QueueFull,
 }
 
 impl fmt::Display for AppError {
@@ -31,16 +39,38 @@ impl fmt::Display for AppError {
         match self {
             AppError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             AppError::VerificationFailed(msg) => write!(f, "Verification failed: {}", msg),
+            AppError::ProvingFailed(msg) => write!(f, "Proving failed: {}", msg),
+            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
             AppError::DownloadFailed(msg) => write!(f, "Download failed: {}", msg),
             AppError::Internal(msg) => write!(f, "Internal error: {}", msg),
             AppError::Timeout => write!(f, "Request timeout"),
             AppError::Cancelled => write!(f, "Request cancelled"),
+            AppError::RateLimited(_) => write!(f, "Rate limit exceeded"),
+            AppError::QueueFull => write!(f, "Proving queue is full"),
         }
     }
 }
 
 impl std::error::Error for AppError {}
 
+impl AppError {
+    /// Short name of the error kind, e.g. for metric labels
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::InvalidInput(_) => "invalid_input",
+            AppError::VerificationFailed(_) => "verification_failed",
+            AppError::ProvingFailed(_) => "proving_failed",
+            AppError::NotFound(_) => "not_found",
+            AppError::DownloadFailed(_) => "download_failed",
+            AppError::Internal(_) => "internal",
+            AppError::Timeout => "timeout",
+            AppError::Cancelled => "cancelled",
+            AppError::RateLimited(_) => "rate_limited",
+            AppError::QueueFull => "queue_full",
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, error_message, error_code) = match &self {
@@ -52,6 +82,12 @@ impl IntoResponse for AppError {
                 self.to_string(),
                 "VERIFICATION_FAILED",
             ),
+            AppError::ProvingFailed(_) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                self.to_string(),
+                "PROVING_FAILED",
+            ),
+            AppError::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string(), "NOT_FOUND"),
             AppError::DownloadFailed(_) => {
                 (StatusCode::BAD_GATEWAY, self.to_string(), "DOWNLOAD_FAILED")
             }
@@ -65,6 +101,16 @@ impl IntoResponse for AppError {
             }
             AppError::Timeout => (StatusCode::REQUEST_TIMEOUT, self.to_string(), "TIMEOUT"),
             AppError::Cancelled => (StatusCode::REQUEST_TIMEOUT, self.to_string(), "CANCELLED"),
+            AppError::RateLimited(_) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                self.to_string(),
+                "RATE_LIMITED",
+            ),
+            AppError::QueueFull => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                self.to_string(),
+                "QUEUE_FULL",
+            ),
         };
 
         let body = Json(json!({
@@ -75,7 +121,13 @@ impl IntoResponse for AppError {
             }
         }));
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let AppError::RateLimited(retry_after) = self {
+            // Whole seconds, rounded up so that the retry is not limited again
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response.headers_mut().insert(RETRY_AFTER, seconds.into());
+        }
+        response
     }
 }
 
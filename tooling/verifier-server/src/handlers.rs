@@ -1,23 +1,91 @@
 use {
     crate::{
         error::{AppError, AppResult},
-        models::{VerificationStatus, VerifyRequest, VerifyResponse},
+        models::{
+            BinaryVerifyQuery, JobResponse, ProveRequest, ProveResponse, VerificationStatus,
+            VerifyRequest, VerifyResponse,
+        },
         state::AppState,
     },
     axum::{
-        extract::{Json, State},
-        response::Json as ResponseJson,
+        body::Bytes,
+        extract::{FromRequest, Json, Path, Query, Request, State},
+        http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+        response::{IntoResponse, Json as ResponseJson, Response},
     },
+    provekit_common::Prover,
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
     std::time::Instant,
     tokio::sync::OwnedSemaphorePermit,
     tokio_util::sync::CancellationToken,
-    tracing::{info, warn},
+    tracing::{error, info, warn},
 };
 
+/// Verification request extracted by content type.
+///
+/// `application/octet-stream` bodies hold the NoirProof in its binary `.np`
+/// format, with the remaining fields passed as query parameters. Any other
+/// body is parsed as a JSON [`VerifyRequest`].
+pub struct VerifyPayload(pub VerifyRequest);
+
+impl<S: Send + Sync> FromRequest<S> for VerifyPayload {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_octet_stream(req.headers()) {
+            let Json(payload) = Json::<VerifyRequest>::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            return Ok(Self(payload));
+        }
+
+        let Query(query) = Query::<BinaryVerifyQuery>::try_from_uri(req.uri())
+            .map_err(IntoResponse::into_response)?;
+        let np = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(Self(VerifyRequest::from_binary(query, np)))
+    }
+}
+
+fn is_octet_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/octet-stream"))
+}
+
 /// Handle proof verification requests
 pub async fn verify_handler(
     State(state): State<AppState>,
-    Json(payload): Json<VerifyRequest>,
+    VerifyPayload(payload): VerifyPayload,
+) -> AppResult<ResponseJson<VerifyResponse>> {
+    let metrics = state.metrics.clone();
+    metrics.verify_requests_total.inc();
+    // Observed on drop, so cancelled requests are timed as well
+    let _timer = metrics.verify_duration_seconds.start_timer();
+
+    let result = handle_verify(state, payload).await;
+
+    let failure_kind = match &result {
+        Ok(ResponseJson(response)) if !response.is_valid => Some(response.result.status.as_str()),
+        Ok(_) => None,
+        Err(error) => Some(error.kind()),
+    };
+    if let Some(kind) = failure_kind {
+        metrics
+            .verify_failures_total
+            .with_label_values(&[kind])
+            .inc();
+    }
+
+    result
+}
+
+async fn handle_verify(
+    state: AppState,
+    payload: VerifyRequest,
 ) -> AppResult<ResponseJson<VerifyResponse>> {
     let start_time = Instant::now();
     let request_id = payload.metadata.as_ref().and_then(|m| m.request_id.clone());
@@ -166,15 +234,21 @@ async fn verification(
     request: &VerifyRequest,
     cancellation_token: CancellationToken,
 ) -> AppResult<u64> {
-    // Decode and validate the NoirProof
-    let proof = request
-        .decode_noir_proof()
-        .map_err(|e| AppError::InvalidInput(e.to_string()))?;
+    // Decode and validate the NoirProof. Decompression and deserialization
+    // are CPU-bound, so they run on the blocking thread pool.
+    let max_decompressed_size = state.config.server.max_decompressed_size();
+    let decode_request = request.clone();
+    let proof = tokio::task::spawn_blocking(move || {
+        decode_request.decode_noir_proof(max_decompressed_size)
+    })
+    .await
+    .map_err(|join_error| AppError::Internal(format!("Decoding task failed: {}", join_error)))?
+    .map_err(|e| AppError::InvalidInput(e.to_string()))?;
 
     info!("Successfully decoded NoirProof from request");
 
     // Download and prepare artifacts
-    let (verifier, paths) = state
+    let paths = state
         .artifact_service
         .prepare_artifacts(
             &request.pkv_url,
@@ -184,9 +258,130 @@ async fn verification(
         )
         .await?;
 
+    // Load the Verifier, skipping deserialization for known circuits
+    let verifier = state.verifier_cache.load(&paths.pkv_file).await?;
+
     // Perform verification
     state
         .verification_service
         .verify_proof(request, &proof, &verifier, &paths, cancellation_token)
         .await
 }
+
+/// Handle proof generation requests
+///
+/// Proving is CPU-bound, so it runs on the blocking thread pool. The permit
+/// moves into the blocking task: if the request times out, the proof still
+/// runs to completion and keeps its slot until then.
+pub async fn prove_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ProveRequest>,
+) -> AppResult<ResponseJson<ProveResponse>> {
+    prove(&state, payload).await.map(ResponseJson)
+}
+
+/// Generate the proof requested by `payload`
+async fn prove(state: &AppState, payload: ProveRequest) -> AppResult<ProveResponse> {
+    let start_time = Instant::now();
+    let request_id = payload.metadata.as_ref().and_then(|m| m.request_id.clone());
+
+    info!(
+        request_id = %request_id.as_deref().unwrap_or("unknown"),
+        nps_url = %payload.nps_url.as_deref().unwrap_or("not provided"),
+        "Received proving request"
+    );
+
+    payload.validate().map_err(|validation_error| {
+        warn!("Request validation failed: {}", validation_error);
+        AppError::InvalidInput(validation_error)
+    })?;
+
+    let scheme = match payload
+        .decode_noir_proof_scheme()
+        .map_err(|e| AppError::InvalidInput(e.to_string()))?
+    {
+        Some(scheme) => scheme,
+        None => {
+            let nps_url = payload.nps_url.as_deref().unwrap_or_default();
+            state.artifact_service.prepare_scheme(nps_url).await?
+        }
+    };
+
+    let inputs = payload
+        .decode_inputs(scheme.witness_generator.abi())
+        .map_err(|e| AppError::InvalidInput(e.to_string()))?;
+
+    let permit: OwnedSemaphorePermit = state
+        .proving_semaphore
+        .clone()
+        .acquire_owned()
+        .await
+        .map_err(|_| AppError::Internal("proving semaphore closed".into()))?;
+
+    let proof = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        Prover::from_noir_proof_scheme(scheme)
+            .prove_with(ProveInputs::Map(inputs), ProveOptions::default())
+    })
+    .await
+    .map_err(|join_error| AppError::Internal(format!("Proving task failed: {}", join_error)))?
+    .map_err(|e| AppError::ProvingFailed(format!("{:#}", e)))?;
+
+    let proving_time_ms = start_time.elapsed().as_millis() as u64;
+    info!(proving_time_ms, "Proving completed successfully");
+
+    Ok(ProveResponse::new(proof, proving_time_ms, request_id))
+}
+
+/// Handle proving job submissions
+///
+/// The request is validated right away, then proved like `/prove` in the
+/// background. The returned job id is polled with `GET /jobs/{id}`.
+pub async fn create_job_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ProveRequest>,
+) -> AppResult<(StatusCode, ResponseJson<JobResponse>)> {
+    payload.validate().map_err(|validation_error| {
+        warn!("Request validation failed: {}", validation_error);
+        AppError::InvalidInput(validation_error)
+    })?;
+
+    // Take the queue slot before spawning so that rejected jobs cost nothing
+    let job_id = state.job_store.create().ok_or_else(|| {
+        warn!("Rejecting proving job, the queue is full");
+        AppError::QueueFull
+    })?;
+    info!(job_id = %job_id, "Created proving job");
+
+    tokio::spawn({
+        let state = state.clone();
+        let job_id = job_id.clone();
+        async move {
+            let result = prove(&state, payload).await.map_err(|e| match e {
+                AppError::Internal(_) => {
+                    error!(job_id = %job_id, "Proving job failed: {}", e);
+                    "Internal server error".to_string()
+                }
+                _ => e.to_string(),
+            });
+            state.job_store.finish(&job_id, result);
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        ResponseJson(JobResponse::pending(job_id)),
+    ))
+}
+
+/// Handle proving job status requests
+pub async fn job_handler(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> AppResult<ResponseJson<JobResponse>> {
+    state
+        .job_store
+        .get(&job_id)
+        .map(ResponseJson)
+        .ok_or_else(|| AppError::NotFound(format!("Job {job_id}")))
+}
@@ -0,0 +1,136 @@
+//! ProveKit verifier server
+//!
+//! HTTP API that downloads verification artifacts and verifies Noir proofs
+//! with the external gnark verifier binary.
+
+pub mod config;
+pub mod error;
+pub mod handlers;
+pub mod metrics;
+pub mod models;
+pub mod rate_limit;
+pub mod services;
+pub mod state;
+
+use {
+    crate::{
+        handlers::{create_job_handler, job_handler, prove_handler, verify_handler},
+        metrics::{metrics_handler, track_http},
+        rate_limit::rate_limit,
+        state::AppState,
+    },
+    axum::{
+        extract::{DefaultBodyLimit, Json},
+        middleware,
+        response::IntoResponse,
+        routing::{get, post},
+        Router,
+    },
+    std::{future::Future, net::SocketAddr},
+    tokio::net::TcpListener,
+    tower::ServiceBuilder,
+    tower_http::{
+        cors::{Any, CorsLayer},
+        timeout::TimeoutLayer,
+        trace::TraceLayer,
+    },
+    tracing::info,
+};
+
+/// Create the Axum application with all routes and middleware
+pub fn create_app(state: AppState) -> Router {
+    let config = state.config.clone();
+    // Health checks and metric scrapes are not limited
+    let rate_limit = middleware::from_fn_with_state(state.clone(), rate_limit);
+    Router::new()
+        .route("/verify", post(verify_handler).layer(rate_limit.clone()))
+        .route(
+            "/prove",
+            // Inline proof schemes are much larger than proofs
+            post(prove_handler)
+                .layer(DefaultBodyLimit::max(config.server.max_prove_request_size))
+                .layer(rate_limit.clone()),
+        )
+        .route(
+            "/jobs",
+            post(create_job_handler)
+                .layer(DefaultBodyLimit::max(config.server.max_prove_request_size))
+                .layer(rate_limit),
+        )
+        .route("/jobs/{id}", get(job_handler))
+        .route("/health", get(health_check))
+        .route("/metrics", get(metrics_handler))
+        .layer(
+            ServiceBuilder::new()
+                // Add request tracing
+                .layer(TraceLayer::new_for_http())
+                // Add request metrics
+                .layer(middleware::from_fn_with_state(state.clone(), track_http))
+                // Add CORS support
+                .layer(
+                    CorsLayer::new()
+                        .allow_origin(Any)
+                        .allow_methods(Any)
+                        .allow_headers(Any),
+                )
+                // Add request timeout
+                .layer(TimeoutLayer::new(config.server.request_timeout))
+                // Limit request body size
+                .layer(DefaultBodyLimit::max(config.server.max_request_size)),
+        )
+        .with_state(state)
+}
+
+/// Serve `app` on `listener`, with the peer address of each connection
+/// available for rate limiting, until `shutdown` completes, then stop
+/// accepting connections and wait for the requests in flight to finish. The
+/// request timeout of the app bounds how long that takes.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> std::io::Result<()> {
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown.await;
+        info!("Shutting down, waiting for requests in flight");
+    })
+    .await?;
+    info!("Server stopped");
+    Ok(())
+}
+
+/// Complete on SIGINT or, on Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install the SIGINT handler");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install the SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => info!("Received SIGINT"),
+        () = terminate => info!("Received SIGTERM"),
+    }
+}
+
+/// Health check endpoint
+async fn health_check() -> impl IntoResponse {
+    Json(serde_json::json!({
+        "status": "healthy",
+        "version": env!("CARGO_PKG_VERSION"),
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    }))
+}
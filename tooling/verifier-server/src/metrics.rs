@@ -0,0 +1,147 @@
+//! Prometheus metrics
+//!
+//! Each [`Metrics`] owns its own registry, so independent server instances
+//! (e.g. in tests) do not share counters.
+
+use {
+    crate::state::AppState,
+    axum::{
+        extract::{MatchedPath, Request, State},
+        http::header::CONTENT_TYPE,
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    prometheus::{
+        Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry,
+        TextEncoder,
+    },
+    std::time::Instant,
+};
+
+/// Latency buckets in seconds, from fast cache hits up to the default
+/// verifier timeout
+const DURATION_BUCKETS: &[f64] = &[
+    0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0, 1200.0,
+];
+
+/// Server metrics
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    registry:                    Registry,
+    /// HTTP requests by method, route and status code
+    pub http_requests_total:     IntCounterVec,
+    /// HTTP request latency by method and route
+    pub http_request_duration:   HistogramVec,
+    /// Verification requests received
+    pub verify_requests_total:   IntCounter,
+    /// Verification latency, including artifact downloads
+    pub verify_duration_seconds: Histogram,
+    /// Verifications that did not produce a valid result, by kind
+    pub verify_failures_total:   IntCounterVec,
+}
+
+impl Metrics {
+    /// Create and register all metrics
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by route and status"),
+            &["method", "path", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            )
+            .buckets(DURATION_BUCKETS.to_vec()),
+            &["method", "path"],
+        )
+        .expect("valid metric");
+        let verify_requests_total =
+            IntCounter::new("verify_requests_total", "Verification requests received")
+                .expect("valid metric");
+        let verify_duration_seconds = Histogram::with_opts(
+            HistogramOpts::new("verify_duration_seconds", "Verification latency in seconds")
+                .buckets(DURATION_BUCKETS.to_vec()),
+        )
+        .expect("valid metric");
+        let verify_failures_total = IntCounterVec::new(
+            Opts::new(
+                "verify_failures_total",
+                "Verifications without a valid result, by kind",
+            ),
+            &["kind"],
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(http_requests_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(verify_requests_total.clone()),
+            Box::new(verify_duration_seconds.clone()),
+            Box::new(verify_failures_total.clone()),
+        ] {
+            registry.register(collector).expect("unique metric");
+        }
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration,
+            verify_requests_total,
+            verify_duration_seconds,
+            verify_failures_total,
+        }
+    }
+
+    /// Encode all metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding does not fail");
+        String::from_utf8(buffer).expect("text encoding is UTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware recording request counts and latency for every route
+pub async fn track_http(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let start_time = Instant::now();
+    let method = request.method().to_string();
+    // Label by route template to keep the label cardinality bounded
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", MatchedPath::as_str)
+        .to_owned();
+
+    let response = next.run(request).await;
+
+    let metrics = &state.metrics;
+    metrics
+        .http_requests_total
+        .with_label_values(&[method.as_str(), path.as_str(), response.status().as_str()])
+        .inc();
+    metrics
+        .http_request_duration
+        .with_label_values(&[method.as_str(), path.as_str()])
+        .observe(start_time.elapsed().as_secs_f64());
+
+    response
+}
+
+/// Metrics endpoint
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, TextEncoder::new().format_type().to_owned())],
+        state.metrics.render(),
+    )
+}
@@ -1,5 +1,7 @@
 use {
-    provekit_common::NoirProof,
+    axum::body::Bytes,
+    noirc_abi::{input_parser::Format, Abi, InputMap},
+    provekit_common::{NoirProof, NoirProofScheme},
     serde::{Deserialize, Serialize},
     std::collections::HashMap,
     tracing::info,
@@ -13,6 +15,10 @@ pub struct VerifyRequest {
     pub pkv_url:             String,
     /// JSON encoded NoirProof (.np file content)
     pub np:                  serde_json::Value,
+    /// Binary encoded NoirProof (.np file content), set for
+    /// `application/octet-stream` requests instead of `np`
+    #[serde(skip)]
+    pub np_binary:           Option<Bytes>,
     /// URL to the R1CS file
     #[serde(rename = "r1csUrl")]
     pub r1cs_url:            String,
@@ -30,6 +36,30 @@ pub struct VerifyRequest {
     pub metadata:            Option<RequestMetadata>,
 }
 
+/// Query parameters of a binary verification request, where the body holds
+/// the NoirProof in its binary `.np` format
+#[derive(Debug, Clone, Deserialize)]
+pub struct BinaryVerifyQuery {
+    /// URL to the ProveKit Verifier file (.pkv)
+    #[serde(rename = "pkvUrl")]
+    pub pkv_url:               String,
+    /// URL to the R1CS file
+    #[serde(rename = "r1csUrl")]
+    pub r1cs_url:              String,
+    /// URL to the proving key file
+    #[serde(rename = "pkUrl")]
+    pub pk_url:                Option<String>,
+    /// URL to the verification key file
+    #[serde(rename = "vkUrl")]
+    pub vk_url:                Option<String>,
+    /// Maximum verification time in seconds
+    #[serde(rename = "maxVerificationTime")]
+    pub max_verification_time: Option<u64>,
+    /// Request ID for tracking
+    #[serde(rename = "requestId")]
+    pub request_id:            Option<String>,
+}
+
 /// Verification parameters
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct VerificationParams {
@@ -58,6 +88,62 @@ pub struct RequestMetadata {
     pub custom_fields: HashMap<String, serde_json::Value>,
 }
 
+/// Request payload for proof generation
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProveRequest {
+    /// JSON encoded NoirProofScheme (.nps file content)
+    #[serde(default)]
+    pub nps:      Option<serde_json::Value>,
+    /// URL to the NoirProofScheme file (.nps), used instead of `nps`
+    #[serde(rename = "npsUrl")]
+    pub nps_url:  Option<String>,
+    /// Program inputs, keyed like a Prover.toml file
+    pub inputs:   serde_json::Value,
+    /// Request metadata
+    #[serde(default)]
+    pub metadata: Option<RequestMetadata>,
+}
+
+/// Response payload for proof generation
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProveResponse {
+    /// JSON encoded NoirProof, as accepted by `/verify`
+    pub np:              NoirProof,
+    /// Proving time in milliseconds
+    #[serde(rename = "provingTimeMs")]
+    pub proving_time_ms: u64,
+    /// Response metadata
+    pub metadata:        ResponseMetadata,
+}
+
+/// Status of a proving job
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Job is queued or running
+    Pending,
+    /// Proof was generated
+    Done,
+    /// Proof generation failed
+    Error,
+}
+
+/// Response payload for proving jobs
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobResponse {
+    /// Job identifier
+    #[serde(rename = "jobId")]
+    pub job_id: String,
+    /// Job status
+    pub status: JobStatus,
+    /// Proof generation response, once done
+    #[serde(default)]
+    pub result: Option<ProveResponse>,
+    /// Error message, if proof generation failed
+    #[serde(default)]
+    pub error:  Option<String>,
+}
+
 /// Response payload for proof verification
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct VerifyResponse {
@@ -145,7 +231,7 @@ impl VerifyRequest {
         }
 
         // Validate np (JSON NoirProof) - basic check for null
-        if self.np.is_null() {
+        if self.np_binary.is_none() && self.np.is_null() {
             return Err("np (NoirProof) cannot be null".to_string());
         }
 
@@ -163,9 +249,40 @@ impl VerifyRequest {
         Ok(())
     }
 
-    /// Decode the NoirProof from the JSON np field
-    pub fn decode_noir_proof(&self) -> anyhow::Result<NoirProof> {
-        serde_json::from_value(self.np.clone()).map_err(Into::into)
+    /// Build a request from the query parameters and body of a binary
+    /// verification request
+    pub fn from_binary(query: BinaryVerifyQuery, np: Bytes) -> Self {
+        Self {
+            pkv_url:             query.pkv_url,
+            np:                  serde_json::Value::Null,
+            np_binary:           Some(np),
+            r1cs_url:            query.r1cs_url,
+            pk_url:              query.pk_url,
+            vk_url:              query.vk_url,
+            verification_params: query.max_verification_time.map(|max_verification_time| {
+                VerificationParams {
+                    max_verification_time,
+                    ..VerificationParams::default()
+                }
+            }),
+            metadata:            query.request_id.map(|request_id| RequestMetadata {
+                request_id: Some(request_id),
+                ..RequestMetadata::default()
+            }),
+        }
+    }
+
+    /// Decode the NoirProof from the binary body, or else the JSON np field
+    ///
+    /// The binary body is rejected if it decompresses to more than
+    /// `max_decompressed_size` bytes.
+    pub fn decode_noir_proof(&self, max_decompressed_size: usize) -> anyhow::Result<NoirProof> {
+        match &self.np_binary {
+            Some(bytes) => {
+                provekit_common::file::read_bytes_limited(bytes, max_decompressed_size as u64)
+            }
+            None => serde_json::from_value(self.np.clone()).map_err(Into::into),
+        }
     }
 
     /// Validate that a URL is properly formatted
@@ -177,6 +294,104 @@ impl VerifyRequest {
     }
 }
 
+impl VerificationStatus {
+    /// Name of the status, as serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationStatus::Valid => "valid",
+            VerificationStatus::Invalid => "invalid",
+            VerificationStatus::Error => "error",
+            VerificationStatus::Timeout => "timeout",
+        }
+    }
+}
+
+impl ProveRequest {
+    /// Validate the request data
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.nps, &self.nps_url) {
+            (Some(_), Some(_)) => {
+                return Err("Only one of nps and nps_url can be provided".to_string());
+            }
+            (None, None) => return Err("Either nps or nps_url must be provided".to_string()),
+            (None, Some(nps_url)) => {
+                if !nps_url.starts_with("http://") && !nps_url.starts_with("https://") {
+                    return Err("nps_url must be a valid HTTP/HTTPS URL".to_string());
+                }
+            }
+            (Some(_), None) => {}
+        }
+
+        if !self.inputs.is_object() {
+            return Err("inputs must be an object".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Decode the NoirProofScheme from the JSON nps field
+    pub fn decode_noir_proof_scheme(&self) -> anyhow::Result<Option<NoirProofScheme>> {
+        self.nps
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Decode the program inputs against the program ABI
+    pub fn decode_inputs(&self, abi: &Abi) -> anyhow::Result<InputMap> {
+        Format::Json
+            .parse(&self.inputs.to_string(), abi)
+            .map_err(Into::into)
+    }
+}
+
+impl ProveResponse {
+    /// Create a proof generation response
+    pub fn new(np: NoirProof, proving_time_ms: u64, request_id: Option<String>) -> Self {
+        Self {
+            np,
+            proving_time_ms,
+            metadata: ResponseMetadata {
+                server_version: env!("CARGO_PKG_VERSION").to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                request_id,
+                processing_time_ms: proving_time_ms,
+            },
+        }
+    }
+}
+
+impl JobResponse {
+    /// Create the response of a job that is not finished
+    pub fn pending(job_id: String) -> Self {
+        Self {
+            job_id,
+            status: JobStatus::Pending,
+            result: None,
+            error: None,
+        }
+    }
+
+    /// Create the response of a finished job
+    pub fn finished(job_id: String, result: Result<ProveResponse, String>) -> Self {
+        match result {
+            Ok(response) => Self {
+                job_id,
+                status: JobStatus::Done,
+                result: Some(response),
+                error: None,
+            },
+            Err(error) => Self {
+                job_id,
+                status: JobStatus::Error,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
 impl VerifyResponse {
     /// Create a successful verification response
     pub fn success(verification_time_ms: u64, request_id: Option<String>) -> Self {
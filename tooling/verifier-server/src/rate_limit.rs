@@ -0,0 +1,100 @@
+//! Per-client rate limiting
+//!
+//! Each client IP gets a token bucket holding up to `burst` requests, refilled
+//! at `per_second` requests per second. Requests without a known peer address
+//! (e.g. when the app is not served over TCP) are not limited.
+
+use {
+    crate::{error::AppError, state::AppState},
+    axum::{
+        extract::{ConnectInfo, Request, State},
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    std::{
+        collections::HashMap,
+        net::{IpAddr, SocketAddr},
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+    tracing::warn,
+};
+
+/// Number of tracked clients above which full buckets are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens:  f64,
+    updated: Instant,
+}
+
+/// Token bucket rate limiter keyed by client IP
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Tokens added per second, 0 disables limiting
+    per_second: f64,
+    /// Capacity of a bucket
+    burst:      f64,
+    buckets:    Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl Bucket {
+    /// Refill the bucket up to `now` and return its tokens
+    fn refill(&mut self, now: Instant, per_second: f64, burst: f64) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * per_second).min(burst);
+        self.updated = now;
+        self.tokens
+    }
+}
+
+impl RateLimiter {
+    pub fn new(per_second: u32, burst: u32) -> Self {
+        Self {
+            per_second: per_second as f64,
+            burst:      burst.max(1) as f64,
+            buckets:    Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `ip`, or return how long until one is available
+    pub fn check(&self, ip: IpAddr) -> Result<(), Duration> {
+        if self.per_second == 0.0 {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            // Full buckets behave like new ones
+            buckets
+                .retain(|_, bucket| bucket.refill(now, self.per_second, self.burst) < self.burst);
+        }
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens:  self.burst,
+            updated: now,
+        });
+        let tokens = bucket.refill(now, self.per_second, self.burst);
+        if tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - tokens) / self.per_second))
+        }
+    }
+}
+
+/// Middleware rejecting requests of clients above their rate limit
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if let Some(ip) = client {
+        if let Err(retry_after) = state.rate_limiter.check(ip) {
+            warn!(%ip, "Rate limit exceeded");
+            return AppError::RateLimited(retry_after).into_response();
+        }
+    }
+    next.run(request).await
+}
@@ -5,7 +5,7 @@
 
 use {
     crate::error::{AppError, AppResult},
-    provekit_common::Verifier,
+    provekit_common::NoirProofScheme,
     sha2::{Digest, Sha256},
     std::path::{Path, PathBuf},
     tracing::{info, instrument},
@@ -34,7 +34,7 @@ impl ArtifactService {
         r1cs_url: &str,
         pk_url: Option<&str>,
         vk_url: Option<&str>,
-    ) -> AppResult<(Verifier, ArtifactPaths)> {
+    ) -> AppResult<ArtifactPaths> {
         let cache_dir = self.create_cache_directory(pkv_url).await?;
         let paths = ArtifactPaths::new(&cache_dir);
 
@@ -47,16 +47,45 @@ impl ArtifactService {
         self.download_artifacts_if_missing(&paths, pkv_url, r1cs_url, pk_url, vk_url)
             .await?;
 
-        // Load and return the Verifier
-        let verifier = self.load_verifier(&paths.pkv_file).await?;
+        Ok(paths)
+    }
+
+    /// Download and cache a NoirProofScheme for proving
+    #[instrument(skip(self))]
+    pub async fn prepare_scheme(&self, nps_url: &str) -> AppResult<NoirProofScheme> {
+        let cache_dir = self.create_cache_directory(nps_url).await?;
+        let paths = ArtifactPaths::new(&cache_dir);
+
+        if !paths.nps_file.exists() {
+            info!(
+                url = %nps_url,
+                file_path = %paths.nps_file.display(),
+                "Downloading NoirProofScheme"
+            );
+
+            self.download_file(nps_url, &paths.nps_file)
+                .await
+                .map_err(|e| {
+                    AppError::DownloadFailed(format!("Failed to download NoirProofScheme: {}", e))
+                })?;
+        } else {
+            info!(
+                file_path = %paths.nps_file.display(),
+                "NoirProofScheme already exists in cache"
+            );
+        }
 
-        Ok((verifier, paths))
+        let scheme = provekit_common::file::read(&paths.nps_file)
+            .map_err(|e| AppError::Internal(format!("Failed to load NoirProofScheme: {}", e)))?;
+
+        info!("Successfully loaded NoirProofScheme");
+        Ok(scheme)
     }
 
-    /// Create a cache directory based on the PKV URL hash
-    async fn create_cache_directory(&self, pkv_url: &str) -> AppResult<PathBuf> {
+    /// Create a cache directory based on the artifact URL hash
+    async fn create_cache_directory(&self, url: &str) -> AppResult<PathBuf> {
         let mut hasher = Sha256::new();
-        hasher.update(pkv_url.as_bytes());
+        hasher.update(url.as_bytes());
         let url_hash = format!("{:x}", hasher.finalize());
 
         let cache_dir = self.artifacts_dir.join(&url_hash);
@@ -178,20 +207,6 @@ impl ArtifactService {
 
         Ok(())
     }
-
-    /// Load a Verifier from the PKV file
-    async fn load_verifier(&self, pkv_file: &Path) -> AppResult<Verifier> {
-        info!(
-            pkv_file = %pkv_file.display(),
-            "Loading Verifier"
-        );
-
-        let verifier = provekit_common::file::read(pkv_file)
-            .map_err(|e| AppError::Internal(format!("Failed to load Verifier: {}", e)))?;
-
-        info!("Successfully loaded Verifier");
-        Ok(verifier)
-    }
 }
 
 impl Default for ArtifactService {
@@ -204,6 +219,7 @@ impl Default for ArtifactService {
 #[derive(Debug, Clone)]
 pub struct ArtifactPaths {
     pub pkv_file:          PathBuf,
+    pub nps_file:          PathBuf,
     pub r1cs_file:         PathBuf,
     pub pk_file:           PathBuf,
     pub vk_file:           PathBuf,
@@ -215,6 +231,7 @@ impl ArtifactPaths {
     pub fn new(cache_dir: &Path) -> Self {
         Self {
             pkv_file:          cache_dir.join("verifier.pkv"),
+            nps_file:          cache_dir.join("scheme.nps"),
             r1cs_file:         cache_dir.join("r1cs.json"),
             pk_file:           cache_dir.join("proving_key.bin"),
             vk_file:           cache_dir.join("verification_key.bin"),
@@ -0,0 +1,96 @@
+//! Verifier cache
+//!
+//! Keeps deserialized verifiers in memory, keyed by the SHA-256 of their
+//! `.pkv` file, so repeated verifications of the same circuit skip parsing.
+
+use {
+    crate::error::{AppError, AppResult},
+    lru::LruCache,
+    provekit_common::Verifier,
+    sha2::{Digest, Sha256},
+    std::{
+        num::NonZeroUsize,
+        path::Path,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    },
+    tracing::info,
+};
+
+/// LRU cache of deserialized verifiers
+#[derive(Debug)]
+pub struct VerifierCache {
+    /// Cached verifiers, `None` if caching is disabled
+    entries: Option<Mutex<LruCache<[u8; 32], Arc<Verifier>>>>,
+    /// Number of lookups served from the cache
+    hits:    AtomicU64,
+    /// Number of lookups that had to deserialize the verifier
+    misses:  AtomicU64,
+}
+
+impl VerifierCache {
+    /// Create a cache holding up to `capacity` verifiers. A capacity of zero
+    /// disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: NonZeroUsize::new(capacity)
+                .map(|capacity| Mutex::new(LruCache::new(capacity))),
+            hits:    AtomicU64::new(0),
+            misses:  AtomicU64::new(0),
+        }
+    }
+
+    /// Load the Verifier from a PKV file, reusing a cached copy if the file
+    /// contents were seen before
+    pub async fn load(&self, pkv_file: &Path) -> AppResult<Arc<Verifier>> {
+        let bytes = tokio::fs::read(pkv_file)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read Verifier: {}", e)))?;
+        let key: [u8; 32] = Sha256::digest(&bytes).into();
+
+        if let Some(verifier) = self
+            .lock()
+            .and_then(|mut entries| entries.get(&key).cloned())
+        {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            info!("Verifier cache hit");
+            return Ok(verifier);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        info!(
+            pkv_file = %pkv_file.display(),
+            "Loading Verifier"
+        );
+        let verifier: Arc<Verifier> = Arc::new(
+            provekit_common::file::read_bytes(&bytes)
+                .map_err(|e| AppError::Internal(format!("Failed to load Verifier: {}", e)))?,
+        );
+        info!("Successfully loaded Verifier");
+
+        if let Some(mut entries) = self.lock() {
+            entries.put(key, verifier.clone());
+        }
+        Ok(verifier)
+    }
+
+    /// Number of lookups served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that had to deserialize the verifier
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> Option<std::sync::MutexGuard<'_, LruCache<[u8; 32], Arc<Verifier>>>> {
+        // A panic while holding the lock cannot leave the LRU inconsistent
+        // for our purposes, so recover from poisoning.
+        self.entries
+            .as_ref()
+            .map(|entries| entries.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
@@ -0,0 +1,100 @@
+//! Proving job store
+//!
+//! Keeps the state of background proving jobs in memory. Finished jobs are
+//! evicted once they are older than the TTL, so their results must be polled
+//! before then. The number of pending jobs is bounded, and jobs pending for
+//! longer than the maximum age are dropped as if they never existed.
+
+use {
+    crate::models::{JobResponse, JobStatus, ProveResponse},
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+#[derive(Debug)]
+struct Job {
+    response: JobResponse,
+    /// When the job was created or finished
+    updated:  Instant,
+}
+
+/// In-memory store of proving jobs
+#[derive(Debug)]
+pub struct JobStore {
+    /// How long finished jobs are kept
+    ttl:             Duration,
+    /// Maximum number of pending jobs
+    max_pending:     usize,
+    /// How long a job may stay pending
+    max_pending_age: Duration,
+    jobs:            Mutex<HashMap<String, Job>>,
+}
+
+impl JobStore {
+    /// Create a store keeping finished jobs for `ttl` and at most
+    /// `max_pending` pending jobs for up to `max_pending_age`
+    pub fn new(ttl: Duration, max_pending: usize, max_pending_age: Duration) -> Self {
+        Self {
+            ttl,
+            max_pending,
+            max_pending_age,
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a new pending job and return its id, `None` if the maximum
+    /// number of jobs is already pending
+    pub fn create(&self) -> Option<String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        self.evict(&mut jobs);
+        let pending = jobs
+            .values()
+            .filter(|job| job.response.status == JobStatus::Pending)
+            .count();
+        if pending >= self.max_pending {
+            return None;
+        }
+
+        // Random so that clients cannot poll the jobs of others
+        let job_id = format!("{:032x}", rand::random::<u128>());
+        jobs.insert(job_id.clone(), Job {
+            response: JobResponse::pending(job_id.clone()),
+            updated:  Instant::now(),
+        });
+        Some(job_id)
+    }
+
+    /// Record the outcome of a job, unless it was dropped in the meantime
+    pub fn finish(&self, job_id: &str, result: Result<ProveResponse, String>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.get_mut(job_id) {
+            *job = Job {
+                response: JobResponse::finished(job_id.to_string(), result),
+                updated:  Instant::now(),
+            };
+        }
+    }
+
+    /// Current state of a job, `None` if unknown or evicted
+    pub fn get(&self, job_id: &str) -> Option<JobResponse> {
+        let mut jobs = self.jobs.lock().unwrap();
+        self.evict(&mut jobs);
+        jobs.get(job_id).map(|job| job.response.clone())
+    }
+
+    /// Drop the finished jobs older than the TTL and the pending jobs older
+    /// than the maximum age
+    fn evict(&self, jobs: &mut HashMap<String, Job>) {
+        jobs.retain(|_, job| {
+            let max_age = if job.response.status == JobStatus::Pending {
+                self.max_pending_age
+            } else {
+                self.ttl
+            };
+            job.updated.elapsed() < max_age
+        });
+    }
+}
@@ -1,9 +1,15 @@
 //! Service layer for the verifier server
 //!
 //! This module contains the business logic for proof verification,
-//! artifact management, and external system interactions.
+//! artifact management, background proving jobs, and external system
+//! interactions.
 
 pub mod artifact;
+pub mod cache;
+pub mod jobs;
 pub mod verification;
 
-pub use {artifact::ArtifactService, verification::VerificationService};
+pub use {
+    artifact::ArtifactService, cache::VerifierCache, jobs::JobStore,
+    verification::VerificationService,
+};
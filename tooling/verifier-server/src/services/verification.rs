@@ -90,14 +90,14 @@ impl VerificationService {
         write_gnark_parameters_to_file(
             &whir_scheme.whir_witness,
             &whir_scheme.whir_for_hiding_spartan,
-            &proof.whir_r1cs_proof.transcript,
+            proof.transcript_bytes(),
             &whir_scheme.create_io_pattern(),
             whir_scheme.m_0,
             whir_scheme.m,
             whir_scheme.a_num_terms,
             whir_scheme.num_challenges,
             whir_scheme.w1_size,
-            &proof.public_inputs,
+            proof.public_inputs(),
             gnark_params_path,
         );
 
@@ -6,7 +6,9 @@
 use {
     crate::{
         config::Config,
-        services::{ArtifactService, VerificationService},
+        metrics::Metrics,
+        rate_limit::RateLimiter,
+        services::{ArtifactService, JobStore, VerificationService, VerifierCache},
     },
     std::sync::Arc,
     tokio::sync::Semaphore,
@@ -21,8 +23,18 @@ pub struct AppState {
     pub artifact_service:       Arc<ArtifactService>,
     /// Verification service
     pub verification_service:   Arc<VerificationService>,
+    /// Cache of deserialized verifiers
+    pub verifier_cache:         Arc<VerifierCache>,
     /// Semaphore for limiting the number of concurrent verifications
     pub verification_semaphore: Arc<Semaphore>,
+    /// Semaphore for limiting the number of concurrent proof generations
+    pub proving_semaphore:      Arc<Semaphore>,
+    /// Background proving jobs
+    pub job_store:              Arc<JobStore>,
+    /// Per-client request rate limiter
+    pub rate_limiter:           Arc<RateLimiter>,
+    /// Prometheus metrics
+    pub metrics:                Metrics,
 }
 
 impl AppState {
@@ -33,15 +45,35 @@ impl AppState {
             &config.verification.verifier_binary_path,
             config.verification.verifier_timeout_seconds,
         ));
+        let verifier_cache = Arc::new(VerifierCache::new(
+            config.verification.verifier_cache_capacity,
+        ));
         let verification_semaphore = Arc::new(Semaphore::new(
             config.server.verification_semaphore_limit as usize,
         ));
+        let proving_semaphore = Arc::new(Semaphore::new(
+            config.server.proving_semaphore_limit as usize,
+        ));
+        let job_store = Arc::new(JobStore::new(
+            config.server.job_ttl,
+            config.server.max_pending_jobs,
+            config.server.job_max_pending_age,
+        ));
+        let rate_limiter = Arc::new(RateLimiter::new(
+            config.server.rate_limit_per_second,
+            config.server.rate_limit_burst,
+        ));
 
         Self {
             config,
             artifact_service,
             verification_service,
+            verifier_cache,
             verification_semaphore,
+            proving_semaphore,
+            job_store,
+            rate_limiter,
+            metrics: Metrics::new(),
         }
     }
 }
@@ -0,0 +1,55 @@
+mod common;
+
+use {
+    crate::common::{
+        app_with_state, compile_scheme, scratch_dir, seed_artifacts, test_state, PKV_URL, PROGRAM,
+        R1CS_URL,
+    },
+    axum::{
+        body::{to_bytes, Body},
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+    },
+    provekit_common::{file::write, Prover},
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
+    std::path::Path,
+    tower::ServiceExt,
+    verifier_server::models::VerifyResponse,
+};
+
+#[tokio::test]
+async fn second_verification_hits_cache() {
+    let dir = scratch_dir("cache");
+    let artifacts_dir = dir.join("artifacts");
+
+    let scheme = compile_scheme();
+    seed_artifacts(&artifacts_dir, &scheme);
+    let proof = Prover::from_noir_proof_scheme(scheme)
+        .prove_with(
+            ProveInputs::File(Path::new(PROGRAM).join("Prover.toml")),
+            ProveOptions::default(),
+        )
+        .expect("Proving");
+    let proof_path = dir.join("proof.np");
+    write(&proof, &proof_path).expect("Writing proof");
+    let proof = std::fs::read(&proof_path).expect("Reading proof");
+
+    let state = test_state(artifacts_dir);
+    let app = app_with_state(state.clone());
+    for _ in 0..2 {
+        let request = Request::post(format!("/verify?pkvUrl={PKV_URL}&r1csUrl={R1CS_URL}"))
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(Body::from(proof.clone()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: VerifyResponse = serde_json::from_slice(&body).unwrap();
+        assert!(response.is_valid, "{:?}", response.result.error_message);
+    }
+
+    assert_eq!(state.verifier_cache.misses(), 1);
+    assert_eq!(state.verifier_cache.hits(), 1);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
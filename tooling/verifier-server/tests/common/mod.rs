@@ -0,0 +1,73 @@
+// Each test binary uses a different subset of these helpers.
+#![allow(dead_code)]
+
+use {
+    axum::Router,
+    provekit_common::{file::write, NoirProofScheme, Verifier},
+    sha2::{Digest, Sha256},
+    std::path::{Path, PathBuf},
+    verifier_server::{
+        config::{ArtifactConfig, Config, VerificationConfig},
+        create_app,
+        state::AppState,
+    },
+};
+
+pub const PROGRAM: &str = "../../noir-examples/noir-r1cs-test-programs/range-check-u8";
+pub const PKV_URL: &str = "http://artifacts.invalid/verifier.pkv";
+pub const R1CS_URL: &str = "http://artifacts.invalid/r1cs.json";
+
+/// Scratch directory for a single test binary.
+pub fn scratch_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("verifier-server-{name}-{}", std::process::id()))
+}
+
+/// Compile the test program into a proof scheme.
+pub fn compile_scheme() -> NoirProofScheme {
+    provekit_test_utils::compile_scheme(PROGRAM, "main")
+}
+
+/// Seed the artifact cache for [`PKV_URL`] so the server does not download
+/// anything.
+pub fn seed_artifacts(artifacts_dir: &Path, scheme: &NoirProofScheme) {
+    let cache_dir = artifacts_dir.join(format!("{:x}", Sha256::digest(PKV_URL)));
+    std::fs::create_dir_all(&cache_dir).expect("Creating cache directory");
+    write(
+        &Verifier::from_noir_proof_scheme(scheme.clone()),
+        &cache_dir.join("verifier.pkv"),
+    )
+    .expect("Writing verifier");
+    std::fs::write(
+        cache_dir.join("r1cs.json"),
+        serde_json::to_vec(&scheme.r1cs).expect("Encoding R1CS"),
+    )
+    .expect("Writing R1CS");
+}
+
+/// Create the configuration over `artifacts_dir`.
+pub fn test_config(artifacts_dir: PathBuf) -> Config {
+    // The gnark verifier is not available here, stand in a binary that succeeds.
+    Config {
+        verification: VerificationConfig {
+            verifier_binary_path: "true".to_string(),
+            ..VerificationConfig::default()
+        },
+        artifacts: ArtifactConfig { artifacts_dir },
+        ..Config::default()
+    }
+}
+
+/// Create the application state over `artifacts_dir`.
+pub fn test_state(artifacts_dir: PathBuf) -> AppState {
+    AppState::new(test_config(artifacts_dir))
+}
+
+/// Create the application over `artifacts_dir`.
+pub fn test_app(artifacts_dir: PathBuf) -> Router {
+    app_with_state(test_state(artifacts_dir))
+}
+
+/// Create the application sharing `state`, e.g. to inspect its metrics.
+pub fn app_with_state(state: AppState) -> Router {
+    create_app(state)
+}
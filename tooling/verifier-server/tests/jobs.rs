@@ -0,0 +1,137 @@
+mod common;
+
+use {
+    crate::common::{
+        app_with_state, compile_scheme, scratch_dir, seed_artifacts, test_app, test_config,
+        PKV_URL, R1CS_URL,
+    },
+    axum::{
+        body::{to_bytes, Body},
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+        Router,
+    },
+    serde::de::DeserializeOwned,
+    serde_json::{json, Value},
+    std::time::Duration,
+    tower::ServiceExt,
+    verifier_server::{
+        models::{JobResponse, JobStatus, VerifyResponse},
+        services::JobStore,
+        state::AppState,
+    },
+};
+
+async fn send<T: DeserializeOwned>(
+    app: &Router,
+    request: Request<Body>,
+    expected: StatusCode,
+) -> T {
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), expected);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn post_json(uri: &str, body: &Value) -> Request<Body> {
+    Request::post(uri)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn job_runs_to_completion() {
+    let dir = scratch_dir("jobs");
+    let artifacts_dir = dir.join("artifacts");
+
+    let scheme = compile_scheme();
+    seed_artifacts(&artifacts_dir, &scheme);
+    let app = test_app(artifacts_dir);
+
+    let job: JobResponse = send(
+        &app,
+        post_json(
+            "/jobs",
+            &json!({
+                "nps": scheme,
+                "inputs": { "x": 2 },
+            }),
+        ),
+        StatusCode::ACCEPTED,
+    )
+    .await;
+    assert_eq!(job.status, JobStatus::Pending);
+
+    let mut job = job;
+    for _ in 0..600 {
+        let request = Request::get(format!("/jobs/{}", job.job_id))
+            .body(Body::empty())
+            .unwrap();
+        job = send(&app, request, StatusCode::OK).await;
+        if job.status != JobStatus::Pending {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(job.status, JobStatus::Done, "{:?}", job.error);
+
+    let verified: VerifyResponse = send(
+        &app,
+        post_json(
+            "/verify",
+            &json!({
+                "pkvUrl": PKV_URL,
+                "r1csUrl": R1CS_URL,
+                "np": job.result.expect("Job result").np,
+            }),
+        ),
+        StatusCode::OK,
+    )
+    .await;
+    assert!(verified.is_valid, "{:?}", verified.result.error_message);
+
+    let request = Request::get("/jobs/unknown").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn full_queue_rejects_jobs() {
+    let dir = scratch_dir("jobs-queue");
+    let artifacts_dir = dir.join("artifacts");
+
+    let scheme = compile_scheme();
+    seed_artifacts(&artifacts_dir, &scheme);
+    // No queue slots, so the outcome does not depend on how fast jobs finish
+    let mut config = test_config(artifacts_dir);
+    config.server.max_pending_jobs = 0;
+    let app = app_with_state(AppState::new(config));
+
+    let request = post_json(
+        "/jobs",
+        &json!({
+            "nps": scheme,
+            "inputs": { "x": 2 },
+        }),
+    );
+    let response: Value = send(&app, request, StatusCode::SERVICE_UNAVAILABLE).await;
+    assert_eq!(response["error"]["code"], "QUEUE_FULL");
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn stale_pending_jobs_are_dropped() {
+    let store = JobStore::new(Duration::from_secs(3600), 1, Duration::ZERO);
+    let job_id = store.create().expect("Queue slot");
+    assert!(store.get(&job_id).is_none());
+
+    // The slot of the dropped job is free again, and its outcome is ignored
+    let next_id = store.create().expect("Queue slot");
+    store.finish(&job_id, Err("Too late".to_string()));
+    assert!(store.get(&job_id).is_none());
+    assert!(store.get(&next_id).is_none());
+}
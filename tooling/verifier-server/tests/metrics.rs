@@ -0,0 +1,61 @@
+mod common;
+
+use {
+    crate::common::{
+        compile_scheme, scratch_dir, seed_artifacts, test_app, PKV_URL, PROGRAM, R1CS_URL,
+    },
+    axum::{
+        body::{to_bytes, Body},
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+    },
+    provekit_common::{file::write, Prover},
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
+    std::path::Path,
+    tower::ServiceExt,
+};
+
+#[tokio::test]
+async fn metrics_count_verifications() {
+    let dir = scratch_dir("metrics");
+    let artifacts_dir = dir.join("artifacts");
+
+    let scheme = compile_scheme();
+    seed_artifacts(&artifacts_dir, &scheme);
+    let proof = Prover::from_noir_proof_scheme(scheme)
+        .prove_with(
+            ProveInputs::File(Path::new(PROGRAM).join("Prover.toml")),
+            ProveOptions::default(),
+        )
+        .expect("Proving");
+    let proof_path = dir.join("proof.np");
+    write(&proof, &proof_path).expect("Writing proof");
+
+    let app = test_app(artifacts_dir);
+    let request = Request::post(format!("/verify?pkvUrl={PKV_URL}&r1csUrl={R1CS_URL}"))
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(Body::from(
+            std::fs::read(&proof_path).expect("Reading proof"),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::get("/metrics").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let metrics = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(metrics.contains("verify_requests_total 1\n"), "{metrics}");
+    assert!(
+        metrics.contains("verify_duration_seconds_count 1\n"),
+        "{metrics}"
+    );
+    assert!(
+        metrics.contains(r#"http_requests_total{method="POST",path="/verify",status="200"} 1"#),
+        "{metrics}"
+    );
+    assert!(!metrics.contains("verify_failures_total{"), "{metrics}");
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use {
+    crate::common::{compile_scheme, scratch_dir, seed_artifacts, test_app, PKV_URL, R1CS_URL},
+    axum::{
+        body::{to_bytes, Body},
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+        Router,
+    },
+    serde::de::DeserializeOwned,
+    serde_json::{json, Value},
+    tower::ServiceExt,
+    verifier_server::models::{ProveResponse, VerifyResponse},
+};
+
+async fn post_json<T: DeserializeOwned>(app: &Router, uri: &str, body: &Value) -> T {
+    let request = Request::post(uri)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn prove_then_verify() {
+    let dir = scratch_dir("prove");
+    let artifacts_dir = dir.join("artifacts");
+
+    let scheme = compile_scheme();
+    seed_artifacts(&artifacts_dir, &scheme);
+    let app = test_app(artifacts_dir);
+
+    let proved: ProveResponse = post_json(
+        &app,
+        "/prove",
+        &json!({
+            "nps": scheme,
+            "inputs": { "x": 2 },
+        }),
+    )
+    .await;
+
+    let verified: VerifyResponse = post_json(
+        &app,
+        "/verify",
+        &json!({
+            "pkvUrl": PKV_URL,
+            "r1csUrl": R1CS_URL,
+            "np": proved.np,
+        }),
+    )
+    .await;
+    assert!(verified.is_valid, "{:?}", verified.result.error_message);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
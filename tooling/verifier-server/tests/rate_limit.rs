@@ -0,0 +1,68 @@
+mod common;
+
+use {
+    crate::common::{app_with_state, scratch_dir},
+    axum::{
+        body::Body,
+        extract::ConnectInfo,
+        http::{header::RETRY_AFTER, Request, StatusCode},
+        Router,
+    },
+    std::net::SocketAddr,
+    tower::ServiceExt,
+    verifier_server::{
+        config::{ArtifactConfig, Config, ServerConfig},
+        state::AppState,
+    },
+};
+
+async fn verify_from(app: &Router, addr: &str) -> (StatusCode, Option<String>) {
+    let mut request = Request::post("/verify").body(Body::empty()).unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(addr.parse::<SocketAddr>().unwrap()));
+    let response = app.clone().oneshot(request).await.unwrap();
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string());
+    (response.status(), retry_after)
+}
+
+#[tokio::test]
+async fn requests_above_limit_are_rejected() {
+    let app = app_with_state(AppState::new(Config {
+        server: ServerConfig {
+            rate_limit_per_second: 1,
+            rate_limit_burst: 3,
+            ..ServerConfig::default()
+        },
+        artifacts: ArtifactConfig {
+            artifacts_dir: scratch_dir("rate-limit"),
+        },
+        ..Config::default()
+    }));
+
+    let mut statuses = Vec::new();
+    for _ in 0..6 {
+        statuses.push(verify_from(&app, "192.0.2.1:1234").await);
+    }
+    let limited = statuses
+        .iter()
+        .filter(|(status, _)| *status == StatusCode::TOO_MANY_REQUESTS)
+        .count();
+    assert!(limited >= 2, "{statuses:?}");
+    // The burst goes through to the handler, which rejects the empty request
+    assert!(statuses[..3]
+        .iter()
+        .all(|(status, _)| *status != StatusCode::TOO_MANY_REQUESTS));
+    for (status, retry_after) in &statuses {
+        if *status == StatusCode::TOO_MANY_REQUESTS {
+            assert_eq!(retry_after.as_deref(), Some("1"));
+        }
+    }
+
+    // Other clients have their own bucket
+    let (status, _) = verify_from(&app, "192.0.2.2:1234").await;
+    assert_ne!(status, StatusCode::TOO_MANY_REQUESTS);
+}
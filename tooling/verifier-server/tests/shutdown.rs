@@ -0,0 +1,51 @@
+mod common;
+
+use {
+    crate::common::{scratch_dir, test_app},
+    axum::routing::get,
+    std::{sync::Arc, time::Duration},
+    tokio::{
+        net::TcpListener,
+        sync::{oneshot, Notify},
+        time::{sleep, timeout},
+    },
+};
+
+#[tokio::test]
+async fn shutdown_waits_for_requests_in_flight() {
+    let started = Arc::new(Notify::new());
+    let handler_started = started.clone();
+    let app = test_app(scratch_dir("shutdown")).route(
+        "/slow",
+        get(move || {
+            let started = handler_started.clone();
+            async move {
+                started.notify_one();
+                sleep(Duration::from_millis(500)).await;
+                "done"
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server = tokio::spawn(verifier_server::serve(listener, app, async {
+        shutdown_rx.await.ok();
+    }));
+
+    let request = tokio::spawn(reqwest::get(format!("http://{addr}/slow")));
+    started.notified().await;
+    shutdown_tx.send(()).unwrap();
+
+    let response = request.await.unwrap().expect("Request in flight failed");
+    assert_eq!(response.text().await.unwrap(), "done");
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("Server did not stop")
+        .unwrap()
+        .expect("Serving failed");
+
+    // The listener is closed once the server stopped
+    assert!(reqwest::get(format!("http://{addr}/health")).await.is_err());
+}
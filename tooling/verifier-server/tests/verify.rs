@@ -0,0 +1,74 @@
+mod common;
+
+use {
+    crate::common::{compile_scheme, scratch_dir, seed_artifacts, test_app, PKV_URL, R1CS_URL},
+    axum::{
+        body::{to_bytes, Body},
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+    },
+    provekit_common::{file::write, Prover},
+    provekit_prover::{Prove, ProveInputs, ProveOptions},
+    std::path::Path,
+    tower::ServiceExt,
+    verifier_server::{config::ServerConfig, models::VerifyResponse},
+};
+
+#[tokio::test]
+async fn verify_binary_proof() {
+    let dir = scratch_dir("verify");
+    let artifacts_dir = dir.join("artifacts");
+
+    let scheme = compile_scheme();
+    seed_artifacts(&artifacts_dir, &scheme);
+    let proof = Prover::from_noir_proof_scheme(scheme)
+        .prove_with(
+            ProveInputs::File(Path::new(common::PROGRAM).join("Prover.toml")),
+            ProveOptions::default(),
+        )
+        .expect("Proving");
+    let proof_path = dir.join("proof.np");
+    write(&proof, &proof_path).expect("Writing proof");
+
+    let request = Request::post(format!("/verify?pkvUrl={PKV_URL}&r1csUrl={R1CS_URL}"))
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(Body::from(
+            std::fs::read(&proof_path).expect("Reading proof"),
+        ))
+        .unwrap();
+    let response = test_app(artifacts_dir).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response: VerifyResponse = serde_json::from_slice(&body).unwrap();
+    assert!(response.is_valid, "{:?}", response.result.error_message);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn reject_decompression_bomb() {
+    let dir = scratch_dir("verify-bomb");
+    let limit = ServerConfig::default().max_decompressed_size();
+
+    // A valid NoirProof header followed by a small zstd frame of zeros that
+    // decompresses past the limit.
+    let mut body = b"\xDC\xDFOZkp\x01\x00NPSProof\x00\x00\x00\x00".to_vec();
+    body.extend(zstd::encode_all(&vec![0u8; limit + 1][..], 19).expect("Compressing"));
+
+    let request = Request::post(format!("/verify?pkvUrl={PKV_URL}&r1csUrl={R1CS_URL}"))
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(Body::from(body))
+        .unwrap();
+    let response = test_app(dir.join("artifacts"))
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let message = body["error"]["message"].as_str().unwrap();
+    assert!(message.contains("Decompressed data exceeds"), "{message}");
+
+    std::fs::remove_dir_all(&dir).ok();
+}